@@ -6,9 +6,12 @@ use goose::config::{Config, ExtensionConfig};
 use crate::commands::acp::run_acp_agent;
 use crate::commands::bench::agent_generator;
 use crate::commands::configure::handle_configure;
+use crate::commands::doctor::handle_doctor;
 use crate::commands::info::handle_info;
 use crate::commands::project::{handle_project_default, handle_projects_interactive};
-use crate::commands::recipe::{handle_deeplink, handle_list, handle_open, handle_validate};
+use crate::commands::recipe::{
+    handle_deeplink, handle_lint, handle_list, handle_open, handle_validate,
+};
 // Import the new handlers from commands::schedule
 use crate::commands::schedule::{
     handle_schedule_add, handle_schedule_cron_help, handle_schedule_list, handle_schedule_remove,
@@ -155,6 +158,13 @@ enum SessionCommand {
             default_value = "markdown"
         )]
         format: String,
+
+        #[arg(
+            long = "token-usage",
+            help = "Include per-turn token usage",
+            long_help = "Include the input and output tokens of each turn, to show where the context grew. Added as a table in Markdown and as a token_usage field in JSON and YAML"
+        )]
+        token_usage: bool,
     },
     #[command(name = "diagnostics")]
     Diagnostics {
@@ -283,6 +293,25 @@ enum RecipeCommand {
         recipe_name: String,
     },
 
+    /// Check a recipe without running it
+    #[command(
+        about = "Lint a recipe",
+        long_about = "Report every problem found in a recipe without running it: parameter mismatches, unreachable sub-recipes, extensions that won't start, and invalid response schemas"
+    )]
+    Lint {
+        #[arg(help = "recipe name to get recipe file or full path to the recipe file to lint")]
+        recipe_name: String,
+
+        #[arg(
+            long,
+            value_name = "KEY=VALUE",
+            help = "Parameter values the recipe would be run with (e.g., --params username=alice)",
+            action = clap::ArgAction::Append,
+            value_parser = parse_key_val,
+        )]
+        params: Vec<(String, String)>,
+    },
+
     /// Generate a deeplink for a recipe file
     #[command(about = "Generate a deeplink for a recipe")]
     Deeplink {
@@ -337,6 +366,17 @@ enum Command {
         verbose: bool,
     },
 
+    /// Check the configuration file for problems
+    #[command(about = "Check goose configuration for unknown, invalid or deprecated settings")]
+    Doctor {
+        /// Migrate deprecated settings and move plaintext secrets to the keyring
+        #[arg(
+            long,
+            help = "Migrate deprecated settings and move plaintext secrets to the system keyring"
+        )]
+        fix: bool,
+    },
+
     /// Manage system prompts and behaviors
     #[command(about = "Run one of the mcp servers bundled with goose")]
     Mcp { name: String },
@@ -405,7 +445,7 @@ enum Command {
             long = "with-extension",
             value_name = "COMMAND",
             help = "Add stdio extensions (can be specified multiple times)",
-            long_help = "Add stdio extensions from full commands with environment variables. Can be specified multiple times. Format: 'ENV1=val1 ENV2=val2 command args...'",
+            long_help = "Add stdio extensions from full commands with environment variables. Can be specified multiple times. Format: 'ENV1=val1 ENV2=val2 [timeout=secs] command args...'",
             action = clap::ArgAction::Append
         )]
         extensions: Vec<String>,
@@ -415,7 +455,7 @@ enum Command {
             long = "with-remote-extension",
             value_name = "URL",
             help = "Add remote extensions (can be specified multiple times)",
-            long_help = "Add remote extensions from a URL. Can be specified multiple times. Format: 'url...'",
+            long_help = "Add remote extensions from a URL. Can be specified multiple times. Format: 'url [timeout=secs]'",
             action = clap::ArgAction::Append
         )]
         remote_extensions: Vec<String>,
@@ -425,7 +465,7 @@ enum Command {
             long = "with-streamable-http-extension",
             value_name = "URL",
             help = "Add streamable HTTP extensions (can be specified multiple times)",
-            long_help = "Add streamable HTTP extensions from a URL. Can be specified multiple times. Format: 'url...'",
+            long_help = "Add streamable HTTP extensions from a URL, optionally followed by request headers. Can be specified multiple times. Format: 'url \"Header-Name=value\"...'. Header values may reference environment variables as ${VAR}, e.g. 'https://example.com/mcp \"Authorization=Bearer ${MY_TOKEN}\"'",
             action = clap::ArgAction::Append
         )]
         streamable_http_extensions: Vec<String>,
@@ -435,8 +475,8 @@ enum Command {
             long = "with-builtin",
             value_name = "NAME",
             help = "Add builtin extensions by name (e.g., 'developer' or multiple: 'developer,github')",
-            long_help = "Add one or more builtin extensions that are bundled with goose by specifying their names, comma-separated",
-            value_delimiter = ','
+            long_help = "Add one or more builtin extensions that are bundled with goose by specifying their names, comma-separated. Can be specified multiple times. Append 'timeout=secs' to change how long all extensions in the same value may take to respond, e.g. 'developer,memory timeout=600'",
+            action = clap::ArgAction::Append
         )]
         builtins: Vec<String>,
     },
@@ -583,7 +623,7 @@ enum Command {
             long = "with-extension",
             value_name = "COMMAND",
             help = "Add stdio extensions (can be specified multiple times)",
-            long_help = "Add stdio extensions from full commands with environment variables. Can be specified multiple times. Format: 'ENV1=val1 ENV2=val2 command args...'",
+            long_help = "Add stdio extensions from full commands with environment variables. Can be specified multiple times. Format: 'ENV1=val1 ENV2=val2 [timeout=secs] command args...'",
             action = clap::ArgAction::Append
         )]
         extensions: Vec<String>,
@@ -593,7 +633,7 @@ enum Command {
             long = "with-remote-extension",
             value_name = "URL",
             help = "Add remote extensions (can be specified multiple times)",
-            long_help = "Add remote extensions. Can be specified multiple times. Format: 'url...'",
+            long_help = "Add remote extensions. Can be specified multiple times. Format: 'url [timeout=secs]'",
             action = clap::ArgAction::Append
         )]
         remote_extensions: Vec<String>,
@@ -603,7 +643,7 @@ enum Command {
             long = "with-streamable-http-extension",
             value_name = "URL",
             help = "Add streamable HTTP extensions (can be specified multiple times)",
-            long_help = "Add streamable HTTP extensions. Can be specified multiple times. Format: 'url...'",
+            long_help = "Add streamable HTTP extensions from a URL, optionally followed by request headers. Can be specified multiple times. Format: 'url \"Header-Name=value\"...'. Header values may reference environment variables as ${VAR}, e.g. 'https://example.com/mcp \"Authorization=Bearer ${MY_TOKEN}\"'",
             action = clap::ArgAction::Append
         )]
         streamable_http_extensions: Vec<String>,
@@ -613,8 +653,8 @@ enum Command {
             long = "with-builtin",
             value_name = "NAME",
             help = "Add builtin extensions by name (e.g., 'developer' or multiple: 'developer,github')",
-            long_help = "Add one or more builtin extensions that are bundled with goose by specifying their names, comma-separated",
-            value_delimiter = ','
+            long_help = "Add one or more builtin extensions that are bundled with goose by specifying their names, comma-separated. Can be specified multiple times. Append 'timeout=secs' to change how long all extensions in the same value may take to respond, e.g. 'developer,memory timeout=600'",
+            action = clap::ArgAction::Append
         )]
         builtins: Vec<String>,
 
@@ -626,6 +666,14 @@ enum Command {
         )]
         quiet: bool,
 
+        /// Show the tool calls the agent would make without running them
+        #[arg(
+            long = "dry-run",
+            help = "Answer tool calls with a 'would execute' note instead of running them",
+            long_help = "Let the agent choose tools as usual but run none of them. Each tool call is answered with a note saying what would have run, so a recipe can be tried against real prompts without side effects."
+        )]
+        dry_run: bool,
+
         /// Scheduled job ID (used internally for scheduled executions)
         #[arg(
             long = "scheduled-job-id",
@@ -753,6 +801,7 @@ pub struct RecipeInfo {
     pub sub_recipes: Option<Vec<goose::recipe::SubRecipe>>,
     pub final_output_response: Option<goose::recipe::Response>,
     pub retry_config: Option<goose::agents::types::RetryConfig>,
+    pub secrets: goose::recipe::RecipeSecrets,
 }
 
 pub async fn cli() -> Result<()> {
@@ -766,6 +815,7 @@ pub async fn cli() -> Result<()> {
     let command_name = match &cli.command {
         Some(Command::Configure {}) => "configure",
         Some(Command::Info { .. }) => "info",
+        Some(Command::Doctor { .. }) => "doctor",
         Some(Command::Mcp { .. }) => "mcp",
         Some(Command::Acp {}) => "acp",
         Some(Command::Session { .. }) => "session",
@@ -795,6 +845,10 @@ pub async fn cli() -> Result<()> {
             handle_info(verbose)?;
             return Ok(());
         }
+        Some(Command::Doctor { fix }) => {
+            handle_doctor(fix)?;
+            return Ok(());
+        }
         Some(Command::Mcp { name }) => {
             crate::logging::setup_logging(Some(&format!("mcp-{name}")), None)?;
             let _ = goose_mcp::mcp_server_runner::run_mcp_server(&name).await;
@@ -834,6 +888,7 @@ pub async fn cli() -> Result<()> {
                     identifier,
                     output,
                     format,
+                    token_usage,
                 }) => {
                     let session_identifier = if let Some(id) = identifier {
                         get_session_id(id).await?
@@ -853,6 +908,7 @@ pub async fn cli() -> Result<()> {
                         session_identifier,
                         output,
                         format,
+                        token_usage,
                     )
                     .await?;
                     Ok(())
@@ -901,6 +957,8 @@ pub async fn cli() -> Result<()> {
                         sub_recipes: None,
                         final_output_response: None,
                         retry_config: None,
+                        recipe_secrets: Default::default(),
+                        dry_run: false,
                     })
                     .await;
 
@@ -980,6 +1038,7 @@ pub async fn cli() -> Result<()> {
             render_recipe,
             scheduled_job_id,
             quiet,
+            dry_run,
             additional_sub_recipes,
             provider,
             model,
@@ -1102,6 +1161,11 @@ pub async fn cli() -> Result<()> {
                     .as_ref()
                     .and_then(|r| r.final_output_response.clone()),
                 retry_config: recipe_info.as_ref().and_then(|r| r.retry_config.clone()),
+                recipe_secrets: recipe_info
+                    .as_ref()
+                    .map(|r| r.secrets.clone())
+                    .unwrap_or_default(),
+                dry_run,
             })
             .await;
 
@@ -1234,6 +1298,12 @@ pub async fn cli() -> Result<()> {
                 RecipeCommand::Validate { recipe_name } => {
                     handle_validate(&recipe_name)?;
                 }
+                RecipeCommand::Lint {
+                    recipe_name,
+                    params,
+                } => {
+                    handle_lint(&recipe_name, params).await?;
+                }
                 RecipeCommand::Deeplink { recipe_name } => {
                     handle_deeplink(&recipe_name)?;
                 }
@@ -1283,6 +1353,8 @@ pub async fn cli() -> Result<()> {
                     sub_recipes: None,
                     final_output_response: None,
                     retry_config: None,
+                    recipe_secrets: Default::default(),
+                    dry_run: false,
                 })
                 .await;
                 if let Err(e) = session.interactive(None).await {
@@ -576,8 +576,9 @@ impl acp::Agent for GooseAcpAgent {
 
         use futures::StreamExt;
 
-        // Track if we were cancelled
+        // Track if we were cancelled, and why the model last stopped
         let mut was_cancelled = false;
+        let mut last_stop_reason = None;
 
         // Process the agent's response stream
         while let Some(event) = stream.next().await {
@@ -604,6 +605,9 @@ impl acp::Agent for GooseAcpAgent {
                             .await?;
                     }
                 }
+                Ok(goose::agents::AgentEvent::StopReason(reason)) => {
+                    last_stop_reason = Some(reason);
+                }
                 Ok(_) => {
                     // Ignore other events for now
                 }
@@ -624,7 +628,15 @@ impl acp::Agent for GooseAcpAgent {
             stop_reason: if was_cancelled {
                 acp::StopReason::Cancelled
             } else {
-                acp::StopReason::EndTurn
+                match last_stop_reason {
+                    Some(goose::providers::base::StopReason::MaxTokens) => {
+                        acp::StopReason::MaxTokens
+                    }
+                    Some(goose::providers::base::StopReason::ContentFilter) => {
+                        acp::StopReason::Refusal
+                    }
+                    _ => acp::StopReason::EndTurn,
+                }
             },
             meta: None,
         })
@@ -57,6 +57,8 @@ pub async fn agent_generator(
         sub_recipes: None,
         final_output_response: None,
         retry_config: None,
+        recipe_secrets: Default::default(),
+        dry_run: false,
     })
     .await;
 
@@ -14,7 +14,7 @@ use goose::config::permission::PermissionLevel;
 use goose::config::{Config, ConfigError, ExperimentManager, ExtensionEntry, PermissionManager};
 use goose::conversation::message::Message;
 use goose::model::ModelConfig;
-use goose::providers::{create, providers};
+use goose::providers::{create, list_models, providers};
 use rmcp::model::{Tool, ToolAnnotations};
 use rmcp::object;
 use serde_json::Value;
@@ -203,6 +203,18 @@ pub async fn handle_configure() -> Result<(), Box<dyn Error>> {
             style("  if you prefer, you can edit it directly at").dim(),
             config.path()
         );
+        if let Ok(issues) = config.validate() {
+            if !issues.is_empty() {
+                println!();
+                for issue in &issues {
+                    println!("{} {}", style("Warning:").yellow(), issue);
+                }
+                println!(
+                    "{}",
+                    style("  run 'goose doctor --fix' to migrate deprecated settings").dim()
+                );
+            }
+        }
         println!();
 
         cliclack::intro(style(" goose-configure ").on_cyan().black())?;
@@ -548,22 +560,21 @@ pub async fn configure_provider_dialog() -> Result<bool, Box<dyn Error>> {
     // Attempt to fetch supported models for this provider
     let spin = spinner();
     spin.start("Attempting to fetch supported models...");
-    let models_res = {
-        let temp_model_config = ModelConfig::new(&provider_meta.default_model)?;
-        let temp_provider = create(provider_name, temp_model_config).await?;
-        temp_provider.fetch_supported_models().await
-    };
+    let models_res = list_models(provider_name).await;
     spin.stop(style("Model fetch complete").green());
 
-    // Select a model: on fetch error show styled error and abort; if Some(models), show list; if None, free-text input
+    // Select a model: on fetch error show styled error and abort; if there are models, show list; otherwise free-text input
     let model: String = match models_res {
         Err(e) => {
             // Provider hook error
             cliclack::outro(style(e.to_string()).on_red().white())?;
             return Ok(false);
         }
-        Ok(Some(models)) => select_model_from_list(&models, provider_meta)?,
-        Ok(None) => {
+        Ok(models) if !models.is_empty() => {
+            let model_names: Vec<String> = models.into_iter().map(|m| m.name).collect();
+            select_model_from_list(&model_names, provider_meta)?
+        }
+        Ok(_) => {
             let default_model =
                 std::env::var("GOOSE_MODEL").unwrap_or(provider_meta.default_model.clone());
             cliclack::input("Enter a model from that provider:")
@@ -1316,29 +1327,23 @@ pub fn configure_goose_router_strategy_dialog() -> Result<(), Box<dyn Error>> {
 
 pub fn configure_tool_output_dialog() -> Result<(), Box<dyn Error>> {
     let config = Config::global();
-    // Check if GOOSE_CLI_MIN_PRIORITY is set as an environment variable
-    if std::env::var("GOOSE_CLI_MIN_PRIORITY").is_ok() {
-        let _ = cliclack::log::info("Notice: GOOSE_CLI_MIN_PRIORITY environment variable is set and will override the configuration here.");
+    // Check if GOOSE_VERBOSITY is set as an environment variable
+    if std::env::var("GOOSE_VERBOSITY").is_ok() {
+        let _ = cliclack::log::info("Notice: GOOSE_VERBOSITY environment variable is set and will override the configuration here.");
     }
-    let tool_log_level = cliclack::select("Which tool output would you like to show?")
-        .item("high", "High Importance", "")
-        .item("medium", "Medium Importance", "Ex. results of file-writes")
-        .item("all", "All (default)", "Ex. shell command output")
+    let verbosity = cliclack::select("Which tool output would you like to show?")
+        .item("quiet", "High Importance", "")
+        .item("normal", "Medium Importance", "Ex. results of file-writes")
+        .item("verbose", "All", "Ex. shell command output")
+        .item("debug", "Debug", "Raw tool responses")
         .interact()?;
 
-    match tool_log_level {
-        "high" => {
-            config.set_param("GOOSE_CLI_MIN_PRIORITY", Value::from(0.8))?;
-            cliclack::outro("Showing tool output of high importance only.")?;
-        }
-        "medium" => {
-            config.set_param("GOOSE_CLI_MIN_PRIORITY", Value::from(0.2))?;
-            cliclack::outro("Showing tool output of medium importance.")?;
-        }
-        "all" => {
-            config.set_param("GOOSE_CLI_MIN_PRIORITY", Value::from(0.0))?;
-            cliclack::outro("Showing all tool output.")?;
-        }
+    config.set_param("GOOSE_VERBOSITY", Value::String(verbosity.to_string()))?;
+    match verbosity {
+        "quiet" => cliclack::outro("Showing tool output of high importance only.")?,
+        "normal" => cliclack::outro("Showing tool output of medium importance.")?,
+        "verbose" => cliclack::outro("Showing all tool output.")?,
+        "debug" => cliclack::outro("Showing all tool output, including raw responses.")?,
         _ => unreachable!(),
     };
 
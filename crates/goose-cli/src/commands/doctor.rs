@@ -0,0 +1,55 @@
+use anyhow::Result;
+use console::style;
+use goose::config::{Config, ConfigIssueKind};
+
+pub fn handle_doctor(fix: bool) -> Result<()> {
+    let config = Config::global();
+    println!("{} {}", style("Checking").cyan().bold(), config.path());
+
+    if fix {
+        let migrations = config.migrate()?;
+        for migration in &migrations {
+            println!(
+                "  {} renamed {} to {}",
+                style("fixed").green(),
+                migration.from,
+                migration.to
+            );
+        }
+        for key in config.protect_secrets()? {
+            println!(
+                "  {} moved {} from the config file to the system keyring",
+                style("fixed").green(),
+                key
+            );
+        }
+    }
+
+    let issues = config.validate()?;
+    if issues.is_empty() {
+        println!("  {}", style("No problems found").green());
+        return Ok(());
+    }
+
+    for issue in &issues {
+        let label = match issue.kind {
+            ConfigIssueKind::UnknownKey => style("unknown").yellow(),
+            ConfigIssueKind::InvalidValue => style("invalid").red(),
+            ConfigIssueKind::Deprecated => style("deprecated").yellow(),
+        };
+        println!("  {} {}", label, issue);
+    }
+
+    if !fix
+        && issues
+            .iter()
+            .any(|issue| issue.kind == ConfigIssueKind::Deprecated)
+    {
+        println!(
+            "\nRun '{}' to migrate deprecated settings",
+            style("goose doctor --fix").cyan()
+        );
+    }
+
+    Ok(())
+}
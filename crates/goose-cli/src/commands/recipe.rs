@@ -1,6 +1,7 @@
 use anyhow::Result;
 use console::style;
 use goose::recipe::validate_recipe::validate_recipe_template_from_file;
+use goose::recipe::{lint, LintContext, LintSeverity};
 
 use crate::recipes::github_recipe::RecipeSource;
 use crate::recipes::search_recipe::{list_available_recipes, load_recipe_file};
@@ -20,6 +21,46 @@ pub fn handle_validate(recipe_name: &str) -> Result<()> {
     Ok(())
 }
 
+pub async fn handle_lint(recipe_name: &str, params: Vec<(String, String)>) -> Result<()> {
+    let recipe_file = load_recipe_file(recipe_name)?;
+    let context = LintContext {
+        recipe_dir: Some(recipe_file.parent_dir.clone()),
+        params: params.into_iter().collect(),
+    };
+    let diagnostics = lint(&recipe_file.content, &context).await;
+
+    if diagnostics.is_empty() {
+        println!("{} no problems found", style("✓").green().bold());
+        return Ok(());
+    }
+
+    for diagnostic in &diagnostics {
+        let marker = match diagnostic.severity {
+            LintSeverity::Error => style("✗").red().bold(),
+            LintSeverity::Warning => style("!").yellow().bold(),
+        };
+        println!(
+            "{} {}: {}",
+            marker,
+            style(&diagnostic.location).dim(),
+            diagnostic.message
+        );
+    }
+
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == LintSeverity::Error)
+        .count();
+    if errors > 0 {
+        return Err(anyhow::anyhow!(
+            "{} error(s) found in {}",
+            errors,
+            recipe_file.file_path.display()
+        ));
+    }
+    Ok(())
+}
+
 pub fn handle_deeplink(recipe_name: &str) -> Result<String> {
     match generate_deeplink(recipe_name) {
         Ok((deeplink_url, recipe)) => {
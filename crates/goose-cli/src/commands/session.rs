@@ -2,7 +2,9 @@ use crate::session::message_to_markdown;
 use anyhow::{Context, Result};
 
 use cliclack::{confirm, multiselect, select};
-use goose::session::{generate_diagnostics, Session, SessionManager};
+use goose::session::{
+    generate_diagnostics, ExtensionState, Session, SessionManager, TokenUsageState,
+};
 use goose::utils::safe_truncate;
 use regex::Regex;
 use std::fs;
@@ -170,6 +172,7 @@ pub async fn handle_session_export(
     session_id: String,
     output_path: Option<PathBuf>,
     format: String,
+    include_token_usage: bool,
 ) -> Result<()> {
     let session = match SessionManager::get_session(&session_id, true).await {
         Ok(session) => session,
@@ -182,14 +185,36 @@ pub async fn handle_session_export(
         }
     };
 
+    let token_usage = include_token_usage
+        .then(|| TokenUsageState::from_extension_data(&session.extension_data).unwrap_or_default());
+
     let output = match format.as_str() {
+        "json" | "yaml" if token_usage.is_some() => {
+            let mut value = serde_json::to_value(&session)?;
+            if let (Some(token_usage), Some(fields)) = (&token_usage, value.as_object_mut()) {
+                fields.insert(
+                    "token_usage".to_string(),
+                    serde_json::to_value(&token_usage.turns)?,
+                );
+            }
+            if format == "json" {
+                serde_json::to_string_pretty(&value)?
+            } else {
+                serde_yaml::to_string(&value)?
+            }
+        }
         "json" => serde_json::to_string_pretty(&session)?,
         "yaml" => serde_yaml::to_string(&session)?,
         "markdown" => {
             let conversation = session
                 .conversation
                 .ok_or_else(|| anyhow::anyhow!("Session has no messages"))?;
-            export_session_to_markdown(conversation.messages().to_vec(), &session.description)
+            let mut markdown =
+                export_session_to_markdown(conversation.messages().to_vec(), &session.description);
+            if let Some(token_usage) = &token_usage {
+                markdown.push_str(&token_usage_to_markdown(token_usage));
+            }
+            markdown
         }
         _ => return Err(anyhow::anyhow!("Unsupported format: {}", format)),
     };
@@ -306,6 +331,30 @@ fn export_session_to_markdown(
     markdown_output
 }
 
+/// Render per-turn token usage as a Markdown table
+fn token_usage_to_markdown(token_usage: &TokenUsageState) -> String {
+    let mut markdown = String::from("## Token Usage\n\n");
+    if token_usage.turns.is_empty() {
+        markdown.push_str("*(No token usage was recorded for this session)*\n");
+        return markdown;
+    }
+
+    let format_tokens = |tokens: Option<i32>| tokens.map_or("-".to_string(), |t| t.to_string());
+    markdown.push_str("| Turn | Messages | Input | Output | Total |\n");
+    markdown.push_str("|---:|---:|---:|---:|---:|\n");
+    for (i, turn) in token_usage.turns.iter().enumerate() {
+        markdown.push_str(&format!(
+            "| {} | {} | {} | {} | {} |\n",
+            i + 1,
+            turn.message_count,
+            format_tokens(turn.input_tokens),
+            format_tokens(turn.output_tokens),
+            format_tokens(turn.total_tokens),
+        ));
+    }
+    markdown
+}
+
 /// Prompt the user to interactively select a session
 ///
 /// Shows a list of available sessions and lets the user select one
@@ -358,3 +407,32 @@ pub async fn prompt_interactive_session_selection() -> Result<String> {
         Err(anyhow::anyhow!("Invalid selection"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use goose::session::TurnUsage;
+
+    #[test]
+    fn test_token_usage_to_markdown() {
+        let token_usage = TokenUsageState {
+            turns: vec![
+                TurnUsage {
+                    message_count: 1,
+                    input_tokens: Some(1200),
+                    output_tokens: Some(300),
+                    total_tokens: Some(1500),
+                },
+                TurnUsage {
+                    message_count: 3,
+                    input_tokens: Some(5400),
+                    output_tokens: None,
+                    total_tokens: None,
+                },
+            ],
+        };
+        let markdown = token_usage_to_markdown(&token_usage);
+        assert!(markdown.contains("| 1 | 1 | 1200 | 300 | 1500 |"));
+        assert!(markdown.contains("| 2 | 3 | 5400 | - | - |"));
+    }
+}
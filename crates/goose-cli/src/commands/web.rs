@@ -475,7 +475,10 @@ async fn process_message_streaming(
         schedule_id: None,
         execution_mode: None,
         max_turns: None,
+        max_tool_calls_per_turn: None,
         retry_config: None,
+        request_metadata: Default::default(),
+        dry_run: false,
     };
 
     match agent
@@ -580,6 +583,9 @@ async fn process_message_streaming(
                     Ok(AgentEvent::ModelChange { model, mode }) => {
                         tracing::info!("Model changed to {} in {} mode", model, mode);
                     }
+                    Ok(AgentEvent::StopReason(reason)) => {
+                        tracing::info!("Model stopped: {}", reason);
+                    }
                     Err(e) => {
                         error!("Error in message stream: {}", e);
                         let mut sender = sender.lock().await;
@@ -3,12 +3,12 @@ use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::Once;
 use tokio::sync::Mutex;
-use tracing_appender::rolling::Rotation;
 use tracing_subscriber::{
     filter::LevelFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
     Registry,
 };
 
+use goose::logging::{LogFormat, LogRotationConfig, RotatingFileWriter};
 use goose::tracing::{langfuse_layer, otlp_layer};
 use goose_bench::bench_session::BenchAgentError;
 use goose_bench::error_capture::ErrorCaptureLayer;
@@ -61,12 +61,11 @@ fn setup_logging_internal(
                 format!("{}.log", timestamp)
             };
 
-            // Create non-rolling file appender for detailed logs
-            let file_appender = tracing_appender::rolling::RollingFileAppender::new(
-                Rotation::NEVER,
-                log_dir,
-                log_filename,
-            );
+            // Create size-rotated file writer for detailed logs
+            let file_appender = std::sync::Mutex::new(RotatingFileWriter::new(
+                log_dir.join(log_filename),
+                LogRotationConfig::from_config(),
+            )?);
 
             // Create JSON file logging layer with all logs (DEBUG and above)
             let file_layer = fmt::layer()
@@ -74,7 +73,8 @@ fn setup_logging_internal(
                 .with_level(true)
                 .with_writer(file_appender)
                 .with_ansi(false)
-                .json();
+                .json()
+                .flatten_event(LogFormat::from_config() == LogFormat::Json);
 
             // Base filter
             let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
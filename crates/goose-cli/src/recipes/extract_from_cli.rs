@@ -4,7 +4,7 @@ use anyhow::{anyhow, Result};
 use goose::recipe::SubRecipe;
 
 use crate::recipes::print_recipe::print_recipe_info;
-use crate::recipes::recipe::load_recipe;
+use crate::recipes::recipe::load_recipe_with_secrets;
 use crate::recipes::search_recipe::load_recipe_file;
 use crate::{
     cli::{InputConfig, RecipeInfo},
@@ -16,10 +16,11 @@ pub fn extract_recipe_info_from_cli(
     params: Vec<(String, String)>,
     additional_sub_recipes: Vec<String>,
 ) -> Result<(InputConfig, RecipeInfo)> {
-    let recipe = load_recipe(&recipe_name, params.clone()).unwrap_or_else(|err| {
-        eprintln!("{}: {}", console::style("Error").red().bold(), err);
-        std::process::exit(1);
-    });
+    let (recipe, secrets) =
+        load_recipe_with_secrets(&recipe_name, params.clone()).unwrap_or_else(|err| {
+            eprintln!("{}: {}", console::style("Error").red().bold(), err);
+            std::process::exit(1);
+        });
     print_recipe_info(&recipe, params);
     let mut all_sub_recipes = recipe.sub_recipes.clone().unwrap_or_default();
     if !additional_sub_recipes.is_empty() {
@@ -58,10 +59,13 @@ pub fn extract_recipe_info_from_cli(
             goose_provider: s.goose_provider,
             goose_model: s.goose_model,
             temperature: s.temperature,
+            max_output_tokens: s.max_output_tokens,
+            tool_rate_limits: s.tool_rate_limits,
         }),
         sub_recipes: Some(all_sub_recipes),
         final_output_response: recipe.response,
         retry_config: recipe.retry,
+        secrets,
     };
 
     Ok((input_config, recipe_info))
@@ -114,6 +118,7 @@ mod tests {
         assert_eq!(settings.goose_provider, Some("test_provider".to_string()));
         assert_eq!(settings.goose_model, Some("test_model".to_string()));
         assert_eq!(settings.temperature, Some(0.7));
+        assert_eq!(settings.max_output_tokens, Some(2048));
 
         assert!(sub_recipes.is_some());
         let sub_recipes = sub_recipes.unwrap();
@@ -240,6 +245,7 @@ settings:
   goose_provider: test_provider
   goose_model: test_model
   temperature: 0.7
+  max_output_tokens: 2048
 sub_recipes:
 - path: existing_sub_recipe.yaml
   name: existing_sub_recipe
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use anstream::println;
 use console::style;
-use goose::recipe::{Recipe, BUILT_IN_RECIPE_DIR_PARAM};
+use goose::recipe::{redact_secret_values, Recipe, BUILT_IN_RECIPE_DIR_PARAM};
 
 pub fn print_recipe_explanation(recipe: &Recipe) {
     println!(
@@ -46,9 +46,11 @@ pub fn print_parameters_with_values(params: HashMap<String, String>) {
 }
 
 pub fn print_required_parameters_for_template(
-    params_for_template: HashMap<String, String>,
+    recipe: &Recipe,
+    mut params_for_template: HashMap<String, String>,
     missing_params: Vec<String>,
 ) {
+    redact_secret_values(recipe.parameters.as_deref(), &mut params_for_template);
     if !params_for_template.is_empty() {
         println!(
             "{}",
@@ -91,7 +93,9 @@ pub fn print_recipe_info(recipe: &Recipe, params: Vec<(String, String)>) {
 
     if !params.is_empty() {
         eprintln!("{}", style("Parameters used to load this recipe:").bold());
-        print_parameters_with_values(params.into_iter().collect());
+        let mut params = params.into_iter().collect();
+        redact_secret_values(recipe.parameters.as_deref(), &mut params);
+        print_parameters_with_values(params);
     }
     eprintln!();
 }
@@ -7,31 +7,43 @@ use crate::recipes::secret_discovery::{discover_recipe_secrets, SecretRequiremen
 use anyhow::Result;
 use goose::config::Config;
 use goose::recipe::build_recipe::{
-    apply_values_to_parameters, build_recipe_from_template, RecipeError,
+    apply_values_to_parameters, build_recipe_with_secrets_from_template, RecipeError,
 };
 use goose::recipe::validate_recipe::parse_and_validate_parameters;
-use goose::recipe::Recipe;
+use goose::recipe::{Recipe, RecipeParameter, RecipeSecrets};
 use serde_json::Value;
 
-fn create_user_prompt_callback() -> impl Fn(&str, &str) -> Result<String> {
-    |key: &str, description: &str| -> Result<String> {
-        let input_value =
-            cliclack::input(format!("Please enter {} ({})", key, description)).interact()?;
+fn create_user_prompt_callback() -> impl Fn(&RecipeParameter) -> Result<String> {
+    |param: &RecipeParameter| -> Result<String> {
+        let prompt = format!("Please enter {} ({})", param.key, param.description);
+        let input_value = if param.is_secret() {
+            cliclack::password(prompt).mask('▪').interact()?
+        } else {
+            cliclack::input(prompt).interact()?
+        };
         Ok(input_value)
     }
 }
 
 pub fn load_recipe(recipe_name: &str, params: Vec<(String, String)>) -> Result<Recipe> {
+    load_recipe_with_secrets(recipe_name, params).map(|(recipe, _)| recipe)
+}
+
+/// Load a recipe to run, along with the values of its secret parameters
+pub fn load_recipe_with_secrets(
+    recipe_name: &str,
+    params: Vec<(String, String)>,
+) -> Result<(Recipe, RecipeSecrets)> {
     let recipe_file = load_recipe_file(recipe_name)?;
     let recipe_content = recipe_file.content;
     let recipe_dir = recipe_file.parent_dir;
-    match build_recipe_from_template(
+    match build_recipe_with_secrets_from_template(
         recipe_content,
         &recipe_dir,
         params,
         Some(create_user_prompt_callback()),
     ) {
-        Ok(recipe) => {
+        Ok((recipe, secrets)) => {
             let secret_requirements = discover_recipe_secrets(&recipe);
             if let Err(e) = collect_missing_secrets(&secret_requirements) {
                 eprintln!(
@@ -39,7 +51,7 @@ pub fn load_recipe(recipe_name: &str, params: Vec<(String, String)>) -> Result<R
                     e
                 );
             }
-            Ok(recipe)
+            Ok((recipe, secrets))
         }
         Err(RecipeError::MissingParams { parameters }) => Err(anyhow::anyhow!(
             "Please provide the following parameters in the command line: {}",
@@ -138,10 +150,10 @@ pub fn explain_recipe(recipe_name: &str, params: Vec<(String, String)>) -> Resul
         &params,
         recipe_parameters,
         &recipe_dir_str,
-        None::<fn(&str, &str) -> Result<String>>,
+        None::<fn(&RecipeParameter) -> Result<String>>,
     )?;
     print_recipe_explanation(&recipe_template);
-    print_required_parameters_for_template(params_for_template, missing_params);
+    print_required_parameters_for_template(&recipe_template, params_for_template, missing_params);
 
     Ok(())
 }
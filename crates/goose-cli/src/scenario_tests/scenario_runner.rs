@@ -182,7 +182,7 @@ where
 
         let inner_provider = create(&factory_name, ModelConfig::new(config.model_name)?).await?;
 
-        let test_provider = Arc::new(TestProvider::new_recording(inner_provider, &file_path));
+        let test_provider = Arc::new(TestProvider::new_recording(inner_provider, &file_path)?);
         (
             test_provider.clone(),
             Some(test_provider),
@@ -8,15 +8,17 @@ use goose::config::{
     get_all_extensions, get_enabled_extensions, Config, ExtensionConfig,
 };
 use goose::providers::create;
-use goose::recipe::{Response, SubRecipe};
+use goose::recipe::{RecipeSecrets, Response, SubRecipe};
 
 use goose::agents::extension::PlatformExtensionContext;
+use goose::agents::extension_manager::extension_startup_concurrency;
 use goose::session::SessionManager;
 use goose::session::{EnabledExtensionsState, ExtensionState};
 use rustyline::EditMode;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::process;
 use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tokio::task::JoinSet;
 
 /// Configuration for building a new Goose session
@@ -67,6 +69,10 @@ pub struct SessionBuilderConfig {
     pub final_output_response: Option<Response>,
     /// Retry configuration for automated validation and recovery
     pub retry_config: Option<RetryConfig>,
+    /// Secret parameter values of the recipe, passed to the session's extensions
+    pub recipe_secrets: RecipeSecrets,
+    /// Answer tool calls with a note instead of running them
+    pub dry_run: bool,
 }
 
 /// Offers to help debug an extension failure by creating a minimal debugging session
@@ -194,6 +200,8 @@ pub struct SessionSettings {
     pub goose_model: Option<String>,
     pub goose_provider: Option<String>,
     pub temperature: Option<f32>,
+    pub max_output_tokens: Option<i32>,
+    pub tool_rate_limits: Option<HashMap<String, u32>>,
 }
 
 pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
@@ -224,20 +232,41 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
 
     let temperature = session_config.settings.as_ref().and_then(|s| s.temperature);
 
-    let model_config = goose::model::ModelConfig::new(&model_name)
+    let mut model_config = goose::model::ModelConfig::new(&model_name)
         .unwrap_or_else(|e| {
             output::render_error(&format!("Failed to create model configuration: {}", e));
             process::exit(1);
         })
         .with_temperature(temperature);
+    if let Some(max_output_tokens) = session_config
+        .settings
+        .as_ref()
+        .and_then(|s| s.max_output_tokens)
+    {
+        model_config = model_config.with_max_tokens(Some(max_output_tokens));
+    }
 
     // Create the agent
     let agent: Agent = Agent::new();
 
+    if let Some(limits) = session_config
+        .settings
+        .as_ref()
+        .and_then(|s| s.tool_rate_limits.as_ref())
+    {
+        agent.set_tool_rate_limits(limits);
+    }
+
     if let Some(sub_recipes) = session_config.sub_recipes {
         agent.add_sub_recipes(sub_recipes).await;
     }
 
+    if !session_config.recipe_secrets.is_empty() {
+        agent
+            .set_recipe_secrets(session_config.recipe_secrets)
+            .await;
+    }
+
     if let Some(final_output_response) = session_config.final_output_response {
         agent.add_final_output_tool(final_output_response).await;
     }
@@ -394,12 +423,15 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
 
     let mut set = JoinSet::new();
     let agent_ptr = Arc::new(agent);
+    let startup_permits = Arc::new(Semaphore::new(extension_startup_concurrency()));
 
     let mut waiting_on = HashSet::new();
     for extension in extensions_to_run {
         waiting_on.insert(extension.name());
         let agent_ptr = agent_ptr.clone();
+        let startup_permits = startup_permits.clone();
         set.spawn(async move {
+            let _permit = startup_permits.acquire().await;
             (
                 extension.name(),
                 agent_ptr.add_extension(extension.clone()).await,
@@ -469,6 +501,7 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
         session_config.retry_config.clone(),
     )
     .await;
+    session.set_dry_run(session_config.dry_run);
 
     // Add stdio extensions if provided
     for extension_str in session_config.extensions {
@@ -584,7 +617,10 @@ pub async fn build_session(session_config: SessionBuilderConfig) -> CliSession {
             schedule_id: None,
             execution_mode: None,
             max_turns: None,
+            max_tool_calls_per_turn: None,
             retry_config: None,
+            request_metadata: Default::default(),
+            dry_run: false,
         };
 
         if let Err(e) = session
@@ -655,6 +691,8 @@ mod tests {
             sub_recipes: None,
             final_output_response: None,
             retry_config: None,
+            recipe_secrets: Default::default(),
+            dry_run: false,
         };
 
         assert_eq!(config.extensions.len(), 1);
@@ -1,3 +1,4 @@
+use goose::config::GooseMode;
 use rustyline::completion::{Completer, FilenameCompleter, Pair};
 use rustyline::highlight::{CmdKind, Highlighter};
 use rustyline::hint::Hinter;
@@ -81,7 +82,7 @@ impl GooseCompleter {
 
     /// Complete flags for the /mode command
     fn complete_mode_flags(&self, line: &str) -> Result<(usize, Vec<Pair>)> {
-        let modes = ["auto", "approve", "smart_approve", "chat"];
+        let modes = GooseMode::ALL.map(|mode| mode.as_str());
 
         let parts: Vec<&str> = line.split_whitespace().collect();
 
@@ -1,8 +1,10 @@
 use super::completion::GooseCompleter;
+use super::output::Verbosity;
 use anyhow::Result;
-use rustyline::Editor;
+use rustyline::{EditMode, Editor};
 use shlex;
 use std::collections::HashMap;
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum InputResult {
@@ -10,17 +12,37 @@ pub enum InputResult {
     Exit,
     AddExtension(String),
     AddBuiltin(String),
+    SearchExtensions(String),
+    InstallExtension(String),
     ToggleTheme,
     SelectTheme(String),
+    /// Set the verbosity, or show it when None
+    SetVerbosity(Option<Verbosity>),
     Retry,
     ListPrompts(Option<String>),
     PromptCommand(PromptCommandOptions),
     GooseMode(String),
+    SetEditMode(EditMode),
     Plan(PlanCommandOptions),
     EndPlan,
     Clear,
     Recipe(Option<String>),
     Summarize,
+    Resend,
+    Copy(CopyTarget),
+    Pin,
+    Unpin,
+    Checkpoint,
+    /// Restore a checkpoint, the latest one when no id is given
+    Restore(Option<i64>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTarget {
+    /// The full text of the last assistant message
+    Message,
+    /// Only the last fenced code block of the last assistant message
+    CodeBlock,
 }
 
 #[derive(Debug)]
@@ -106,6 +128,46 @@ pub fn get_input(
     }
 }
 
+/// Like [`get_input`], but gives up and returns `None` when no line is entered within `timeout`.
+///
+/// rustyline can't be interrupted, so the read happens on its own thread, which hands the editor
+/// back along with the input. On timeout that thread is left blocked on the terminal; we restore
+/// the terminal settings it changed so the shell is usable once goose exits.
+pub async fn get_input_with_idle_timeout(
+    mut editor: Editor<GooseCompleter, rustyline::history::DefaultHistory>,
+    timeout: Duration,
+) -> Result<
+    Option<(
+        Editor<GooseCompleter, rustyline::history::DefaultHistory>,
+        InputResult,
+    )>,
+> {
+    #[cfg(unix)]
+    let saved_termios = nix::sys::termios::tcgetattr(std::io::stdin()).ok();
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        let input = get_input(&mut editor);
+        let _ = tx.send((editor, input));
+    });
+
+    match tokio::time::timeout(timeout, rx).await {
+        Ok(Ok((editor, input))) => Ok(Some((editor, input?))),
+        Ok(Err(_)) => Err(anyhow::anyhow!("The input reader stopped unexpectedly")),
+        Err(_) => {
+            #[cfg(unix)]
+            if let Some(termios) = saved_termios {
+                let _ = nix::sys::termios::tcsetattr(
+                    std::io::stdin(),
+                    nix::sys::termios::SetArg::TCSANOW,
+                    &termios,
+                );
+            }
+            Ok(None)
+        }
+    }
+}
+
 fn handle_slash_command(input: &str) -> Option<InputResult> {
     let input = input.trim();
 
@@ -115,12 +177,23 @@ fn handle_slash_command(input: &str) -> Option<InputResult> {
     const CMD_PROMPT_WITH_SPACE: &str = "/prompt ";
     const CMD_EXTENSION: &str = "/extension ";
     const CMD_BUILTIN: &str = "/builtin ";
+    const CMD_EXTENSIONS_SEARCH: &str = "/extensions search";
+    const CMD_EXTENSIONS_INSTALL: &str = "/extensions install ";
     const CMD_MODE: &str = "/mode ";
+    const CMD_EDITMODE: &str = "/editmode ";
     const CMD_PLAN: &str = "/plan";
     const CMD_ENDPLAN: &str = "/endplan";
     const CMD_CLEAR: &str = "/clear";
     const CMD_RECIPE: &str = "/recipe";
     const CMD_SUMMARIZE: &str = "/summarize";
+    const CMD_RESEND: &str = "/resend";
+    const CMD_COPY: &str = "/copy";
+    const CMD_COPY_CODE: &str = "/copy code";
+    const CMD_PIN: &str = "/pin";
+    const CMD_UNPIN: &str = "/unpin";
+    const CMD_CHECKPOINT: &str = "/checkpoint";
+    const CMD_RESTORE: &str = "/restore";
+    const CMD_VERBOSITY: &str = "/verbosity";
 
     match input {
         "/exit" | "/quit" => Some(InputResult::Exit),
@@ -170,17 +243,70 @@ fn handle_slash_command(input: &str) -> Option<InputResult> {
         s if s.starts_with(CMD_EXTENSION) => Some(InputResult::AddExtension(
             s[CMD_EXTENSION.len()..].to_string(),
         )),
+        s if s.starts_with(CMD_EXTENSIONS_SEARCH) => Some(InputResult::SearchExtensions(
+            s[CMD_EXTENSIONS_SEARCH.len()..].trim().to_string(),
+        )),
+        s if s.starts_with(CMD_EXTENSIONS_INSTALL) => Some(InputResult::InstallExtension(
+            s[CMD_EXTENSIONS_INSTALL.len()..].trim().to_string(),
+        )),
         s if s.starts_with(CMD_BUILTIN) => {
             Some(InputResult::AddBuiltin(s[CMD_BUILTIN.len()..].to_string()))
         }
         s if s.starts_with(CMD_MODE) => {
             Some(InputResult::GooseMode(s[CMD_MODE.len()..].to_string()))
         }
+        s if s.starts_with(CMD_EDITMODE) => {
+            match s[CMD_EDITMODE.len()..].trim().to_lowercase().as_str() {
+                "vi" => Some(InputResult::SetEditMode(EditMode::Vi)),
+                "emacs" => Some(InputResult::SetEditMode(EditMode::Emacs)),
+                other => {
+                    println!(
+                        "Unknown edit mode: {} Available modes are: vi, emacs",
+                        other
+                    );
+                    Some(InputResult::Retry)
+                }
+            }
+        }
         s if s.starts_with(CMD_PLAN) => parse_plan_command(s[CMD_PLAN.len()..].trim().to_string()),
         s if s == CMD_ENDPLAN => Some(InputResult::EndPlan),
         s if s == CMD_CLEAR => Some(InputResult::Clear),
         s if s.starts_with(CMD_RECIPE) => parse_recipe_command(s),
         s if s == CMD_SUMMARIZE => Some(InputResult::Summarize),
+        s if s == CMD_RESEND => Some(InputResult::Resend),
+        s if s == CMD_COPY => Some(InputResult::Copy(CopyTarget::Message)),
+        s if s == CMD_COPY_CODE => Some(InputResult::Copy(CopyTarget::CodeBlock)),
+        s if s == CMD_PIN => Some(InputResult::Pin),
+        s if s == CMD_UNPIN => Some(InputResult::Unpin),
+        s if s == CMD_CHECKPOINT => Some(InputResult::Checkpoint),
+        s if s == CMD_RESTORE => Some(InputResult::Restore(None)),
+        s if s.starts_with(CMD_RESTORE) && s[CMD_RESTORE.len()..].starts_with(' ') => {
+            let id = s[CMD_RESTORE.len()..].trim();
+            match id.parse() {
+                Ok(id) => Some(InputResult::Restore(Some(id))),
+                Err(_) => {
+                    println!(
+                        "Invalid checkpoint '{}'. Use the number printed by /checkpoint.",
+                        id
+                    );
+                    Some(InputResult::Retry)
+                }
+            }
+        }
+        s if s == CMD_VERBOSITY => Some(InputResult::SetVerbosity(None)),
+        s if s.starts_with(CMD_VERBOSITY) && s[CMD_VERBOSITY.len()..].starts_with(' ') => {
+            let level = s[CMD_VERBOSITY.len()..].trim();
+            match Verbosity::from_config_str(level) {
+                Some(verbosity) => Some(InputResult::SetVerbosity(Some(verbosity))),
+                None => {
+                    println!(
+                        "Unknown verbosity: {} Available levels are: quiet, normal, verbose, debug",
+                        level
+                    );
+                    Some(InputResult::Retry)
+                }
+            }
+        }
         _ => None,
     }
 }
@@ -292,11 +418,15 @@ fn print_help() {
 /exit or /quit - Exit the session
 /t - Toggle Light/Dark/Ansi theme
 /t <name> - Set theme directly (light, dark, ansi)
-/extension <command> - Add a stdio extension (format: ENV1=val1 command args...)
-/builtin <names> - Add builtin extensions by name (comma-separated)
+/verbosity [level] - Show or set how much tool output is shown (quiet, normal, verbose, debug), the choice is saved for future sessions
+/extension <command> - Add a stdio extension (format: ENV1=val1 [timeout=secs] command args...)
+/builtin <names> [timeout=secs] - Add builtin extensions by name (comma-separated)
+/extensions search [query] - Search the bundled extensions and the registry at $GOOSE_EXTENSION_REGISTRY_URL
+/extensions install <id> - Add an extension from the registry by id
 /prompts [--extension <name>] - List all available prompts, optionally filtered by extension
 /prompt <n> [--info] [key=value...] - Get prompt info or execute a prompt
 /mode <name> - Set the goose mode to use ('auto', 'approve', 'chat', 'smart_approve')
+/editmode <vi|emacs> - Switch the line editing keybindings, the choice is saved for future sessions
 /plan <message_text> -  Enters 'plan' mode with optional message. Create a plan based on the current messages and asks user if they want to act on it.
                         If user acts on the plan, goose mode is set to 'auto' and returns to 'normal' goose mode.
                         To warm up goose before using '/plan', we recommend setting '/mode approve' & putting appropriate context into goose.
@@ -306,6 +436,13 @@ fn print_help() {
 /recipe [filepath] - Generate a recipe from the current conversation and save it to the specified filepath (must end with .yaml).
                        If no filepath is provided, it will be saved to ./recipe.yaml.
 /summarize - Summarize the current conversation to reduce context length while preserving key information.
+/resend - Send your last message again, e.g. after a provider error
+/copy - Copy goose's last response to the clipboard
+/copy code - Copy the last code block in goose's last response to the clipboard
+/pin - Pin your last message so it is kept word for word when the conversation is summarized
+/unpin - Remove all pins
+/checkpoint - Save the session as it is now so it can be restored later
+/restore [id] - Put the session back to a checkpoint, the latest one if no id is given
 /? or /help - Display this help message
 /clear - Clears the current chat history
 
@@ -552,6 +689,114 @@ mod tests {
         assert!(matches!(result, Some(InputResult::Summarize)));
     }
 
+    #[test]
+    fn test_editmode_command() {
+        let result = handle_slash_command("/editmode vi");
+        assert!(matches!(
+            result,
+            Some(InputResult::SetEditMode(EditMode::Vi))
+        ));
+
+        let result = handle_slash_command("/editmode Emacs");
+        assert!(matches!(
+            result,
+            Some(InputResult::SetEditMode(EditMode::Emacs))
+        ));
+
+        let result = handle_slash_command("/editmode nano");
+        assert!(matches!(result, Some(InputResult::Retry)));
+    }
+
+    #[test]
+    fn test_extensions_commands() {
+        let result = handle_slash_command("/extensions search web");
+        assert!(matches!(result, Some(InputResult::SearchExtensions(q)) if q == "web"));
+
+        let result = handle_slash_command("/extensions search");
+        assert!(matches!(result, Some(InputResult::SearchExtensions(q)) if q.is_empty()));
+
+        let result = handle_slash_command("/extensions install fetch");
+        assert!(matches!(result, Some(InputResult::InstallExtension(id)) if id == "fetch"));
+    }
+
+    #[test]
+    fn test_copy_command() {
+        let result = handle_slash_command("/copy");
+        assert!(matches!(
+            result,
+            Some(InputResult::Copy(CopyTarget::Message))
+        ));
+
+        let result = handle_slash_command("/copy code");
+        assert!(matches!(
+            result,
+            Some(InputResult::Copy(CopyTarget::CodeBlock))
+        ));
+    }
+
+    #[test]
+    fn test_pin_commands() {
+        assert!(matches!(
+            handle_slash_command("/pin"),
+            Some(InputResult::Pin)
+        ));
+        assert!(matches!(
+            handle_slash_command("/unpin"),
+            Some(InputResult::Unpin)
+        ));
+        assert!(handle_slash_command("/pinned").is_none());
+    }
+
+    #[test]
+    fn test_checkpoint_commands() {
+        assert!(matches!(
+            handle_slash_command("/checkpoint"),
+            Some(InputResult::Checkpoint)
+        ));
+        assert!(matches!(
+            handle_slash_command("/restore"),
+            Some(InputResult::Restore(None))
+        ));
+        assert!(matches!(
+            handle_slash_command("/restore 12"),
+            Some(InputResult::Restore(Some(12)))
+        ));
+        assert!(matches!(
+            handle_slash_command("/restore latest"),
+            Some(InputResult::Retry)
+        ));
+        assert!(handle_slash_command("/restored").is_none());
+    }
+
+    #[test]
+    fn test_verbosity_command() {
+        assert!(matches!(
+            handle_slash_command("/verbosity"),
+            Some(InputResult::SetVerbosity(None))
+        ));
+        assert!(matches!(
+            handle_slash_command("/verbosity Verbose"),
+            Some(InputResult::SetVerbosity(Some(Verbosity::Verbose)))
+        ));
+        assert!(matches!(
+            handle_slash_command("/verbosity loud"),
+            Some(InputResult::Retry)
+        ));
+    }
+
+    #[test]
+    fn test_resend_command() {
+        let result = handle_slash_command("/resend");
+        assert!(matches!(result, Some(InputResult::Resend)));
+
+        let result = handle_slash_command("  /resend  ");
+        assert!(matches!(result, Some(InputResult::Resend)));
+
+        // Arguments are not accepted
+        let result = handle_slash_command("/resend now");
+        assert!(result.is_none());
+    }
+
     #[test]
     fn test_get_input_prompt_string() {
         let prompt = get_input_prompt_string();
@@ -4,6 +4,7 @@ mod export;
 mod input;
 mod output;
 mod prompt;
+mod result_format;
 mod task_execution_display;
 mod thinking;
 
@@ -22,15 +23,18 @@ use goose::permission::Permission;
 use goose::permission::PermissionConfirmation;
 use goose::providers::base::Provider;
 use goose::utils::safe_truncate;
+pub use output::register_result_formatter;
+pub use result_format::{FormattedResult, ResultFormatter, ResultFormatterRegistry};
 
 use anyhow::{Context, Result};
 use completion::GooseCompleter;
 use goose::agents::extension::{Envs, ExtensionConfig};
+use goose::agents::extension_registry;
 use goose::agents::types::RetryConfig;
-use goose::agents::{Agent, SessionConfig};
-use goose::config::Config;
+use goose::agents::{Agent, PromptDescriptor, SessionConfig};
+use goose::config::{Config, GooseMode};
 use goose::providers::pricing::initialize_pricing_cache;
-use goose::session::SessionManager;
+use goose::session::{CheckpointId, SessionManager};
 use input::InputResult;
 use rmcp::model::PromptMessage;
 use rmcp::model::ServerNotification;
@@ -39,7 +43,9 @@ use rmcp::model::{ErrorCode, ErrorData};
 use goose::config::paths::Paths;
 use goose::conversation::message::{Message, MessageContent};
 use rand::{distributions::Alphanumeric, Rng};
+use rustyline::config::Configurer;
 use rustyline::EditMode;
+use serde::Deserialize;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -65,6 +71,8 @@ pub struct CliSession {
     max_turns: Option<u32>,
     edit_mode: Option<EditMode>,
     retry_config: Option<RetryConfig>,
+    last_user_message: Option<String>,
+    dry_run: bool,
 }
 
 // Cache structure for completion data
@@ -84,11 +92,80 @@ impl CompletionCache {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PlannerResponseType {
     Plan,
     ClarifyingQuestions,
 }
 
+/// Below this confidence the user is asked whether the planner produced a plan
+const PLANNER_CLASSIFICATION_MIN_CONFIDENCE: f32 = 0.7;
+
+/// How long the "Allow for N Minutes" choice of the tool confirmation menu trusts a tool
+const TRUST_TOOL_MINUTES: u32 = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannerClassification {
+    pub response_type: PlannerResponseType,
+    /// How sure the classifier is, from 0 to 1. `None` when the model did not give a
+    /// structured answer and the label was guessed from its text.
+    pub confidence: Option<f32>,
+}
+
+impl PlannerClassification {
+    pub fn is_confident(&self) -> bool {
+        self.confidence
+            .is_some_and(|c| c >= PLANNER_CLASSIFICATION_MIN_CONFIDENCE)
+    }
+}
+
+/// Parse the classifier's reply, either the requested JSON object or a bare label
+fn parse_planner_classification(predicted: &str) -> PlannerClassification {
+    #[derive(Deserialize)]
+    struct Structured {
+        label: String,
+        confidence: f32,
+    }
+
+    let trimmed = predicted
+        .trim()
+        .trim_start_matches("```json")
+        .trim_start_matches("```")
+        .trim_end_matches("```")
+        .trim();
+    if let Ok(structured) = serde_json::from_str::<Structured>(trimmed) {
+        let label = structured.label.to_lowercase();
+        let response_type = match label.trim() {
+            "plan" => Some(PlannerResponseType::Plan),
+            "clarifying_questions" | "clarifying questions" => {
+                Some(PlannerResponseType::ClarifyingQuestions)
+            }
+            _ => None,
+        };
+        if let Some(response_type) = response_type {
+            return PlannerClassification {
+                response_type,
+                confidence: Some(structured.confidence.clamp(0.0, 1.0)),
+            };
+        }
+    }
+
+    // Fall back to looking for the label in the text. Check for questions first, since a
+    // reply like "clarifying questions about the plan" also mentions the plan.
+    let lower = predicted.to_lowercase();
+    let response_type = if lower.contains("clarifying") || lower.contains("question") {
+        PlannerResponseType::ClarifyingQuestions
+    } else if lower.contains("plan") {
+        PlannerResponseType::Plan
+    } else {
+        PlannerResponseType::ClarifyingQuestions
+    };
+    PlannerClassification {
+        response_type,
+        confidence: None,
+    }
+}
+
 /// Decide if the planner's reponse is a plan or a clarifying question
 ///
 /// This function is called after the planner has generated a response
@@ -97,25 +174,19 @@ pub enum PlannerResponseType {
 pub async fn classify_planner_response(
     message_text: String,
     provider: Arc<dyn Provider>,
-) -> Result<PlannerResponseType> {
-    let prompt = format!("The text below is the output from an AI model which can either provide a plan or list of clarifying questions. Based on the text below, decide if the output is a \"plan\" or \"clarifying questions\".\n---\n{message_text}");
+) -> Result<PlannerClassification> {
+    let prompt = format!("The text below is the output from an AI model which can either provide a plan or list of clarifying questions. Based on the text below, decide if the output is a \"plan\" or \"clarifying_questions\".\n---\n{message_text}");
 
-    // Generate the description
     let message = Message::user().with_text(&prompt);
     let (result, _usage) = provider
         .complete(
-            "Reply only with the classification label: \"plan\" or \"clarifying questions\"",
+            "Reply only with a JSON object of the form {\"label\": \"plan\" | \"clarifying_questions\", \"confidence\": <number between 0 and 1>}, with no other text",
             &[message],
             &[],
         )
         .await?;
 
-    let predicted = result.as_concat_text();
-    if predicted.to_lowercase().contains("plan") {
-        Ok(PlannerResponseType::Plan)
-    } else {
-        Ok(PlannerResponseType::ClarifyingQuestions)
-    }
+    Ok(parse_planner_classification(&result.as_concat_text()))
 }
 
 impl CliSession {
@@ -148,9 +219,16 @@ impl CliSession {
             max_turns,
             edit_mode,
             retry_config,
+            last_user_message: None,
+            dry_run: false,
         }
     }
 
+    /// Answer the agent's tool calls with a note instead of running them
+    pub fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
     pub fn session_id(&self) -> Option<&String> {
         self.session_id.as_ref()
     }
@@ -158,11 +236,13 @@ impl CliSession {
     /// Add a stdio extension to the session
     ///
     /// # Arguments
-    /// * `extension_command` - Full command string including environment variables
-    ///   Format: "ENV1=val1 ENV2=val2 command args..."
+    /// * `extension_command` - Full command string including environment variables and an
+    ///   optional timeout in seconds
+    ///   Format: "ENV1=val1 ENV2=val2 [timeout=600] command args..."
     pub async fn add_extension(&mut self, extension_command: String) -> Result<()> {
         let mut parts: Vec<&str> = extension_command.split_whitespace().collect();
         let mut envs = HashMap::new();
+        let mut timeout = None;
 
         while let Some(part) = parts.first() {
             if !part.contains('=') {
@@ -170,7 +250,11 @@ impl CliSession {
             }
             let env_part = parts.remove(0);
             let (key, value) = env_part.split_once('=').unwrap();
-            envs.insert(key.to_string(), value.to_string());
+            if key == TIMEOUT_TOKEN {
+                timeout = Some(parse_timeout(value)?);
+            } else {
+                envs.insert(key.to_string(), value.to_string());
+            }
         }
 
         if parts.is_empty() {
@@ -191,8 +275,7 @@ impl CliSession {
             envs: Envs::new(envs),
             env_keys: Vec::new(),
             description: goose::config::DEFAULT_EXTENSION_DESCRIPTION.to_string(),
-            // TODO: should set timeout
-            timeout: Some(goose::config::DEFAULT_EXTENSION_TIMEOUT),
+            timeout: Some(timeout.unwrap_or(goose::config::DEFAULT_EXTENSION_TIMEOUT)),
             bundled: None,
             available_tools: Vec::new(),
         };
@@ -211,8 +294,10 @@ impl CliSession {
     /// Add a remote extension to the session
     ///
     /// # Arguments
-    /// * `extension_url` - URL of the server
+    /// * `extension_url` - URL of the server, optionally followed by a timeout in seconds
+    ///   Format: "url [timeout=600]"
     pub async fn add_remote_extension(&mut self, extension_url: String) -> Result<()> {
+        let (extension_url, timeout) = split_timeout(&extension_url)?;
         let name: String = rand::thread_rng()
             .sample_iter(&Alphanumeric)
             .take(8)
@@ -225,8 +310,7 @@ impl CliSession {
             envs: Envs::new(HashMap::new()),
             env_keys: Vec::new(),
             description: goose::config::DEFAULT_EXTENSION_DESCRIPTION.to_string(),
-            // TODO: should set timeout
-            timeout: Some(goose::config::DEFAULT_EXTENSION_TIMEOUT),
+            timeout: Some(timeout.unwrap_or(goose::config::DEFAULT_EXTENSION_TIMEOUT)),
             bundled: None,
             available_tools: Vec::new(),
         };
@@ -245,8 +329,11 @@ impl CliSession {
     /// Add a streamable HTTP extension to the session
     ///
     /// # Arguments
-    /// * `extension_url` - URL of the server
-    pub async fn add_streamable_http_extension(&mut self, extension_url: String) -> Result<()> {
+    /// * `extension_spec` - URL optionally followed by headers and a timeout in seconds, with
+    ///   shell-style quoting
+    ///   Format: "url \"Header-Name=value\"... [timeout=600]"
+    pub async fn add_streamable_http_extension(&mut self, extension_spec: String) -> Result<()> {
+        let (extension_url, headers, timeout) = parse_streamable_http_spec(&extension_spec)?;
         let name: String = rand::thread_rng()
             .sample_iter(&Alphanumeric)
             .take(8)
@@ -258,10 +345,9 @@ impl CliSession {
             uri: extension_url,
             envs: Envs::new(HashMap::new()),
             env_keys: Vec::new(),
-            headers: HashMap::new(),
+            headers,
             description: goose::config::DEFAULT_EXTENSION_DESCRIPTION.to_string(),
-            // TODO: should set timeout
-            timeout: Some(goose::config::DEFAULT_EXTENSION_TIMEOUT),
+            timeout: Some(timeout.unwrap_or(goose::config::DEFAULT_EXTENSION_TIMEOUT)),
             bundled: None,
             available_tools: Vec::new(),
         };
@@ -280,15 +366,17 @@ impl CliSession {
     /// Add a builtin extension to the session
     ///
     /// # Arguments
-    /// * `builtin_name` - Name of the builtin extension(s), comma separated
+    /// * `builtin_name` - Name of the builtin extension(s), comma separated, optionally
+    ///   followed by a timeout in seconds that applies to all of them
+    ///   Format: "developer,memory [timeout=600]"
     pub async fn add_builtin(&mut self, builtin_name: String) -> Result<()> {
+        let (builtin_name, timeout) = split_timeout(&builtin_name)?;
         for name in builtin_name.split(',') {
             let extension_name = name.trim().to_string();
             let config = ExtensionConfig::Builtin {
                 name: extension_name,
                 display_name: None,
-                // TODO: should set a timeout
-                timeout: Some(goose::config::DEFAULT_EXTENSION_TIMEOUT),
+                timeout: Some(timeout.unwrap_or(goose::config::DEFAULT_EXTENSION_TIMEOUT)),
                 bundled: None,
                 description: name.trim().to_string(),
                 available_tools: Vec::new(),
@@ -418,52 +506,41 @@ impl CliSession {
                 }
             };
 
+        output::load_theme();
         output::display_greeting();
+        let idle_timeout = idle_timeout();
         loop {
             // Display context usage before each prompt
             self.display_context_usage().await?;
 
-            match input::get_input(&mut editor)? {
-                InputResult::Message(content) => {
-                    match self.run_mode {
-                        RunMode::Normal => {
-                            save_history(&mut editor);
-
-                            self.push_message(Message::user().with_text(&content));
-
-                            // Track the current directory and last instruction in projects.json
-                            if let Err(e) = crate::project_tracker::update_project_tracker(
-                                Some(&content),
-                                self.session_id.as_deref(),
-                            ) {
-                                eprintln!("Warning: Failed to update project tracker with instruction: {}", e);
-                            }
-
-                            let _provider = self.agent.provider().await?;
-
-                            output::show_thinking();
-                            let start_time = Instant::now();
-                            self.process_agent_response(true, CancellationToken::default())
-                                .await?;
-                            output::hide_thinking();
+            let input = match idle_timeout {
+                Some(timeout) => match input::get_input_with_idle_timeout(editor, timeout).await? {
+                    Some((returned, input)) => {
+                        editor = returned;
+                        input
+                    }
+                    None => {
+                        self.close_idle_session(timeout).await;
+                        break;
+                    }
+                },
+                None => input::get_input(&mut editor)?,
+            };
 
-                            // Display elapsed time
-                            let elapsed = start_time.elapsed();
-                            let elapsed_str = format_elapsed_time(elapsed);
-                            println!(
-                                "\n{}",
-                                console::style(format!("⏱️  Elapsed time: {}", elapsed_str)).dim()
-                            );
-                        }
-                        RunMode::Plan => {
-                            let mut plan_messages = self.messages.clone();
-                            plan_messages.push(Message::user().with_text(&content));
-                            let reasoner = get_reasoner().await?;
-                            self.plan_with_reasoner_model(plan_messages, reasoner)
-                                .await?;
+            match input {
+                InputResult::Message(content) => match self.run_mode {
+                    RunMode::Normal => {
+                        save_history(&mut editor);
+                        self.send_user_message(content).await?;
+                    }
+                    RunMode::Plan => {
+                        let mut plan_messages = self.messages.clone();
+                        plan_messages.push(Message::user().with_text(&content));
+                        if let Err(e) = self.plan_with_reasoner_model(plan_messages).await {
+                            output::render_error(&format!("Planning failed: {}", e));
                         }
                     }
-                }
+                },
                 input::InputResult::Exit => break,
                 input::InputResult::AddExtension(cmd) => {
                     save_history(&mut editor);
@@ -481,6 +558,30 @@ impl CliSession {
                         Err(e) => output::render_builtin_error(&names, &e.to_string()),
                     }
                 }
+                input::InputResult::SearchExtensions(query) => {
+                    save_history(&mut editor);
+
+                    match extension_registry::list_extensions().await {
+                        Ok(entries) => output::render_registry_entries(
+                            &extension_registry::search(&entries, &query),
+                        ),
+                        Err(e) => output::render_error(&format!(
+                            "Failed to load the extension registry: {}",
+                            e
+                        )),
+                    }
+                }
+                input::InputResult::InstallExtension(id) => {
+                    save_history(&mut editor);
+
+                    match self.agent.install_extension_from_registry(&id).await {
+                        Ok(_) => {
+                            self.invalidate_completion_cache().await;
+                            output::render_extension_success(&id);
+                        }
+                        Err(e) => output::render_extension_error(&id, &e.to_string()),
+                    }
+                }
                 input::InputResult::ToggleTheme => {
                     save_history(&mut editor);
 
@@ -524,6 +625,18 @@ impl CliSession {
                     output::set_theme(new_theme);
                     continue;
                 }
+                input::InputResult::SetVerbosity(verbosity) => {
+                    save_history(&mut editor);
+
+                    match verbosity {
+                        Some(verbosity) => {
+                            output::set_verbosity(verbosity);
+                            println!("Verbosity set to {}", verbosity.as_str());
+                        }
+                        None => println!("Verbosity is {}", output::get_verbosity().as_str()),
+                    }
+                    continue;
+                }
                 input::InputResult::Retry => continue,
                 input::InputResult::ListPrompts(extension) => {
                     save_history(&mut editor);
@@ -537,16 +650,13 @@ impl CliSession {
                     save_history(&mut editor);
 
                     let config = Config::global();
-                    let mode = mode.to_lowercase();
-
-                    // Check if mode is valid
-                    if !["auto", "approve", "chat", "smart_approve"].contains(&mode.as_str()) {
-                        output::render_error(&format!(
-                            "Invalid mode '{}'. Mode must be one of: auto, approve, chat",
-                            mode
-                        ));
-                        continue;
-                    }
+                    let mode = match mode.parse::<GooseMode>() {
+                        Ok(mode) => mode,
+                        Err(e) => {
+                            output::render_error(&e.to_string());
+                            continue;
+                        }
+                    };
 
                     config
                         .set_param("GOOSE_MODE", Value::String(mode.to_string()))
@@ -554,6 +664,24 @@ impl CliSession {
                     output::goose_mode_message(&format!("Goose mode set to '{}'", mode));
                     continue;
                 }
+                input::InputResult::SetEditMode(edit_mode) => {
+                    save_history(&mut editor);
+
+                    editor.set_edit_mode(edit_mode);
+                    self.edit_mode = Some(edit_mode);
+
+                    let name = match edit_mode {
+                        EditMode::Vi => "vi",
+                        EditMode::Emacs => "emacs",
+                    };
+                    if let Err(e) =
+                        Config::global().set_param("EDIT_MODE", Value::String(name.to_string()))
+                    {
+                        eprintln!("Warning: Failed to save edit mode: {}", e);
+                    }
+                    println!("Switched to {} edit mode", name);
+                    continue;
+                }
                 input::InputResult::Plan(options) => {
                     self.run_mode = RunMode::Plan;
                     output::render_enter_plan_mode();
@@ -565,9 +693,9 @@ impl CliSession {
                     let mut plan_messages = self.messages.clone();
                     plan_messages.push(Message::user().with_text(&message_text));
 
-                    let reasoner = get_reasoner().await?;
-                    self.plan_with_reasoner_model(plan_messages, reasoner)
-                        .await?;
+                    if let Err(e) = self.plan_with_reasoner_model(plan_messages).await {
+                        output::render_error(&format!("Planning failed: {}", e));
+                    }
                 }
                 input::InputResult::EndPlan => {
                     self.run_mode = RunMode::Normal;
@@ -635,6 +763,158 @@ impl CliSession {
 
                     continue;
                 }
+                InputResult::Resend => {
+                    save_history(&mut editor);
+
+                    match self.last_user_message_text() {
+                        Some(content) => {
+                            output::render_text(&content, Some(Color::Cyan), true);
+                            self.send_user_message(content).await?;
+                        }
+                        None => println!(
+                            "{}",
+                            console::style("No previous message to resend").yellow()
+                        ),
+                    }
+                }
+                InputResult::Pin => {
+                    save_history(&mut editor);
+
+                    match self.last_user_message_index() {
+                        Some(index) => {
+                            self.messages.set_pinned(index, true);
+                            if self.save_conversation().await {
+                                println!(
+                                    "{}",
+                                    console::style(
+                                        "Pinned your last message, it will be kept when the conversation is summarized"
+                                    )
+                                    .green()
+                                );
+                            }
+                        }
+                        None => println!("{}", console::style("No message to pin").yellow()),
+                    }
+                }
+                InputResult::Unpin => {
+                    save_history(&mut editor);
+
+                    let pinned: Vec<usize> = self
+                        .messages
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, message)| message.metadata.pinned)
+                        .map(|(index, _)| index)
+                        .collect();
+                    for index in &pinned {
+                        self.messages.set_pinned(*index, false);
+                    }
+                    if self.save_conversation().await {
+                        println!("Removed {} pin(s)", pinned.len());
+                    }
+                }
+                InputResult::Checkpoint => {
+                    save_history(&mut editor);
+
+                    let Some(session_id) = &self.session_id else {
+                        output::render_error("Checkpoints need a saved session");
+                        continue;
+                    };
+                    match SessionManager::checkpoint(session_id).await {
+                        Ok(checkpoint) => println!(
+                            "{}",
+                            console::style(format!(
+                                "Saved checkpoint {}, use /restore {} to return to it",
+                                checkpoint, checkpoint
+                            ))
+                            .green()
+                        ),
+                        Err(e) => {
+                            output::render_error(&format!("Failed to save checkpoint: {}", e))
+                        }
+                    }
+                }
+                InputResult::Restore(id) => {
+                    save_history(&mut editor);
+
+                    let Some(session_id) = self.session_id.clone() else {
+                        output::render_error("Checkpoints need a saved session");
+                        continue;
+                    };
+                    let checkpoint = match id {
+                        Some(id) => Some(CheckpointId(id)),
+                        None => match SessionManager::list_checkpoints(&session_id).await {
+                            Ok(checkpoints) => checkpoints.last().map(|checkpoint| checkpoint.id),
+                            Err(e) => {
+                                output::render_error(&format!("Failed to list checkpoints: {}", e));
+                                continue;
+                            }
+                        },
+                    };
+                    let Some(checkpoint) = checkpoint else {
+                        println!(
+                            "{}",
+                            console::style("No checkpoints, save one with /checkpoint").yellow()
+                        );
+                        continue;
+                    };
+                    match SessionManager::restore(&session_id, checkpoint).await {
+                        Ok(conversation) => {
+                            self.messages = conversation;
+                            println!(
+                                "{}",
+                                console::style(format!(
+                                    "Restored checkpoint {} ({} messages)",
+                                    checkpoint,
+                                    self.messages.len()
+                                ))
+                                .green()
+                            );
+                        }
+                        Err(e) => output::render_error(&format!(
+                            "Failed to restore checkpoint {}: {}",
+                            checkpoint, e
+                        )),
+                    }
+                }
+                InputResult::Copy(target) => {
+                    save_history(&mut editor);
+
+                    let last_response = self
+                        .messages
+                        .iter()
+                        .rev()
+                        .find(|message| {
+                            message.role == rmcp::model::Role::Assistant
+                                && !message.as_concat_text().trim().is_empty()
+                        })
+                        .map(|message| message.as_concat_text());
+                    let Some(last_response) = last_response else {
+                        output::render_error("No response from goose to copy yet");
+                        continue;
+                    };
+
+                    let text = match target {
+                        input::CopyTarget::Message => last_response,
+                        input::CopyTarget::CodeBlock => {
+                            match output::last_code_block(&last_response) {
+                                Some(code) => code,
+                                None => {
+                                    output::render_error("No code block in the last response");
+                                    continue;
+                                }
+                            }
+                        }
+                    };
+
+                    match output::copy_to_clipboard(&text) {
+                        Ok(()) => println!("{}", console::style("Copied to clipboard").green()),
+                        Err(e) => {
+                            output::render_error(&format!("Failed to copy to clipboard: {}", e))
+                        }
+                    }
+                    continue;
+                }
                 InputResult::Summarize => {
                     save_history(&mut editor);
 
@@ -660,6 +940,7 @@ impl CliSession {
                                 &self.agent,
                                 &self.messages,
                                 false,
+                                goose::context_mgmt::CompactionStrategy::Summarize,
                             )
                             .await?;
 
@@ -743,8 +1024,15 @@ impl CliSession {
     async fn plan_with_reasoner_model(
         &mut self,
         plan_messages: Conversation,
-        reasoner: Arc<dyn Provider>,
     ) -> Result<(), anyhow::Error> {
+        let reasoner = match get_reasoner().await {
+            Ok(reasoner) => reasoner,
+            Err(e) => {
+                output::render_error(&format!("{}\nPlanning with the main model instead.", e));
+                self.agent.provider().await?
+            }
+        };
+
         let plan_prompt = self.agent.get_plan_prompt().await?;
         output::show_thinking();
         let (plan_response, _usage) = reasoner
@@ -752,9 +1040,32 @@ impl CliSession {
             .await?;
         output::render_message(&plan_response, self.debug);
         output::hide_thinking();
-        let planner_response_type =
+        let classification =
             classify_planner_response(plan_response.as_concat_text(), self.agent.provider().await?)
                 .await?;
+        let planner_response_type = if classification.is_confident() {
+            classification.response_type
+        } else {
+            match cliclack::select("Did goose produce a plan or ask clarifying questions?")
+                .item(PlannerResponseType::Plan, "A plan", "")
+                .item(
+                    PlannerResponseType::ClarifyingQuestions,
+                    "Clarifying questions",
+                    "",
+                )
+                .initial_value(classification.response_type)
+                .interact()
+            {
+                Ok(choice) => choice,
+                Err(e) => {
+                    if e.kind() == std::io::ErrorKind::Interrupted {
+                        classification.response_type
+                    } else {
+                        return Err(e.into());
+                    }
+                }
+            }
+        };
 
         match planner_response_type {
             PlannerResponseType::Plan => {
@@ -841,7 +1152,10 @@ impl CliSession {
             schedule_id: self.scheduled_job_id.clone(),
             execution_mode: None,
             max_turns: self.max_turns,
+            max_tool_calls_per_turn: None,
             retry_config: self.retry_config.clone(),
+            request_metadata: Default::default(),
+            dry_run: self.dry_run,
         });
         let mut stream = self
             .agent
@@ -877,6 +1191,11 @@ impl CliSession {
                                     // No security message - show all options including "Always Allow"
                                     cliclack::select(prompt)
                                         .item(Permission::AllowOnce, "Allow", "Allow the tool call once")
+                                        .item(
+                                            Permission::AllowForMinutes(TRUST_TOOL_MINUTES),
+                                            format!("Allow for {} Minutes", TRUST_TOOL_MINUTES),
+                                            format!("Allow calls to this tool for the next {} minutes", TRUST_TOOL_MINUTES),
+                                        )
                                         .item(Permission::AlwaysAllow, "Always Allow", "Always allow the tool call")
                                         .item(Permission::DenyOnce, "Deny", "Deny the tool call")
                                         .item(Permission::Cancel, "Cancel", "Cancel the AI response and tool call")
@@ -966,9 +1285,12 @@ impl CliSession {
                                 }
                                 self.messages.push(message.clone());
 
-                                if interactive {output::hide_thinking()};
-                                let _ = progress_bars.hide();
-                                output::render_message(&message, self.debug);
+                                // Agent-only messages, such as subagent responses, are kept but not rendered
+                                if message.is_user_visible() {
+                                    if interactive {output::hide_thinking()};
+                                    let _ = progress_bars.hide();
+                                    output::render_message(&message, self.debug);
+                                }
                             }
                         }
                         Some(Ok(AgentEvent::McpNotification((_id, message)))) => {
@@ -997,22 +1319,15 @@ impl CliSession {
                                                         format!("💭 {}", msg)
                                                     }
                                                     Some("response_generated") => {
-                                                        // Check verbosity setting for subagent response content
-                                                        let config = Config::global();
-                                                        let min_priority = config
-                                                            .get_param::<f32>("GOOSE_CLI_MIN_PRIORITY")
-                                                            .ok()
-                                                            .unwrap_or(0.5);
-
-                                                        if min_priority > 0.1 && !self.debug {
-                                                            // High/Medium verbosity: show truncated response
+                                                        if output::get_verbosity() < output::Verbosity::Verbose && !self.debug {
+                                                            // Quiet/Normal verbosity: show truncated response
                                                             if let Some(response_content) = msg.strip_prefix("Responded: ") {
                                                                 format!("🤖 Responded: {}", safe_truncate(response_content, 100))
                                                             } else {
                                                                 format!("🤖 {}", msg)
                                                             }
                                                         } else {
-                                                            // All verbosity or debug: show full response
+                                                            // Verbose or debug: show full response
                                                             format!("🤖 {}", msg)
                                                         }
                                                     }
@@ -1035,8 +1350,16 @@ impl CliSession {
                                         },
                                     };
 
+                                    let quiet = output::get_verbosity() == output::Verbosity::Quiet;
+                                    let is_subagent_activity = matches!(
+                                        message_notification_type.as_deref(),
+                                        Some("tool_usage" | "tool_completed" | "tool_error" | "message_processing" | "turn_progress")
+                                    );
+
                                     // Handle subagent notifications - show immediately
-                                    if let Some(_id) = subagent_id {
+                                    if subagent_id.is_some() && quiet && is_subagent_activity {
+                                        // Quiet verbosity only reports subagents starting, finishing and responding
+                                    } else if let Some(_id) = subagent_id {
                                         // TODO: proper display for subagent notifications
                                         if interactive {
                                             let _ = progress_bars.hide();
@@ -1086,6 +1409,17 @@ impl CliSession {
                                 eprintln!("Model changed to {} in {} mode", model, mode);
                             }
                         }
+                        Some(Ok(AgentEvent::StopReason(reason))) => {
+                            if reason.is_truncation() {
+                                output::render_text(
+                                    &format!("The response may be incomplete: {}.", reason),
+                                    Some(Color::Yellow),
+                                    true
+                                );
+                            } else if self.debug {
+                                eprintln!("Model stopped: {}", reason);
+                            }
+                        }
 
                         Some(Err(e)) => {
                             // TODO(Douwe): Delete this
@@ -1122,6 +1456,19 @@ impl CliSession {
                 _ = tokio::signal::ctrl_c() => {
                     cancel_token_clone.cancel();
                     drop(stream);
+                    output::render_text(
+                        "Interrupted. Press Ctrl-C again to stop all extensions and subagents.",
+                        Some(Color::Yellow),
+                        true,
+                    );
+                    if tokio::time::timeout(EMERGENCY_STOP_WINDOW, tokio::signal::ctrl_c()).await.is_ok() {
+                        self.agent.emergency_stop();
+                        output::render_text(
+                            "Emergency stop: cancelled all running tool calls and subagents.",
+                            Some(Color::Red),
+                            true,
+                        );
+                    }
                     if let Err(e) = self.handle_interrupted_messages(true).await {
                         eprintln!("Error handling interruption: {}", e);
                     }
@@ -1322,15 +1669,7 @@ impl CliSession {
         // Do not get costing information if show cost is disabled
         // This will prevent the API call to openrouter.ai
         // This is useful if for cases where openrouter.ai may be blocked by corporate firewalls
-        if show_cost {
-            // Initialize pricing cache on startup
-            tracing::info!("Initializing pricing cache...");
-            if let Err(e) = initialize_pricing_cache().await {
-                tracing::warn!(
-                    "Failed to initialize pricing cache: {e}. Pricing data may not be available."
-                );
-            }
-        }
+        let show_cost = show_cost && ensure_pricing_cache().await;
 
         match self.get_metadata().await {
             Ok(metadata) => {
@@ -1372,46 +1711,43 @@ impl CliSession {
                 None => output::render_error(&format!("Prompt '{}' not found", opts.name)),
             }
         } else {
+            let Some(info) = self.get_prompt_info(&opts.name).await? else {
+                output::render_error(&format!("Prompt '{}' not found", opts.name));
+                return Ok(());
+            };
+            let descriptor = PromptDescriptor {
+                name: info.name,
+                description: info.description,
+                arguments: info.arguments.unwrap_or_default(),
+                extension: info.extension.unwrap_or_default(),
+            };
+            if let Err(e) = descriptor.validate_arguments(&opts.arguments) {
+                output::render_error(&e);
+                return Ok(());
+            }
+
             // Convert the arguments HashMap to a Value
             let arguments = serde_json::to_value(opts.arguments)
                 .map_err(|e| anyhow::anyhow!("Failed to serialize arguments: {}", e))?;
 
             match self.get_prompt(&opts.name, arguments).await {
                 Ok(messages) => {
-                    let start_len = self.messages.len();
-                    let mut valid = true;
-                    for (i, prompt_message) in messages.into_iter().enumerate() {
-                        let msg = Message::from(prompt_message);
-                        // ensure we get a User - Assistant - User type pattern
-                        let expected_role = if i % 2 == 0 {
-                            rmcp::model::Role::User
-                        } else {
-                            rmcp::model::Role::Assistant
-                        };
-
-                        if msg.role != expected_role {
-                            output::render_error(&format!(
-                                "Expected {:?} message at position {}, but found {:?}",
-                                expected_role, i, msg.role
-                            ));
-                            valid = false;
-                            // get rid of everything we added to messages
-                            self.messages.truncate(start_len);
-                            break;
-                        }
+                    // ensure we get a User - Assistant - User type pattern
+                    let mut prompt =
+                        Conversation::new_unvalidated(messages.into_iter().map(Message::from));
+                    prompt.normalize_roles(true);
 
+                    for msg in prompt {
                         if msg.role == rmcp::model::Role::User {
                             output::render_message(&msg, self.debug);
                         }
                         self.push_message(msg);
                     }
 
-                    if valid {
-                        output::show_thinking();
-                        self.process_agent_response(true, CancellationToken::default())
-                            .await?;
-                        output::hide_thinking();
-                    }
+                    output::show_thinking();
+                    self.process_agent_response(true, CancellationToken::default())
+                        .await?;
+                    output::hide_thinking();
                 }
                 Err(e) => output::render_error(&e.to_string()),
             }
@@ -1466,6 +1802,139 @@ impl CliSession {
     fn push_message(&mut self, message: Message) {
         self.messages.push(message);
     }
+
+    /// Send a user message to the agent and render the response
+    async fn send_user_message(&mut self, content: String) -> Result<()> {
+        self.push_message(Message::user().with_text(&content));
+
+        // Track the current directory and last instruction in projects.json
+        if let Err(e) = crate::project_tracker::update_project_tracker(
+            Some(&content),
+            self.session_id.as_deref(),
+        ) {
+            eprintln!(
+                "Warning: Failed to update project tracker with instruction: {}",
+                e
+            );
+        }
+        self.last_user_message = Some(content);
+
+        let _provider = self.agent.provider().await?;
+
+        output::show_thinking();
+        let start_time = Instant::now();
+        self.process_agent_response(true, CancellationToken::default())
+            .await?;
+        output::hide_thinking();
+
+        // Display elapsed time
+        let elapsed = start_time.elapsed();
+        let elapsed_str = format_elapsed_time(elapsed);
+        println!(
+            "\n{}",
+            console::style(format!("⏱️  Elapsed time: {}", elapsed_str)).dim()
+        );
+        Ok(())
+    }
+
+    /// Index of the most recent message the user typed, skipping tool responses
+    fn last_user_message_index(&self) -> Option<usize> {
+        self.messages.iter().rposition(|message| {
+            message.role == rmcp::model::Role::User
+                && !message.is_tool_response()
+                && !message.as_concat_text().trim().is_empty()
+        })
+    }
+
+    /// Save the session and shut down its extensions after it sat idle at the prompt
+    async fn close_idle_session(&mut self, timeout: std::time::Duration) {
+        let idle = match timeout.as_secs() {
+            secs if secs % 60 == 0 => format!("{} minute(s)", secs / 60),
+            secs => format!("{} second(s)", secs),
+        };
+        println!();
+        println!(
+            "{}",
+            console::style(format!(
+                "No input for {}, closing the session ({}).",
+                idle, IDLE_TIMEOUT_KEY
+            ))
+            .yellow()
+        );
+
+        self.save_conversation().await;
+        for name in self.agent.list_extensions().await {
+            if let Err(e) = self.agent.remove_extension(&name).await {
+                warn!("Failed to shut down extension {}: {}", name, e);
+            }
+        }
+    }
+
+    /// Write the in-memory conversation back to the session, reporting any failure
+    async fn save_conversation(&self) -> bool {
+        let Some(session_id) = &self.session_id else {
+            return true;
+        };
+        match SessionManager::replace_conversation(session_id, &self.messages).await {
+            Ok(()) => true,
+            Err(e) => {
+                output::render_error(&format!("Failed to save session: {}", e));
+                false
+            }
+        }
+    }
+
+    /// The text of the last message the user typed. This is remembered separately from the
+    /// conversation because a failed exchange is removed from it, which is exactly when
+    /// resending is useful. Resumed sessions fall back to the conversation history.
+    fn last_user_message_text(&self) -> Option<String> {
+        if let Some(content) = &self.last_user_message {
+            return Some(content.clone());
+        }
+        self.messages
+            .iter()
+            .rev()
+            .filter(|message| message.role == rmcp::model::Role::User)
+            .filter(|message| !message.is_tool_response() && message.is_user_visible())
+            .map(|message| message.as_concat_text())
+            .find(|text| !text.trim().is_empty())
+    }
+}
+
+/// A provider and model that can be used for planning
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+struct ReasonerCandidate {
+    /// Defaults to the main provider
+    #[serde(default)]
+    provider: Option<String>,
+    model: String,
+}
+
+/// The models to try for planning, in order: the planner provider and model (each falling back
+/// to the main one), then the configured fallback list
+fn reasoner_candidates(
+    planner_provider: Option<String>,
+    planner_model: Option<String>,
+    default_provider: Option<String>,
+    default_model: Option<String>,
+    fallbacks: Vec<ReasonerCandidate>,
+) -> Vec<(String, String)> {
+    let mut candidates = Vec::new();
+    if let (Some(provider), Some(model)) = (
+        planner_provider.or_else(|| default_provider.clone()),
+        planner_model.or_else(|| default_model.clone()),
+    ) {
+        candidates.push((provider, model));
+    }
+    for fallback in fallbacks {
+        if let Some(provider) = fallback.provider.or_else(|| default_provider.clone()) {
+            let candidate = (provider, fallback.model);
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    candidates
 }
 
 async fn get_reasoner() -> Result<Arc<dyn Provider>, anyhow::Error> {
@@ -1474,31 +1943,148 @@ async fn get_reasoner() -> Result<Arc<dyn Provider>, anyhow::Error> {
 
     let config = Config::global();
 
-    // Try planner-specific provider first, fallback to default provider
-    let provider = if let Ok(provider) = config.get_param::<String>("GOOSE_PLANNER_PROVIDER") {
-        provider
-    } else {
+    let planner_provider = config.get_param::<String>("GOOSE_PLANNER_PROVIDER").ok();
+    if planner_provider.is_none() {
         println!("WARNING: GOOSE_PLANNER_PROVIDER not found. Using default provider...");
-        config
-            .get_param::<String>("GOOSE_PROVIDER")
-            .expect("No provider configured. Run 'goose configure' first")
-    };
-
-    // Try planner-specific model first, fallback to default model
-    let model = if let Ok(model) = config.get_param::<String>("GOOSE_PLANNER_MODEL") {
-        model
-    } else {
+    }
+    let planner_model = config.get_param::<String>("GOOSE_PLANNER_MODEL").ok();
+    if planner_model.is_none() {
         println!("WARNING: GOOSE_PLANNER_MODEL not found. Using default model...");
+    }
+
+    let candidates = reasoner_candidates(
+        planner_provider,
+        planner_model,
+        config.get_param::<String>("GOOSE_PROVIDER").ok(),
+        config.get_param::<String>("GOOSE_MODEL").ok(),
         config
-            .get_param::<String>("GOOSE_MODEL")
-            .expect("No model configured. Run 'goose configure' first")
-    };
+            .get_param::<Vec<ReasonerCandidate>>("GOOSE_PLANNER_FALLBACK_MODELS")
+            .unwrap_or_default(),
+    );
+    if candidates.is_empty() {
+        return Err(anyhow::anyhow!(
+            "No provider or model configured for planning. Run 'goose configure' first"
+        ));
+    }
+
+    let mut errors = Vec::new();
+    for (provider, model) in candidates {
+        let reasoner = match ModelConfig::new_with_context_env(
+            model.clone(),
+            Some("GOOSE_PLANNER_CONTEXT_LIMIT"),
+        ) {
+            Ok(model_config) => create(&provider, model_config).await,
+            Err(e) => Err(e.into()),
+        };
+        match reasoner {
+            Ok(reasoner) => return Ok(reasoner),
+            Err(e) => {
+                println!(
+                    "WARNING: Could not use {}/{} for planning: {}",
+                    provider, model, e
+                );
+                errors.push(format!("{}/{}: {}", provider, model, e));
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "No planner model could be created ({})",
+        errors.join("; ")
+    ))
+}
+
+/// Split a streamable HTTP extension spec into its URL and headers. Header values are kept as
+/// written, `${VAR}` references are resolved when the extension connects.
+fn parse_streamable_http_spec(
+    spec: &str,
+) -> Result<(String, HashMap<String, String>, Option<u64>)> {
+    let parts = shlex::split(spec)
+        .ok_or_else(|| anyhow::anyhow!("Invalid quoting in extension '{}'", spec))?;
+    let mut parts = parts.into_iter();
+    let url = parts
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("No URL provided in extension string"))?;
+
+    let mut headers = HashMap::new();
+    let mut timeout = None;
+    for part in parts {
+        let (key, value) = part
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Invalid header '{}', expected Name=value", part))?;
+        if key == TIMEOUT_TOKEN {
+            timeout = Some(parse_timeout(value)?);
+        } else {
+            headers.insert(key.trim().to_string(), value.trim().to_string());
+        }
+    }
+    Ok((url, headers, timeout))
+}
+
+/// The `timeout=<seconds>` token accepted in extension strings
+const TIMEOUT_TOKEN: &str = "timeout";
+
+fn parse_timeout(value: &str) -> Result<u64> {
+    value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Invalid timeout '{}', expected a number of seconds", value))
+}
 
-    let model_config =
-        ModelConfig::new_with_context_env(model, Some("GOOSE_PLANNER_CONTEXT_LIMIT"))?;
-    let reasoner = create(&provider, model_config).await?;
+/// Remove a `timeout=<seconds>` token from a whitespace separated extension string
+fn split_timeout(spec: &str) -> Result<(String, Option<u64>)> {
+    let mut timeout = None;
+    let mut rest = Vec::new();
+    for part in spec.split_whitespace() {
+        match part.split_once('=') {
+            Some((TIMEOUT_TOKEN, value)) => timeout = Some(parse_timeout(value)?),
+            _ => rest.push(part),
+        }
+    }
+    Ok((rest.join(" "), timeout))
+}
 
-    Ok(reasoner)
+/// Seconds an interactive session may wait for input before it closes itself; unset or 0 disables it
+const IDLE_TIMEOUT_KEY: &str = "GOOSE_SESSION_IDLE_TIMEOUT";
+
+fn idle_timeout() -> Option<std::time::Duration> {
+    Config::global()
+        .get_param::<u64>(IDLE_TIMEOUT_KEY)
+        .ok()
+        .filter(|secs| *secs > 0)
+        .map(std::time::Duration::from_secs)
+}
+
+/// How long to wait for pricing data before giving up on showing costs
+const PRICING_INIT_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+/// How long after an interrupt a second Ctrl-C triggers an emergency stop
+const EMERGENCY_STOP_WINDOW: std::time::Duration = std::time::Duration::from_secs(1);
+
+static PRICING_CACHE_READY: tokio::sync::OnceCell<bool> = tokio::sync::OnceCell::const_new();
+
+/// Initialize the pricing cache once per process, returning whether pricing data is available.
+/// Fetching pricing can block on openrouter.ai, so a slow network is not retried on every prompt.
+async fn ensure_pricing_cache() -> bool {
+    *PRICING_CACHE_READY
+        .get_or_init(|| async {
+            tracing::info!("Initializing pricing cache...");
+            match tokio::time::timeout(PRICING_INIT_TIMEOUT, initialize_pricing_cache()).await {
+                Ok(Ok(())) => true,
+                Ok(Err(e)) => {
+                    tracing::warn!(
+                        "Failed to initialize pricing cache: {e}. Pricing data may not be available."
+                    );
+                    true
+                }
+                Err(_) => {
+                    tracing::warn!(
+                        "Timed out after {:?} initializing pricing cache, cost display is disabled",
+                        PRICING_INIT_TIMEOUT
+                    );
+                    false
+                }
+            }
+        })
+        .await
 }
 
 /// Format elapsed time duration
@@ -1519,6 +2105,115 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    #[test]
+    fn test_parse_planner_classification_structured() {
+        let classification = parse_planner_classification(
+            r#"{"label": "clarifying_questions", "confidence": 0.92}"#,
+        );
+        assert_eq!(
+            classification.response_type,
+            PlannerResponseType::ClarifyingQuestions
+        );
+        assert_eq!(classification.confidence, Some(0.92));
+        assert!(classification.is_confident());
+
+        let classification = parse_planner_classification(
+            "```json\n{\"label\": \"plan\", \"confidence\": 0.4}\n```",
+        );
+        assert_eq!(classification.response_type, PlannerResponseType::Plan);
+        assert!(!classification.is_confident());
+    }
+
+    #[test]
+    fn test_parse_planner_classification_fallback() {
+        // A question that mentions the plan is not a plan
+        let classification = parse_planner_classification("clarifying questions about the plan");
+        assert_eq!(
+            classification.response_type,
+            PlannerResponseType::ClarifyingQuestions
+        );
+        assert_eq!(classification.confidence, None);
+        assert!(!classification.is_confident());
+
+        let classification = parse_planner_classification("Plan");
+        assert_eq!(classification.response_type, PlannerResponseType::Plan);
+    }
+
+    #[test]
+    fn test_parse_streamable_http_spec() {
+        let (url, headers, timeout) =
+            parse_streamable_http_spec("https://example.com/mcp").unwrap();
+        assert_eq!(url, "https://example.com/mcp");
+        assert!(headers.is_empty());
+        assert_eq!(timeout, None);
+
+        let (url, headers, timeout) = parse_streamable_http_spec(
+            "https://example.com/mcp 'Authorization=Bearer ${MY_TOKEN}' X-Team=a=b timeout=900",
+        )
+        .unwrap();
+        assert_eq!(timeout, Some(900));
+        assert_eq!(url, "https://example.com/mcp");
+        assert_eq!(
+            headers.get("Authorization").map(String::as_str),
+            Some("Bearer ${MY_TOKEN}")
+        );
+        assert_eq!(headers.get("X-Team").map(String::as_str), Some("a=b"));
+
+        assert!(parse_streamable_http_spec("https://example.com/mcp NoValue").is_err());
+    }
+
+    #[test]
+    fn test_split_timeout() {
+        assert_eq!(
+            split_timeout("developer,memory timeout=600").unwrap(),
+            ("developer,memory".to_string(), Some(600))
+        );
+        assert_eq!(
+            split_timeout("http://localhost:8080/sse").unwrap(),
+            ("http://localhost:8080/sse".to_string(), None)
+        );
+        assert!(split_timeout("developer timeout=soon").is_err());
+    }
+
+    #[test]
+    fn test_reasoner_candidates_order() {
+        let some = |s: &str| Some(s.to_string());
+        let candidates = reasoner_candidates(
+            some("anthropic"),
+            None,
+            some("openai"),
+            some("gpt-4o"),
+            vec![
+                ReasonerCandidate {
+                    provider: some("openrouter"),
+                    model: "o3".to_string(),
+                },
+                ReasonerCandidate {
+                    provider: None,
+                    model: "o4-mini".to_string(),
+                },
+                // Duplicates of an earlier candidate are skipped
+                ReasonerCandidate {
+                    provider: some("anthropic"),
+                    model: "gpt-4o".to_string(),
+                },
+            ],
+        );
+        assert_eq!(
+            candidates,
+            vec![
+                ("anthropic".to_string(), "gpt-4o".to_string()),
+                ("openrouter".to_string(), "o3".to_string()),
+                ("openai".to_string(), "o4-mini".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reasoner_candidates_unconfigured() {
+        assert!(reasoner_candidates(None, None, None, None, vec![]).is_empty());
+    }
+
     #[test]
     fn test_format_elapsed_time_under_60_seconds() {
         // Test sub-second duration
@@ -1,22 +1,23 @@
 use anstream::println;
 use bat::WrappingMode;
 use console::{measure_text_width, style, Color, Term};
+use goose::agents::extension_registry::{RegistryEntry, RegistrySource};
 use goose::config::Config;
 use goose::conversation::message::{Message, MessageContent, ToolRequest, ToolResponse};
-use goose::providers::pricing::get_model_pricing;
-use goose::providers::pricing::parse_model_id;
+use goose::providers::pricing::estimate_cost;
 use goose::utils::safe_truncate;
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
-use regex::Regex;
-use rmcp::model::{CallToolRequestParam, JsonObject, PromptArgument};
+use rmcp::model::{CallToolRequestParam, JsonObject, PromptArgument, RawContent, ResourceContents};
 use serde_json::Value;
 use std::cell::RefCell;
 use std::collections::HashMap;
 use std::io::{Error, IsTerminal, Write};
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::Duration;
 
+use super::result_format::{ResultFormatter, ResultFormatterRegistry};
+
 // Re-export theme for use in main
 #[derive(Clone, Copy)]
 pub enum Theme {
@@ -53,39 +54,153 @@ impl Theme {
     }
 }
 
-thread_local! {
-    static CURRENT_THEME: RefCell<Theme> = RefCell::new(
-        std::env::var("GOOSE_CLI_THEME").ok()
-            .map(|val| Theme::from_config_str(&val))
-            .unwrap_or_else(||
-                Config::global().get_param::<String>("GOOSE_CLI_THEME").ok()
-                    .map(|val| Theme::from_config_str(&val))
-                    .unwrap_or(Theme::Dark)
-            )
-    );
+static RESULT_FORMATTERS: LazyLock<Mutex<ResultFormatterRegistry>> =
+    LazyLock::new(|| Mutex::new(ResultFormatterRegistry::with_builtins()));
+
+/// Format tool results of the formatter's content types with it, replacing the built in one
+pub fn register_result_formatter(formatter: Arc<dyn ResultFormatter>) {
+    RESULT_FORMATTERS.lock().unwrap().register(formatter);
 }
 
-pub fn set_theme(theme: Theme) {
+// Shared by all threads, the async runtime may render from any of its workers
+static CURRENT_THEME: LazyLock<Mutex<Theme>> = LazyLock::new(|| Mutex::new(configured_theme()));
+
+/// The theme saved by `/t` (`GOOSE_CLI_THEME`), with `GOOSE_THEME` as the default until one is saved
+pub fn configured_theme() -> Theme {
     let config = Config::global();
     config
-        .set_param("GOOSE_CLI_THEME", Value::String(theme.as_config_string()))
-        .expect("Failed to set theme");
-    CURRENT_THEME.with(|t| *t.borrow_mut() = theme);
+        .get_param::<String>("GOOSE_CLI_THEME")
+        .or_else(|_| config.get_param::<String>("GOOSE_THEME"))
+        .map(|val| Theme::from_config_str(&val))
+        .unwrap_or(Theme::Dark)
+}
 
-    let config = Config::global();
-    let theme_str = match theme {
-        Theme::Light => "light",
-        Theme::Dark => "dark",
-        Theme::Ansi => "ansi",
-    };
+/// Reload the theme from the environment and config, e.g. when starting a session
+pub fn load_theme() {
+    *CURRENT_THEME.lock().unwrap() = configured_theme();
+}
+
+/// Switch to `theme` and save it so future sessions start with it
+pub fn set_theme(theme: Theme) {
+    *CURRENT_THEME.lock().unwrap() = theme;
 
-    if let Err(e) = config.set_param("GOOSE_CLI_THEME", Value::String(theme_str.to_string())) {
+    if let Err(e) =
+        Config::global().set_param("GOOSE_CLI_THEME", Value::String(theme.as_config_string()))
+    {
         eprintln!("Failed to save theme setting to config: {}", e);
     }
 }
 
 pub fn get_theme() -> Theme {
-    CURRENT_THEME.with(|t| *t.borrow())
+    *CURRENT_THEME.lock().unwrap()
+}
+
+/// How much tool output and subagent activity is rendered, set with `GOOSE_VERBOSITY` or
+/// `/verbosity`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Only high-importance tool output, no subagent tool or progress notifications
+    Quiet,
+    /// Tool output of medium importance or more, such as the results of file writes
+    Normal,
+    /// All tool output, such as shell command output, and full subagent responses
+    Verbose,
+    /// Everything, including the raw content of tool responses
+    Debug,
+}
+
+impl Verbosity {
+    pub const ALL: [Verbosity; 4] = [
+        Verbosity::Quiet,
+        Verbosity::Normal,
+        Verbosity::Verbose,
+        Verbosity::Debug,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Verbosity::Quiet => "quiet",
+            Verbosity::Normal => "normal",
+            Verbosity::Verbose => "verbose",
+            Verbosity::Debug => "debug",
+        }
+    }
+
+    pub fn from_config_str(val: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|verbosity| verbosity.as_str().eq_ignore_ascii_case(val.trim()))
+    }
+
+    /// The lowest priority of tool output that is shown
+    fn min_priority(&self) -> f32 {
+        match self {
+            Verbosity::Quiet => 0.8,
+            Verbosity::Normal => 0.2,
+            Verbosity::Verbose | Verbosity::Debug => 0.0,
+        }
+    }
+
+    /// The level matching a `GOOSE_CLI_MIN_PRIORITY` threshold, which this setting replaces
+    fn from_min_priority(min_priority: f32) -> Self {
+        if min_priority >= 0.8 {
+            Verbosity::Quiet
+        } else if min_priority > 0.1 {
+            Verbosity::Normal
+        } else {
+            Verbosity::Verbose
+        }
+    }
+}
+
+/// The `GOOSE_CLI_MIN_PRIORITY` default, kept for tool output until a verbosity is chosen so
+/// existing setups render the same as before
+const DEFAULT_MIN_PRIORITY: f32 = 0.5;
+
+static CURRENT_VERBOSITY: LazyLock<Mutex<Option<Verbosity>>> =
+    LazyLock::new(|| Mutex::new(configured_verbosity()));
+
+/// The verbosity from `GOOSE_VERBOSITY`, falling back to the older `GOOSE_CLI_MIN_PRIORITY`,
+/// or None when neither is set
+pub fn configured_verbosity() -> Option<Verbosity> {
+    let config = Config::global();
+    config
+        .get_param::<String>("GOOSE_VERBOSITY")
+        .ok()
+        .and_then(|val| Verbosity::from_config_str(&val))
+        .or_else(|| {
+            config
+                .get_param::<f32>("GOOSE_CLI_MIN_PRIORITY")
+                .ok()
+                .map(Verbosity::from_min_priority)
+        })
+}
+
+/// Switch to `verbosity` and save it so future sessions start with it
+pub fn set_verbosity(verbosity: Verbosity) {
+    *CURRENT_VERBOSITY.lock().unwrap() = Some(verbosity);
+
+    if let Err(e) = Config::global().set_param(
+        "GOOSE_VERBOSITY",
+        Value::String(verbosity.as_str().to_string()),
+    ) {
+        eprintln!("Failed to save verbosity setting to config: {}", e);
+    }
+}
+
+pub fn get_verbosity() -> Verbosity {
+    CURRENT_VERBOSITY
+        .lock()
+        .unwrap()
+        .unwrap_or(Verbosity::Normal)
+}
+
+/// The lowest priority of tool output that is shown
+fn min_tool_priority() -> f32 {
+    CURRENT_VERBOSITY
+        .lock()
+        .unwrap()
+        .map_or(DEFAULT_MIN_PRIORITY, |verbosity| verbosity.min_priority())
 }
 
 // Simple wrapper around spinner to manage its state
@@ -269,7 +384,8 @@ fn render_tool_request(req: &ToolRequest, theme: Theme, debug: bool) {
 }
 
 fn render_tool_response(resp: &ToolResponse, theme: Theme, debug: bool) {
-    let config = Config::global();
+    let verbosity = get_verbosity();
+    let debug = debug || verbosity == Verbosity::Debug;
 
     match &resp.tool_result {
         Ok(contents) => {
@@ -280,10 +396,7 @@ fn render_tool_response(resp: &ToolResponse, theme: Theme, debug: bool) {
                     }
                 }
 
-                let min_priority = config
-                    .get_param::<f32>("GOOSE_CLI_MIN_PRIORITY")
-                    .ok()
-                    .unwrap_or(0.5);
+                let min_priority = min_tool_priority();
 
                 if content
                     .priority()
@@ -295,8 +408,19 @@ fn render_tool_response(resp: &ToolResponse, theme: Theme, debug: bool) {
 
                 if debug {
                     println!("{:#?}", content);
-                } else if let Some(text) = content.as_text() {
-                    print_markdown(&text.text, theme);
+                    continue;
+                }
+                match &content.raw {
+                    RawContent::Text(text) => print_tool_result(None, &text.text, theme),
+                    RawContent::Resource(resource) => {
+                        if let ResourceContents::TextResourceContents {
+                            mime_type, text, ..
+                        } = &resource.resource
+                        {
+                            print_tool_result(mime_type.as_deref(), text, theme);
+                        }
+                    }
+                    _ => {}
                 }
             }
         }
@@ -304,6 +428,93 @@ fn render_tool_response(resp: &ToolResponse, theme: Theme, debug: bool) {
     }
 }
 
+/// Find the last fenced code block in markdown text, without its fences
+pub fn last_code_block(text: &str) -> Option<String> {
+    let mut last = None;
+    let mut current: Option<Vec<&str>> = None;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            match current.take() {
+                Some(block) => last = Some(block.join("\n")),
+                None => current = Some(Vec::new()),
+            }
+        } else if let Some(block) = current.as_mut() {
+            block.push(line);
+        }
+    }
+    last
+}
+
+/// A clipboard command, its arguments and how it expects the text on stdin to be encoded
+type ClipboardCommand = (&'static str, &'static [&'static str], fn(&str) -> Vec<u8>);
+
+/// Clipboard commands to try in order, each reading the text from stdin
+#[cfg(target_os = "macos")]
+const CLIPBOARD_COMMANDS: &[ClipboardCommand] = &[("pbcopy", &[], utf8)];
+/// `clip` reads stdin in the console code page unless it starts with a UTF-16 byte order mark,
+/// so PowerShell is told to read UTF-8 and `clip` is given UTF-16LE
+#[cfg(windows)]
+const CLIPBOARD_COMMANDS: &[ClipboardCommand] = &[
+    (
+        "powershell",
+        &[
+            "-NoProfile",
+            "-NonInteractive",
+            "-Command",
+            "[Console]::InputEncoding = [Text.Encoding]::UTF8; Set-Clipboard -Value ([Console]::In.ReadToEnd())",
+        ],
+        utf8,
+    ),
+    ("clip", &[], utf16le_with_bom),
+];
+#[cfg(not(any(target_os = "macos", windows)))]
+const CLIPBOARD_COMMANDS: &[ClipboardCommand] = &[
+    ("wl-copy", &[], utf8),
+    ("xclip", &["-selection", "clipboard"], utf8),
+    ("xsel", &["--clipboard", "--input"], utf8),
+];
+
+fn utf8(text: &str) -> Vec<u8> {
+    text.as_bytes().to_vec()
+}
+
+#[cfg(windows)]
+fn utf16le_with_bom(text: &str) -> Vec<u8> {
+    std::iter::once(0xFEFF)
+        .chain(text.encode_utf16())
+        .flat_map(u16::to_le_bytes)
+        .collect()
+}
+
+pub fn copy_to_clipboard(text: &str) -> anyhow::Result<()> {
+    for (program, args, encode) in CLIPBOARD_COMMANDS {
+        // Not installed, try the next one
+        let Ok(mut child) = std::process::Command::new(program)
+            .args(*args)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(&encode(text))?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    let programs: Vec<_> = CLIPBOARD_COMMANDS
+        .iter()
+        .map(|(program, _, _)| *program)
+        .collect();
+    anyhow::bail!(
+        "no working clipboard command found, tried {}",
+        programs.join(", ")
+    )
+}
+
 pub fn render_error(message: &str) {
     println!("\n  {} {}\n", style("error:").red().bold(), message);
 }
@@ -375,6 +586,30 @@ pub fn render_extension_error(name: &str, error: &str) {
     println!();
 }
 
+pub fn render_registry_entries(entries: &[&RegistryEntry]) {
+    if entries.is_empty() {
+        println!("No matching extensions found");
+        return;
+    }
+    for entry in entries {
+        let source = match entry.source {
+            RegistrySource::Bundled => "bundled",
+            RegistrySource::Community => "community",
+        };
+        println!(
+            "{} {} {}",
+            style(&entry.id).cyan().bold(),
+            style(format!("({})", source)).dim(),
+            entry.description
+        );
+        println!("    {}", style(entry.install_command()).dim());
+    }
+    println!(
+        "\nUse {} to add one to this session",
+        style("/extensions install <id>").cyan()
+    );
+}
+
 pub fn render_builtin_success(names: &str) {
     println!();
     println!(
@@ -549,6 +784,31 @@ pub fn env_no_color() -> bool {
     std::env::var_os("NO_COLOR").is_none()
 }
 
+/// Print a tool result with the formatter for its content type, or as markdown if none applies
+fn print_tool_result(content_type: Option<&str>, text: &str, theme: Theme) {
+    let formatted = RESULT_FORMATTERS.lock().unwrap().format(content_type, text);
+    match formatted {
+        Some(formatted) => print_highlighted(&formatted.text, formatted.language, theme),
+        None => print_markdown(text, theme),
+    }
+}
+
+fn print_highlighted(content: &str, language: &str, theme: Theme) {
+    if std::io::stdout().is_terminal() {
+        bat::PrettyPrinter::new()
+            .input(bat::Input::from_bytes(content.as_bytes()))
+            .theme(theme.as_str())
+            .colored_output(env_no_color())
+            .language(language)
+            .wrapping_mode(WrappingMode::NoWrapping(true))
+            .print()
+            .unwrap();
+        println!();
+    } else {
+        println!("{}", content);
+    }
+}
+
 fn print_markdown(content: &str, theme: Theme) {
     if std::io::stdout().is_terminal() {
         bat::PrettyPrinter::new()
@@ -813,61 +1073,6 @@ pub fn display_context_usage(total_tokens: usize, context_limit: usize) {
     );
 }
 
-fn normalize_model_name(model: &str) -> String {
-    let mut result = model.to_string();
-
-    // Remove "-latest" suffix
-    if result.ends_with("-latest") {
-        result = result.strip_suffix("-latest").unwrap().to_string();
-    }
-
-    // Remove date-like suffixes: -YYYYMMDD
-    let re_date = Regex::new(r"-\d{8}$").unwrap();
-    if re_date.is_match(&result) {
-        result = re_date.replace(&result, "").to_string();
-    }
-
-    // Convert version numbers like -3-7- to -3.7- (e.g., claude-3-7-sonnet -> claude-3.7-sonnet)
-    let re_version = Regex::new(r"-(\d+)-(\d+)-").unwrap();
-    if re_version.is_match(&result) {
-        result = re_version.replace(&result, "-$1.$2-").to_string();
-    }
-
-    result
-}
-
-async fn estimate_cost_usd(
-    provider: &str,
-    model: &str,
-    input_tokens: usize,
-    output_tokens: usize,
-) -> Option<f64> {
-    // For OpenRouter, parse the model name to extract real provider/model
-    let openrouter_data = if provider == "openrouter" {
-        parse_model_id(model)
-    } else {
-        None
-    };
-
-    let (provider_to_use, model_to_use) = match &openrouter_data {
-        Some((real_provider, real_model)) => (real_provider.as_str(), real_model.as_str()),
-        None => (provider, model),
-    };
-
-    // Use the pricing module's get_model_pricing which handles model name mapping internally
-    let cleaned_model = normalize_model_name(model_to_use);
-    let pricing_info = get_model_pricing(provider_to_use, &cleaned_model).await;
-
-    match pricing_info {
-        Some(pricing) => {
-            let input_cost = pricing.input_cost * input_tokens as f64;
-            let output_cost = pricing.output_cost * output_tokens as f64;
-            Some(input_cost + output_cost)
-        }
-        None => None,
-    }
-}
-
 /// Display cost information, if price data is available.
 pub async fn display_cost_usage(
     provider: &str,
@@ -875,7 +1080,7 @@ pub async fn display_cost_usage(
     input_tokens: usize,
     output_tokens: usize,
 ) {
-    if let Some(cost) = estimate_cost_usd(provider, model, input_tokens, output_tokens).await {
+    if let Some(cost) = estimate_cost(provider, model, input_tokens, output_tokens).await {
         use console::style;
         eprintln!(
             "Cost: {} USD ({} tokens: in {}, out {})",
@@ -956,6 +1161,30 @@ mod tests {
     use super::*;
     use std::env;
 
+    #[cfg(windows)]
+    #[test]
+    fn test_utf16le_with_bom() {
+        assert_eq!(utf16le_with_bom("é"), vec![0xFF, 0xFE, 0xE9, 0x00]);
+    }
+
+    #[test]
+    fn test_verbosity_levels() {
+        assert_eq!(
+            Verbosity::from_config_str(" Quiet "),
+            Some(Verbosity::Quiet)
+        );
+        assert_eq!(Verbosity::from_config_str("loud"), None);
+
+        // The thresholds `goose configure` used to write
+        assert_eq!(Verbosity::from_min_priority(0.8), Verbosity::Quiet);
+        assert_eq!(Verbosity::from_min_priority(0.5), Verbosity::Normal);
+        assert_eq!(Verbosity::from_min_priority(0.2), Verbosity::Normal);
+        assert_eq!(Verbosity::from_min_priority(0.0), Verbosity::Verbose);
+
+        assert!(Verbosity::Quiet.min_priority() > Verbosity::Normal.min_priority());
+        assert!(Verbosity::Quiet < Verbosity::Debug);
+    }
+
     #[test]
     fn test_short_paths_unchanged() {
         assert_eq!(shorten_path("/usr/bin", false), "/usr/bin");
@@ -998,6 +1227,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_last_code_block() {
+        let text = "Run this:\n```bash\ncargo build\n```\nthen:\n```rust\nfn main() {}\n\nlet x = 1;\n```\ndone";
+        assert_eq!(
+            last_code_block(text),
+            Some("fn main() {}\n\nlet x = 1;".to_string())
+        );
+
+        // Unterminated blocks are ignored
+        assert_eq!(
+            last_code_block("```\nfirst\n```\n```\nno end"),
+            Some("first".to_string())
+        );
+        assert_eq!(last_code_block("no code here"), None);
+    }
+
     #[test]
     fn test_long_path_shortening() {
         assert_eq!(
@@ -0,0 +1,268 @@
+//! Formatters that make tool results readable in the terminal, chosen by the mime type a result
+//! declares or, for plain text, by recognising its content.
+
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Text ready to print, highlighted as `language` by bat
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormattedResult {
+    pub text: String,
+    pub language: &'static str,
+}
+
+pub trait ResultFormatter: Send + Sync {
+    /// The mime types this formatter is used for when a result declares one
+    fn content_types(&self) -> &[&'static str];
+
+    /// Whether undeclared text looks like something this formatter handles
+    fn detect(&self, text: &str) -> bool;
+
+    /// None when the text turns out not to be in the expected format
+    fn format(&self, text: &str) -> Option<FormattedResult>;
+}
+
+/// Formatters by declared content type, with detection tried in registration order for results
+/// that do not declare one
+pub struct ResultFormatterRegistry {
+    formatters: Vec<Arc<dyn ResultFormatter>>,
+    by_content_type: HashMap<&'static str, Arc<dyn ResultFormatter>>,
+}
+
+impl ResultFormatterRegistry {
+    pub fn new() -> Self {
+        Self {
+            formatters: Vec::new(),
+            by_content_type: HashMap::new(),
+        }
+    }
+
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(JsonFormatter));
+        registry.register(Arc::new(DiffFormatter));
+        registry.register(Arc::new(TableFormatter));
+        registry
+    }
+
+    /// Add a formatter, taking over the content types of any registered before it
+    pub fn register(&mut self, formatter: Arc<dyn ResultFormatter>) {
+        for content_type in formatter.content_types() {
+            self.by_content_type.insert(content_type, formatter.clone());
+        }
+        self.formatters.push(formatter);
+    }
+
+    /// The formatted text, or None to print the result as markdown
+    pub fn format(&self, content_type: Option<&str>, text: &str) -> Option<FormattedResult> {
+        let content_type = content_type.map(|t| t.split(';').next().unwrap_or(t).trim());
+        if let Some(formatter) = content_type.and_then(|t| self.by_content_type.get(t)) {
+            return formatter.format(text);
+        }
+        self.formatters
+            .iter()
+            .filter(|formatter| formatter.detect(text))
+            .find_map(|formatter| formatter.format(text))
+    }
+}
+
+impl Default for ResultFormatterRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+/// Pretty-prints JSON objects and arrays
+pub struct JsonFormatter;
+
+impl ResultFormatter for JsonFormatter {
+    fn content_types(&self) -> &[&'static str] {
+        &["application/json"]
+    }
+
+    fn detect(&self, text: &str) -> bool {
+        let text = text.trim();
+        (text.starts_with('{') && text.ends_with('}'))
+            || (text.starts_with('[') && text.ends_with(']'))
+    }
+
+    fn format(&self, text: &str) -> Option<FormattedResult> {
+        let value: Value = serde_json::from_str(text).ok()?;
+        Some(FormattedResult {
+            text: serde_json::to_string_pretty(&value).ok()?,
+            language: "JSON",
+        })
+    }
+}
+
+/// Highlights unified diffs
+pub struct DiffFormatter;
+
+impl ResultFormatter for DiffFormatter {
+    fn content_types(&self) -> &[&'static str] {
+        &["text/x-diff", "text/x-patch"]
+    }
+
+    fn detect(&self, text: &str) -> bool {
+        let mut lines = text.lines();
+        let has_header = text.starts_with("diff --git ")
+            || (text.starts_with("--- ") && lines.nth(1).is_some_and(|l| l.starts_with("+++ ")));
+        has_header && text.lines().any(|line| line.starts_with("@@ "))
+    }
+
+    fn format(&self, text: &str) -> Option<FormattedResult> {
+        Some(FormattedResult {
+            text: text.to_string(),
+            language: "Diff",
+        })
+    }
+}
+
+/// Aligns the columns of tab or comma separated tables
+pub struct TableFormatter;
+
+impl TableFormatter {
+    fn split_rows(text: &str, separator: char) -> Vec<Vec<String>> {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| split_row(line, separator))
+            .collect()
+    }
+}
+
+impl ResultFormatter for TableFormatter {
+    fn content_types(&self) -> &[&'static str] {
+        &["text/csv", "text/tab-separated-values"]
+    }
+
+    // Comma separated text is too easily confused with prose, so only tabs are detected
+    fn detect(&self, text: &str) -> bool {
+        let rows = Self::split_rows(text, '\t');
+        rows.len() >= 2 && rows[0].len() >= 2 && rows.iter().all(|row| row.len() == rows[0].len())
+    }
+
+    fn format(&self, text: &str) -> Option<FormattedResult> {
+        let separator = if text.contains('\t') { '\t' } else { ',' };
+        let rows = Self::split_rows(text, separator);
+        let columns = rows.iter().map(Vec::len).max()?;
+
+        let widths: Vec<usize> = (0..columns)
+            .map(|i| {
+                rows.iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| cell.chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let lines: Vec<String> = rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect::<Vec<_>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            })
+            .collect();
+
+        Some(FormattedResult {
+            text: lines.join("\n"),
+            language: "Plain Text",
+        })
+    }
+}
+
+/// Split on `separator` outside of double quotes, unquoting quoted cells
+fn split_row(line: &str, separator: char) -> Vec<String> {
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == separator && !in_quotes => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell);
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_by_declared_type() {
+        let registry = ResultFormatterRegistry::with_builtins();
+
+        let json = registry
+            .format(Some("application/json; charset=utf-8"), r#"{"a":[1,2]}"#)
+            .unwrap();
+        assert_eq!(json.language, "JSON");
+        assert!(json.text.contains("\n  \"a\": [\n"));
+
+        let csv = registry
+            .format(Some("text/csv"), "name,count\n\"goose, the\",3\n")
+            .unwrap();
+        assert_eq!(csv.text, "name        count\ngoose, the  3");
+
+        assert!(registry
+            .format(Some("application/json"), "not json")
+            .is_none());
+    }
+
+    #[test]
+    fn test_format_by_detection() {
+        let registry = ResultFormatterRegistry::with_builtins();
+
+        let diff = "--- a/x.rs\n+++ b/x.rs\n@@ -1 +1 @@\n-old\n+new\n";
+        assert_eq!(registry.format(None, diff).unwrap().language, "Diff");
+
+        let table = registry.format(None, "a\tbb\nccc\td").unwrap();
+        assert_eq!(table.text, "a    bb\nccc  d");
+
+        assert!(registry.format(None, "Created file x.rs").is_none());
+        assert!(registry.format(None, "[not json]").is_none());
+    }
+
+    struct UpperFormatter;
+
+    impl ResultFormatter for UpperFormatter {
+        fn content_types(&self) -> &[&'static str] {
+            &["application/json"]
+        }
+
+        fn detect(&self, _text: &str) -> bool {
+            false
+        }
+
+        fn format(&self, text: &str) -> Option<FormattedResult> {
+            Some(FormattedResult {
+                text: text.to_uppercase(),
+                language: "Plain Text",
+            })
+        }
+    }
+
+    #[test]
+    fn test_registered_formatter_takes_over_content_type() {
+        let mut registry = ResultFormatterRegistry::with_builtins();
+        registry.register(Arc::new(UpperFormatter));
+
+        let formatted = registry.format(Some("application/json"), "{}").unwrap();
+        assert_eq!(formatted.text, "{}".to_uppercase());
+        // Detection still falls through to the built in formatter
+        assert_eq!(registry.format(None, "{}").unwrap().language, "JSON");
+    }
+}
@@ -152,6 +152,7 @@ fn format_task_display(task: &TaskInfo) -> String {
         TaskStatus::Running => "🏃",
         TaskStatus::Completed => "✅",
         TaskStatus::Failed => "❌",
+        TaskStatus::Cancelled => "🚫",
     };
 
     task_display.push_str(&format!(
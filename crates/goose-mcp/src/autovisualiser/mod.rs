@@ -50,6 +50,134 @@ fn validate_data_param(params: &Value, allow_array: bool) -> Result<Value, Error
     Ok(data_value.clone())
 }
 
+/// Largest GeoJSON object render_map embeds, serialized
+const MAX_GEOJSON_BYTES: usize = 5 * 1024 * 1024;
+
+/// Check that `geojson` is a well-formed GeoJSON object (RFC 7946) that is small enough to embed
+fn validate_geojson(geojson: &Value) -> Result<(), ErrorData> {
+    let invalid = |message: String| {
+        ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("Invalid geojson: {}", message),
+            None,
+        )
+    };
+
+    let size = serde_json::to_string(geojson)
+        .map_err(|e| invalid(e.to_string()))?
+        .len();
+    if size > MAX_GEOJSON_BYTES {
+        return Err(invalid(format!(
+            "{} bytes is more than the {} byte limit, simplify the geometries or split the map",
+            size, MAX_GEOJSON_BYTES
+        )));
+    }
+
+    check_geojson_object(geojson).map_err(invalid)
+}
+
+fn check_geojson_object(value: &Value) -> Result<(), String> {
+    let object_type = value
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "every GeoJSON object needs a string 'type'".to_string())?;
+
+    match object_type {
+        "FeatureCollection" => value
+            .get("features")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "a FeatureCollection needs a 'features' array".to_string())?
+            .iter()
+            .try_for_each(|feature| {
+                if feature.get("type").and_then(Value::as_str) != Some("Feature") {
+                    return Err("FeatureCollection features must have type 'Feature'".to_string());
+                }
+                check_geojson_object(feature)
+            }),
+        "Feature" => match value.get("geometry") {
+            // A feature without a location is allowed, it is just not drawn
+            Some(Value::Null) => Ok(()),
+            Some(geometry) => check_geometry(geometry),
+            None => Err("a Feature needs a 'geometry'".to_string()),
+        },
+        _ => check_geometry(value),
+    }
+}
+
+fn check_geometry(value: &Value) -> Result<(), String> {
+    let geometry_type = value
+        .get("type")
+        .and_then(Value::as_str)
+        .ok_or_else(|| "a geometry needs a string 'type'".to_string())?;
+
+    // How deeply positions are nested in the coordinates of each geometry type
+    let depth = match geometry_type {
+        "GeometryCollection" => {
+            return value
+                .get("geometries")
+                .and_then(Value::as_array)
+                .ok_or_else(|| "a GeometryCollection needs a 'geometries' array".to_string())?
+                .iter()
+                .try_for_each(check_geometry);
+        }
+        "Point" => 0,
+        "MultiPoint" | "LineString" => 1,
+        "MultiLineString" | "Polygon" => 2,
+        "MultiPolygon" => 3,
+        other => return Err(format!("unknown GeoJSON type '{}'", other)),
+    };
+
+    let coordinates = value
+        .get("coordinates")
+        .ok_or_else(|| format!("a {} needs 'coordinates'", geometry_type))?;
+    check_positions(coordinates, depth)
+        .map_err(|message| format!("{} coordinates: {}", geometry_type, message))
+}
+
+fn check_positions(value: &Value, depth: usize) -> Result<(), String> {
+    let items = value
+        .as_array()
+        .ok_or_else(|| "expected an array".to_string())?;
+
+    if depth == 0 {
+        if items.len() < 2 || !items.iter().all(Value::is_number) {
+            return Err("a position must be an array of at least two numbers".to_string());
+        }
+        return Ok(());
+    }
+    items
+        .iter()
+        .try_for_each(|item| check_positions(item, depth - 1))
+}
+
+/// Check that a custom tile server URL is a Leaflet URL template Leaflet can fill in
+fn validate_tile_url(tile_url: &str) -> Result<(), ErrorData> {
+    if !tile_url.starts_with("https://") && !tile_url.starts_with("http://") {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("tileUrl must be an http or https URL, got '{}'", tile_url),
+            None,
+        ));
+    }
+
+    let missing: Vec<&str> = ["{z}", "{x}", "{y}"]
+        .into_iter()
+        .filter(|placeholder| !tile_url.contains(placeholder))
+        .collect();
+    if !missing.is_empty() {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "tileUrl must contain the {{z}}, {{x}} and {{y}} placeholders, e.g. https://tiles.example.com/{{z}}/{{x}}/{{y}}.png; missing {}",
+                missing.join(", ")
+            ),
+            None,
+        ));
+    }
+
+    Ok(())
+}
+
 /// Sankey node structure
 #[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
 pub struct SankeyNode {
@@ -218,6 +346,223 @@ pub struct RenderChordParams {
     pub data: ChordData,
 }
 
+/// Network graph node structure
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct NetworkNode {
+    /// Unique id that edges refer to
+    pub id: String,
+    /// Label shown next to the node
+    pub label: String,
+    /// Optional group for coloring
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+/// Network graph edge structure
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct NetworkEdge {
+    /// Id of the node the edge starts at
+    pub source: String,
+    /// Id of the node the edge ends at
+    pub target: String,
+    /// Optional weight, drawn as the edge thickness
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub weight: Option<f64>,
+    /// Optional boolean to draw the edge as an arrow from source to target
+    #[serde(default)]
+    pub directed: bool,
+}
+
+/// Network graph data structure
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct NetworkData {
+    /// The nodes of the graph
+    pub nodes: Vec<NetworkNode>,
+    /// The edges between nodes
+    pub edges: Vec<NetworkEdge>,
+    /// Optional title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// Parameters for render_network tool
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct RenderNetworkParams {
+    /// The data for the network graph
+    pub data: NetworkData,
+}
+
+/// Timeline item structure
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct TimelineItem {
+    /// Optional unique id that dependencies refer to (default: the label)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// Label shown on the bar
+    pub label: String,
+    /// Start date, as YYYY-MM-DD or an RFC 3339 date and time
+    pub start: String,
+    /// End date, as YYYY-MM-DD or an RFC 3339 date and time
+    pub end: String,
+    /// Optional category for coloring
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Ids of the items this one depends on, drawn as arrows
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+impl TimelineItem {
+    fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.label)
+    }
+}
+
+/// Timeline data structure
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct TimelineData {
+    /// The items to show
+    pub items: Vec<TimelineItem>,
+    /// Optional title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+/// Parameters for render_timeline tool
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct RenderTimelineParams {
+    /// The data for the timeline
+    pub data: TimelineData,
+}
+
+/// A timeline item as the template draws it, with parsed dates and a row that no other item
+/// overlapping it shares
+#[derive(Debug, Serialize)]
+struct TimelineBar<'a> {
+    id: &'a str,
+    label: &'a str,
+    #[serde(rename = "startMs")]
+    start_ms: i64,
+    #[serde(rename = "endMs")]
+    end_ms: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<&'a str>,
+    dependencies: &'a [String],
+    row: usize,
+}
+
+fn parse_timeline_date(value: &str) -> Option<i64> {
+    if let Ok(datetime) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(datetime.timestamp_millis());
+    }
+    if let Ok(datetime) = chrono::NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S") {
+        return Some(datetime.and_utc().timestamp_millis());
+    }
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .ok()
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .map(|datetime| datetime.and_utc().timestamp_millis())
+}
+
+/// Validate the items and lay them out, with each item on the first row that is free for its
+/// whole duration
+fn layout_timeline(data: &TimelineData) -> Result<Vec<TimelineBar<'_>>, ErrorData> {
+    let invalid = |message: String| ErrorData::new(ErrorCode::INVALID_PARAMS, message, None);
+
+    if data.items.is_empty() {
+        return Err(invalid("A timeline needs at least one item".to_string()));
+    }
+
+    let mut bars = Vec::with_capacity(data.items.len());
+    let mut ids = std::collections::HashSet::new();
+    for item in &data.items {
+        let parse = |value: &str| {
+            parse_timeline_date(value).ok_or_else(|| {
+                invalid(format!(
+                    "Item '{}' has an invalid date '{}', expected YYYY-MM-DD or RFC 3339",
+                    item.label, value
+                ))
+            })
+        };
+        let start_ms = parse(&item.start)?;
+        let end_ms = parse(&item.end)?;
+        if end_ms < start_ms {
+            return Err(invalid(format!(
+                "Item '{}' ends ({}) before it starts ({})",
+                item.label, item.end, item.start
+            )));
+        }
+        if !ids.insert(item.id()) {
+            return Err(invalid(format!("Duplicate item id '{}'", item.id())));
+        }
+
+        bars.push(TimelineBar {
+            id: item.id(),
+            label: &item.label,
+            start_ms,
+            end_ms,
+            category: item.category.as_deref(),
+            dependencies: &item.dependencies,
+            row: 0,
+        });
+    }
+
+    for bar in &bars {
+        if let Some(unknown) = bar
+            .dependencies
+            .iter()
+            .find(|id| !ids.contains(id.as_str()))
+        {
+            return Err(invalid(format!(
+                "Item '{}' depends on unknown item id '{}'",
+                bar.label, unknown
+            )));
+        }
+    }
+
+    let mut order: Vec<usize> = (0..bars.len()).collect();
+    order.sort_by_key(|&i| (bars[i].start_ms, bars[i].end_ms));
+    let mut row_ends: Vec<i64> = Vec::new();
+    for i in order {
+        let bar = &mut bars[i];
+        bar.row = match row_ends.iter().position(|&end| end <= bar.start_ms) {
+            Some(row) => row,
+            None => {
+                row_ends.push(bar.start_ms);
+                row_ends.len() - 1
+            }
+        };
+        row_ends[bar.row] = bar.end_ms;
+    }
+
+    Ok(bars)
+}
+
+/// Check that node ids are unique and that every edge connects existing nodes
+fn validate_network(data: &NetworkData) -> Result<(), ErrorData> {
+    let invalid = |message: String| ErrorData::new(ErrorCode::INVALID_PARAMS, message, None);
+
+    let mut ids = std::collections::HashSet::new();
+    for node in &data.nodes {
+        if !ids.insert(node.id.as_str()) {
+            return Err(invalid(format!("Duplicate node id '{}'", node.id)));
+        }
+    }
+
+    for (i, edge) in data.edges.iter().enumerate() {
+        for endpoint in [&edge.source, &edge.target] {
+            if !ids.contains(endpoint.as_str()) {
+                return Err(invalid(format!(
+                    "Edge {} ({} -> {}) refers to unknown node id '{}'",
+                    i, edge.source, edge.target, endpoint
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 /// Map marker structure
 #[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
 pub struct MapMarker {
@@ -262,6 +607,7 @@ pub struct MapCenter {
 #[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
 pub struct MapData {
     /// Array of markers
+    #[serde(default)]
     pub markers: Vec<MapMarker>,
     /// Optional title for the map
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -282,6 +628,17 @@ pub struct MapData {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "clusterRadius")]
     pub cluster_radius: Option<f64>,
+    /// Optional tile server URL template, replaces the public tile layers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "tileUrl")]
+    pub tile_url: Option<String>,
+    /// Optional maximum zoom level
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "maxZoom")]
+    pub max_zoom: Option<u32>,
+    /// Optional GeoJSON object with regions and routes to draw alongside the markers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geojson: Option<Value>,
     /// Optional boolean to auto-fit map to markers
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "autoFit")]
@@ -378,6 +735,97 @@ pub struct ChartData {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(rename = "yAxisLabel")]
     pub y_axis_label: Option<String>,
+    /// Optional axis scaling and stacking
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "chartOptions")]
+    pub chart_options: Option<ChartOptions>,
+    /// Optional raw Chart.js options, merged over everything else
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Value>,
+}
+
+/// Common chart customizations, mapped onto the Chart.js config by the template
+#[derive(Debug, Default, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct ChartOptions {
+    /// Optional boolean to use a logarithmic y-axis
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "yLogScale")]
+    pub y_log_scale: Option<bool>,
+    /// Optional lower bound of the y-axis
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "yMin")]
+    pub y_min: Option<f64>,
+    /// Optional upper bound of the y-axis
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "yMax")]
+    pub y_max: Option<f64>,
+    /// Optional lower bound of the x-axis (scatter charts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "xMin")]
+    pub x_min: Option<f64>,
+    /// Optional upper bound of the x-axis (scatter charts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "xMax")]
+    pub x_max: Option<f64>,
+    /// Optional boolean to stack the datasets (bar and line charts)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stacked: Option<bool>,
+}
+
+/// Check that the typed chart options make sense for the chart and its data
+fn validate_chart_options(data: &ChartData) -> Result<(), ErrorData> {
+    let Some(options) = &data.chart_options else {
+        return Ok(());
+    };
+    let invalid = |message: String| ErrorData::new(ErrorCode::INVALID_PARAMS, message, None);
+
+    for (axis, min, max) in [
+        ("y", options.y_min, options.y_max),
+        ("x", options.x_min, options.x_max),
+    ] {
+        if let (Some(min), Some(max)) = (min, max) {
+            if min >= max {
+                return Err(invalid(format!(
+                    "{}Min ({}) must be less than {}Max ({})",
+                    axis, min, axis, max
+                )));
+            }
+        }
+    }
+
+    if (options.x_min.is_some() || options.x_max.is_some())
+        && !matches!(data.chart_type, ChartType::Scatter)
+    {
+        return Err(invalid(
+            "xMin and xMax only apply to scatter charts, whose x-axis is numeric".to_string(),
+        ));
+    }
+
+    if options.stacked == Some(true) && matches!(data.chart_type, ChartType::Scatter) {
+        return Err(invalid(
+            "stacked only applies to bar and line charts".to_string(),
+        ));
+    }
+
+    if options.y_log_scale == Some(true) {
+        // A logarithmic axis cannot show zero or negative values
+        if options.y_min.is_some_and(|min| min <= 0.0) {
+            return Err(invalid(
+                "yMin must be positive with a logarithmic y-axis".to_string(),
+            ));
+        }
+        let has_non_positive = data.datasets.iter().any(|dataset| match &dataset.data {
+            ChartDataValues::Numbers(values) => values.iter().any(|y| *y <= 0.0),
+            ChartDataValues::Points(points) => points.iter().any(|point| point.y <= 0.0),
+        });
+        if has_non_positive {
+            return Err(invalid(
+                "a logarithmic y-axis needs all values to be positive".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
 }
 
 /// Parameters for show_chart tool
@@ -387,6 +835,301 @@ pub struct ShowChartParams {
     pub data: ChartData,
 }
 
+/// Serialize a tool's params and return its validated `data` as JSON for a template
+fn template_data_json<T: Serialize>(params: T, allow_array: bool) -> Result<String, ErrorData> {
+    let data = validate_data_param(
+        &serde_json::to_value(params).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("Invalid parameters: {}", e),
+                None,
+            )
+        })?,
+        allow_array,
+    )?;
+
+    serde_json::to_string(&data).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("Invalid JSON data: {}", e),
+            None,
+        )
+    })
+}
+
+// Load all resources at compile time using include_str!
+const D3_MIN: &str = include_str!("templates/assets/d3.min.js");
+const CHART_MIN: &str = include_str!("templates/assets/chart.min.js");
+
+fn sankey_html(params: RenderSankeyParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/sankey_template.html");
+    const D3_SANKEY: &str = include_str!("templates/assets/d3.sankey.min.js");
+
+    let data_json = template_data_json(params, false)?;
+    Ok(TEMPLATE
+        .replace("{{D3_MIN}}", D3_MIN)
+        .replace("{{D3_SANKY}}", D3_SANKEY) // Note: keeping the typo to match template
+        .replace("{{SANKEY_DATA}}", &data_json))
+}
+
+fn radar_html(params: RenderRadarParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/radar_template.html");
+
+    let data_json = template_data_json(params, false)?;
+    Ok(TEMPLATE
+        .replace("{{CHART_MIN}}", CHART_MIN)
+        .replace("{{RADAR_DATA}}", &data_json))
+}
+
+fn donut_html(params: RenderDonutParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/donut_template.html");
+
+    // true because donut accepts arrays
+    let data_json = template_data_json(params, true)?;
+    Ok(TEMPLATE
+        .replace("{{CHART_MIN}}", CHART_MIN)
+        .replace("{{CHARTS_DATA}}", &data_json))
+}
+
+fn treemap_html(params: RenderTreemapParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/treemap_template.html");
+
+    let data_json = template_data_json(params, false)?;
+    Ok(TEMPLATE
+        .replace("{{D3_MIN}}", D3_MIN)
+        .replace("{{TREEMAP_DATA}}", &data_json))
+}
+
+fn chord_html(params: RenderChordParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/chord_template.html");
+
+    let data_json = template_data_json(params, false)?;
+    Ok(TEMPLATE
+        .replace("{{D3_MIN}}", D3_MIN)
+        .replace("{{CHORD_DATA}}", &data_json))
+}
+
+fn network_html(params: RenderNetworkParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/network_template.html");
+
+    validate_network(&params.data)?;
+
+    let data_json = template_data_json(params, false)?;
+    Ok(TEMPLATE
+        .replace("{{D3_MIN}}", D3_MIN)
+        .replace("{{NETWORK_DATA}}", &data_json))
+}
+
+fn timeline_html(params: RenderTimelineParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/timeline_template.html");
+
+    let items = layout_timeline(&params.data)?;
+    let data_json = serde_json::to_string(&serde_json::json!({
+        "title": &params.data.title,
+        "items": items,
+    }))
+    .map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("Invalid JSON data: {}", e),
+            None,
+        )
+    })?;
+
+    Ok(TEMPLATE
+        .replace("{{D3_MIN}}", D3_MIN)
+        .replace("{{TIMELINE_DATA}}", &data_json))
+}
+
+fn map_html(params: RenderMapParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/map_template.html");
+    const LEAFLET_JS: &str = include_str!("templates/assets/leaflet.min.js");
+    const LEAFLET_CSS: &str = include_str!("templates/assets/leaflet.min.css");
+    const MARKERCLUSTER_JS: &str = include_str!("templates/assets/leaflet.markercluster.min.js");
+
+    if let Some(tile_url) = &params.data.tile_url {
+        validate_tile_url(tile_url)?;
+    }
+    if let Some(geojson) = &params.data.geojson {
+        validate_geojson(geojson)?;
+    }
+
+    let title = params
+        .data
+        .title
+        .clone()
+        .unwrap_or_else(|| "Interactive Map".to_string());
+    let subtitle = params
+        .data
+        .subtitle
+        .clone()
+        .unwrap_or_else(|| "Geographic data visualization".to_string());
+    let data_json = template_data_json(params, false)?;
+
+    Ok(TEMPLATE
+        .replace("{{LEAFLET_JS}}", LEAFLET_JS)
+        .replace("{{LEAFLET_CSS}}", LEAFLET_CSS)
+        .replace("{{MARKERCLUSTER_JS}}", MARKERCLUSTER_JS)
+        .replace("{{MAP_DATA}}", &data_json)
+        .replace("{{TITLE}}", &title)
+        .replace("{{SUBTITLE}}", &subtitle))
+}
+
+fn chart_html(params: ShowChartParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/chart_template.html");
+
+    validate_chart_options(&params.data)?;
+
+    let data_json = template_data_json(params, false)?;
+    Ok(TEMPLATE
+        .replace("{{CHART_MIN}}", CHART_MIN)
+        .replace("{{CHART_DATA}}", &data_json))
+}
+
+/// Wrap a rendered visualization as a resource shown to the user
+fn html_resource_result(html_content: &str, debug_path: &str, uri: &str) -> CallToolResult {
+    // Save to the debug path for debugging
+    if let Err(e) = std::fs::write(debug_path, html_content) {
+        tracing::warn!("Failed to write debug HTML to {}: {}", debug_path, e);
+    } else {
+        tracing::info!("Debug HTML saved to {}", debug_path);
+    }
+
+    // Use BlobResourceContents with base64 encoding to avoid JSON string escaping issues
+    let resource_contents = ResourceContents::BlobResourceContents {
+        uri: uri.to_string(),
+        mime_type: Some("text/html".to_string()),
+        blob: STANDARD.encode(html_content.as_bytes()),
+        meta: None,
+    };
+
+    CallToolResult::success(vec![
+        Content::resource(resource_contents).with_audience(vec![Role::User])
+    ])
+}
+
+/// Most panels a dashboard can hold
+const MAX_DASHBOARD_PANELS: usize = 12;
+
+/// One visualization on a dashboard, with the same data as the tool that renders it alone
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum DashboardPanel {
+    /// Same data as render_sankey
+    Sankey(RenderSankeyParams),
+    /// Same data as render_radar
+    Radar(RenderRadarParams),
+    /// Same data as render_donut
+    Donut(RenderDonutParams),
+    /// Same data as render_treemap
+    Treemap(RenderTreemapParams),
+    /// Same data as render_chord
+    Chord(RenderChordParams),
+    /// Same data as render_network
+    Network(RenderNetworkParams),
+    /// Same data as render_timeline
+    Timeline(RenderTimelineParams),
+    /// Same data as render_map
+    Map(RenderMapParams),
+    /// Same data as show_chart
+    Chart(ShowChartParams),
+}
+
+impl DashboardPanel {
+    fn html(self) -> Result<String, ErrorData> {
+        match self {
+            DashboardPanel::Sankey(params) => sankey_html(params),
+            DashboardPanel::Radar(params) => radar_html(params),
+            DashboardPanel::Donut(params) => donut_html(params),
+            DashboardPanel::Treemap(params) => treemap_html(params),
+            DashboardPanel::Chord(params) => chord_html(params),
+            DashboardPanel::Network(params) => network_html(params),
+            DashboardPanel::Timeline(params) => timeline_html(params),
+            DashboardPanel::Map(params) => map_html(params),
+            DashboardPanel::Chart(params) => chart_html(params),
+        }
+    }
+}
+
+/// Dashboard data structure
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct DashboardData {
+    /// The visualizations, laid out left to right and top to bottom
+    pub panels: Vec<DashboardPanel>,
+    /// Optional dashboard title
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Optional number of grid columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub columns: Option<u32>,
+    /// Optional height of each panel in pixels
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "panelHeight")]
+    pub panel_height: Option<u32>,
+}
+
+/// Parameters for render_dashboard tool
+#[derive(Debug, Serialize, Deserialize, rmcp::schemars::JsonSchema)]
+pub struct RenderDashboardParams {
+    /// The data for the dashboard
+    pub data: DashboardData,
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn dashboard_html(params: RenderDashboardParams) -> Result<String, ErrorData> {
+    const TEMPLATE: &str = include_str!("templates/dashboard_template.html");
+
+    let data = params.data;
+    if data.panels.is_empty() || data.panels.len() > MAX_DASHBOARD_PANELS {
+        return Err(ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!(
+                "A dashboard needs between 1 and {} panels, got {}",
+                MAX_DASHBOARD_PANELS,
+                data.panels.len()
+            ),
+            None,
+        ));
+    }
+
+    // Up to two columns by default, beyond that panels get too narrow to read
+    let columns = data
+        .columns
+        .unwrap_or(if data.panels.len() > 1 { 2 } else { 1 })
+        .clamp(1, 4);
+    let panel_height = data.panel_height.unwrap_or(600).clamp(200, 2000);
+
+    // Each panel is a complete page of its own in an iframe, so the libraries and scripts of
+    // different visualizations cannot interfere with each other
+    let mut panels = String::new();
+    for (i, panel) in data.panels.into_iter().enumerate() {
+        let html = panel.html().map_err(|e| {
+            ErrorData::new(
+                e.code,
+                format!("Dashboard panel {}: {}", i + 1, e.message),
+                e.data,
+            )
+        })?;
+        panels.push_str(&format!(
+            "<iframe class=\"panel\" sandbox=\"allow-scripts\" srcdoc=\"{}\"></iframe>\n",
+            escape_html(&html)
+        ));
+    }
+
+    let title = data.title.unwrap_or_else(|| "Dashboard".to_string());
+    Ok(TEMPLATE
+        .replace("{{TITLE}}", &escape_html(&title))
+        .replace("{{COLUMNS}}", &columns.to_string())
+        .replace("{{PANEL_HEIGHT}}", &panel_height.to_string())
+        .replace("{{PANELS}}", &panels))
+}
+
 /// An extension for automatic data visualization and UI generation
 #[derive(Clone)]
 pub struct AutoVisualiserRouter {
@@ -447,8 +1190,11 @@ impl AutoVisualiserRouter {
             - **render_donut**: Creates interactive donut/pie charts for categorical data (supports multiple charts)
             - **render_treemap**: Creates interactive treemap visualizations for hierarchical data
             - **render_chord**: Creates interactive chord diagrams for relationship/flow visualization
+            - **render_network**: Creates interactive node-link graphs for dependencies, hierarchies, and other networks
+            - **render_timeline**: Creates Gantt-style timelines for plans, projects, and schedules
             - **render_map**: Creates interactive map visualizations with location markers
             - **show_chart**: Creates interactive line, scatter, or bar charts for data visualization
+            - **render_dashboard**: Lays out several of the visualizations above together on one page
         "#};
 
         Self {
@@ -481,60 +1227,12 @@ Example:
         &self,
         params: Parameters<RenderSankeyParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let data = validate_data_param(
-            &serde_json::to_value(params.0).map_err(|e| {
-                ErrorData::new(
-                    ErrorCode::INVALID_PARAMS,
-                    format!("Invalid parameters: {}", e),
-                    None,
-                )
-            })?,
-            false,
-        )?;
-
-        // Convert the data to JSON string
-        let data_json = serde_json::to_string(&data).map_err(|e| {
-            ErrorData::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("Invalid JSON data: {}", e),
-                None,
-            )
-        })?;
-
-        // Load all resources at compile time using include_str!
-        const TEMPLATE: &str = include_str!("templates/sankey_template.html");
-        const D3_MIN: &str = include_str!("templates/assets/d3.min.js");
-        const D3_SANKEY: &str = include_str!("templates/assets/d3.sankey.min.js");
-
-        // Replace all placeholders with actual content
-        let html_content = TEMPLATE
-            .replace("{{D3_MIN}}", D3_MIN)
-            .replace("{{D3_SANKY}}", D3_SANKEY) // Note: keeping the typo to match template
-            .replace("{{SANKEY_DATA}}", &data_json);
-
-        // Save to /tmp/vis.html for debugging
-        let debug_path = std::path::Path::new("/tmp/vis.html");
-        if let Err(e) = std::fs::write(debug_path, &html_content) {
-            tracing::warn!("Failed to write debug HTML to /tmp/vis.html: {}", e);
-        } else {
-            tracing::info!("Debug HTML saved to /tmp/vis.html");
-        }
-
-        // Use BlobResourceContents with base64 encoding to avoid JSON string escaping issues
-        let html_bytes = html_content.as_bytes();
-        let base64_encoded = STANDARD.encode(html_bytes);
-
-        let resource_contents = ResourceContents::BlobResourceContents {
-            uri: "ui://sankey/diagram".to_string(),
-            mime_type: Some("text/html".to_string()),
-            blob: base64_encoded,
-            meta: None,
-        };
-
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let html_content = sankey_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/vis.html",
+            "ui://sankey/diagram",
+        ))
     }
 
     /// show a radar chart (spider chart) for multi-dimensional data comparison
@@ -565,58 +1263,12 @@ Example:
         &self,
         params: Parameters<RenderRadarParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let data = validate_data_param(
-            &serde_json::to_value(params.0).map_err(|e| {
-                ErrorData::new(
-                    ErrorCode::INVALID_PARAMS,
-                    format!("Invalid parameters: {}", e),
-                    None,
-                )
-            })?,
-            false,
-        )?;
-
-        // Convert the data to JSON string
-        let data_json = serde_json::to_string(&data).map_err(|e| {
-            ErrorData::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("Invalid JSON data: {}", e),
-                None,
-            )
-        })?;
-
-        // Load all resources at compile time using include_str!
-        const TEMPLATE: &str = include_str!("templates/radar_template.html");
-        const CHART_MIN: &str = include_str!("templates/assets/chart.min.js");
-
-        // Replace all placeholders with actual content
-        let html_content = TEMPLATE
-            .replace("{{CHART_MIN}}", CHART_MIN)
-            .replace("{{RADAR_DATA}}", &data_json);
-
-        // Save to /tmp/radar.html for debugging
-        let debug_path = std::path::Path::new("/tmp/radar.html");
-        if let Err(e) = std::fs::write(debug_path, &html_content) {
-            tracing::warn!("Failed to write debug HTML to /tmp/radar.html: {}", e);
-        } else {
-            tracing::info!("Debug HTML saved to /tmp/radar.html");
-        }
-
-        // Use BlobResourceContents with base64 encoding to avoid JSON string escaping issues
-        let html_bytes = html_content.as_bytes();
-        let base64_encoded = STANDARD.encode(html_bytes);
-
-        let resource_contents = ResourceContents::BlobResourceContents {
-            uri: "ui://radar/chart".to_string(),
-            mime_type: Some("text/html".to_string()),
-            blob: base64_encoded,
-            meta: None,
-        };
-
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let html_content = radar_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/radar.html",
+            "ui://radar/chart",
+        ))
     }
 
     /// show pie or donut charts for categorical data visualization
@@ -652,58 +1304,12 @@ Example multiple charts:
         &self,
         params: Parameters<RenderDonutParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let data = validate_data_param(
-            &serde_json::to_value(params.0).map_err(|e| {
-                ErrorData::new(
-                    ErrorCode::INVALID_PARAMS,
-                    format!("Invalid parameters: {}", e),
-                    None,
-                )
-            })?,
-            true,
-        )?; // true because donut accepts arrays
-
-        // Convert the data to JSON string
-        let data_json = serde_json::to_string(&data).map_err(|e| {
-            ErrorData::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("Invalid JSON data: {}", e),
-                None,
-            )
-        })?;
-
-        // Load all resources at compile time using include_str!
-        const TEMPLATE: &str = include_str!("templates/donut_template.html");
-        const CHART_MIN: &str = include_str!("templates/assets/chart.min.js");
-
-        // Replace all placeholders with actual content
-        let html_content = TEMPLATE
-            .replace("{{CHART_MIN}}", CHART_MIN)
-            .replace("{{CHARTS_DATA}}", &data_json);
-
-        // Save to /tmp/donut.html for debugging
-        let debug_path = std::path::Path::new("/tmp/donut.html");
-        if let Err(e) = std::fs::write(debug_path, &html_content) {
-            tracing::warn!("Failed to write debug HTML to /tmp/donut.html: {}", e);
-        } else {
-            tracing::info!("Debug HTML saved to /tmp/donut.html");
-        }
-
-        // Use BlobResourceContents with base64 encoding to avoid JSON string escaping issues
-        let html_bytes = html_content.as_bytes();
-        let base64_encoded = STANDARD.encode(html_bytes);
-
-        let resource_contents = ResourceContents::BlobResourceContents {
-            uri: "ui://donut/chart".to_string(),
-            mime_type: Some("text/html".to_string()),
-            blob: base64_encoded,
-            meta: None,
-        };
-
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let html_content = donut_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/donut.html",
+            "ui://donut/chart",
+        ))
     }
 
     /// show a treemap visualization for hierarchical data
@@ -736,58 +1342,12 @@ Example:
         &self,
         params: Parameters<RenderTreemapParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let data = validate_data_param(
-            &serde_json::to_value(params.0).map_err(|e| {
-                ErrorData::new(
-                    ErrorCode::INVALID_PARAMS,
-                    format!("Invalid parameters: {}", e),
-                    None,
-                )
-            })?,
-            false,
-        )?;
-
-        // Convert the data to JSON string
-        let data_json = serde_json::to_string(&data).map_err(|e| {
-            ErrorData::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("Invalid JSON data: {}", e),
-                None,
-            )
-        })?;
-
-        // Load all resources at compile time using include_str!
-        const TEMPLATE: &str = include_str!("templates/treemap_template.html");
-        const D3_MIN: &str = include_str!("templates/assets/d3.min.js");
-
-        // Replace all placeholders with actual content
-        let html_content = TEMPLATE
-            .replace("{{D3_MIN}}", D3_MIN)
-            .replace("{{TREEMAP_DATA}}", &data_json);
-
-        // Save to /tmp/treemap.html for debugging
-        let debug_path = std::path::Path::new("/tmp/treemap.html");
-        if let Err(e) = std::fs::write(debug_path, &html_content) {
-            tracing::warn!("Failed to write debug HTML to /tmp/treemap.html: {}", e);
-        } else {
-            tracing::info!("Debug HTML saved to /tmp/treemap.html");
-        }
-
-        // Use BlobResourceContents with base64 encoding to avoid JSON string escaping issues
-        let html_bytes = html_content.as_bytes();
-        let base64_encoded = STANDARD.encode(html_bytes);
-
-        let resource_contents = ResourceContents::BlobResourceContents {
-            uri: "ui://treemap/visualization".to_string(),
-            mime_type: Some("text/html".to_string()),
-            blob: base64_encoded,
-            meta: None,
-        };
-
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let html_content = treemap_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/treemap.html",
+            "ui://treemap/visualization",
+        ))
     }
 
     /// Show a chord diagram visualization for relationships and flows
@@ -814,58 +1374,84 @@ Example:
         &self,
         params: Parameters<RenderChordParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let data = validate_data_param(
-            &serde_json::to_value(params.0).map_err(|e| {
-                ErrorData::new(
-                    ErrorCode::INVALID_PARAMS,
-                    format!("Invalid parameters: {}", e),
-                    None,
-                )
-            })?,
-            false,
-        )?;
-
-        // Convert the data to JSON string
-        let data_json = serde_json::to_string(&data).map_err(|e| {
-            ErrorData::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("Invalid JSON data: {}", e),
-                None,
-            )
-        })?;
+        let html_content = chord_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/chord.html",
+            "ui://chord/diagram",
+        ))
+    }
 
-        // Load all resources at compile time using include_str!
-        const TEMPLATE: &str = include_str!("templates/chord_template.html");
-        const D3_MIN: &str = include_str!("templates/assets/d3.min.js");
+    /// show a force-directed network graph of nodes and edges
+    #[tool(
+        name = "render_network",
+        description = r#"show an interactive force-directed network graph of nodes and the edges between them, e.g. dependency trees, org charts or knowledge graphs.
 
-        // Replace all placeholders with actual content
-        let html_content = TEMPLATE
-            .replace("{{D3_MIN}}", D3_MIN)
-            .replace("{{CHORD_DATA}}", &data_json);
+The data must contain:
+- nodes: Array of objects with a unique 'id', a 'label', and an optional 'group' used for coloring
+- edges: Array of objects with 'source' and 'target' node ids, an optional numeric 'weight' drawn as the edge thickness, and an optional boolean 'directed' to draw an arrow
+- title: Optional title
 
-        // Save to /tmp/chord.html for debugging
-        let debug_path = std::path::Path::new("/tmp/chord.html");
-        if let Err(e) = std::fs::write(debug_path, &html_content) {
-            tracing::warn!("Failed to write debug HTML to /tmp/chord.html: {}", e);
-        } else {
-            tracing::info!("Debug HTML saved to /tmp/chord.html");
-        }
+Example:
+{
+  "title": "Service Dependencies",
+  "nodes": [
+    {"id": "web", "label": "Web", "group": "frontend"},
+    {"id": "api", "label": "API", "group": "backend"},
+    {"id": "db", "label": "Database", "group": "storage"}
+  ],
+  "edges": [
+    {"source": "web", "target": "api", "directed": true},
+    {"source": "api", "target": "db", "weight": 3, "directed": true}
+  ]
+}"#
+    )]
+    pub async fn render_network(
+        &self,
+        params: Parameters<RenderNetworkParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let html_content = network_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/network.html",
+            "ui://network/graph",
+        ))
+    }
 
-        // Use BlobResourceContents with base64 encoding to avoid JSON string escaping issues
-        let html_bytes = html_content.as_bytes();
-        let base64_encoded = STANDARD.encode(html_bytes);
+    /// show a Gantt-style timeline of items with start and end dates
+    #[tool(
+        name = "render_timeline",
+        description = r#"show a Gantt-style timeline of tasks or events with start and end dates, e.g. project plans or schedules. Overlapping items are placed on separate rows.
 
-        let resource_contents = ResourceContents::BlobResourceContents {
-            uri: "ui://chord/diagram".to_string(),
-            mime_type: Some("text/html".to_string()),
-            blob: base64_encoded,
-            meta: None,
-        };
+The data must contain:
+- items: Array of objects with:
+  - label: Text shown on the bar (required)
+  - start, end: Dates as YYYY-MM-DD or RFC 3339 date-times (required, end must not be before start)
+  - id: Optional unique id for dependencies to refer to (default: the label)
+  - category: Optional category used for coloring
+  - dependencies: Optional array of ids of items this one depends on, drawn as arrows
+- title: Optional title
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+Example:
+{
+  "title": "Release Plan",
+  "items": [
+    {"id": "design", "label": "Design", "start": "2025-01-06", "end": "2025-01-17", "category": "Planning"},
+    {"id": "build", "label": "Build", "start": "2025-01-20", "end": "2025-02-14", "category": "Engineering", "dependencies": ["design"]},
+    {"label": "Docs", "start": "2025-02-03", "end": "2025-02-14", "category": "Writing", "dependencies": ["design"]}
+  ]
+}"#
+    )]
+    pub async fn render_timeline(
+        &self,
+        params: Parameters<RenderTimelineParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let html_content = timeline_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/timeline.html",
+            "ui://timeline/chart",
+        ))
     }
 
     /// show an interactive map visualization with location markers
@@ -874,13 +1460,17 @@ Example:
         description = r#"show an interactive map visualization with location markers using Leaflet.
 
 The data must contain:
-- markers: Array of objects with 'lat', 'lng', and optional properties
+- markers: Array of objects with 'lat', 'lng', and optional properties (may be empty when geojson is given)
 - title: Optional title for the map (default: "Interactive Map")
 - subtitle: Optional subtitle (default: "Geographic data visualization")
 - center: Optional center point {lat, lng} (default: USA center)
 - zoom: Optional initial zoom level (default: 4)
 - clustering: Optional boolean to enable/disable clustering (default: true)
-- autoFit: Optional boolean to auto-fit map to markers (default: true)
+- clusterRadius: Optional maximum radius in pixels of a marker cluster (default: 50)
+- tileUrl: Optional tile server URL template with {z}, {x} and {y} placeholders, e.g. "https://tiles.example.com/{z}/{x}/{y}.png" (default: public OpenStreetMap, satellite and topographic layers)
+- maxZoom: Optional maximum zoom level (default: 18)
+- geojson: Optional GeoJSON object (FeatureCollection, Feature or geometry) of regions and routes drawn alongside the markers. Feature properties 'name', 'description', 'popup' and 'color' are used for popups and styling
+- autoFit: Optional boolean to auto-fit map to markers and GeoJSON (default: true)
 
 Marker properties:
 - lat: Latitude (required)
@@ -906,75 +1496,12 @@ Example:
         &self,
         params: Parameters<RenderMapParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let data = validate_data_param(
-            &serde_json::to_value(params.0).map_err(|e| {
-                ErrorData::new(
-                    ErrorCode::INVALID_PARAMS,
-                    format!("Invalid parameters: {}", e),
-                    None,
-                )
-            })?,
-            false,
-        )?;
-
-        // Extract title and subtitle from data if provided
-        let title = data
-            .get("title")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Interactive Map");
-        let subtitle = data
-            .get("subtitle")
-            .and_then(|v| v.as_str())
-            .unwrap_or("Geographic data visualization");
-
-        // Convert the data to JSON string
-        let data_json = serde_json::to_string(&data).map_err(|e| {
-            ErrorData::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("Invalid JSON data: {}", e),
-                None,
-            )
-        })?;
-
-        // Load all resources at compile time using include_str!
-        const TEMPLATE: &str = include_str!("templates/map_template.html");
-        const LEAFLET_JS: &str = include_str!("templates/assets/leaflet.min.js");
-        const LEAFLET_CSS: &str = include_str!("templates/assets/leaflet.min.css");
-        const MARKERCLUSTER_JS: &str =
-            include_str!("templates/assets/leaflet.markercluster.min.js");
-
-        // Replace all placeholders with actual content
-        let html_content = TEMPLATE
-            .replace("{{LEAFLET_JS}}", LEAFLET_JS)
-            .replace("{{LEAFLET_CSS}}", LEAFLET_CSS)
-            .replace("{{MARKERCLUSTER_JS}}", MARKERCLUSTER_JS)
-            .replace("{{MAP_DATA}}", &data_json)
-            .replace("{{TITLE}}", title)
-            .replace("{{SUBTITLE}}", subtitle);
-
-        // Save to /tmp/map.html for debugging
-        let debug_path = std::path::Path::new("/tmp/map.html");
-        if let Err(e) = std::fs::write(debug_path, &html_content) {
-            tracing::warn!("Failed to write debug HTML to /tmp/map.html: {}", e);
-        } else {
-            tracing::info!("Debug HTML saved to /tmp/map.html");
-        }
-
-        // Use BlobResourceContents with base64 encoding to avoid JSON string escaping issues
-        let html_bytes = html_content.as_bytes();
-        let base64_encoded = STANDARD.encode(html_bytes);
-
-        let resource_contents = ResourceContents::BlobResourceContents {
-            uri: "ui://map/visualization".to_string(),
-            mime_type: Some("text/html".to_string()),
-            blob: base64_encoded,
-            meta: None,
-        };
-
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+        let html_content = map_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/map.html",
+            "ui://map/visualization",
+        ))
     }
 
     /// show interactive line, scatter, or bar charts
@@ -983,7 +1510,15 @@ Example:
         description = r#"show interactive line, scatter, or bar charts
 
 Required: type ('line', 'scatter', or 'bar'), datasets array
-Optional: labels, title, subtitle, xAxisLabel, yAxisLabel, options
+Optional: labels, title, subtitle, xAxisLabel, yAxisLabel, chartOptions, options
+
+chartOptions:
+- yLogScale: Optional boolean for a logarithmic y-axis (all values must be positive)
+- yMin, yMax: Optional fixed y-axis bounds
+- xMin, xMax: Optional fixed x-axis bounds (scatter charts only)
+- stacked: Optional boolean to stack the datasets (bar and line charts only)
+
+options: Optional raw Chart.js options, for anything chartOptions does not cover
 
 Example:
 {
@@ -999,58 +1534,50 @@ Example:
         &self,
         params: Parameters<ShowChartParams>,
     ) -> Result<CallToolResult, ErrorData> {
-        let data = validate_data_param(
-            &serde_json::to_value(params.0).map_err(|e| {
-                ErrorData::new(
-                    ErrorCode::INVALID_PARAMS,
-                    format!("Invalid parameters: {}", e),
-                    None,
-                )
-            })?,
-            false,
-        )?;
-
-        // Convert the data to JSON string
-        let data_json = serde_json::to_string(&data).map_err(|e| {
-            ErrorData::new(
-                ErrorCode::INVALID_PARAMS,
-                format!("Invalid JSON data: {}", e),
-                None,
-            )
-        })?;
-
-        // Load all resources at compile time using include_str!
-        const TEMPLATE: &str = include_str!("templates/chart_template.html");
-        const CHART_MIN: &str = include_str!("templates/assets/chart.min.js");
-
-        // Replace all placeholders with actual content
-        let html_content = TEMPLATE
-            .replace("{{CHART_MIN}}", CHART_MIN)
-            .replace("{{CHART_DATA}}", &data_json);
+        let html_content = chart_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/chart.html",
+            "ui://chart/interactive",
+        ))
+    }
 
-        // Save to /tmp/chart.html for debugging
-        let debug_path = std::path::Path::new("/tmp/chart.html");
-        if let Err(e) = std::fs::write(debug_path, &html_content) {
-            tracing::warn!("Failed to write debug HTML to /tmp/chart.html: {}", e);
-        } else {
-            tracing::info!("Debug HTML saved to /tmp/chart.html");
-        }
+    /// show several visualizations together on one dashboard page
+    #[tool(
+        name = "render_dashboard",
+        description = r#"show several visualizations laid out together in a grid on one page
 
-        // Use BlobResourceContents with base64 encoding to avoid JSON string escaping issues
-        let html_bytes = html_content.as_bytes();
-        let base64_encoded = STANDARD.encode(html_bytes);
+Use this instead of calling several render tools when presenting related results together.
 
-        let resource_contents = ResourceContents::BlobResourceContents {
-            uri: "ui://chart/interactive".to_string(),
-            mime_type: Some("text/html".to_string()),
-            blob: base64_encoded,
-            meta: None,
-        };
+The data must contain:
+- panels: Array of 1 to 12 panels. Each has a 'kind' and the same 'data' its own tool takes:
+  'sankey' (render_sankey), 'radar' (render_radar), 'donut' (render_donut), 'treemap' (render_treemap),
+  'chord' (render_chord), 'network' (render_network),
+  'timeline' (render_timeline), 'map' (render_map) or 'chart' (show_chart)
+- title: Optional dashboard title (default: "Dashboard")
+- columns: Optional number of grid columns from 1 to 4 (default: 2, or 1 for a single panel)
+- panelHeight: Optional height of each panel in pixels (default: 600)
 
-        Ok(CallToolResult::success(vec![Content::resource(
-            resource_contents,
-        )
-        .with_audience(vec![Role::User])]))
+Example:
+{
+  "title": "Quarterly Review",
+  "columns": 2,
+  "panels": [
+    {"kind": "chart", "data": {"type": "bar", "labels": ["Q1", "Q2"], "datasets": [{"label": "Revenue", "data": [120, 150]}]}},
+    {"kind": "donut", "data": {"data": [{"label": "Marketing", "value": 25000}, {"label": "Development", "value": 35000}]}}
+  ]
+}"#
+    )]
+    pub async fn render_dashboard(
+        &self,
+        params: Parameters<RenderDashboardParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let html_content = dashboard_html(params.0)?;
+        Ok(html_resource_result(
+            &html_content,
+            "/tmp/dashboard.html",
+            "ui://dashboard/panels",
+        ))
     }
 }
 
@@ -1414,6 +1941,9 @@ mod tests {
                 zoom: None,
                 clustering: None,
                 cluster_radius: None,
+                tile_url: None,
+                max_zoom: None,
+                geojson: None,
                 auto_fit: None,
             },
         });
@@ -1431,6 +1961,52 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_geojson() {
+        let route = serde_json::json!({
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": {"name": "Route"},
+                    "geometry": {"type": "LineString", "coordinates": [[-122.4, 37.8], [-74.0, 40.7]]}
+                },
+                {
+                    "type": "Feature",
+                    "properties": {},
+                    "geometry": {"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 0]]]}
+                }
+            ]
+        });
+        assert!(validate_geojson(&route).is_ok());
+
+        let flat_polygon = serde_json::json!({"type": "Polygon", "coordinates": [[0, 0], [1, 0]]});
+        let err = validate_geojson(&flat_polygon).unwrap_err();
+        assert!(err.message.contains("Polygon coordinates"));
+
+        assert!(validate_geojson(&serde_json::json!({"type": "Circle"})).is_err());
+        assert!(validate_geojson(&serde_json::json!({"features": []})).is_err());
+
+        let huge = serde_json::json!({
+            "type": "MultiPoint",
+            "coordinates": vec![[0.123456789, 0.123456789]; MAX_GEOJSON_BYTES / 20],
+        });
+        assert!(validate_geojson(&huge)
+            .unwrap_err()
+            .message
+            .contains("byte limit"));
+    }
+
+    #[test]
+    fn test_validate_tile_url() {
+        assert!(validate_tile_url("https://tiles.example.com/{z}/{x}/{y}.png").is_ok());
+        assert!(validate_tile_url("https://{s}.tiles.example.com/{z}/{y}/{x}").is_ok());
+
+        let err = validate_tile_url("https://tiles.example.com/{z}/tile.png").unwrap_err();
+        assert!(err.message.contains("missing {x}, {y}"));
+        assert!(validate_tile_url("tiles.example.com/{z}/{x}/{y}.png").is_err());
+    }
+
     #[tokio::test]
     async fn test_show_chart() {
         let router = AutoVisualiserRouter::new();
@@ -1454,6 +2030,8 @@ mod tests {
                 subtitle: None,
                 x_axis_label: None,
                 y_axis_label: None,
+                chart_options: None,
+                options: None,
             },
         });
 
@@ -1472,4 +2050,208 @@ mod tests {
             &vec![Role::User]
         );
     }
+
+    #[test]
+    fn test_validate_chart_options() {
+        let chart = |chart_type: ChartType, values: Vec<f64>, options: ChartOptions| ChartData {
+            chart_type,
+            datasets: vec![ChartDataset {
+                label: "Requests".to_string(),
+                data: ChartDataValues::Numbers(values),
+                background_color: None,
+                border_color: None,
+                border_width: None,
+                tension: None,
+                fill: None,
+            }],
+            labels: None,
+            title: None,
+            subtitle: None,
+            x_axis_label: None,
+            y_axis_label: None,
+            chart_options: Some(options),
+            options: None,
+        };
+
+        let log_scale = ChartOptions {
+            y_log_scale: Some(true),
+            y_min: Some(1.0),
+            ..Default::default()
+        };
+        assert!(
+            validate_chart_options(&chart(ChartType::Line, vec![1.0, 10.0], log_scale)).is_ok()
+        );
+
+        let log_scale = ChartOptions {
+            y_log_scale: Some(true),
+            ..Default::default()
+        };
+        assert!(
+            validate_chart_options(&chart(ChartType::Line, vec![0.0, 10.0], log_scale)).is_err()
+        );
+
+        let inverted = ChartOptions {
+            y_min: Some(10.0),
+            y_max: Some(1.0),
+            ..Default::default()
+        };
+        let err = validate_chart_options(&chart(ChartType::Bar, vec![5.0], inverted)).unwrap_err();
+        assert!(err.message.contains("yMin (10) must be less than yMax (1)"));
+
+        let stacked = ChartOptions {
+            stacked: Some(true),
+            ..Default::default()
+        };
+        assert!(validate_chart_options(&chart(ChartType::Bar, vec![5.0], stacked)).is_ok());
+        let stacked = ChartOptions {
+            stacked: Some(true),
+            ..Default::default()
+        };
+        assert!(validate_chart_options(&chart(ChartType::Scatter, vec![5.0], stacked)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_dashboard() {
+        let router = AutoVisualiserRouter::new();
+        let params: RenderDashboardParams = serde_json::from_value(serde_json::json!({
+            "data": {
+                "title": "Q&A",
+                "panels": [
+                    {
+                        "kind": "radar",
+                        "data": {
+                            "labels": ["Speed", "Power"],
+                            "datasets": [{"label": "Player 1", "data": [80, 90]}]
+                        }
+                    },
+                    {
+                        "kind": "donut",
+                        "data": {"data": [{"label": "Marketing", "value": 25000}]}
+                    }
+                ]
+            }
+        }))
+        .unwrap();
+
+        let tool_result = router.render_dashboard(Parameters(params)).await.unwrap();
+        let RawContent::Resource(resource) = &*tool_result.content[0] else {
+            panic!("Expected Resource content");
+        };
+        let ResourceContents::BlobResourceContents { uri, blob, .. } = &resource.resource else {
+            panic!("Expected BlobResourceContents");
+        };
+        assert_eq!(uri, "ui://dashboard/panels");
+
+        let html = String::from_utf8(STANDARD.decode(blob).unwrap()).unwrap();
+        assert_eq!(html.matches("<iframe").count(), 2);
+        assert!(html.contains("<h1>Q&amp;A</h1>"));
+        assert!(html.contains("repeat(2, minmax(0, 1fr))"));
+
+        let empty = RenderDashboardParams {
+            data: DashboardData {
+                panels: vec![],
+                title: None,
+                columns: None,
+                panel_height: None,
+            },
+        };
+        assert!(router.render_dashboard(Parameters(empty)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_render_network() {
+        let router = AutoVisualiserRouter::new();
+        let node = |id: &str| NetworkNode {
+            id: id.to_string(),
+            label: id.to_uppercase(),
+            group: None,
+        };
+        let edge = |source: &str, target: &str| NetworkEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            weight: None,
+            directed: true,
+        };
+
+        let params = RenderNetworkParams {
+            data: NetworkData {
+                nodes: vec![node("web"), node("api")],
+                edges: vec![edge("web", "api")],
+                title: None,
+            },
+        };
+        let tool_result = router.render_network(Parameters(params)).await.unwrap();
+        assert_eq!(tool_result.content.len(), 1);
+
+        let dangling = RenderNetworkParams {
+            data: NetworkData {
+                nodes: vec![node("web"), node("api")],
+                edges: vec![edge("web", "db")],
+                title: None,
+            },
+        };
+        let err = router
+            .render_network(Parameters(dangling))
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("unknown node id 'db'"));
+
+        let duplicate = NetworkData {
+            nodes: vec![node("web"), node("web")],
+            edges: vec![],
+            title: None,
+        };
+        assert!(validate_network(&duplicate).is_err());
+    }
+
+    #[test]
+    fn test_layout_timeline() {
+        let item = |label: &str, start: &str, end: &str, dependencies: &[&str]| TimelineItem {
+            id: None,
+            label: label.to_string(),
+            start: start.to_string(),
+            end: end.to_string(),
+            category: None,
+            dependencies: dependencies.iter().map(|d| d.to_string()).collect(),
+        };
+
+        let data = TimelineData {
+            items: vec![
+                item("Design", "2025-01-06", "2025-01-17", &[]),
+                item("Build", "2025-01-17", "2025-02-14", &["Design"]),
+                item("Docs", "2025-02-03", "2025-02-14T12:00:00Z", &["Design"]),
+                item("Review", "2025-01-10", "2025-01-12", &[]),
+            ],
+            title: None,
+        };
+        let rows: Vec<(&str, usize)> = layout_timeline(&data)
+            .unwrap()
+            .iter()
+            .map(|bar| (bar.label, bar.row))
+            .collect();
+        // Build starts as Design ends so shares its row, Review and Docs overlap other items
+        assert_eq!(
+            rows,
+            vec![("Design", 0), ("Build", 0), ("Docs", 1), ("Review", 1)]
+        );
+
+        let backwards = TimelineData {
+            items: vec![item("Design", "2025-01-17", "2025-01-06", &[])],
+            title: None,
+        };
+        let err = layout_timeline(&backwards).unwrap_err();
+        assert!(err.message.contains("ends (2025-01-06) before it starts"));
+
+        let dangling = TimelineData {
+            items: vec![item("Build", "2025-01-17", "2025-02-14", &["Design"])],
+            title: None,
+        };
+        assert!(layout_timeline(&dangling).is_err());
+
+        let bad_date = TimelineData {
+            items: vec![item("Build", "next week", "2025-02-14", &[])],
+            title: None,
+        };
+        assert!(layout_timeline(&bad_date).is_err());
+    }
 }
@@ -1,3 +1,4 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
 use etcetera::{choose_app_strategy, AppStrategy};
 use indoc::{formatdoc, indoc};
 use reqwest::{Client, Url};
@@ -107,8 +108,18 @@ pub struct CacheParams {
     pub command: CacheCommand,
     /// Path to the cached file for view/delete commands
     pub path: Option<String>,
+    /// Where view starts: a line number for text files, a byte offset for binary files
+    pub offset: Option<usize>,
+    /// How much view returns: lines for text files, bytes for binary files
+    pub limit: Option<usize>,
 }
 
+/// Lines returned by cache view when no limit is given
+const DEFAULT_VIEW_LINES: usize = 500;
+/// Bytes shown as a hexdump by cache view when no limit is given, and the most it will show
+const DEFAULT_VIEW_BYTES: usize = 512;
+const MAX_VIEW_BYTES: usize = 4096;
+
 /// Parameters for the pdf_tool
 /// Enum for operation parameter in pdf_tool
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Clone)]
@@ -240,16 +251,18 @@ pub enum XlsxOperation {
     ListWorksheets,
     /// Get column names from a worksheet
     GetColumns,
-    /// Get values and formulas from a cell range
+    /// Get values and/or formulas from a cell range
     GetRange,
     /// Search for text in a worksheet
     FindText,
     /// Update a single cell's value
     UpdateCell,
-    /// Get value and formula from a specific cell
+    /// Get value and/or formula from a specific cell
     GetCell,
     /// Save changes back to the file
     Save,
+    /// Write a worksheet or range to a CSV file
+    ExportCsv,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -260,7 +273,7 @@ pub struct XlsxToolParams {
     pub operation: XlsxOperation,
     /// Worksheet name (if not provided, uses first worksheet)
     pub worksheet: Option<String>,
-    /// Cell range in A1 notation (e.g., 'A1:C10') for get_range operation
+    /// Cell range in A1 notation (e.g., 'A1:C10') for get_range and export_csv operations
     pub range: Option<String>,
     /// Text to search for in find_text operation
     pub search_text: Option<String>,
@@ -273,6 +286,9 @@ pub struct XlsxToolParams {
     pub col: Option<u64>,
     /// New value for update_cell operation
     pub value: Option<String>,
+    /// What get_range and get_cell return for each cell: values, formulas or both (default)
+    #[serde(default)]
+    pub content: xlsx_tool::CellContent,
 }
 
 /// ComputerController MCP Server using official RMCP SDK
@@ -481,7 +497,8 @@ impl ComputerControllerServer {
             - binary (for images and other files)
             The content is cached locally and can be accessed later using the cache_path
             returned in the response.
-        "
+        ",
+        annotations(read_only_hint = true, idempotent_hint = true, open_world_hint = true)
     )]
     pub async fn web_scrape(
         &self,
@@ -749,7 +766,7 @@ impl ComputerControllerServer {
             result.push_str(&format!("\n\nOutput saved to: {}", cache_path.display()));
 
             // Register as a resource
-            self.register_as_resource(&cache_path, "text")?;
+            self.register_as_resource(&cache_path, "text/plain")?;
         }
 
         Ok(CallToolResult::success(vec![Content::text(result)]))
@@ -876,7 +893,7 @@ impl ComputerControllerServer {
             result.push_str(&format!("\n\nOutput saved to: {}", cache_path.display()));
 
             // Register as a resource
-            self.register_as_resource(&cache_path, "text")?;
+            self.register_as_resource(&cache_path, "text/plain")?;
         }
 
         Ok(CallToolResult::success(vec![Content::text(result)]))
@@ -891,10 +908,14 @@ impl ComputerControllerServer {
             - list_worksheets: List all worksheets in the workbook (returns name, index, column_count, row_count)
             - get_columns: Get column names from a worksheet (returns values from the first row)
             - get_range: Get values and formulas from a cell range (e.g., 'A1:C10') (returns a 2D array organized as [row][column])
-            - find_text: Search for text in a worksheet (returns a list of (row, column) coordinates)
+              Set content to 'values' or 'formulas' to return only one of them, which keeps large ranges small
+            - find_text: Search for text in a worksheet (returns a list of {row, col} coordinates)
             - update_cell: Update a single cell's value (returns confirmation message)
-            - get_cell: Get value and formula from a specific cell (returns both value and formula if present)
+            - get_cell: Get value and formula from a specific cell (returns both value and formula if present, or one of them per content)
             - save: Save changes back to the file (returns confirmation message)
+            - export_csv: Write a worksheet, or a range of it, to a CSV file (returns the file path)
+
+            Read operations return JSON.
 
             Use this when working with Excel spreadsheets to analyze or modify data.
         "
@@ -914,10 +935,7 @@ impl ComputerControllerServer {
                 let worksheets = xlsx
                     .list_worksheets()
                     .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
-                Ok(CallToolResult::success(vec![Content::text(format!(
-                    "{:#?}",
-                    worksheets
-                ))]))
+                json_result(&worksheets)
             }
             XlsxOperation::GetColumns => {
                 let xlsx = xlsx_tool::XlsxTool::new(path)
@@ -934,10 +952,7 @@ impl ComputerControllerServer {
                 let columns = xlsx
                     .get_column_names(worksheet)
                     .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
-                Ok(CallToolResult::success(vec![Content::text(format!(
-                    "{:#?}",
-                    columns
-                ))]))
+                json_result(&columns)
             }
             XlsxOperation::GetRange => {
                 let range = params.range.as_ref().ok_or_else(|| {
@@ -962,10 +977,11 @@ impl ComputerControllerServer {
                 let range_data = xlsx
                     .get_range(worksheet, range)
                     .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
-                Ok(CallToolResult::success(vec![Content::text(format!(
-                    "{:#?}",
-                    range_data
-                ))]))
+                let range_data = params
+                    .content
+                    .select_range(&range_data)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                json_result(&range_data)
             }
             XlsxOperation::FindText => {
                 let search_text = params.search_text.as_ref().ok_or_else(|| {
@@ -992,10 +1008,11 @@ impl ComputerControllerServer {
                 let matches = xlsx
                     .find_in_worksheet(worksheet, search_text, case_sensitive)
                     .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
-                Ok(CallToolResult::success(vec![Content::text(format!(
-                    "Found matches at: {:#?}",
-                    matches
-                ))]))
+                let matches: Vec<_> = matches
+                    .into_iter()
+                    .map(|(row, col)| serde_json::json!({ "row": row, "col": col }))
+                    .collect();
+                json_result(&matches)
             }
             XlsxOperation::UpdateCell => {
                 let row = params.row.ok_or_else(|| {
@@ -1033,6 +1050,30 @@ impl ComputerControllerServer {
                     row, col, value, worksheet_name
                 ))]))
             }
+            XlsxOperation::ExportCsv => {
+                let xlsx = xlsx_tool::XlsxTool::new(path)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                let worksheet = if let Some(name) = &params.worksheet {
+                    xlsx.get_worksheet_by_name(name).map_err(|e| {
+                        ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None)
+                    })?
+                } else {
+                    xlsx.get_worksheet_by_index(0).map_err(|e| {
+                        ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None)
+                    })?
+                };
+                let csv = xlsx
+                    .to_csv(worksheet, params.range.as_deref())
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+
+                let cache_path = self.save_to_cache(csv.as_bytes(), "xlsx", "csv").await?;
+                self.register_as_resource(&cache_path, "text/csv")?;
+
+                Ok(CallToolResult::success(vec![Content::text(format!(
+                    "CSV saved to: {}",
+                    cache_path.display()
+                ))]))
+            }
             XlsxOperation::Save => {
                 let xlsx = xlsx_tool::XlsxTool::new(path)
                     .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
@@ -1073,10 +1114,11 @@ impl ComputerControllerServer {
                 let cell_value = xlsx
                     .get_cell_value(worksheet, row as u32, col as u32)
                     .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
-                Ok(CallToolResult::success(vec![Content::text(format!(
-                    "{:#?}",
-                    cell_value
-                ))]))
+                let cell_value = params
+                    .content
+                    .select_cell(&cell_value)
+                    .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+                json_result(&cell_value)
             }
         }
     }
@@ -1170,7 +1212,8 @@ impl ComputerControllerServer {
         description = "
             Manage cached files and data:
             - list: List all cached files
-            - view: View content of a cached file
+            - view: View content of a cached file, a page of lines at a time (use offset and limit
+              to page through large files). Binary files are shown as a hexdump of their first bytes.
             - delete: Delete a cached file
             - clear: Clear all cached files
         "
@@ -1181,6 +1224,7 @@ impl ComputerControllerServer {
     ) -> Result<CallToolResult, ErrorData> {
         let command = params.0.command;
         let path = params.0.path.as_deref();
+        let offset = params.0.offset.unwrap_or(0);
 
         match command {
             CacheCommand::List => {
@@ -1216,7 +1260,7 @@ impl ComputerControllerServer {
                     )
                 })?;
 
-                let content = fs::read_to_string(path).map_err(|e| {
+                let bytes = fs::read(path).map_err(|e| {
                     ErrorData::new(
                         ErrorCode::INTERNAL_ERROR,
                         format!("Failed to read file: {}", e),
@@ -1224,10 +1268,21 @@ impl ComputerControllerServer {
                     )
                 })?;
 
-                Ok(CallToolResult::success(vec![Content::text(format!(
-                    "Content of {}:\n\n{}",
-                    path, content
-                ))]))
+                let view = match std::str::from_utf8(&bytes) {
+                    Ok(text) if !text.contains('\0') => view_lines(
+                        path,
+                        text,
+                        offset,
+                        params.0.limit.unwrap_or(DEFAULT_VIEW_LINES),
+                    ),
+                    _ => view_binary(
+                        path,
+                        &bytes,
+                        offset,
+                        params.0.limit.unwrap_or(DEFAULT_VIEW_BYTES),
+                    ),
+                };
+                Ok(CallToolResult::success(vec![Content::text(view)]))
             }
             CacheCommand::Delete => {
                 let path = path.ok_or_else(|| {
@@ -1286,6 +1341,96 @@ impl ComputerControllerServer {
     }
 }
 
+/// The lines of `text` from line `offset`, with the position in the file and how to see more
+fn view_lines(path: &str, text: &str, offset: usize, limit: usize) -> String {
+    let total = text.lines().count();
+    let start = offset.min(total);
+    let lines: Vec<&str> = text.lines().skip(start).take(limit.max(1)).collect();
+    let end = start + lines.len();
+
+    let mut view = format!(
+        "Content of {} ({} bytes, lines {}-{} of {}):\n\n{}",
+        path,
+        text.len(),
+        if lines.is_empty() { start } else { start + 1 },
+        end,
+        total,
+        lines.join("\n")
+    );
+    if end < total {
+        view.push_str(&format!(
+            "\n\n[{} more lines, view with offset {} to continue]",
+            total - end,
+            end
+        ));
+    }
+    view
+}
+
+/// A hexdump of `bytes` from `offset` rather than the raw binary content
+fn view_binary(path: &str, bytes: &[u8], offset: usize, limit: usize) -> String {
+    let start = offset.min(bytes.len());
+    let end = (start + limit.clamp(1, MAX_VIEW_BYTES)).min(bytes.len());
+
+    let rows: Vec<String> = bytes[start..end]
+        .chunks(16)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+            let ascii: String = chunk
+                .iter()
+                .map(|&b| {
+                    if b.is_ascii_graphic() || b == b' ' {
+                        b as char
+                    } else {
+                        '.'
+                    }
+                })
+                .collect();
+            format!("{:08x}  {:<47}  |{}|", start + i * 16, hex.join(" "), ascii)
+        })
+        .collect();
+
+    format!(
+        "{} is a binary file ({} bytes), showing bytes {}-{} as a hexdump:\n\n{}",
+        path,
+        bytes.len(),
+        start,
+        end,
+        rows.join("\n")
+    )
+}
+
+/// A successful result carrying `value` as pretty-printed JSON
+fn json_result<T: Serialize>(value: &T) -> Result<CallToolResult, ErrorData> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
+    Ok(CallToolResult::success(vec![Content::text(json)]))
+}
+
+fn is_text_mime_type(mime_type: &str) -> bool {
+    mime_type.starts_with("text/") || mime_type == "application/json"
+}
+
+/// Text resources are returned as text, anything else base64-encoded as a blob
+fn resource_contents(uri: String, mime_type: Option<String>, bytes: &[u8]) -> ResourceContents {
+    if mime_type.as_deref().is_some_and(is_text_mime_type) {
+        ResourceContents::TextResourceContents {
+            uri,
+            text: String::from_utf8_lossy(bytes).into_owned(),
+            mime_type,
+            meta: None,
+        }
+    } else {
+        ResourceContents::BlobResourceContents {
+            uri,
+            blob: STANDARD.encode(bytes),
+            mime_type,
+            meta: None,
+        }
+    }
+}
+
 #[tool_handler(router = self.tool_router)]
 impl ServerHandler for ComputerControllerServer {
     fn get_info(&self) -> ServerInfo {
@@ -1312,16 +1457,25 @@ impl ServerHandler for ComputerControllerServer {
         _context: RequestContext<RoleServer>,
     ) -> Result<ListResourcesResult, ErrorData> {
         let active_resources = self.active_resources.lock().unwrap();
-        let resources: Vec<Resource> = active_resources
-            .keys()
-            .map(|uri| {
-                RawResource::new(
+        let mut resources: Vec<Resource> = active_resources
+            .iter()
+            .map(|(uri, contents)| {
+                let mut resource = RawResource::new(
                     uri.clone(),
                     uri.split('/').next_back().unwrap_or("").to_string(),
-                )
-                .no_annotation()
+                );
+                if let ResourceContents::TextResourceContents { mime_type, .. } = contents {
+                    resource.mime_type = mime_type.clone();
+                }
+                resource.size = Url::parse(uri)
+                    .ok()
+                    .and_then(|url| url.to_file_path().ok())
+                    .and_then(|path| fs::metadata(path).ok())
+                    .and_then(|metadata| u32::try_from(metadata.len()).ok());
+                resource.no_annotation()
             })
             .collect();
+        resources.sort_by(|a, b| a.uri.cmp(&b.uri));
         Ok(ListResourcesResult {
             resources,
             next_cursor: None,
@@ -1333,18 +1487,134 @@ impl ServerHandler for ComputerControllerServer {
         params: ReadResourceRequestParam,
         _context: RequestContext<RoleServer>,
     ) -> Result<ReadResourceResult, ErrorData> {
-        let active_resources = self.active_resources.lock().unwrap();
-        let resource = active_resources.get(&params.uri).ok_or_else(|| {
+        let not_found = || {
             ErrorData::new(
                 ErrorCode::INVALID_REQUEST,
                 format!("Resource not found: {}", params.uri),
                 None,
             )
+        };
+        let mime_type = match self.active_resources.lock().unwrap().get(&params.uri) {
+            Some(ResourceContents::TextResourceContents { mime_type, .. })
+            | Some(ResourceContents::BlobResourceContents { mime_type, .. }) => mime_type.clone(),
+            None => return Err(not_found()),
+        };
+
+        // Resources are registered without their contents, read them from the cache now
+        let path = Url::parse(&params.uri)
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .ok_or_else(not_found)?;
+        let bytes = fs::read(&path).map_err(|_| {
+            // The file was removed from the cache some other way
+            self.active_resources.lock().unwrap().remove(&params.uri);
+            not_found()
         })?;
 
-        // Clone the resource to return
         Ok(ReadResourceResult {
-            contents: vec![resource.clone()],
+            contents: vec![resource_contents(params.uri, mime_type, &bytes)],
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_contents_by_mime_type() {
+        let text = resource_contents(
+            "file:///cache/page.txt".to_string(),
+            Some("text/plain".to_string()),
+            b"hello",
+        );
+        assert!(matches!(
+            text,
+            ResourceContents::TextResourceContents { ref text, .. } if text == "hello"
+        ));
+
+        let json = resource_contents(
+            "file:///cache/data.json".to_string(),
+            Some("application/json".to_string()),
+            b"{}",
+        );
+        assert!(matches!(
+            json,
+            ResourceContents::TextResourceContents { .. }
+        ));
+
+        let image = resource_contents(
+            "file:///cache/shot.png".to_string(),
+            Some("image/png".to_string()),
+            &[0x89, b'P', b'N', b'G'],
+        );
+        assert!(matches!(
+            image,
+            ResourceContents::BlobResourceContents { ref blob, .. } if blob == "iVBORw=="
+        ));
+
+        let unknown = resource_contents("file:///cache/blob".to_string(), None, b"\0");
+        assert!(matches!(
+            unknown,
+            ResourceContents::BlobResourceContents { .. }
+        ));
+    }
+
+    #[test]
+    fn test_view_lines_pages_through_file() {
+        let text: String = (1..=10).map(|i| format!("line {}\n", i)).collect();
+
+        let first = view_lines("cache.txt", &text, 0, 4);
+        assert!(first.contains("lines 1-4 of 10"));
+        assert!(first.contains("line 1\nline 2\nline 3\nline 4"));
+        assert!(!first.contains("line 5"));
+        assert!(first.contains("[6 more lines, view with offset 4 to continue]"));
+
+        let last = view_lines("cache.txt", &text, 8, 4);
+        assert!(last.contains("lines 9-10 of 10"));
+        assert!(last.contains("line 9\nline 10"));
+        assert!(!last.contains("more lines"));
+    }
+
+    #[test]
+    fn test_view_lines_clamps_offset_and_limit() {
+        let text = "a\nb\nc\n";
+
+        let past_end = view_lines("cache.txt", text, 50, 10);
+        assert!(past_end.contains("lines 3-3 of 3"));
+        assert!(!past_end.contains("more lines"));
+
+        // A zero limit still shows one line
+        let zero_limit = view_lines("cache.txt", text, 0, 0);
+        assert!(zero_limit.contains("lines 1-1 of 3"));
+        assert!(zero_limit.contains("[2 more lines, view with offset 1 to continue]"));
+    }
+
+    #[test]
+    fn test_view_binary_hexdump() {
+        let bytes: Vec<u8> = (0u8..20).chain(*b"AB").collect();
+
+        let view = view_binary("cache.bin", &bytes, 0, 64);
+        assert!(view.contains("(22 bytes), showing bytes 0-22"));
+        assert!(view.contains(
+            "00000000  00 01 02 03 04 05 06 07 08 09 0a 0b 0c 0d 0e 0f  |................|"
+        ));
+        assert!(view.contains("00000010  10 11 12 13 41 42"));
+        assert!(view.contains("|....AB|"));
+    }
+
+    #[test]
+    fn test_view_binary_clamps_offset_and_limit() {
+        let bytes = vec![0xffu8; MAX_VIEW_BYTES * 2];
+
+        let capped = view_binary("cache.bin", &bytes, 0, usize::MAX);
+        assert!(capped.contains(&format!("showing bytes 0-{}", MAX_VIEW_BYTES)));
+
+        let offset = view_binary("cache.bin", &bytes, 32, 16);
+        assert!(offset.contains("showing bytes 32-48"));
+        assert!(offset.contains("00000020  ff ff"));
+
+        let past_end = view_binary("cache.bin", &bytes, bytes.len() + 10, 16);
+        assert!(past_end.contains(&format!("showing bytes {}-{}", bytes.len(), bytes.len())));
+    }
+}
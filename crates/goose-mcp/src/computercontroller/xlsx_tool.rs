@@ -1,7 +1,10 @@
 use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDate};
+use rmcp::schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::path::Path;
-use umya_spreadsheet::{Spreadsheet, Worksheet};
+use umya_spreadsheet::{Cell, Spreadsheet, Worksheet};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WorksheetInfo {
@@ -27,6 +30,48 @@ pub struct RangeData {
     values: Vec<Vec<CellValue>>,
 }
 
+/// Which parts of each cell get_range and get_cell return
+#[derive(Debug, Serialize, Deserialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CellContent {
+    /// Only the values, one string per cell
+    Values,
+    /// Only the formulas, null for cells without one
+    Formulas,
+    /// Both the value and the formula of each cell
+    #[default]
+    Both,
+}
+
+impl CellContent {
+    pub fn select_cell(self, cell: &CellValue) -> Result<Value> {
+        Ok(match self {
+            CellContent::Values => Value::from(cell.value.clone()),
+            CellContent::Formulas => Value::from(cell.formula.clone()),
+            CellContent::Both => serde_json::to_value(cell)?,
+        })
+    }
+
+    /// The range with each cell reduced to the selected content
+    pub fn select_range(self, range: &RangeData) -> Result<Value> {
+        if self == CellContent::Both {
+            return Ok(serde_json::to_value(range)?);
+        }
+        let values = range
+            .values
+            .iter()
+            .map(|row| row.iter().map(|cell| self.select_cell(cell)).collect())
+            .collect::<Result<Vec<Vec<Value>>>>()?;
+        Ok(serde_json::json!({
+            "start_row": range.start_row,
+            "end_row": range.end_row,
+            "start_col": range.start_col,
+            "end_col": range.end_col,
+            "values": values,
+        }))
+    }
+}
+
 pub struct XlsxTool {
     workbook: Spreadsheet,
 }
@@ -191,6 +236,35 @@ impl XlsxTool {
         Ok(matches)
     }
 
+    /// The worksheet, or only `range` of it, as CSV with dates written in ISO 8601
+    pub fn to_csv(&self, worksheet: &Worksheet, range: Option<&str>) -> Result<String> {
+        let (start_row, start_col, end_row, end_col) = match range {
+            Some(range) => parse_range(range)?,
+            None => (
+                1,
+                1,
+                worksheet.get_highest_row(),
+                worksheet.get_highest_column(),
+            ),
+        };
+
+        let mut csv = String::new();
+        for row_idx in start_row..=end_row {
+            let fields: Vec<String> = (start_col..=end_col)
+                .map(|col_idx| {
+                    let value = worksheet
+                        .get_cell((col_idx, row_idx))
+                        .map(csv_cell_value)
+                        .unwrap_or_default();
+                    csv_field(&value)
+                })
+                .collect();
+            csv.push_str(&fields.join(","));
+            csv.push_str("\r\n");
+        }
+        Ok(csv)
+    }
+
     pub fn get_cell_value(&self, worksheet: &Worksheet, row: u32, col: u32) -> Result<CellValue> {
         let cell = worksheet.get_cell((col, row)).context("Cell not found")?;
 
@@ -205,6 +279,72 @@ impl XlsxTool {
     }
 }
 
+// Numbers are written as stored, without the cell's display format, except that dates and
+// times are written in ISO 8601 rather than as Excel serial numbers
+fn csv_cell_value(cell: &Cell) -> String {
+    let is_date = cell
+        .get_style()
+        .get_number_format()
+        .is_some_and(|format| is_date_format(format.get_format_code()));
+    match cell.get_value_number() {
+        Some(serial) if is_date => {
+            excel_serial_to_iso(serial).unwrap_or_else(|| serial.to_string())
+        }
+        _ => cell.get_value().into_owned(),
+    }
+}
+
+/// Whether a number format code displays a date or time
+fn is_date_format(code: &str) -> bool {
+    let mut chars = code.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // Skip quoted literals, escaped characters and [Red] or [$-409] style sections
+            '"' => while chars.next().is_some_and(|c| c != '"') {},
+            '\\' | '_' | '*' => {
+                chars.next();
+            }
+            '[' => {
+                let section: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                // Elapsed time such as [h]:mm
+                if ["h", "hh", "m", "mm", "s", "ss"].contains(&section.to_lowercase().as_str()) {
+                    return true;
+                }
+            }
+            'y' | 'Y' | 'd' | 'D' | 'h' | 'H' | 's' | 'S' | 'm' | 'M' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// An Excel serial date, days since 1899-12-30 with the time of day as the fraction
+fn excel_serial_to_iso(serial: f64) -> Option<String> {
+    if !(0.0..2_958_466.0).contains(&serial) {
+        return None;
+    }
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+    let seconds = (serial * 86_400.0).round() as i64;
+    let datetime = epoch + Duration::seconds(seconds);
+
+    Some(if serial < 1.0 {
+        datetime.format("%H:%M:%S").to_string()
+    } else if seconds % 86_400 == 0 {
+        datetime.format("%Y-%m-%d").to_string()
+    } else {
+        datetime.format("%Y-%m-%dT%H:%M:%S").to_string()
+    })
+}
+
+/// Quote a CSV field when it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
 fn parse_range(range: &str) -> Result<(u32, u32, u32, u32)> {
     // Handle ranges like "A1:B10" and return (start_row, start_col, end_row, end_col)
     let parts: Vec<&str> = range.split(':').collect();
@@ -413,4 +553,64 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_to_csv() -> Result<()> {
+        let xlsx = XlsxTool::new(get_test_file())?;
+        let worksheet = xlsx.get_worksheet_by_index(0)?;
+
+        let csv = xlsx.to_csv(worksheet, Some("A1:B2"))?;
+        assert_eq!(csv, "Segment,Country\r\nGovernment,Canada\r\n");
+
+        let csv = xlsx.to_csv(worksheet, None)?;
+        assert!(csv.starts_with("Segment,Country,"));
+        assert_eq!(csv.lines().count(), worksheet.get_highest_row() as usize);
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_field_quoting() {
+        assert_eq!(csv_field("plain"), "plain");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("two\nlines"), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_date_cells() {
+        assert!(is_date_format("m/d/yyyy"));
+        assert!(is_date_format("[$-409]h:mm AM/PM"));
+        assert!(is_date_format("[h]:mm"));
+        assert!(!is_date_format("General"));
+        assert!(!is_date_format("#,##0.00"));
+        assert!(!is_date_format("[Red]0.00\" days\""));
+
+        assert_eq!(excel_serial_to_iso(41640.0).unwrap(), "2014-01-01");
+        assert_eq!(excel_serial_to_iso(41640.5).unwrap(), "2014-01-01T12:00:00");
+        assert_eq!(excel_serial_to_iso(0.25).unwrap(), "06:00:00");
+    }
+
+    #[test]
+    fn test_select_cell_content() -> Result<()> {
+        let xlsx = XlsxTool::new(get_test_file())?;
+        let worksheet = xlsx.get_worksheet_by_index(0)?;
+        let range = xlsx.get_range(worksheet, "A1:B2")?;
+
+        let values = CellContent::Values.select_range(&range)?;
+        assert_eq!(
+            values["values"],
+            serde_json::json!([["Segment", "Country"], ["Government", "Canada"]])
+        );
+        assert_eq!(values["end_col"], 2);
+
+        let formulas = CellContent::Formulas.select_range(&range)?;
+        assert_eq!(formulas["values"][0], serde_json::json!([null, null]));
+
+        let both = CellContent::Both.select_range(&range)?;
+        assert_eq!(both["values"][1][0]["value"], "Government");
+
+        let cell = xlsx.get_cell_value(worksheet, 1, 1)?;
+        assert_eq!(CellContent::Values.select_cell(&cell)?, "Segment");
+        Ok(())
+    }
 }
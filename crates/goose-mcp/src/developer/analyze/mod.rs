@@ -13,6 +13,7 @@ use ignore::gitignore::Gitignore;
 use rmcp::model::{CallToolResult, ErrorCode, ErrorData};
 use std::path::{Path, PathBuf};
 
+use crate::developer::encoding::decode_text;
 use crate::developer::lang;
 
 use self::cache::AnalysisCache;
@@ -184,10 +185,13 @@ impl CodeAnalyzer {
             return Ok(cached);
         }
 
-        let content = match std::fs::read_to_string(path) {
-            Ok(content) => content,
+        let content = match std::fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| decode_text(&bytes).map_err(|e| e.to_string()))
+        {
+            Ok((content, _)) => content,
             Err(e) => {
-                tracing::trace!("Skipping binary/non-UTF-8 file {:?}: {}", path, e);
+                tracing::trace!("Skipping unreadable or binary file {:?}: {}", path, e);
                 return Ok(AnalysisResult::empty(0));
             }
         };
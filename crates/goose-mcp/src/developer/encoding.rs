@@ -0,0 +1,211 @@
+use std::fmt;
+
+/// How many leading bytes are sampled when guessing whether content is binary
+const SNIFF_LEN: usize = 8192;
+
+/// Text encodings recognised when reading files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// Fallback for bytes that are not valid UTF-8; decoded as Windows-1252/Latin-1
+    Latin1,
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf8Bom => "UTF-8 with BOM",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Latin1 => "Latin-1",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The content looks like binary data rather than text
+    Binary,
+    /// The content claims an encoding but is not valid in it
+    Invalid(TextEncoding),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Binary => f.write_str("the file appears to be binary"),
+            DecodeError::Invalid(encoding) => {
+                write!(f, "the file is not valid {}", encoding)
+            }
+        }
+    }
+}
+
+/// Decodes file content to UTF-8, detecting the encoding from a BOM, the layout of
+/// NUL bytes (BOM-less UTF-16), or falling back to Latin-1 for invalid UTF-8.
+pub fn decode_text(bytes: &[u8]) -> Result<(String, TextEncoding), DecodeError> {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return String::from_utf8(rest.to_vec())
+            .map(|text| (text, TextEncoding::Utf8Bom))
+            .map_err(|_| DecodeError::Invalid(TextEncoding::Utf8Bom));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return decode_utf16(rest, TextEncoding::Utf16Le);
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return decode_utf16(rest, TextEncoding::Utf16Be);
+    }
+
+    let sample = &bytes[..bytes.len().min(SNIFF_LEN)];
+    if sample.contains(&0) {
+        return match guess_utf16(sample) {
+            Some(encoding) => decode_utf16(bytes, encoding),
+            None => Err(DecodeError::Binary),
+        };
+    }
+
+    if let Ok(text) = std::str::from_utf8(bytes) {
+        return Ok((text.to_string(), TextEncoding::Utf8));
+    }
+
+    if looks_binary(sample) {
+        return Err(DecodeError::Binary);
+    }
+    Ok((decode_latin1(bytes), TextEncoding::Latin1))
+}
+
+fn decode_utf16(
+    bytes: &[u8],
+    encoding: TextEncoding,
+) -> Result<(String, TextEncoding), DecodeError> {
+    if bytes.len() % 2 != 0 {
+        return Err(DecodeError::Invalid(encoding));
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| match encoding {
+            TextEncoding::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+            _ => u16::from_le_bytes([pair[0], pair[1]]),
+        })
+        .collect();
+    String::from_utf16(&units)
+        .map(|text| (text, encoding))
+        .map_err(|_| DecodeError::Invalid(encoding))
+}
+
+/// ASCII-range UTF-16 text has a NUL in every other byte; which half is NUL gives the order
+fn guess_utf16(sample: &[u8]) -> Option<TextEncoding> {
+    let pairs = sample.len() / 2;
+    if pairs == 0 {
+        return None;
+    }
+    let even_nuls = sample.iter().step_by(2).filter(|&&b| b == 0).count();
+    let odd_nuls = sample
+        .iter()
+        .skip(1)
+        .step_by(2)
+        .filter(|&&b| b == 0)
+        .count();
+
+    if odd_nuls * 10 >= pairs * 7 && even_nuls * 10 <= pairs {
+        Some(TextEncoding::Utf16Le)
+    } else if even_nuls * 10 >= pairs * 7 && odd_nuls * 10 <= pairs {
+        Some(TextEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+/// Text rarely contains control characters other than whitespace
+fn looks_binary(sample: &[u8]) -> bool {
+    let control = sample
+        .iter()
+        .filter(|&&b| b < 0x20 && !matches!(b, b'\n' | b'\r' | b'\t' | 0x0C))
+        .count();
+    control * 10 > sample.len()
+}
+
+/// Windows-1252 maps 0x80..0x9F to punctuation; everything else matches Latin-1
+fn decode_latin1(bytes: &[u8]) -> String {
+    const CP1252_HIGH: [char; 32] = [
+        '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž',
+        '\u{8F}', '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}',
+        'ž', 'Ÿ',
+    ];
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => CP1252_HIGH[(b - 0x80) as usize],
+            _ => b as char,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf8_and_bom() {
+        assert_eq!(
+            decode_text("héllo".as_bytes()),
+            Ok(("héllo".to_string(), TextEncoding::Utf8))
+        );
+        assert_eq!(
+            decode_text(b"\xEF\xBB\xBFhello"),
+            Ok(("hello".to_string(), TextEncoding::Utf8Bom))
+        );
+    }
+
+    #[test]
+    fn test_utf16_with_and_without_bom() {
+        let le: Vec<u8> = "hi\n"
+            .encode_utf16()
+            .flat_map(|u| u.to_le_bytes())
+            .collect();
+        let be: Vec<u8> = "hi\n"
+            .encode_utf16()
+            .flat_map(|u| u.to_be_bytes())
+            .collect();
+
+        let with_bom = [&[0xFF, 0xFE][..], &le].concat();
+        assert_eq!(
+            decode_text(&with_bom),
+            Ok(("hi\n".to_string(), TextEncoding::Utf16Le))
+        );
+        let with_bom = [&[0xFE, 0xFF][..], &be].concat();
+        assert_eq!(
+            decode_text(&with_bom),
+            Ok(("hi\n".to_string(), TextEncoding::Utf16Be))
+        );
+        assert_eq!(
+            decode_text(&le),
+            Ok(("hi\n".to_string(), TextEncoding::Utf16Le))
+        );
+    }
+
+    #[test]
+    fn test_latin1_fallback() {
+        assert_eq!(
+            decode_text(b"caf\xE9 \x93quoted\x94"),
+            Ok(("café “quoted”".to_string(), TextEncoding::Latin1))
+        );
+    }
+
+    #[test]
+    fn test_binary_detected() {
+        assert_eq!(
+            decode_text(b"\x7FELF\x02\x01\x01\x00\x00\x00\x00\x00\x03\x00\x3E\x00"),
+            Err(DecodeError::Binary)
+        );
+        assert_eq!(
+            decode_text(b"\x89PNG\r\n\x1A\n\x01\x02\x03\x04\x05\xFF"),
+            Err(DecodeError::Binary)
+        );
+    }
+}
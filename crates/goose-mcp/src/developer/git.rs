@@ -0,0 +1,886 @@
+use rmcp::model::{ErrorCode, ErrorData};
+use rmcp::schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use tokio::process::Command;
+
+/// Patches larger than this are cut off, with `truncated` set on the result
+const MAX_PATCH_BYTES: usize = 64 * 1024;
+const DEFAULT_LOG_COUNT: usize = 20;
+const MAX_LOG_COUNT: usize = 200;
+
+/// Parameters for git tools that only need to locate the repository
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitRepoParams {
+    /// Optional directory inside the repository, instead of the working directory.
+    pub cwd: Option<String>,
+}
+
+/// Parameters for the git_diff tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitDiffParams {
+    /// Optional directory inside the repository, instead of the working directory.
+    pub cwd: Option<String>,
+    /// Show staged changes instead of unstaged ones.
+    #[serde(default)]
+    pub staged: bool,
+    /// Compare against this revision (e.g. `main` or `HEAD~3`) instead of the index.
+    pub revision: Option<String>,
+    /// Limit the diff to this file or directory.
+    pub path: Option<String>,
+}
+
+/// Parameters for the git_log tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitLogParams {
+    /// Optional directory inside the repository, instead of the working directory.
+    pub cwd: Option<String>,
+    /// Number of commits to return (default 20, at most 200).
+    pub max_count: Option<usize>,
+    /// Revision or range to list, e.g. `main..HEAD`. Defaults to HEAD.
+    pub revision: Option<String>,
+    /// Only list commits touching this file or directory.
+    pub path: Option<String>,
+}
+
+/// Parameters for the git_blame tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitBlameParams {
+    /// Optional directory inside the repository, instead of the working directory.
+    pub cwd: Option<String>,
+    /// File to blame.
+    pub path: String,
+    /// First line to blame (1-indexed).
+    pub start_line: Option<u64>,
+    /// Last line to blame, inclusive.
+    pub end_line: Option<u64>,
+}
+
+/// Parameters for the git_branch tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitBranchParams {
+    /// Optional directory inside the repository, instead of the working directory.
+    pub cwd: Option<String>,
+    /// One of `list`, `create` or `switch`.
+    pub action: String,
+    /// Branch to create or switch to.
+    pub name: Option<String>,
+    /// Revision a new branch starts from. Defaults to HEAD.
+    pub start_point: Option<String>,
+    /// Switch to the branch after creating it.
+    #[serde(default)]
+    pub checkout: bool,
+}
+
+/// Parameters for the git_commit tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitCommitParams {
+    /// Optional directory inside the repository, instead of the working directory.
+    pub cwd: Option<String>,
+    /// The commit message.
+    pub message: String,
+    /// Files to stage before committing. Already staged changes are committed either way.
+    #[serde(default)]
+    pub paths: Vec<String>,
+    /// Stage all changes to tracked files before committing, like `git commit -a`.
+    #[serde(default)]
+    pub all: bool,
+}
+
+/// Parameters for the git_reset tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitResetParams {
+    /// Optional directory inside the repository, instead of the working directory.
+    pub cwd: Option<String>,
+    /// Revision to reset to. Defaults to HEAD.
+    pub target: Option<String>,
+    /// One of `soft`, `mixed` (default) or `hard`. `hard` discards uncommitted changes.
+    pub mode: Option<String>,
+}
+
+/// Parameters for the git_push tool
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct GitPushParams {
+    /// Optional directory inside the repository, instead of the working directory.
+    pub cwd: Option<String>,
+    /// Remote to push to. Defaults to the branch's upstream.
+    pub remote: Option<String>,
+    /// Branch to push. Defaults to the current branch.
+    pub branch: Option<String>,
+    /// Overwrite the remote branch (uses `--force-with-lease`).
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileChange {
+    pub path: String,
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub original_path: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct GitStatus {
+    pub branch: Option<String>,
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+    pub staged: Vec<FileChange>,
+    pub unstaged: Vec<FileChange>,
+    pub untracked: Vec<String>,
+    pub conflicted: Vec<String>,
+    pub clean: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct DiffFile {
+    pub path: String,
+    pub additions: Option<u64>,
+    pub deletions: Option<u64>,
+    pub binary: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitDiff {
+    pub files: Vec<DiffFile>,
+    pub patch: String,
+    pub truncated: bool,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GitCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub email: String,
+    pub date: String,
+    pub subject: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct BlameLine {
+    pub line: u64,
+    pub commit: String,
+    pub author: String,
+    pub summary: String,
+    pub content: String,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct GitBranch {
+    pub name: String,
+    pub commit: String,
+    pub current: bool,
+    pub upstream: Option<String>,
+}
+
+fn invalid(message: String) -> ErrorData {
+    ErrorData::new(ErrorCode::INVALID_PARAMS, message, None)
+}
+
+/// Rejects values git would parse as an option, e.g. a branch named `--force`
+fn check_arg<'a>(kind: &str, value: &'a str) -> Result<&'a str, ErrorData> {
+    if value.is_empty() || value.starts_with('-') {
+        return Err(invalid(format!("Invalid {}: '{}'", kind, value)));
+    }
+    Ok(value)
+}
+
+/// Like `check_arg`, also rejecting a leading `+`, which makes a push refspec force-push
+fn check_ref<'a>(kind: &str, value: &'a str) -> Result<&'a str, ErrorData> {
+    if value.starts_with('+') {
+        return Err(invalid(format!(
+            "Invalid {}: '{}'; use `force` to overwrite the remote branch",
+            kind, value
+        )));
+    }
+    check_arg(kind, value)
+}
+
+/// A git repository, identified by its top-level directory
+#[derive(Debug, Clone)]
+pub struct GitRepo {
+    root: PathBuf,
+}
+
+impl GitRepo {
+    /// Finds the repository containing `dir`
+    pub async fn discover(dir: &Path) -> Result<Self, ErrorData> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["rev-parse", "--show-toplevel"])
+            .output()
+            .await
+            .map_err(|e| {
+                ErrorData::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to run git: {}", e),
+                    None,
+                )
+            })?;
+        if !output.status.success() {
+            return Err(invalid(format!(
+                "'{}' is not inside a git repository",
+                dir.display()
+            )));
+        }
+
+        let root = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        let root = root.canonicalize().unwrap_or(root);
+        Ok(Self { root })
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Turns a path given to a tool into one relative to the repository root, refusing
+    /// anything that points outside of it
+    pub fn relative_path(&self, path: &str) -> Result<String, ErrorData> {
+        let path = Path::new(path);
+        if path.components().any(|c| matches!(c, Component::ParentDir)) {
+            return Err(invalid(format!(
+                "Path '{}' cannot contain '..'",
+                path.display()
+            )));
+        }
+
+        let relative = if path.is_absolute() {
+            let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+            canonical
+                .strip_prefix(&self.root)
+                .map(Path::to_path_buf)
+                .map_err(|_| {
+                    invalid(format!(
+                        "Path '{}' is outside the repository at {}",
+                        path.display(),
+                        self.root.display()
+                    ))
+                })?
+        } else {
+            path.to_path_buf()
+        };
+
+        let relative = relative.to_string_lossy().into_owned();
+        Ok(if relative.is_empty() {
+            ".".to_string()
+        } else {
+            relative
+        })
+    }
+
+    async fn run<S: AsRef<std::ffi::OsStr>>(&self, args: &[S]) -> Result<String, ErrorData> {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(&self.root)
+            .args(["-c", "core.quotepath=false"])
+            .args(args)
+            // Never block waiting for credentials
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .output()
+            .await
+            .map_err(|e| {
+                ErrorData::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to run git: {}", e),
+                    None,
+                )
+            })?;
+
+        if !output.status.success() {
+            let command = args
+                .first()
+                .map(|arg| arg.as_ref().to_string_lossy().into_owned())
+                .unwrap_or_default();
+            return Err(ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!(
+                    "git {} failed: {}",
+                    command,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                None,
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    /// The working tree status, leaving out files for which `hidden` returns true
+    pub async fn status(&self, hidden: impl Fn(&str) -> bool) -> Result<GitStatus, ErrorData> {
+        let output = self
+            .run(&["status", "--porcelain=v1", "--branch", "-z"])
+            .await?;
+        let mut status = parse_status(&output);
+        status.staged.retain(|change| !hidden(&change.path));
+        status.unstaged.retain(|change| !hidden(&change.path));
+        status.untracked.retain(|path| !hidden(path));
+        status.conflicted.retain(|path| !hidden(path));
+        status.clean = status.staged.is_empty()
+            && status.unstaged.is_empty()
+            && status.untracked.is_empty()
+            && status.conflicted.is_empty();
+        Ok(status)
+    }
+
+    /// The changes to files for which `hidden` returns false
+    pub async fn diff(
+        &self,
+        staged: bool,
+        revision: Option<&str>,
+        path: Option<&str>,
+        hidden: impl Fn(&str) -> bool,
+    ) -> Result<GitDiff, ErrorData> {
+        let mut args = vec!["diff".to_string(), "--no-renames".to_string()];
+        if staged {
+            args.push("--cached".to_string());
+        }
+        if let Some(revision) = revision {
+            args.push(check_arg("revision", revision)?.to_string());
+        }
+        args.push("--".to_string());
+        args.push(path.unwrap_or(".").to_string());
+
+        let mut numstat_args = args.clone();
+        numstat_args.insert(1, "--numstat".to_string());
+        let (files, hidden_files): (Vec<_>, Vec<_>) =
+            parse_numstat(&self.run(&numstat_args).await?)
+                .into_iter()
+                .partition(|file| !hidden(&file.path));
+
+        args.extend(
+            hidden_files
+                .iter()
+                .map(|file| format!(":(exclude,literal){}", file.path)),
+        );
+        let mut patch = self.run(&args).await?;
+        let truncated = patch.len() > MAX_PATCH_BYTES;
+        if truncated {
+            let mut end = MAX_PATCH_BYTES;
+            while !patch.is_char_boundary(end) {
+                end -= 1;
+            }
+            patch.truncate(end);
+        }
+
+        Ok(GitDiff {
+            files,
+            patch,
+            truncated,
+        })
+    }
+
+    pub async fn log(
+        &self,
+        max_count: Option<usize>,
+        revision: Option<&str>,
+        path: Option<&str>,
+    ) -> Result<Vec<GitCommit>, ErrorData> {
+        let count = max_count
+            .unwrap_or(DEFAULT_LOG_COUNT)
+            .clamp(1, MAX_LOG_COUNT)
+            .to_string();
+        let mut args = vec![
+            "log",
+            "-n",
+            &count,
+            "--format=%H%x1f%h%x1f%an%x1f%ae%x1f%aI%x1f%s%x1e",
+        ];
+        if let Some(revision) = revision {
+            args.push(check_arg("revision", revision)?);
+        }
+        args.push("--");
+        if let Some(path) = path {
+            args.push(path);
+        }
+        Ok(parse_log(&self.run(&args).await?))
+    }
+
+    pub async fn blame(
+        &self,
+        path: &str,
+        start_line: Option<u64>,
+        end_line: Option<u64>,
+    ) -> Result<Vec<BlameLine>, ErrorData> {
+        let mut args = vec!["blame".to_string(), "--line-porcelain".to_string()];
+        match (start_line, end_line) {
+            (None, None) => {}
+            (start, end) => {
+                let start = start.unwrap_or(1).max(1);
+                let end = end.map(|end| end.to_string()).unwrap_or_default();
+                args.push(format!("-L{},{}", start, end));
+            }
+        }
+        args.push("--".to_string());
+        args.push(path.to_string());
+        Ok(parse_blame(&self.run(&args).await?))
+    }
+
+    pub async fn branches(&self) -> Result<Vec<GitBranch>, ErrorData> {
+        let output = self
+            .run(&[
+                "for-each-ref",
+                "--format=%(refname:short)%1f%(objectname:short)%1f%(HEAD)%1f%(upstream:short)",
+                "refs/heads",
+            ])
+            .await?;
+        Ok(parse_branches(&output))
+    }
+
+    pub async fn create_branch(
+        &self,
+        name: &str,
+        start_point: Option<&str>,
+        checkout: bool,
+    ) -> Result<(), ErrorData> {
+        let name = check_arg("branch name", name)?;
+        self.run(&["check-ref-format", "--branch", name]).await?;
+
+        let mut args = if checkout {
+            vec!["switch", "-c", name]
+        } else {
+            vec!["branch", name]
+        };
+        if let Some(start_point) = start_point {
+            args.push(check_arg("start point", start_point)?);
+        }
+        self.run(&args).await?;
+        Ok(())
+    }
+
+    pub async fn switch_branch(&self, name: &str) -> Result<(), ErrorData> {
+        let name = check_arg("branch name", name)?;
+        self.run(&["switch", name]).await?;
+        Ok(())
+    }
+
+    pub async fn commit(
+        &self,
+        message: &str,
+        paths: &[String],
+        all: bool,
+    ) -> Result<GitCommit, ErrorData> {
+        if message.trim().is_empty() {
+            return Err(invalid("Commit message cannot be empty".to_string()));
+        }
+
+        if !paths.is_empty() {
+            let mut args = vec!["add", "--"];
+            args.extend(paths.iter().map(String::as_str));
+            self.run(&args).await?;
+        }
+
+        let mut args = vec!["commit", "-m", message];
+        if all {
+            args.push("-a");
+        }
+        self.run(&args).await?;
+
+        self.log(Some(1), None, None)
+            .await?
+            .pop()
+            .ok_or_else(|| invalid("Commit was not recorded".to_string()))
+    }
+
+    pub async fn reset(&self, target: Option<&str>, mode: Option<&str>) -> Result<(), ErrorData> {
+        let mode = match mode.unwrap_or("mixed") {
+            "soft" => "--soft",
+            "mixed" => "--mixed",
+            "hard" => "--hard",
+            other => {
+                return Err(invalid(format!(
+                    "Invalid reset mode '{}'; expected soft, mixed or hard",
+                    other
+                )))
+            }
+        };
+        let target = check_arg("target", target.unwrap_or("HEAD"))?;
+        self.run(&["reset", mode, target, "--"]).await?;
+        Ok(())
+    }
+
+    pub async fn push(
+        &self,
+        remote: Option<&str>,
+        branch: Option<&str>,
+        force: bool,
+    ) -> Result<String, ErrorData> {
+        let mut args = vec!["push", "--porcelain"];
+        if force {
+            args.push("--force-with-lease");
+        }
+        if let Some(remote) = remote {
+            args.push(check_ref("remote", remote)?);
+            if let Some(branch) = branch {
+                args.push(check_ref("branch", branch)?);
+            }
+        } else if branch.is_some() {
+            return Err(invalid(
+                "A remote is required when pushing a specific branch".to_string(),
+            ));
+        }
+        self.run(&args).await
+    }
+}
+
+fn change_status(code: char) -> &'static str {
+    match code {
+        'M' => "modified",
+        'A' => "added",
+        'D' => "deleted",
+        'R' => "renamed",
+        'C' => "copied",
+        'T' => "type_changed",
+        _ => "unknown",
+    }
+}
+
+/// Parses `git status --porcelain=v1 --branch -z`
+fn parse_status(output: &str) -> GitStatus {
+    let mut status = GitStatus::default();
+    let mut entries = output.split('\0').filter(|entry| !entry.is_empty());
+
+    while let Some(entry) = entries.next() {
+        if let Some(branch) = entry.strip_prefix("## ") {
+            parse_branch_header(branch, &mut status);
+            continue;
+        }
+        if entry.len() < 4 {
+            continue;
+        }
+
+        let (code, path) = entry.split_at(3);
+        let mut code = code.chars();
+        let (x, y) = (code.next().unwrap_or(' '), code.next().unwrap_or(' '));
+        let path = path.to_string();
+
+        // Renames and copies are followed by the original path
+        let original_path = if matches!(x, 'R' | 'C') {
+            entries.next().map(str::to_string)
+        } else {
+            None
+        };
+
+        match (x, y) {
+            ('?', '?') => status.untracked.push(path),
+            ('!', '!') => {}
+            ('U', _) | (_, 'U') | ('A', 'A') | ('D', 'D') => status.conflicted.push(path),
+            _ => {
+                if x != ' ' {
+                    status.staged.push(FileChange {
+                        path: path.clone(),
+                        status: change_status(x).to_string(),
+                        original_path,
+                    });
+                }
+                if y != ' ' {
+                    status.unstaged.push(FileChange {
+                        path,
+                        status: change_status(y).to_string(),
+                        original_path: None,
+                    });
+                }
+            }
+        }
+    }
+
+    status.clean = status.staged.is_empty()
+        && status.unstaged.is_empty()
+        && status.untracked.is_empty()
+        && status.conflicted.is_empty();
+    status
+}
+
+/// Parses the `main...origin/main [ahead 1, behind 2]` header of a porcelain status
+fn parse_branch_header(header: &str, status: &mut GitStatus) {
+    if let Some(branch) = header.strip_prefix("No commits yet on ") {
+        status.branch = Some(branch.to_string());
+        return;
+    }
+
+    let (head, tracking) = match header.split_once(" [") {
+        Some((head, tracking)) => (head, tracking.trim_end_matches(']')),
+        None => (header, ""),
+    };
+    match head.split_once("...") {
+        Some((branch, upstream)) => {
+            status.branch = Some(branch.to_string());
+            status.upstream = Some(upstream.to_string());
+        }
+        None if head.starts_with("HEAD (no branch)") => {}
+        None => status.branch = Some(head.to_string()),
+    }
+
+    for part in tracking.split(", ") {
+        if let Some(n) = part.strip_prefix("ahead ") {
+            status.ahead = n.parse().unwrap_or(0);
+        } else if let Some(n) = part.strip_prefix("behind ") {
+            status.behind = n.parse().unwrap_or(0);
+        }
+    }
+}
+
+fn parse_numstat(output: &str) -> Vec<DiffFile> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, '\t');
+            let additions = parts.next()?;
+            let deletions = parts.next()?;
+            let path = parts.next()?;
+            Some(DiffFile {
+                path: path.to_string(),
+                additions: additions.parse().ok(),
+                deletions: deletions.parse().ok(),
+                binary: additions == "-" && deletions == "-",
+            })
+        })
+        .collect()
+}
+
+fn parse_log(output: &str) -> Vec<GitCommit> {
+    output
+        .split('\x1e')
+        .map(str::trim)
+        .filter(|record| !record.is_empty())
+        .filter_map(|record| {
+            let mut fields = record.split('\x1f');
+            Some(GitCommit {
+                hash: fields.next()?.to_string(),
+                short_hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                email: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parses `git blame --line-porcelain`, where every line repeats its commit's headers
+fn parse_blame(output: &str) -> Vec<BlameLine> {
+    let mut lines = Vec::new();
+    let mut commit = String::new();
+    let mut line_number = 0;
+    let mut author = String::new();
+    let mut summary = String::new();
+
+    for line in output.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            lines.push(BlameLine {
+                line: line_number,
+                commit: commit.clone(),
+                author: std::mem::take(&mut author),
+                summary: std::mem::take(&mut summary),
+                content: content.to_string(),
+            });
+        } else if let Some(value) = line.strip_prefix("author ") {
+            author = value.to_string();
+        } else if let Some(value) = line.strip_prefix("summary ") {
+            summary = value.to_string();
+        } else {
+            let mut parts = line.split(' ');
+            if let (Some(hash), Some(_), Some(final_line)) =
+                (parts.next(), parts.next(), parts.next())
+            {
+                if hash.len() >= 40 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+                    commit = hash[..12].to_string();
+                    line_number = final_line.parse().unwrap_or(0);
+                }
+            }
+        }
+    }
+    lines
+}
+
+fn parse_branches(output: &str) -> Vec<GitBranch> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\x1f');
+            let name = fields.next()?.to_string();
+            let commit = fields.next()?.to_string();
+            let current = fields.next()? == "*";
+            let upstream = fields
+                .next()
+                .filter(|upstream| !upstream.is_empty())
+                .map(str::to_string);
+            Some(GitBranch {
+                name,
+                commit,
+                current,
+                upstream,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = StdCommand::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .status()
+            .unwrap();
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn init_repo() -> TempDir {
+        let dir = TempDir::new().unwrap();
+        git(dir.path(), &["init", "-q", "-b", "main"]);
+        git(dir.path(), &["config", "user.name", "Goose"]);
+        git(dir.path(), &["config", "user.email", "goose@example.com"]);
+        git(dir.path(), &["config", "commit.gpgsign", "false"]);
+        dir
+    }
+
+    #[test]
+    fn test_parse_status() {
+        let output = "## main...origin/main [ahead 2, behind 1]\0M  staged.rs\0 M edited.rs\0MM both.rs\0R  new.rs\0old.rs\0?? scratch.txt\0UU conflict.rs\0";
+        let status = parse_status(output);
+
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((status.ahead, status.behind), (2, 1));
+        assert_eq!(
+            status
+                .staged
+                .iter()
+                .map(|c| c.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["staged.rs", "both.rs", "new.rs"]
+        );
+        assert_eq!(status.staged[2].original_path.as_deref(), Some("old.rs"));
+        assert_eq!(status.unstaged.len(), 2);
+        assert_eq!(status.untracked, vec!["scratch.txt"]);
+        assert_eq!(status.conflicted, vec!["conflict.rs"]);
+        assert!(!status.clean);
+    }
+
+    #[tokio::test]
+    async fn test_status_log_blame_and_commit() {
+        let dir = init_repo();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        let repo = GitRepo::discover(dir.path()).await.unwrap();
+
+        let status = repo.status(|_| false).await.unwrap();
+        assert_eq!(status.branch.as_deref(), Some("main"));
+        assert_eq!(status.untracked, vec!["main.rs"]);
+
+        let commit = repo
+            .commit("Add main", &["main.rs".to_string()], false)
+            .await
+            .unwrap();
+        assert_eq!(commit.subject, "Add main");
+        assert_eq!(commit.author, "Goose");
+        assert!(repo.status(|_| false).await.unwrap().clean);
+
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\nfn helper() {}\n").unwrap();
+        let diff = repo.diff(false, None, None, |_| false).await.unwrap();
+        assert_eq!(
+            diff.files,
+            vec![DiffFile {
+                path: "main.rs".to_string(),
+                additions: Some(1),
+                deletions: Some(0),
+                binary: false,
+            }]
+        );
+        assert!(diff.patch.contains("+fn helper() {}"));
+
+        repo.commit("Add helper", &[], true).await.unwrap();
+        let log = repo.log(None, None, None).await.unwrap();
+        assert_eq!(
+            log.iter().map(|c| c.subject.as_str()).collect::<Vec<_>>(),
+            vec!["Add helper", "Add main"]
+        );
+
+        let blame = repo.blame("main.rs", Some(2), Some(2)).await.unwrap();
+        assert_eq!(blame.len(), 1);
+        assert_eq!(blame[0].line, 2);
+        assert_eq!(blame[0].summary, "Add helper");
+        assert_eq!(blame[0].content, "fn helper() {}");
+    }
+
+    #[tokio::test]
+    async fn test_branches_and_reset() {
+        let dir = init_repo();
+        std::fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+        let repo = GitRepo::discover(dir.path()).await.unwrap();
+        repo.commit("First", &["a.txt".to_string()], false)
+            .await
+            .unwrap();
+
+        repo.create_branch("feature", None, true).await.unwrap();
+        let branches = repo.branches().await.unwrap();
+        assert_eq!(branches.len(), 2);
+        assert!(branches.iter().any(|b| b.name == "feature" && b.current));
+        assert!(repo.create_branch("--force", None, false).await.is_err());
+
+        std::fs::write(dir.path().join("a.txt"), "changed\n").unwrap();
+        repo.reset(None, Some("hard")).await.unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("a.txt")).unwrap(),
+            "a\n"
+        );
+        assert!(repo.reset(None, Some("keep")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_hidden_files_are_left_out() {
+        let dir = init_repo();
+        std::fs::write(dir.path().join("main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=one\n").unwrap();
+        let repo = GitRepo::discover(dir.path()).await.unwrap();
+        repo.commit("First", &["main.rs".to_string(), ".env".to_string()], false)
+            .await
+            .unwrap();
+
+        std::fs::write(dir.path().join("main.rs"), "fn main() { run() }\n").unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=two\n").unwrap();
+        std::fs::write(dir.path().join("secrets.json"), "{}\n").unwrap();
+        let hidden = |path: &str| path == ".env" || path == "secrets.json";
+
+        let status = repo.status(hidden).await.unwrap();
+        assert_eq!(status.unstaged.len(), 1);
+        assert_eq!(status.unstaged[0].path, "main.rs");
+        assert!(status.untracked.is_empty());
+
+        let diff = repo.diff(false, None, None, hidden).await.unwrap();
+        assert_eq!(diff.files.len(), 1);
+        assert!(!diff.patch.contains("TOKEN"));
+    }
+
+    #[tokio::test]
+    async fn test_push_rejects_force_refspec() {
+        let dir = init_repo();
+        let repo = GitRepo::discover(dir.path()).await.unwrap();
+        let err = repo
+            .push(Some("origin"), Some("+main"), false)
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("use `force`"));
+    }
+
+    #[tokio::test]
+    async fn test_relative_path_stays_in_repo() {
+        let dir = init_repo();
+        let repo = GitRepo::discover(dir.path()).await.unwrap();
+
+        assert_eq!(repo.relative_path("src/lib.rs").unwrap(), "src/lib.rs");
+        let absolute = repo.root().join("Cargo.toml");
+        assert_eq!(
+            repo.relative_path(absolute.to_str().unwrap()).unwrap(),
+            "Cargo.toml"
+        );
+        assert!(repo.relative_path("../outside.txt").is_err());
+        assert!(repo.relative_path("/etc/passwd").is_err());
+    }
+}
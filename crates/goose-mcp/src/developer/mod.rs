@@ -1,5 +1,7 @@
 pub mod analyze;
 mod editor_models;
+mod encoding;
+mod git;
 mod goose_hints;
 mod lang;
 mod shell;
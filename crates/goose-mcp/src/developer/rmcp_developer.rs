@@ -33,9 +33,14 @@ use tokio_util::sync::CancellationToken;
 
 use super::analyze::{types::AnalyzeParams, CodeAnalyzer};
 use super::editor_models::{create_editor_model, EditorModel};
+use super::git::{
+    GitBlameParams, GitBranchParams, GitCommitParams, GitDiffParams, GitLogParams, GitPushParams,
+    GitRepo, GitRepoParams, GitResetParams,
+};
 use super::goose_hints::load_hints::{load_hint_files, GOOSE_HINTS_FILENAME};
 use super::shell::{
     configure_shell_command, expand_path, get_shell_config, is_absolute_path, kill_process_group,
+    CommandPolicy,
 };
 use super::text_editor::{
     text_editor_insert, text_editor_replace, text_editor_undo, text_editor_view, text_editor_write,
@@ -69,6 +74,7 @@ pub struct TextEditorParams {
 
     /// Optional array of two integers specifying the start and end line numbers to view.
     /// Line numbers are 1-indexed, and -1 for the end line means read to the end of the file.
+    /// Only the requested lines are returned, prefixed with their line numbers.
     /// This parameter only applies when viewing files, not directories.
     pub view_range: Option<Vec<i64>>,
 
@@ -83,6 +89,9 @@ pub struct TextEditorParams {
 
     /// The line number after which to insert text (0 for beginning). Required for `insert` command.
     pub insert_line: Option<i64>,
+
+    /// Optional directory that a relative `path` is resolved against, instead of the working directory.
+    pub cwd: Option<String>,
 }
 
 /// Parameters for the shell tool
@@ -90,6 +99,10 @@ pub struct TextEditorParams {
 pub struct ShellParams {
     /// The command string to execute in the shell
     pub command: String,
+
+    /// Optional directory to run the command in, instead of the working directory.
+    /// Use this rather than prefixing the command with `cd`.
+    pub cwd: Option<String>,
 }
 
 /// Parameters for the image_processor tool
@@ -115,6 +128,17 @@ pub struct PromptArgumentTemplate {
     pub required: Option<bool>,
 }
 
+/// Directories, separated like `PATH`, that the `cwd` parameter of a tool call may point into.
+/// Defaults to the working directory the extension was started in.
+const ALLOWED_ROOTS_ENV: &str = "GOOSE_DEVELOPER_ALLOWED_ROOTS";
+
+fn allowed_roots(working_dir: &Path) -> Vec<PathBuf> {
+    match std::env::var_os(ALLOWED_ROOTS_ENV) {
+        Some(roots) if !roots.is_empty() => std::env::split_paths(&roots).collect(),
+        _ => vec![working_dir.to_path_buf()],
+    }
+}
+
 // Embeds the prompts directory to the build
 static PROMPTS_DIR: Dir = include_dir!("$CARGO_MANIFEST_DIR/src/developer/prompts");
 
@@ -167,6 +191,18 @@ fn load_prompt_files() -> HashMap<String, Prompt> {
     prompts
 }
 
+/// Git tools return their structured results as pretty-printed JSON
+fn git_result<T: Serialize>(value: &T) -> Result<CallToolResult, ErrorData> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to serialize git output: {}", e),
+            None,
+        )
+    })?;
+    Ok(CallToolResult::success(vec![Content::text(json)]))
+}
+
 /// Developer MCP Server using official RMCP SDK
 #[derive(Clone)]
 pub struct DeveloperServer {
@@ -176,6 +212,7 @@ pub struct DeveloperServer {
     editor_model: Option<EditorModel>,
     prompts: HashMap<String, Prompt>,
     code_analyzer: CodeAnalyzer,
+    command_policy: CommandPolicy,
     #[cfg(test)]
     pub running_processes: Arc<RwLock<HashMap<String, CancellationToken>>>,
     #[cfg(not(test))]
@@ -565,6 +602,7 @@ impl DeveloperServer {
             editor_model,
             prompts: load_prompt_files(),
             code_analyzer: CodeAnalyzer::new(),
+            command_policy: CommandPolicy::from_env(),
             running_processes: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -727,7 +765,8 @@ impl DeveloperServer {
         params: Parameters<TextEditorParams>,
     ) -> Result<CallToolResult, ErrorData> {
         let params = params.0;
-        let path = self.resolve_path(&params.path)?;
+        let base_dir = self.resolve_cwd(params.cwd.as_deref())?;
+        let path = self.resolve_path_in(&params.path, &base_dir)?;
 
         // Check if file is ignored before proceeding with any text editor operation
         if self.is_ignored(&path) {
@@ -743,13 +782,17 @@ impl DeveloperServer {
 
         match params.command.as_str() {
             "view" => {
-                let view_range = params.view_range.as_ref().and_then(|vr| {
-                    if vr.len() == 2 {
-                        Some((vr[0] as usize, vr[1]))
-                    } else {
-                        None
+                let view_range = match params.view_range.as_deref() {
+                    None => None,
+                    Some([start, end]) if *start >= 0 => Some((*start as usize, *end)),
+                    Some(_) => {
+                        return Err(ErrorData::new(
+                            ErrorCode::INVALID_PARAMS,
+                            "view_range must be [start_line, end_line] with a 1-indexed start_line, e.g. [900, 950] or [900, -1]".to_string(),
+                            None,
+                        ));
                     }
-                });
+                };
                 let content = text_editor_view(&path, view_range).await?;
                 Ok(CallToolResult::success(content))
             }
@@ -860,9 +903,10 @@ impl DeveloperServer {
         let command = &params.command;
         let peer = context.peer;
         let request_id = context.id;
+        let working_dir = self.resolve_cwd(params.cwd.as_deref())?;
 
         // Validate the shell command
-        self.validate_shell_command(command)?;
+        self.validate_shell_command(command, &working_dir)?;
 
         let cancellation_token = CancellationToken::new();
         // Track the process using the request ID
@@ -874,7 +918,7 @@ impl DeveloperServer {
 
         // Execute the command and capture output
         let output_result = self
-            .execute_shell_command(command, &peer, cancellation_token.clone())
+            .execute_shell_command(command, &working_dir, &peer, cancellation_token.clone())
             .await;
 
         // Clean up the process from tracking
@@ -908,9 +952,10 @@ impl DeveloperServer {
 
     /// Validate a shell command before execution.
     ///
-    /// Checks for empty commands and ensures the command doesn't attempt to access
-    /// files that are restricted by ignore patterns.
-    fn validate_shell_command(&self, command: &str) -> Result<(), ErrorData> {
+    /// Checks for empty commands, enforces the configured command allowlist and denylist,
+    /// and ensures the command doesn't attempt to access files that are restricted by
+    /// ignore patterns.
+    fn validate_shell_command(&self, command: &str, working_dir: &Path) -> Result<(), ErrorData> {
         // Check for empty commands
         if command.trim().is_empty() {
             return Err(ErrorData::new(
@@ -920,6 +965,10 @@ impl DeveloperServer {
             ));
         }
 
+        if let Err(reason) = self.command_policy.check(command) {
+            return Err(ErrorData::new(ErrorCode::INVALID_REQUEST, reason, None));
+        }
+
         let cmd_parts: Vec<&str> = command.split_whitespace().collect();
 
         // Check if command arguments reference ignored files
@@ -930,12 +979,12 @@ impl DeveloperServer {
             }
 
             // Skip invalid paths
-            let path = Path::new(arg);
+            let path = working_dir.join(arg);
             if !path.exists() {
                 continue;
             }
 
-            if self.is_ignored(path) {
+            if self.is_ignored(&path) {
                 return Err(ErrorData::new(
                     ErrorCode::INTERNAL_ERROR,
                     format!(
@@ -956,6 +1005,7 @@ impl DeveloperServer {
     async fn execute_shell_command(
         &self,
         command: &str,
+        working_dir: &Path,
         peer: &rmcp::service::Peer<RoleServer>,
         cancellation_token: CancellationToken,
     ) -> Result<String, ErrorData> {
@@ -963,6 +1013,7 @@ impl DeveloperServer {
         let shell_config = get_shell_config();
 
         let mut child = configure_shell_command(&shell_config, command)
+            .current_dir(working_dir)
             .spawn()
             .map_err(|e| ErrorData::new(ErrorCode::INTERNAL_ERROR, e.to_string(), None))?;
 
@@ -1120,6 +1171,200 @@ impl DeveloperServer {
             .analyze(params, path, &self.ignore_patterns)
     }
 
+    /// Summarize the working tree of the git repository containing `cwd`.
+    #[tool(
+        name = "git_status",
+        description = "Show the status of the git repository containing the working directory (or `cwd`): current branch, upstream and ahead/behind counts, and staged, unstaged, untracked and conflicted files. Returns JSON.",
+        annotations(read_only_hint = true, idempotent_hint = true)
+    )]
+    pub async fn git_status(
+        &self,
+        params: Parameters<GitRepoParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let repo = self.git_repo(params.0.cwd.as_deref()).await?;
+        git_result(
+            &repo
+                .status(|path| self.is_ignored(&repo.root().join(path)))
+                .await?,
+        )
+    }
+
+    /// Show changes as per-file line counts plus the (possibly truncated) patch.
+    #[tool(
+        name = "git_diff",
+        description = "Show changes in the git repository: unstaged by default, staged with `staged`, or against `revision`. Optionally limited to `path`. Returns JSON with per-file addition/deletion counts and the patch, truncated if very large.",
+        annotations(read_only_hint = true, idempotent_hint = true)
+    )]
+    pub async fn git_diff(
+        &self,
+        params: Parameters<GitDiffParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let repo = self.git_repo(params.cwd.as_deref()).await?;
+        let path = self.git_path(&repo, params.path.as_deref())?;
+        let diff = repo
+            .diff(
+                params.staged,
+                params.revision.as_deref(),
+                path.as_deref(),
+                |path| self.is_ignored(&repo.root().join(path)),
+            )
+            .await?;
+        git_result(&diff)
+    }
+
+    #[tool(
+        name = "git_log",
+        description = "List recent commits in the git repository, newest first, optionally for a `revision` range or limited to `path`. Returns JSON with hash, author, date and subject of each commit.",
+        annotations(read_only_hint = true, idempotent_hint = true)
+    )]
+    pub async fn git_log(
+        &self,
+        params: Parameters<GitLogParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let repo = self.git_repo(params.cwd.as_deref()).await?;
+        let path = self.git_path(&repo, params.path.as_deref())?;
+        let log = repo
+            .log(
+                params.max_count,
+                params.revision.as_deref(),
+                path.as_deref(),
+            )
+            .await?;
+        git_result(&log)
+    }
+
+    #[tool(
+        name = "git_blame",
+        description = "Show which commit and author last changed each line of a file, optionally for a line range. Returns JSON.",
+        annotations(read_only_hint = true, idempotent_hint = true)
+    )]
+    pub async fn git_blame(
+        &self,
+        params: Parameters<GitBlameParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let repo = self.git_repo(params.cwd.as_deref()).await?;
+        let path = self
+            .git_path(&repo, Some(&params.path))?
+            .unwrap_or_default();
+        let blame = repo
+            .blame(&path, params.start_line, params.end_line)
+            .await?;
+        git_result(&blame)
+    }
+
+    #[tool(
+        name = "git_branch",
+        description = "List local branches (`list`), create a branch (`create`, optionally from `start_point` and switching to it with `checkout`), or switch to an existing branch (`switch`). Returns the branch list as JSON."
+    )]
+    pub async fn git_branch(
+        &self,
+        params: Parameters<GitBranchParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let repo = self.git_repo(params.cwd.as_deref()).await?;
+        let name = || {
+            params.name.as_deref().ok_or_else(|| {
+                ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!("The {} action requires a branch name", params.action),
+                    None,
+                )
+            })
+        };
+
+        match params.action.as_str() {
+            "list" => {}
+            "create" => {
+                repo.create_branch(name()?, params.start_point.as_deref(), params.checkout)
+                    .await?
+            }
+            "switch" => repo.switch_branch(name()?).await?,
+            other => {
+                return Err(ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "Unknown action '{}'; expected list, create or switch",
+                        other
+                    ),
+                    None,
+                ))
+            }
+        }
+        git_result(&repo.branches().await?)
+    }
+
+    #[tool(
+        name = "git_commit",
+        description = "Commit changes with a message. Stages `paths` first if given, or all changes to tracked files with `all`; otherwise commits what is already staged. Returns the new commit as JSON."
+    )]
+    pub async fn git_commit(
+        &self,
+        params: Parameters<GitCommitParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let repo = self.git_repo(params.cwd.as_deref()).await?;
+        let paths = params
+            .paths
+            .iter()
+            .map(|path| Ok(self.git_path(&repo, Some(path))?.unwrap_or_default()))
+            .collect::<Result<Vec<_>, ErrorData>>()?;
+        let commit = repo.commit(&params.message, &paths, params.all).await?;
+        git_result(&commit)
+    }
+
+    /// Kept separate from the other git tools so permissions can be set for it on its own.
+    /// A hard reset throws away uncommitted work, so it always asks first through a built-in
+    /// permission rule, even in auto mode.
+    #[tool(
+        name = "git_reset",
+        description = "Reset the current branch to `target` (default HEAD). `mode` is soft, mixed (default) or hard; hard discards all uncommitted changes.",
+        annotations(destructive_hint = true, idempotent_hint = false)
+    )]
+    pub async fn git_reset(
+        &self,
+        params: Parameters<GitResetParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let repo = self.git_repo(params.cwd.as_deref()).await?;
+        repo.reset(params.target.as_deref(), params.mode.as_deref())
+            .await?;
+        git_result(
+            &repo
+                .status(|path| self.is_ignored(&repo.root().join(path)))
+                .await?,
+        )
+    }
+
+    /// Pushing publishes commits, and a force push can overwrite the remote's history, so a
+    /// push with `force` always asks first through a built-in permission rule.
+    #[tool(
+        name = "git_push",
+        description = "Push the current branch to its upstream, or `branch` to `remote`. `force` overwrites the remote branch using --force-with-lease.",
+        annotations(
+            destructive_hint = true,
+            idempotent_hint = false,
+            open_world_hint = true
+        )
+    )]
+    pub async fn git_push(
+        &self,
+        params: Parameters<GitPushParams>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let params = params.0;
+        let repo = self.git_repo(params.cwd.as_deref()).await?;
+        let output = repo
+            .push(
+                params.remote.as_deref(),
+                params.branch.as_deref(),
+                params.force,
+            )
+            .await?;
+        Ok(CallToolResult::success(vec![Content::text(output)]))
+    }
+
     /// Process an image file from disk.
     ///
     /// The image will be:
@@ -1243,6 +1488,11 @@ impl DeveloperServer {
     // Helper method to resolve and validate file paths
     fn resolve_path(&self, path_str: &str) -> Result<PathBuf, ErrorData> {
         let cwd = std::env::current_dir().expect("should have a current working dir");
+        self.resolve_path_in(path_str, &cwd)
+    }
+
+    // Resolve a path, with relative paths taken relative to `base_dir`
+    fn resolve_path_in(&self, path_str: &str, base_dir: &Path) -> Result<PathBuf, ErrorData> {
         let expanded = expand_path(path_str);
         let path = Path::new(&expanded);
 
@@ -1250,9 +1500,91 @@ impl DeveloperServer {
         if is_absolute_path(&expanded) {
             Ok(path.to_path_buf())
         } else {
-            // For relative paths, resolve them relative to the current working directory
-            Ok(cwd.join(path))
+            Ok(base_dir.join(path))
+        }
+    }
+
+    // Resolve the directory a tool call runs in: the working directory unless `cwd` is given,
+    // in which case it must be an existing directory inside one of the allowed roots
+    fn resolve_cwd(&self, cwd: Option<&str>) -> Result<PathBuf, ErrorData> {
+        let current = std::env::current_dir().expect("should have a current working dir");
+        let Some(cwd) = cwd else {
+            return Ok(current);
+        };
+
+        let invalid = |message: String| ErrorData::new(ErrorCode::INVALID_PARAMS, message, None);
+        let dir = self
+            .resolve_path_in(cwd, &current)?
+            .canonicalize()
+            .map_err(|e| invalid(format!("Invalid cwd '{}': {}", cwd, e)))?;
+        if !dir.is_dir() {
+            return Err(invalid(format!("cwd '{}' is not a directory", cwd)));
+        }
+
+        let allowed = allowed_roots(&current)
+            .iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| dir.starts_with(root));
+        if !allowed {
+            return Err(invalid(format!(
+                "cwd '{}' is outside the allowed roots; set {} to allow it",
+                dir.display(),
+                ALLOWED_ROOTS_ENV
+            )));
+        }
+
+        if self.is_ignored(&dir) {
+            return Err(ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!(
+                    "Access to '{}' is restricted by .gooseignore",
+                    dir.display()
+                ),
+                None,
+            ));
+        }
+
+        Ok(dir)
+    }
+
+    // Find the git repository for a tool call, which must lie entirely inside the allowed roots
+    async fn git_repo(&self, cwd: Option<&str>) -> Result<GitRepo, ErrorData> {
+        let dir = self.resolve_cwd(cwd)?;
+        let repo = GitRepo::discover(&dir).await?;
+
+        let current = std::env::current_dir().expect("should have a current working dir");
+        let allowed = allowed_roots(&current)
+            .iter()
+            .filter_map(|root| root.canonicalize().ok())
+            .any(|root| repo.root().starts_with(root));
+        if !allowed {
+            return Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!(
+                    "The git repository at '{}' is outside the allowed roots; set {} to allow it",
+                    repo.root().display(),
+                    ALLOWED_ROOTS_ENV
+                ),
+                None,
+            ));
+        }
+        Ok(repo)
+    }
+
+    // Resolve a path argument of a git tool relative to the repository, honouring .gooseignore
+    fn git_path(&self, repo: &GitRepo, path: Option<&str>) -> Result<Option<String>, ErrorData> {
+        let Some(path) = path else {
+            return Ok(None);
+        };
+        let relative = repo.relative_path(path)?;
+        if self.is_ignored(&repo.root().join(&relative)) {
+            return Err(ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Access to '{}' is restricted by .gooseignore", path),
+                None,
+            ));
         }
+        Ok(Some(relative))
     }
 
     // Helper method to build ignore patterns from .gooseignore or .gitignore files
@@ -1487,6 +1819,7 @@ mod tests {
                 .shell(
                     Parameters(ShellParams {
                         command: "".to_string(),
+                        cwd: None,
                     }),
                     RequestContext {
                         ct: Default::default(),
@@ -1522,6 +1855,7 @@ mod tests {
             // Test PowerShell command
             let shell_params = Parameters(ShellParams {
                 command: "Get-ChildItem".to_string(),
+                cwd: None,
             });
 
             let result = server
@@ -1575,6 +1909,7 @@ mod tests {
                 new_str: None,
                 insert_line: None,
                 diff: None,
+                cwd: None,
             });
 
             let result = server.text_editor(view_params).await;
@@ -1602,6 +1937,7 @@ mod tests {
                 new_str: None,
                 insert_line: None,
                 diff: None,
+                cwd: None,
             });
 
             let result = server.text_editor(view_params).await;
@@ -1613,6 +1949,41 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn test_text_editor_view_range_large_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::env::set_current_dir(&temp_dir).unwrap();
+
+        let server = create_test_server();
+
+        // Well above the 400KB limit for whole-file views
+        let large_file_path = temp_dir.path().join("large.log");
+        let content: String = (1..=50_000)
+            .map(|i| format!("log line {} {}\n", i, "x".repeat(20)))
+            .collect();
+        fs::write(&large_file_path, content).unwrap();
+
+        let view_params = Parameters(TextEditorParams {
+            path: large_file_path.to_str().unwrap().to_string(),
+            command: "view".to_string(),
+            view_range: Some(vec![40_000, 40_002]),
+            file_text: None,
+            old_str: None,
+            new_str: None,
+            insert_line: None,
+            diff: None,
+            cwd: None,
+        });
+
+        let result = server.text_editor(view_params).await.unwrap();
+        let text = result.content[0].as_text().unwrap();
+        assert!(text.text.contains("40000: log line 40000"));
+        assert!(text.text.contains("40002: log line 40002"));
+        assert!(!text.text.contains("log line 39999"));
+        assert!(!text.text.contains("log line 40003"));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_text_editor_write_and_view_file() {
@@ -1633,6 +2004,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -1647,6 +2019,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let view_result = server.text_editor(view_params).await.unwrap();
@@ -1685,6 +2058,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -1699,6 +2073,7 @@ mod tests {
             new_str: Some("Rust".to_string()),
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let replace_result = server.text_editor(replace_params).await.unwrap();
@@ -1744,6 +2119,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -1758,6 +2134,7 @@ mod tests {
             new_str: Some("Modified".to_string()),
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(replace_params).await.unwrap();
@@ -1776,6 +2153,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let undo_result = server.text_editor(undo_params).await.unwrap();
@@ -1856,6 +2234,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(write_params).await;
@@ -1876,6 +2255,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(write_params).await;
@@ -1905,6 +2285,7 @@ mod tests {
                 .shell(
                     Parameters(ShellParams {
                         command: format!("cat {}", secret_file_path.to_str().unwrap()),
+                        cwd: None,
                     }),
                     RequestContext {
                         ct: Default::default(),
@@ -1927,6 +2308,7 @@ mod tests {
                 .shell(
                     Parameters(ShellParams {
                         command: format!("cat {}", allowed_file_path.to_str().unwrap()),
+                        cwd: None,
                     }),
                     RequestContext {
                         ct: Default::default(),
@@ -2043,6 +2425,7 @@ mod tests {
                 view_range: None,
                 insert_line: None,
                 diff: None,
+                cwd: None,
             }))
             .await;
 
@@ -2067,6 +2450,7 @@ mod tests {
                 view_range: None,
                 insert_line: None,
                 diff: None,
+                cwd: None,
             }))
             .await;
 
@@ -2101,6 +2485,7 @@ mod tests {
                 .shell(
                     Parameters(ShellParams {
                         command: format!("cat {}", log_file_path.to_str().unwrap()),
+                        cwd: None,
                     }),
                     RequestContext {
                         ct: Default::default(),
@@ -2126,6 +2511,7 @@ mod tests {
                 .shell(
                     Parameters(ShellParams {
                         command: format!("cat {}", allowed_file_path.to_str().unwrap()),
+                        cwd: None,
                     }),
                     RequestContext {
                         ct: Default::default(),
@@ -2168,6 +2554,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2182,6 +2569,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let view_result = server.text_editor(view_params).await.unwrap();
@@ -2206,6 +2594,22 @@ mod tests {
         // Should not contain other lines
         assert!(!text.text.contains("1: Line 1"));
         assert!(!text.text.contains("7: Line 7"));
+
+        // The assistant only gets the requested range too
+        let assistant_text = view_result
+            .content
+            .iter()
+            .find(|c| {
+                c.audience()
+                    .is_some_and(|roles| roles.contains(&Role::Assistant))
+            })
+            .unwrap()
+            .as_text()
+            .unwrap();
+        assert!(assistant_text.text.contains("3: Line 3"));
+        assert!(assistant_text.text.contains("6: Line 6"));
+        assert!(!assistant_text.text.contains("1: Line 1"));
+        assert!(!assistant_text.text.contains("Line 7"));
     }
 
     #[tokio::test]
@@ -2229,6 +2633,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2243,6 +2648,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let view_result = server.text_editor(view_params).await.unwrap();
@@ -2289,6 +2695,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2303,6 +2710,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(view_params).await;
@@ -2333,6 +2741,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2347,6 +2756,7 @@ mod tests {
             new_str: Some("Line 1".to_string()),
             insert_line: Some(0),
             diff: None,
+            cwd: None,
         });
 
         let insert_result = server.text_editor(insert_params).await.unwrap();
@@ -2390,6 +2800,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2404,6 +2815,7 @@ mod tests {
             new_str: Some("Line 3".to_string()),
             insert_line: Some(2),
             diff: None,
+            cwd: None,
         });
 
         let insert_result = server.text_editor(insert_params).await.unwrap();
@@ -2452,6 +2864,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2466,6 +2879,7 @@ mod tests {
             new_str: Some("Line 4".to_string()),
             insert_line: Some(3),
             diff: None,
+            cwd: None,
         });
 
         let insert_result = server.text_editor(insert_params).await.unwrap();
@@ -2509,6 +2923,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2523,6 +2938,7 @@ mod tests {
             new_str: Some("Line 4".to_string()),
             insert_line: Some(-1),
             diff: None,
+            cwd: None,
         });
 
         let insert_result = server.text_editor(insert_params).await.unwrap();
@@ -2566,6 +2982,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2580,6 +2997,7 @@ mod tests {
             new_str: Some("Line 11".to_string()),
             insert_line: Some(10),
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(insert_params).await;
@@ -2610,6 +3028,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2624,6 +3043,7 @@ mod tests {
             new_str: None, // Missing required parameter
             insert_line: Some(1),
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(insert_params).await;
@@ -2642,6 +3062,7 @@ mod tests {
             new_str: Some("New text".to_string()),
             insert_line: None, // Missing required parameter
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(insert_params).await;
@@ -2672,6 +3093,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2686,6 +3108,7 @@ mod tests {
             new_str: Some("Inserted Line".to_string()),
             insert_line: Some(1),
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(insert_params).await.unwrap();
@@ -2700,6 +3123,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let undo_result = server.text_editor(undo_params).await.unwrap();
@@ -2739,6 +3163,7 @@ mod tests {
             new_str: Some("New line".to_string()),
             insert_line: Some(0),
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(insert_params).await;
@@ -2774,6 +3199,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2788,6 +3214,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(view_params).await;
@@ -2813,6 +3240,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(view_params).await;
@@ -2845,6 +3273,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(view_params).await;
@@ -2876,6 +3305,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2890,6 +3320,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(view_params).await;
@@ -2937,6 +3368,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         server.text_editor(write_params).await.unwrap();
@@ -2951,6 +3383,7 @@ mod tests {
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(view_params).await;
@@ -3004,6 +3437,7 @@ mod tests {
                 new_str: None,
                 insert_line: None,
                 diff: None,
+                cwd: None,
             }))
             .await;
 
@@ -3067,6 +3501,7 @@ mod tests {
                 new_str: None,
                 insert_line: None,
                 diff: None,
+                cwd: None,
             }))
             .await;
 
@@ -3109,6 +3544,7 @@ mod tests {
                 new_str: None,
                 insert_line: None,
                 diff: None,
+                cwd: None,
             }))
             .await;
 
@@ -3146,6 +3582,7 @@ mod tests {
                 .shell(
                     Parameters(ShellParams {
                         command: command.to_string(),
+                        cwd: None,
                     }),
                     RequestContext {
                         ct: Default::default(),
@@ -3292,6 +3729,7 @@ mod tests {
                 .shell(
                     Parameters(ShellParams {
                         command: command.to_string(),
+                        cwd: None,
                     }),
                     RequestContext {
                         ct: Default::default(),
@@ -3561,6 +3999,140 @@ Additional instructions here.
         assert_eq!(resolved, absolute_path);
     }
 
+    #[test]
+    fn test_shell_command_policy() {
+        let policy = CommandPolicy::new(
+            Some(vec![
+                "git".to_string(),
+                "cargo".to_string(),
+                "rm".to_string(),
+            ]),
+            vec!["rm".to_string(), "curl".to_string()],
+        );
+        assert!(policy.check("git status && cargo test").is_ok());
+        assert!(policy.check("RUST_LOG=debug cargo build").is_ok());
+        assert!(policy.check("cargo build 2>&1 | git apply").is_ok());
+
+        let err = policy.check("git log | rm -rf /").unwrap_err();
+        assert!(err.contains("'rm' is blocked"));
+        assert!(policy.check("/usr/bin/curl example.com").is_err());
+        assert!(policy
+            .check("ls -la")
+            .unwrap_err()
+            .contains("not in GOOSE_SHELL_ALLOWED_COMMANDS"));
+
+        // No lists configured means no restrictions
+        assert!(CommandPolicy::default().check("rm -rf target").is_ok());
+        assert!(CommandPolicy::default()
+            .check("sh -c 'echo $(date)'")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_shell_command_policy_sees_through_wrappers() {
+        let policy = CommandPolicy::new(None, vec!["rm".to_string()]);
+
+        for command in [
+            "env rm -rf x",
+            "env FOO=1 rm -rf x",
+            "sudo rm -rf x",
+            "sudo -u root rm -rf x",
+            "find . -name '*.o' | xargs rm",
+            "xargs -n 1 rm < files.txt",
+            "timeout 10 rm -rf x",
+            "nice -n 5 nohup rm -rf x",
+            "find . -exec rm {} ;",
+        ] {
+            assert!(
+                policy
+                    .check(command)
+                    .unwrap_err()
+                    .contains("'rm' is blocked"),
+                "{} should be blocked",
+                command
+            );
+        }
+        assert!(policy.check("sudo ls").is_ok());
+
+        for command in [
+            "echo $(rm -rf x)",
+            "echo `rm -rf x`",
+            "diff <(ls a) <(ls b)",
+        ] {
+            assert!(policy
+                .check(command)
+                .unwrap_err()
+                .contains("Command substitution"));
+        }
+        for command in [
+            "sh -c 'rm -rf x'",
+            "bash -c \"rm -rf x\"",
+            "xargs sh -c 'rm $0'",
+        ] {
+            assert!(policy.check(command).unwrap_err().contains("nested shell"));
+        }
+    }
+
+    #[test]
+    fn test_shell_command_policy_sees_through_shell_syntax() {
+        let policy = CommandPolicy::new(None, vec!["rm".to_string()]);
+
+        for command in [
+            "if true; then rm -rf x; fi",
+            "if false; then :; else rm -rf x; fi",
+            "while true; do rm -rf x; done",
+            "! rm x",
+            "{ rm x; }",
+            "r''m x",
+            "\"rm\" x",
+            "\\rm x",
+        ] {
+            assert!(
+                policy
+                    .check(command)
+                    .unwrap_err()
+                    .contains("'rm' is blocked"),
+                "{} should be blocked",
+                command
+            );
+        }
+        assert!(policy
+            .check("x=rm; $x file")
+            .unwrap_err()
+            .contains("named by a variable"));
+
+        let policy = CommandPolicy::new(Some(vec!["test".to_string(), "ls".to_string()]), vec![]);
+        assert!(policy.check("if test -d x; then ls x; fi").is_ok());
+    }
+
+    #[test]
+    #[serial]
+    fn test_resolve_cwd_within_allowed_roots() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("project")).unwrap();
+        std::env::set_current_dir(&temp_dir).unwrap();
+
+        let server = create_test_server();
+        let resolved = server.resolve_cwd(Some("project")).unwrap();
+        assert_eq!(
+            resolved,
+            temp_dir.path().join("project").canonicalize().unwrap()
+        );
+        assert_eq!(
+            server.resolve_cwd(None).unwrap(),
+            std::env::current_dir().unwrap()
+        );
+
+        let err = server.resolve_cwd(Some("missing")).unwrap_err();
+        assert_eq!(err.code, ErrorCode::INVALID_PARAMS);
+
+        let outside = tempfile::tempdir().unwrap();
+        let err = server
+            .resolve_cwd(Some(outside.path().to_str().unwrap()))
+            .unwrap_err();
+        assert!(err.to_string().contains("outside the allowed roots"));
+    }
+
     #[tokio::test]
     #[serial]
     async fn test_resolve_path_relative() {
@@ -3594,6 +4166,7 @@ Additional instructions here.
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(write_params).await;
@@ -3621,6 +4194,7 @@ Additional instructions here.
             new_str: None,
             insert_line: None,
             diff: None,
+            cwd: None,
         });
 
         let result = server.text_editor(write_params).await;
@@ -3657,6 +4231,7 @@ Additional instructions here.
                     .shell(
                         Parameters(ShellParams {
                             command: "sleep 30".to_string(),
+                            cwd: None,
                         }),
                         context,
                     )
@@ -3745,6 +4320,7 @@ Additional instructions here.
                     .shell(
                         Parameters(ShellParams {
                             command: "bash -c 'sleep 60 & wait'".to_string(),
+                            cwd: None,
                         }),
                         context,
                     )
@@ -3842,6 +4418,7 @@ Additional instructions here.
                 .shell(
                     Parameters(ShellParams {
                         command: "echo 'Hello, World!'".to_string(),
+                        cwd: None,
                     }),
                     context,
                 )
@@ -86,6 +86,242 @@ pub fn get_shell_config() -> ShellConfig {
     ShellConfig::default()
 }
 
+/// Comma-separated command names the shell tool may run. When set, anything else is rejected.
+pub const ALLOWED_COMMANDS_ENV: &str = "GOOSE_SHELL_ALLOWED_COMMANDS";
+/// Comma-separated command names the shell tool refuses to run, e.g. `rm,curl`
+pub const DENIED_COMMANDS_ENV: &str = "GOOSE_SHELL_DENIED_COMMANDS";
+
+/// Which binaries the shell tool may run, matched against the first token of each command in
+/// a pipeline or list (`a | b`, `a && b`, `a; b`) and the commands run by wrappers such as
+/// `sudo` or `xargs`. The denylist wins over the allowlist. While either list is set, command
+/// substitution, nested shells and commands named by a variable are refused, since what they
+/// run can't be checked.
+#[derive(Debug, Clone, Default)]
+pub struct CommandPolicy {
+    allowed: Option<Vec<String>>,
+    denied: Vec<String>,
+}
+
+/// Commands that run the command given in their arguments
+const WRAPPER_COMMANDS: &[&str] = &[
+    "builtin", "command", "doas", "env", "exec", "nice", "nohup", "stdbuf", "sudo", "time",
+    "timeout", "xargs",
+];
+
+/// Shell keywords that can come before the command in a list, as in `if true; then rm x; fi`
+const RESERVED_WORDS: &[&str] = &[
+    "!", "{", "}", "do", "done", "elif", "else", "fi", "if", "then", "until", "while",
+];
+
+/// Commands that run a script passed as a string or file, which the policy can't look into
+const NESTED_SHELLS: &[&str] = &[
+    ".",
+    "bash",
+    "cmd",
+    "dash",
+    "eval",
+    "fish",
+    "ksh",
+    "powershell",
+    "pwsh",
+    "sh",
+    "source",
+    "zsh",
+];
+
+impl CommandPolicy {
+    pub fn new(allowed: Option<Vec<String>>, denied: Vec<String>) -> Self {
+        Self { allowed, denied }
+    }
+
+    pub fn from_env() -> Self {
+        let list = |key: &str| {
+            env::var(key).ok().map(|value| {
+                value
+                    .split(',')
+                    .map(|name| name.trim().to_string())
+                    .filter(|name| !name.is_empty())
+                    .collect::<Vec<_>>()
+            })
+        };
+        Self::new(
+            list(ALLOWED_COMMANDS_ENV),
+            list(DENIED_COMMANDS_ENV).unwrap_or_default(),
+        )
+    }
+
+    fn is_active(&self) -> bool {
+        self.allowed.is_some() || !self.denied.is_empty()
+    }
+
+    /// Check every command in `command`, returning why the first rejected one is not allowed
+    pub fn check(&self, command: &str) -> Result<(), String> {
+        if !self.is_active() {
+            return Ok(());
+        }
+
+        if ["$(", "`", "<(", ">("]
+            .iter()
+            .any(|substitution| command.contains(substitution))
+        {
+            return Err(format!(
+                "Command substitution is not allowed while {} or {} is set",
+                ALLOWED_COMMANDS_ENV, DENIED_COMMANDS_ENV
+            ));
+        }
+
+        for name in command_names(command) {
+            if name.contains(['$', '`']) {
+                return Err(format!(
+                    "Running a command named by a variable ('{}') is not allowed while {} or {} is set",
+                    name, ALLOWED_COMMANDS_ENV, DENIED_COMMANDS_ENV
+                ));
+            }
+            if NESTED_SHELLS.contains(&name.as_str()) {
+                return Err(format!(
+                    "Running a nested shell ('{}') is not allowed while {} or {} is set",
+                    name, ALLOWED_COMMANDS_ENV, DENIED_COMMANDS_ENV
+                ));
+            }
+            if self.denied.iter().any(|denied| denied == &name) {
+                return Err(format!(
+                    "The command '{}' is blocked by {}",
+                    name, DENIED_COMMANDS_ENV
+                ));
+            }
+            if let Some(allowed) = &self.allowed {
+                if !allowed.iter().any(|allowed| allowed == &name) {
+                    return Err(format!(
+                        "The command '{}' is not in {} ({})",
+                        name,
+                        ALLOWED_COMMANDS_ENV,
+                        allowed.join(", ")
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The binary names a command line runs, without leading paths or environment assignments.
+/// Wrappers are listed along with the command they run, as are the commands `find -exec` runs.
+fn command_names(command: &str) -> Vec<String> {
+    // Redirections like `2>&1` and `&>` are not list separators
+    let command = command
+        .replace(">&", ">")
+        .replace("<&", "<")
+        .replace("&>", ">");
+    command
+        .split(['|', '&', ';', '\n'])
+        .flat_map(|segment| {
+            let tokens: Vec<&str> = segment
+                .split_whitespace()
+                .map(|token| token.trim_start_matches('('))
+                .filter(|token| !token.is_empty() && !token.starts_with(['>', '<']))
+                .collect();
+            segment_command_names(&tokens)
+        })
+        .collect()
+}
+
+fn segment_command_names(tokens: &[&str]) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = tokens;
+    while rest
+        .first()
+        .is_some_and(|token| RESERVED_WORDS.contains(token))
+    {
+        rest = &rest[1..];
+    }
+    loop {
+        while rest
+            .first()
+            .is_some_and(|token| token.contains('=') && !token.starts_with('-'))
+        {
+            rest = &rest[1..];
+        }
+        let Some((first, args)) = rest.split_first() else {
+            break;
+        };
+        let name = binary_name(first);
+
+        if name == "find" {
+            let mut args = args.iter();
+            while let Some(arg) = args.next() {
+                if matches!(*arg, "-exec" | "-execdir" | "-ok" | "-okdir") {
+                    if let Some(command) = args.next() {
+                        names.push(binary_name(command));
+                    }
+                }
+            }
+        }
+
+        let is_wrapper = WRAPPER_COMMANDS.contains(&name.as_str());
+        names.push(name);
+        if !is_wrapper {
+            break;
+        }
+        rest = skip_wrapper_options(&names[names.len() - 1], args);
+    }
+    names
+}
+
+/// Skip a wrapper's own options (and the duration of `timeout`) to get to the command it runs
+fn skip_wrapper_options<'a, 'b>(wrapper: &str, args: &'a [&'b str]) -> &'a [&'b str] {
+    let mut rest = args;
+    while let Some((arg, tail)) = rest.split_first() {
+        if *arg == "--" {
+            rest = tail;
+            break;
+        }
+        if !arg.starts_with('-') {
+            break;
+        }
+        let takes_value = matches!(
+            (wrapper, *arg),
+            (
+                "sudo",
+                "-u" | "-g" | "-C" | "-D" | "-h" | "-p" | "-r" | "-t" | "-U" | "-T"
+            ) | ("doas", "-u" | "-C")
+                | ("env", "-u" | "-C")
+                | ("nice", "-n")
+                | ("stdbuf", "-i" | "-o" | "-e")
+                | ("timeout", "-s" | "-k")
+                | (
+                    "xargs",
+                    "-a" | "-d" | "-E" | "-I" | "-L" | "-n" | "-P" | "-s"
+                )
+        );
+        rest = if takes_value {
+            tail.get(1..).unwrap_or_default()
+        } else {
+            tail
+        };
+    }
+    if wrapper == "timeout" {
+        rest = rest.get(1..).unwrap_or_default();
+    }
+    rest
+}
+
+fn binary_name(token: &str) -> String {
+    let name = std::path::Path::new(token)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(token);
+    // The shell drops quotes and escapes, so `r''m`, `"rm"` and `\rm` all run `rm`
+    let name: String = name
+        .chars()
+        .filter(|c| !matches!(c, '"' | '\'' | '\\'))
+        .collect();
+    // `git.exe` on Windows is still `git`
+    match name.strip_suffix(".exe") {
+        Some(name) => name.to_string(),
+        None => name,
+    }
+}
+
 pub fn expand_path(path_str: &str) -> String {
     if cfg!(windows) {
         // Expand Windows environment variables (%VAR%)
@@ -1 +1,2 @@
+mod test_atomic_write;
 mod test_diff;
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod tests {
+    use crate::developer::text_editor::*;
+    use std::io::{self, Write};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_interrupted_write_leaves_original_intact() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("config.yaml");
+        std::fs::write(&file_path, "original: true\n").unwrap();
+
+        let result = write_atomic_with(&file_path, |file| {
+            file.write_all(b"partial: ")?;
+            Err(io::Error::other("simulated crash mid-write"))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "original: true\n"
+        );
+        // The temp file is cleaned up, leaving only the original
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_atomic_write_replaces_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("main.rs");
+        std::fs::write(&file_path, "fn main() {}\n").unwrap();
+
+        write_file_atomic(&file_path, "fn main() { println!(\"hi\"); }\n").unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(&file_path).unwrap(),
+            "fn main() { println!(\"hi\"); }\n"
+        );
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_preserves_permissions_and_symlinks() {
+        use std::os::unix::fs::{symlink, PermissionsExt};
+
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("run.sh");
+        std::fs::write(&file_path, "echo old\n").unwrap();
+        std::fs::set_permissions(&file_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let link_path = temp_dir.path().join("link.sh");
+        symlink(&file_path, &link_path).unwrap();
+
+        write_file_atomic(&link_path, "echo new\n").unwrap();
+
+        assert!(link_path.is_symlink());
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "echo new\n");
+        let mode = std::fs::metadata(&file_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_atomic_write_new_file_respects_umask() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("notes.txt");
+
+        write_file_atomic(&file_path, "hello\n").unwrap();
+
+        // Mirror what a plain create would get under the current umask
+        let reference_path = temp_dir.path().join("reference.txt");
+        std::fs::write(&reference_path, "").unwrap();
+        let expected = std::fs::metadata(&reference_path)
+            .unwrap()
+            .permissions()
+            .mode();
+        let mode = std::fs::metadata(&file_path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, expected & 0o777);
+    }
+}
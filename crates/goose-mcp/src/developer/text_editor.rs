@@ -3,8 +3,8 @@ use indoc::formatdoc;
 use mpatch::{apply_patch, parse_diffs, PatchError};
 use std::{
     collections::HashMap,
-    fs::File,
-    io::Read,
+    fs::{self, File},
+    io::{self, BufRead, Read, Write},
     path::{Path, PathBuf},
 };
 use url::Url;
@@ -12,6 +12,7 @@ use url::Url;
 use rmcp::model::{Content, ErrorCode, ErrorData, Role};
 
 use super::editor_models::EditorModel;
+use super::encoding::{decode_text, TextEncoding};
 use super::lang;
 use super::shell::normalize_line_endings;
 
@@ -20,6 +21,94 @@ pub const LINE_READ_LIMIT: usize = 2000;
 pub const MAX_DIFF_SIZE: usize = 1024 * 1024; // 1MB max diff size
 pub const MAX_FILES_IN_DIFF: usize = 100; // Maximum files in a multi-file diff
 
+/// Writes `content` to `path` atomically so readers never see a partially written file.
+pub fn write_file_atomic(path: &Path, content: &str) -> io::Result<()> {
+    write_atomic_with(path, |file| file.write_all(content.as_bytes()))
+}
+
+/// Writes through a temp file in the target's directory and renames it into place once
+/// `write` succeeds. On failure the temp file is removed and the original left untouched.
+pub(crate) fn write_atomic_with<F>(path: &Path, write: F) -> io::Result<()>
+where
+    F: FnOnce(&mut File) -> io::Result<()>,
+{
+    // Write through symlinks rather than replacing them with a regular file
+    let target = if path.is_symlink() {
+        fs::canonicalize(path)?
+    } else {
+        path.to_path_buf()
+    };
+    let dir = target
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+
+    let existing_permissions = fs::metadata(&target)
+        .ok()
+        .map(|metadata| metadata.permissions());
+
+    let mut builder = tempfile::Builder::new();
+    builder.prefix(".goose-write-").suffix(".tmp");
+    if existing_permissions.is_none() {
+        // New files get the usual permissions, which the OS narrows by the process umask
+        if let Some(permissions) = new_file_permissions() {
+            builder.permissions(permissions);
+        }
+    }
+    let mut temp = builder.tempfile_in(dir)?;
+    write(temp.as_file_mut())?;
+    temp.as_file().sync_all()?;
+
+    // Temp files are created owner-only, so carry over the original file's permissions
+    if let Some(permissions) = existing_permissions {
+        temp.as_file().set_permissions(permissions)?;
+    }
+
+    temp.persist(&target).map_err(|e| e.error)?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn new_file_permissions() -> Option<fs::Permissions> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(fs::Permissions::from_mode(0o666))
+}
+
+#[cfg(not(unix))]
+fn new_file_permissions() -> Option<fs::Permissions> {
+    None
+}
+
+/// Reads a file that is about to be edited. Edits are written back as UTF-8, so files in
+/// other encodings are refused with an explanation rather than silently converted.
+fn read_text_for_edit(path: &Path) -> Result<String, ErrorData> {
+    let bytes = fs::read(path).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to read file: {}", e),
+            None,
+        )
+    })?;
+
+    match String::from_utf8(bytes) {
+        Ok(content) => Ok(content),
+        Err(e) => {
+            let reason = match decode_text(e.as_bytes()) {
+                Ok((_, encoding)) => format!(
+                    "it is {}-encoded and edits are saved as UTF-8; convert it to UTF-8 first",
+                    encoding
+                ),
+                Err(e) => e.to_string(),
+            };
+            Err(ErrorData::new(
+                ErrorCode::INVALID_PARAMS,
+                format!("Cannot edit '{}': {}", path.display(), reason),
+                None,
+            ))
+        }
+    }
+}
+
 /// Validates paths to prevent directory traversal attacks
 fn validate_path_safety(base_dir: &Path, target_path: &Path) -> Result<(), ErrorData> {
     // Check for .. components
@@ -226,11 +315,40 @@ fn apply_single_patch(
         save_file_history(&file_path, file_history)?;
     }
 
+    // mpatch writes its output in place, so apply it to a staged copy and then move the
+    // result over the real file atomically
+    let staging_dir = tempfile::tempdir().map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to create staging directory: {}", e),
+            None,
+        )
+    })?;
+    let staged_path = staging_dir.path().join(&patch.file_path);
+    if file_existed {
+        if let Some(parent) = staged_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                ErrorData::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to stage '{}': {}", file_path.display(), e),
+                    None,
+                )
+            })?;
+        }
+        fs::copy(&file_path, &staged_path).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to stage '{}': {}", file_path.display(), e),
+                None,
+            )
+        })?;
+    }
+
     // Apply patch with fuzzy matching (70% similarity threshold)
-    let success = apply_patch(patch, &adjusted_base_dir, false, 0.7).map_err(|e| match e {
-        PatchError::Io { path, source } => ErrorData::new(
+    let success = apply_patch(patch, staging_dir.path(), false, 0.7).map_err(|e| match e {
+        PatchError::Io { source, .. } => ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
-            format!("Failed to process '{}': {}", path.display(), source),
+            format!("Failed to process '{}': {}", file_path.display(), source),
             None,
         ),
         PatchError::PathTraversal(path) => ErrorData::new(
@@ -241,11 +359,11 @@ fn apply_single_patch(
             ),
             None,
         ),
-        PatchError::TargetNotFound(path) => ErrorData::new(
+        PatchError::TargetNotFound(_) => ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
             format!(
                 "File '{}' not found and patch doesn't create it",
-                path.display()
+                file_path.display()
             ),
             None,
         ),
@@ -256,6 +374,32 @@ fn apply_single_patch(
         ),
     })?;
 
+    if staged_path.exists() {
+        let patched = fs::read(&staged_path).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to read patched '{}': {}", file_path.display(), e),
+                None,
+            )
+        })?;
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).map_err(|e| {
+                ErrorData::new(
+                    ErrorCode::INTERNAL_ERROR,
+                    format!("Failed to create directory '{}': {}", parent.display(), e),
+                    None,
+                )
+            })?;
+        }
+        write_atomic_with(&file_path, |file| file.write_all(&patched)).map_err(|e| {
+            ErrorData::new(
+                ErrorCode::INTERNAL_ERROR,
+                format!("Failed to write '{}': {}", file_path.display(), e),
+                None,
+            )
+        })?;
+    }
+
     if !success {
         // Collect information about failed hunks for better error reporting
         let hunk_count = patch.hunks.len();
@@ -438,17 +582,27 @@ pub fn format_file_content(
     end_idx: usize,
     view_range: Option<(usize, i64)>,
 ) -> String {
-    let display_content = if lines.is_empty() {
-        String::new()
+    let selected = if lines.is_empty() {
+        &[][..]
     } else {
-        let selected_lines: Vec<String> = lines[start_idx..end_idx]
-            .iter()
-            .enumerate()
-            .map(|(i, line)| format!("{}: {}", start_idx + i + 1, line))
-            .collect();
-
-        selected_lines.join("\n")
+        &lines[start_idx..end_idx]
     };
+    format_numbered_lines(path, selected, start_idx, view_range)
+}
+
+// Formats lines that start at the 0-indexed line `first_idx` of the file
+fn format_numbered_lines(
+    path: &Path,
+    lines: &[&str],
+    first_idx: usize,
+    view_range: Option<(usize, i64)>,
+) -> String {
+    let display_content = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("{}: {}", first_idx + i + 1, line))
+        .collect::<Vec<_>>()
+        .join("\n");
 
     let language = lang::get_language_identifier(path);
     if view_range.is_some() {
@@ -614,10 +768,14 @@ pub async fn text_editor_view(
         .len();
 
     if file_size > MAX_FILE_SIZE {
+        // A ranged view only holds the requested lines in memory, so large files stay readable
+        if let Some(range) = view_range {
+            return view_large_file_range(path, f, range, MAX_FILE_SIZE);
+        }
         return Err(ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
             format!(
-                "File '{}' is too large ({:.2}KB). Maximum size is 400KB to prevent memory issues.",
+                "File '{}' is too large ({:.2}KB). Maximum size is 400KB to prevent memory issues. Use view_range to read part of it.",
                 path.display(),
                 file_size as f64 / 1024.0
             ),
@@ -638,8 +796,8 @@ pub async fn text_editor_view(
         })?
         .to_string();
 
-    let mut content = String::new();
-    f.read_to_string(&mut content).map_err(|e| {
+    let mut bytes = Vec::new();
+    f.read_to_end(&mut bytes).map_err(|e| {
         ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
             format!("Failed to read file: {}", e),
@@ -647,6 +805,22 @@ pub async fn text_editor_view(
         )
     })?;
 
+    // Transcode legacy encodings so the file can still be shown
+    let (content, encoding) = decode_text(&bytes).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("Cannot display '{}': {}", path.display(), e),
+            None,
+        )
+    })?;
+    if !matches!(encoding, TextEncoding::Utf8 | TextEncoding::Utf8Bom) {
+        tracing::debug!(
+            "Transcoded {} from {} for display",
+            path.display(),
+            encoding
+        );
+    }
+
     let lines: Vec<&str> = content.lines().collect();
     let total_lines = lines.len();
 
@@ -659,10 +833,88 @@ pub async fn text_editor_view(
     let (start_idx, end_idx) = calculate_view_range(view_range, total_lines)?;
     let formatted = format_file_content(path, &lines, start_idx, end_idx, view_range);
 
+    // A ranged read only gives the LLM the requested lines, numbered, so reading around a
+    // match in a large file doesn't pull the whole file into context
+    let assistant_content = if view_range.is_some() {
+        Content::text(formatted.clone())
+    } else {
+        Content::embedded_text(uri, content)
+    };
+
     // The LLM gets just a quick update as we expect the file to view in the status
     // but we send a low priority message for the human
     Ok(vec![
-        Content::embedded_text(uri, content).with_audience(vec![Role::Assistant]),
+        assistant_content.with_audience(vec![Role::Assistant]),
+        Content::text(formatted)
+            .with_audience(vec![Role::User])
+            .with_priority(0.0),
+    ])
+}
+
+/// Reads the requested range of a file line by line, keeping at most `max_bytes` of it
+fn view_large_file_range(
+    path: &Path,
+    file: File,
+    (start_line, end_line): (usize, i64),
+    max_bytes: u64,
+) -> Result<Vec<Content>, ErrorData> {
+    let read_error = |e: io::Error| {
+        ErrorData::new(
+            ErrorCode::INTERNAL_ERROR,
+            format!("Failed to read file: {}", e),
+            None,
+        )
+    };
+
+    let start_idx = start_line.saturating_sub(1);
+    let end_idx = usize::try_from(end_line).ok();
+    let mut reader = io::BufReader::new(file);
+    let mut selected = Vec::new();
+    let mut line = Vec::new();
+    let mut total_lines = 0;
+
+    loop {
+        if end_idx.is_some_and(|end| total_lines >= end) {
+            break;
+        }
+        line.clear();
+        if reader.read_until(b'\n', &mut line).map_err(read_error)? == 0 {
+            break;
+        }
+        if total_lines >= start_idx {
+            selected.extend_from_slice(&line);
+            if selected.len() as u64 > max_bytes {
+                return Err(ErrorData::new(
+                    ErrorCode::INVALID_PARAMS,
+                    format!(
+                        "Lines {}-{} of '{}' exceed {}KB. Request a smaller view_range.",
+                        start_line,
+                        end_line,
+                        path.display(),
+                        max_bytes / 1024
+                    ),
+                    None,
+                ));
+            }
+        }
+        total_lines += 1;
+    }
+
+    let view_range = Some((start_line, end_line));
+    let (start_idx, _) = calculate_view_range(view_range, total_lines)?;
+
+    let (content, _) = decode_text(&selected).map_err(|e| {
+        ErrorData::new(
+            ErrorCode::INVALID_PARAMS,
+            format!("Cannot display '{}': {}", path.display(), e),
+            None,
+        )
+    })?;
+    let lines: Vec<&str> = content.lines().collect();
+    let formatted = format_numbered_lines(path, &lines, start_idx, view_range);
+
+    Ok(vec![
+        Content::text(formatted.clone()).with_audience(vec![Role::Assistant]),
         Content::text(formatted)
             .with_audience(vec![Role::User])
             .with_priority(0.0),
@@ -679,7 +931,7 @@ pub async fn text_editor_write(path: &PathBuf, file_text: &str) -> Result<Vec<Co
     }
 
     // Write to the file
-    std::fs::write(path, &normalized_text) // Write the potentially modified text
+    write_file_atomic(path, &normalized_text) // Write the potentially modified text
         .map_err(|e| {
             ErrorData::new(
                 ErrorCode::INTERNAL_ERROR,
@@ -749,13 +1001,7 @@ pub async fn text_editor_replace(
     }
 
     // Read content
-    let content = std::fs::read_to_string(path).map_err(|e| {
-        ErrorData::new(
-            ErrorCode::INTERNAL_ERROR,
-            format!("Failed to read file: {}", e),
-            None,
-        )
-    })?;
+    let content = read_text_for_edit(path)?;
 
     // Check if Editor API is configured and use it as the primary path
     if let Some(ref editor) = editor_model {
@@ -766,7 +1012,7 @@ pub async fn text_editor_replace(
             Ok(updated_content) => {
                 // Write the updated content directly
                 let normalized_content = normalize_line_endings(&updated_content);
-                std::fs::write(path, &normalized_content).map_err(|e| {
+                write_file_atomic(path, &normalized_content).map_err(|e| {
                     ErrorData::new(
                         ErrorCode::INTERNAL_ERROR,
                         format!("Failed to write file: {}", e),
@@ -812,7 +1058,7 @@ pub async fn text_editor_replace(
 
     let new_content = content.replace(old_str, new_str);
     let normalized_content = normalize_line_endings(&new_content);
-    std::fs::write(path, &normalized_content).map_err(|e| {
+    write_file_atomic(path, &normalized_content).map_err(|e| {
         ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
             format!("Failed to write file: {}", e),
@@ -895,13 +1141,7 @@ pub async fn text_editor_insert(
     }
 
     // Read content
-    let content = std::fs::read_to_string(path).map_err(|e| {
-        ErrorData::new(
-            ErrorCode::INTERNAL_ERROR,
-            format!("Failed to read file: {}", e),
-            None,
-        )
-    })?;
+    let content = read_text_for_edit(path)?;
 
     // Save history for undo
     save_file_history(path, file_history)?;
@@ -952,7 +1192,7 @@ pub async fn text_editor_insert(
         normalized_content
     };
 
-    std::fs::write(path, &final_content).map_err(|e| {
+    write_file_atomic(path, &final_content).map_err(|e| {
         ErrorData::new(
             ErrorCode::INTERNAL_ERROR,
             format!("Failed to write file: {}", e),
@@ -1017,7 +1257,7 @@ pub async fn text_editor_undo(
     if let Some(contents) = history.get_mut(path) {
         if let Some(previous_content) = contents.pop() {
             // Write previous content back to file
-            std::fs::write(path, previous_content).map_err(|e| {
+            write_file_atomic(path, &previous_content).map_err(|e| {
                 ErrorData::new(
                     ErrorCode::INTERNAL_ERROR,
                     format!("Failed to write file: {}", e),
@@ -1049,13 +1289,7 @@ pub fn save_file_history(
 ) -> Result<(), ErrorData> {
     let mut history = file_history.lock().unwrap();
     let content = if path.exists() {
-        std::fs::read_to_string(path).map_err(|e| {
-            ErrorData::new(
-                ErrorCode::INTERNAL_ERROR,
-                format!("Failed to read file: {}", e),
-                None,
-            )
-        })?
+        read_text_for_edit(path)?
     } else {
         String::new()
     };
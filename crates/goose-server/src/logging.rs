@@ -1,11 +1,12 @@
 use anyhow::{Context, Result};
 use std::path::PathBuf;
-use tracing_appender::rolling::Rotation;
+use std::sync::Mutex;
 use tracing_subscriber::{
     filter::LevelFilter, fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer,
     Registry,
 };
 
+use goose::logging::{LogFormat, LogRotationConfig, RotatingFileWriter};
 use goose::tracing::{langfuse_layer, otlp_layer};
 
 /// Returns the directory where log files should be stored.
@@ -16,10 +17,14 @@ fn get_log_directory() -> Result<PathBuf> {
 
 /// Sets up the logging infrastructure for the application.
 /// This includes:
-/// - File-based logging with JSON formatting (DEBUG level)
+/// - File-based logging (DEBUG level)
 /// - Console output for development (INFO level)
 /// - Optional Langfuse integration (DEBUG level)
+///
+/// File and console output are human-readable unless GOOSE_LOG_FORMAT=json.
 pub fn setup_logging(name: Option<&str>) -> Result<()> {
+    let log_format = LogFormat::from_config();
+
     // Set up file appender for goose module logs
     let log_dir = get_log_directory()?;
     let timestamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
@@ -31,27 +36,39 @@ pub fn setup_logging(name: Option<&str>) -> Result<()> {
         format!("{}.log", timestamp)
     };
 
-    // Create non-rolling file appender for detailed logs
-    let file_appender =
-        tracing_appender::rolling::RollingFileAppender::new(Rotation::NEVER, log_dir, log_filename);
+    // Create size-rotated file writer for detailed logs
+    let file_appender = Mutex::new(RotatingFileWriter::new(
+        log_dir.join(log_filename),
+        LogRotationConfig::from_config(),
+    )?);
 
-    // Create JSON file logging layer
+    // Create file logging layer
     let file_layer = fmt::layer()
         .with_target(true)
         .with_level(true)
         .with_writer(file_appender)
         .with_ansi(false)
         .with_file(true);
+    let file_layer = match log_format {
+        LogFormat::Json => file_layer.json().flatten_event(true).boxed(),
+        LogFormat::Pretty => file_layer.boxed(),
+    };
 
     // Create console logging layer for development - INFO and above only
     let console_layer = fmt::layer()
         .with_writer(std::io::stderr)
         .with_target(true)
         .with_level(true)
-        .with_ansi(true)
         .with_file(true)
-        .with_line_number(true)
-        .pretty();
+        .with_line_number(true);
+    let console_layer = match log_format {
+        LogFormat::Json => console_layer
+            .with_ansi(false)
+            .json()
+            .flatten_event(true)
+            .boxed(),
+        LogFormat::Pretty => console_layer.with_ansi(true).pretty().boxed(),
+    };
 
     // Base filter for all logging
     let env_filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| {
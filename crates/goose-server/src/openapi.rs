@@ -349,6 +349,8 @@ derive_utoipa!(Icon as IconSchema);
         super::routes::agent::update_from_session,
         super::routes::agent::update_agent_provider,
         super::routes::agent::update_router_tool_selector,
+        super::routes::agent::list_operations,
+        super::routes::agent::cancel_operation,
         super::routes::reply::confirm_permission,
         super::routes::reply::reply,
         super::routes::session::list_sessions,
@@ -377,6 +379,7 @@ derive_utoipa!(Icon as IconSchema);
         super::routes::recipe::delete_recipe,
         super::routes::recipe::save_recipe,
         super::routes::recipe::parse_recipe,
+        super::routes::recipe::lint_recipe,
         super::routes::setup::start_openrouter_setup,
         super::routes::setup::start_tetrate_setup,
     ),
@@ -468,6 +471,10 @@ derive_utoipa!(Icon as IconSchema);
         super::routes::errors::ErrorResponse,
         super::routes::recipe::ParseRecipeRequest,
         super::routes::recipe::ParseRecipeResponse,
+        super::routes::recipe::LintRecipeRequest,
+        super::routes::recipe::LintRecipeResponse,
+        goose::recipe::LintDiagnostic,
+        goose::recipe::LintSeverity,
         goose::recipe::Recipe,
         goose::recipe::Author,
         goose::recipe::Settings,
@@ -478,12 +485,15 @@ derive_utoipa!(Icon as IconSchema);
         goose::recipe::SubRecipe,
         goose::agents::types::RetryConfig,
         goose::agents::types::SuccessCheck,
+        goose::agents::OperationInfo,
         super::routes::agent::UpdateProviderRequest,
         super::routes::agent::GetToolsQuery,
         super::routes::agent::UpdateRouterToolSelectorRequest,
         super::routes::agent::StartAgentRequest,
         super::routes::agent::ResumeAgentRequest,
         super::routes::agent::UpdateFromSessionRequest,
+        super::routes::agent::OperationsQuery,
+        super::routes::agent::CancelOperationRequest,
         super::routes::setup::SetupResponse,
     ))
 )]
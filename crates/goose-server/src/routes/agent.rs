@@ -11,7 +11,7 @@ use axum::{
 };
 use goose::config::PermissionManager;
 
-use goose::config::Config;
+use goose::config::{Config, GooseMode};
 use goose::model::ModelConfig;
 use goose::prompt_template::render_global_file;
 use goose::providers::{create, create_with_named_model};
@@ -19,7 +19,7 @@ use goose::recipe::Recipe;
 use goose::recipe_deeplink;
 use goose::session::{Session, SessionManager};
 use goose::{
-    agents::{extension::ToolInfo, extension_manager::get_parameter_names},
+    agents::{extension::ToolInfo, extension_manager::get_parameter_names, OperationInfo},
     config::permission::PermissionLevel,
 };
 use serde::Deserialize;
@@ -48,6 +48,17 @@ pub struct GetToolsQuery {
     session_id: String,
 }
 
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct OperationsQuery {
+    session_id: String,
+}
+
+#[derive(Deserialize, utoipa::ToSchema)]
+pub struct CancelOperationRequest {
+    session_id: String,
+    operation_id: String,
+}
+
 #[derive(Deserialize, utoipa::ToSchema)]
 pub struct UpdateRouterToolSelectorRequest {
     session_id: String,
@@ -229,25 +240,9 @@ async fn resume_agent(
 
         let extensions_result = async {
             let enabled_configs = goose::config::get_enabled_extensions();
-            let agent_clone = agent.clone();
-
-            let extension_futures = enabled_configs
-                .into_iter()
-                .map(|config| {
-                    let config_clone = config.clone();
-                    let agent_ref = agent_clone.clone();
-
-                    async move {
-                        if let Err(e) = agent_ref.add_extension(config_clone.clone()).await {
-                            warn!("Failed to load extension {}: {}", config_clone.name(), e);
-                        }
-                        Ok::<_, ErrorResponse>(())
-                    }
-                })
-                .collect::<Vec<_>>();
-
-            futures::future::join_all(extension_futures).await;
-            Ok::<(), ErrorResponse>(()) // Fixed type annotation
+            for (name, e) in agent.add_extensions(enabled_configs).await {
+                warn!("Failed to load extension {}: {}", name, e);
+            }
         };
 
         let (provider_result, _) = tokio::join!(provider_result, extensions_result);
@@ -295,8 +290,8 @@ async fn update_from_session(
         )
         .await
         {
-            Ok(Some(recipe)) => {
-                if let Some(prompt) = apply_recipe_to_agent(&agent, &recipe, true).await {
+            Ok(Some((recipe, secrets))) => {
+                if let Some(prompt) = apply_recipe_to_agent(&agent, &recipe, secrets, true).await {
                     update_prompt = prompt;
                 }
             }
@@ -334,8 +329,7 @@ async fn get_tools(
     State(state): State<Arc<AppState>>,
     Query(query): Query<GetToolsQuery>,
 ) -> Result<Json<Vec<ToolInfo>>, StatusCode> {
-    let config = Config::global();
-    let goose_mode = config.get_param("GOOSE_MODE").unwrap_or("auto".to_string());
+    let goose_mode = GooseMode::from_config();
     let agent = state.get_agent_for_route(query.session_id).await?;
     let permission_manager = PermissionManager::default();
 
@@ -346,14 +340,12 @@ async fn get_tools(
         .map(|tool| {
             let permission = permission_manager
                 .get_user_permission(&tool.name)
-                .or_else(|| {
-                    if goose_mode == "smart_approve" {
+                .or_else(|| match goose_mode {
+                    GooseMode::SmartApprove => {
                         permission_manager.get_smart_approve_permission(&tool.name)
-                    } else if goose_mode == "approve" {
-                        Some(PermissionLevel::AskBefore)
-                    } else {
-                        None
                     }
+                    GooseMode::Approve => Some(PermissionLevel::AskBefore),
+                    GooseMode::Auto | GooseMode::Chat => None,
                 });
 
             ToolInfo::new(
@@ -451,6 +443,49 @@ async fn update_router_tool_selector(
     ))
 }
 
+#[utoipa::path(
+    get,
+    path = "/agent/operations",
+    params(
+        ("session_id" = String, Query, description = "Session whose running replies to list")
+    ),
+    responses(
+        (status = 200, description = "Running replies of the session's agent", body = Vec<OperationInfo>),
+        (status = 401, description = "Unauthorized - invalid secret key"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn list_operations(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<OperationsQuery>,
+) -> Result<Json<Vec<OperationInfo>>, StatusCode> {
+    let agent = state.get_agent_for_route(query.session_id).await?;
+    Ok(Json(agent.active_operations()))
+}
+
+#[utoipa::path(
+    post,
+    path = "/agent/operations/cancel",
+    request_body = CancelOperationRequest,
+    responses(
+        (status = 200, description = "Operation cancelled"),
+        (status = 401, description = "Unauthorized - invalid secret key"),
+        (status = 404, description = "No such operation is running"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+async fn cancel_operation(
+    State(state): State<Arc<AppState>>,
+    Json(payload): Json<CancelOperationRequest>,
+) -> Result<StatusCode, StatusCode> {
+    let agent = state.get_agent_for_route(payload.session_id).await?;
+    if agent.cancel_operation(&payload.operation_id) {
+        Ok(StatusCode::OK)
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
+
 pub fn routes(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/agent/start", post(start_agent))
@@ -462,5 +497,7 @@ pub fn routes(state: Arc<AppState>) -> Router {
             post(update_router_tool_selector),
         )
         .route("/agent/update_from_session", post(update_from_session))
+        .route("/agent/operations", get(list_operations))
+        .route("/agent/operations/cancel", post(cancel_operation))
         .with_state(state)
 }
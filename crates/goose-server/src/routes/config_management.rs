@@ -318,34 +318,23 @@ pub async fn get_provider_models(
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    let model_config =
-        ModelConfig::new(&metadata.default_model).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let provider = goose::providers::create(&name, model_config)
-        .await
-        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-    match provider.fetch_supported_models().await {
-        Ok(Some(models)) => Ok(Json(models)),
-        Ok(None) => Ok(Json(Vec::new())),
-        Err(provider_error) => {
+    match goose::providers::list_models(&name).await {
+        Ok(models) => Ok(Json(models.into_iter().map(|m| m.name).collect())),
+        Err(e) => {
             use goose::providers::errors::ProviderError;
-            let status_code = match provider_error {
+            let status_code = match e.downcast_ref::<ProviderError>() {
                 // Permanent misconfigurations - client should fix configuration
-                ProviderError::Authentication(_) => StatusCode::BAD_REQUEST,
-                ProviderError::UsageError(_) => StatusCode::BAD_REQUEST,
+                Some(ProviderError::Authentication(_)) => StatusCode::BAD_REQUEST,
+                Some(ProviderError::UsageError(_)) => StatusCode::BAD_REQUEST,
 
                 // Transient errors - client should retry later
-                ProviderError::RateLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
+                Some(ProviderError::RateLimitExceeded { .. }) => StatusCode::TOO_MANY_REQUESTS,
 
                 // All other errors - internal server error
                 _ => StatusCode::INTERNAL_SERVER_ERROR,
             };
 
-            tracing::warn!(
-                "Provider {} failed to fetch models: {}",
-                name,
-                provider_error
-            );
+            tracing::warn!("Provider {} failed to fetch models: {}", name, e);
             Err(status_code)
         }
     }
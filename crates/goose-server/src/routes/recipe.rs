@@ -7,7 +7,7 @@ use axum::routing::get;
 use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
 use goose::recipe::local_recipes;
 use goose::recipe::validate_recipe::validate_recipe_template_from_content;
-use goose::recipe::Recipe;
+use goose::recipe::{lint, LintContext, LintDiagnostic, Recipe};
 use goose::recipe_deeplink;
 use goose::session::SessionManager;
 
@@ -114,6 +114,22 @@ pub struct ParseRecipeResponse {
     pub recipe: Recipe,
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct LintRecipeRequest {
+    pub content: String,
+    /// Directory relative sub-recipe paths are resolved against; they are not checked without it
+    #[serde(default)]
+    pub recipe_dir: Option<String>,
+    /// Parameter values the recipe would be run with
+    #[serde(default)]
+    pub params: HashMap<String, String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct LintRecipeResponse {
+    pub diagnostics: Vec<LintDiagnostic>,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 pub struct RecipeManifestResponse {
     recipe: Recipe,
@@ -432,6 +448,25 @@ async fn parse_recipe(
     Ok(Json(ParseRecipeResponse { recipe }))
 }
 
+#[utoipa::path(
+    post,
+    path = "/recipes/lint",
+    request_body = LintRecipeRequest,
+    responses(
+        (status = 200, description = "Recipe checked, with any problems found", body = LintRecipeResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    tag = "Recipe Management"
+)]
+async fn lint_recipe(Json(request): Json<LintRecipeRequest>) -> Json<LintRecipeResponse> {
+    let context = LintContext {
+        recipe_dir: request.recipe_dir.map(std::path::PathBuf::from),
+        params: request.params,
+    };
+    let diagnostics = lint(&request.content, &context).await;
+    Json(LintRecipeResponse { diagnostics })
+}
+
 pub fn routes(state: Arc<AppState>) -> Router {
     Router::new()
         .route("/recipes/create", post(create_recipe))
@@ -442,6 +477,7 @@ pub fn routes(state: Arc<AppState>) -> Router {
         .route("/recipes/delete", post(delete_recipe))
         .route("/recipes/save", post(save_recipe))
         .route("/recipes/parse", post(parse_recipe))
+        .route("/recipes/lint", post(lint_recipe))
         .with_state(state)
 }
 
@@ -12,10 +12,10 @@ use crate::routes::errors::ErrorResponse;
 use crate::state::AppState;
 use goose::agents::Agent;
 use goose::prompt_template::render_global_file;
-use goose::recipe::build_recipe::{build_recipe_from_template, RecipeError};
+use goose::recipe::build_recipe::{build_recipe_with_secrets_from_template, RecipeError};
 use goose::recipe::local_recipes::{get_recipe_library_dir, list_local_recipes};
 use goose::recipe::validate_recipe::validate_recipe_template_from_content;
-use goose::recipe::Recipe;
+use goose::recipe::{Recipe, RecipeParameter, RecipeSecrets};
 use serde_json::Value;
 use serde_yaml;
 use tracing::error;
@@ -131,19 +131,19 @@ pub async fn load_recipe_by_id(state: &AppState, id: &str) -> Result<Recipe, Err
 pub async fn build_recipe_with_parameter_values(
     original_recipe: &Recipe,
     user_recipe_values: HashMap<String, String>,
-) -> Result<Option<Recipe>> {
+) -> Result<Option<(Recipe, RecipeSecrets)>> {
     let recipe_content = serde_yaml::to_string(&original_recipe)?;
 
     let recipe_dir = get_recipe_library_dir(true);
     let params = user_recipe_values.into_iter().collect();
 
-    let recipe = match build_recipe_from_template(
+    let recipe = match build_recipe_with_secrets_from_template(
         recipe_content,
         &recipe_dir,
         params,
-        None::<fn(&str, &str) -> Result<String, anyhow::Error>>,
+        None::<fn(&RecipeParameter) -> Result<String, anyhow::Error>>,
     ) {
-        Ok(recipe) => Some(recipe),
+        Ok(recipe_with_secrets) => Some(recipe_with_secrets),
         Err(RecipeError::MissingParams { .. }) => None,
         Err(e) => return Err(anyhow::anyhow!(e)),
     };
@@ -154,12 +154,25 @@ pub async fn build_recipe_with_parameter_values(
 pub async fn apply_recipe_to_agent(
     agent: &Arc<Agent>,
     recipe: &Recipe,
+    secrets: RecipeSecrets,
     include_final_output_tool: bool,
 ) -> Option<String> {
+    if !secrets.is_empty() {
+        agent.set_recipe_secrets(secrets).await;
+    }
+
     if let Some(sub_recipes) = &recipe.sub_recipes {
         agent.add_sub_recipes(sub_recipes.clone()).await;
     }
 
+    if let Some(limits) = recipe
+        .settings
+        .as_ref()
+        .and_then(|s| s.tool_rate_limits.as_ref())
+    {
+        agent.set_tool_rate_limits(limits);
+    }
+
     if include_final_output_tool {
         if let Some(response) = &recipe.response {
             agent.add_final_output_tool(response.clone()).await;
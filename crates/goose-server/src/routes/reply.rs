@@ -11,6 +11,7 @@ use futures::{stream::StreamExt, Stream};
 use goose::conversation::message::{Message, MessageContent};
 use goose::conversation::Conversation;
 use goose::permission::{Permission, PermissionConfirmation};
+use goose::providers::base::{StopReason, REQUEST_METADATA_RECIPE_NAME};
 use goose::session::SessionManager;
 use goose::{
     agents::{AgentEvent, SessionConfig},
@@ -20,6 +21,7 @@ use rmcp::model::ServerNotification;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::{
+    collections::HashMap,
     convert::Infallible,
     pin::Pin,
     sync::Arc,
@@ -85,6 +87,9 @@ pub struct ChatRequest {
     session_id: String,
     recipe_name: Option<String>,
     recipe_version: Option<String>,
+    /// Attached to every provider request for attribution, e.g. {"user_id": "..."}
+    #[serde(default)]
+    request_metadata: HashMap<String, String>,
 }
 
 pub struct SseResponse {
@@ -137,6 +142,9 @@ pub enum MessageEvent {
         model: String,
         mode: String,
     },
+    StopReason {
+        reason: StopReason,
+    },
     Notification {
         request_id: String,
         message: ServerNotification,
@@ -208,6 +216,13 @@ pub async fn reply(
         }
     }
 
+    let mut request_metadata = request.request_metadata;
+    if let Some(recipe_name) = request.recipe_name {
+        request_metadata
+            .entry(REQUEST_METADATA_RECIPE_NAME.to_string())
+            .or_insert(recipe_name);
+    }
+
     let (tx, rx) = mpsc::channel(100);
     let stream = ReceiverStream::new(rx);
     let cancel_token = CancellationToken::new();
@@ -256,7 +271,10 @@ pub async fn reply(
             schedule_id: session.schedule_id.clone(),
             execution_mode: None,
             max_turns: None,
+            max_tool_calls_per_turn: None,
             retry_config: None,
+            request_metadata,
+            dry_run: false,
         };
 
         let mut stream = match agent
@@ -312,6 +330,9 @@ pub async fn reply(
                         Ok(Some(Ok(AgentEvent::ModelChange { model, mode }))) => {
                             stream_event(MessageEvent::ModelChange { model, mode }, &tx, &cancel_token).await;
                         }
+                        Ok(Some(Ok(AgentEvent::StopReason(reason)))) => {
+                            stream_event(MessageEvent::StopReason { reason }, &tx, &cancel_token).await;
+                        }
                         Ok(Some(Ok(AgentEvent::McpNotification((request_id, n))))) => {
                             stream_event(MessageEvent::Notification{
                                 request_id: request_id.clone(),
@@ -412,6 +433,9 @@ pub struct PermissionConfirmationRequest {
     #[serde(default = "default_principal_type")]
     principal_type: PrincipalType,
     action: String,
+    /// How long `allow_for` trusts the tool, 10 minutes if not given
+    #[serde(default)]
+    minutes: Option<u32>,
     session_id: String,
 }
 
@@ -437,6 +461,7 @@ pub async fn confirm_permission(
     let permission = match request.action.as_str() {
         "always_allow" => Permission::AlwaysAllow,
         "allow_once" => Permission::AllowOnce,
+        "allow_for" => Permission::AllowForMinutes(request.minutes.unwrap_or(10)),
         "deny" => Permission::DenyOnce,
         _ => Permission::DenyOnce,
     };
@@ -490,6 +515,7 @@ mod tests {
                         session_id: "test-session".to_string(),
                         recipe_name: None,
                         recipe_version: None,
+                        request_metadata: HashMap::new(),
                     })
                     .unwrap(),
                 ))
@@ -9,7 +9,7 @@ use axum::{
     routing::{delete, get, put},
     Json, Router,
 };
-use goose::recipe::Recipe;
+use goose::recipe::{without_secret_values, Recipe};
 use goose::session::session_manager::SessionInsights;
 use goose::session::{Session, SessionManager};
 use serde::{Deserialize, Serialize};
@@ -176,29 +176,33 @@ async fn update_session_user_recipe_values(
     Path(session_id): Path<String>,
     Json(request): Json<UpdateSessionUserRecipeValuesRequest>,
 ) -> Result<Json<UpdateSessionUserRecipeValuesResponse>, ErrorResponse> {
-    SessionManager::update_session(&session_id)
-        .user_recipe_values(Some(request.user_recipe_values))
-        .apply()
+    let session = SessionManager::get_session(&session_id, false)
         .await
         .map_err(|err| ErrorResponse {
             message: err.to_string(),
             status: StatusCode::INTERNAL_SERVER_ERROR,
         })?;
+    let recipe = session.recipe.ok_or_else(|| ErrorResponse {
+        message: "Recipe not found".to_string(),
+        status: StatusCode::NOT_FOUND,
+    })?;
 
-    let session = SessionManager::get_session(&session_id, false)
+    // Secret values are used for this build only and never stored with the session
+    let user_recipe_values = request.user_recipe_values;
+    SessionManager::update_session(&session_id)
+        .user_recipe_values(Some(without_secret_values(
+            recipe.parameters.as_deref(),
+            user_recipe_values.clone(),
+        )))
+        .apply()
         .await
         .map_err(|err| ErrorResponse {
             message: err.to_string(),
             status: StatusCode::INTERNAL_SERVER_ERROR,
         })?;
-    let recipe = session.recipe.ok_or_else(|| ErrorResponse {
-        message: "Recipe not found".to_string(),
-        status: StatusCode::NOT_FOUND,
-    })?;
 
-    let user_recipe_values = session.user_recipe_values.unwrap_or_default();
     match build_recipe_with_parameter_values(&recipe, user_recipe_values).await {
-        Ok(Some(recipe)) => {
+        Ok(Some((recipe, secrets))) => {
             let agent = state
                 .get_agent_for_route(session_id.clone())
                 .await
@@ -206,7 +210,7 @@ async fn update_session_user_recipe_values(
                     message: format!("Failed to get agent: {}", status),
                     status,
                 })?;
-            if let Some(prompt) = apply_recipe_to_agent(&agent, &recipe, false).await {
+            if let Some(prompt) = apply_recipe_to_agent(&agent, &recipe, secrets, false).await {
                 agent.extend_system_prompt(prompt).await;
             }
             Ok(Json(UpdateSessionUserRecipeValuesResponse { recipe }))
@@ -9,9 +9,13 @@ use futures::{stream, FutureExt, Stream, StreamExt, TryStreamExt};
 use uuid::Uuid;
 
 use crate::agents::extension::{ExtensionConfig, ExtensionError, ExtensionResult, ToolInfo};
-use crate::agents::extension_manager::{get_parameter_names, ExtensionManager};
+use crate::agents::extension_manager::{
+    extension_startup_concurrency, get_parameter_names, ExtensionManager,
+};
 use crate::agents::extension_manager_extension::MANAGE_EXTENSIONS_TOOL_NAME_COMPLETE;
+use crate::agents::extension_registry;
 use crate::agents::final_output_tool::{FINAL_OUTPUT_CONTINUATION_MESSAGE, FINAL_OUTPUT_TOOL_NAME};
+use crate::agents::operations::{OperationInfo, OperationRegistry};
 use crate::agents::platform_tools::PLATFORM_MANAGE_SCHEDULE_TOOL_NAME;
 use crate::agents::prompt_manager::PromptManager;
 use crate::agents::recipe_tools::dynamic_task_tools::{
@@ -22,26 +26,31 @@ use crate::agents::router_tools::ROUTER_LLM_SEARCH_TOOL_NAME;
 use crate::agents::sub_recipe_manager::SubRecipeManager;
 use crate::agents::subagent_execution_tool::lib::ExecutionMode;
 use crate::agents::subagent_execution_tool::subagent_execute_task_tool::{
-    self, SUBAGENT_EXECUTE_TASK_TOOL_NAME,
+    self, SUBAGENT_EXECUTE_TASK_TOOL_NAME, SUBAGENT_RESPONSES_IN_CONVERSATION_KEY,
 };
 use crate::agents::subagent_execution_tool::tasks_manager::TasksManager;
 use crate::agents::tool_route_manager::ToolRouteManager;
 use crate::agents::tool_router_index_manager::ToolRouterIndexManager;
 use crate::agents::types::SessionConfig;
-use crate::agents::types::{FrontendTool, ToolResultReceiver};
-use crate::config::{get_enabled_extensions, Config};
-use crate::context_mgmt::DEFAULT_COMPACTION_THRESHOLD;
+use crate::agents::types::{FrontendTool, PromptDescriptor, ToolResultReceiver};
+use crate::config::{get_enabled_extensions, Config, GooseMode};
+use crate::context_mgmt::{CompactionStrategy, DEFAULT_COMPACTION_THRESHOLD};
 use crate::conversation::{debug_conversation_fix, fix_conversation, Conversation};
 use crate::mcp_utils::ToolResult;
+use crate::permission::permission_confirmation::PrincipalType;
 use crate::permission::permission_inspector::PermissionInspector;
 use crate::permission::permission_judge::PermissionCheckResult;
-use crate::permission::PermissionConfirmation;
-use crate::providers::base::Provider;
+use crate::permission::{DecisionSource, Permission, PermissionConfirmation, PermissionDecision};
+use crate::providers::base::{Provider, StopReason};
 use crate::providers::errors::ProviderError;
-use crate::recipe::{Author, Recipe, Response, Settings, SubRecipe};
+use crate::providers::with_retry_notices;
+use crate::recipe::{Author, Recipe, RecipeSecrets, Response, Settings, SubRecipe};
 use crate::scheduler_trait::SchedulerTrait;
+use crate::security::output_scanner::{injection_warning, scan_tool_output, ToolOutputScanMode};
 use crate::security::security_inspector::SecurityInspector;
-use crate::tool_inspection::ToolInspectionManager;
+use crate::tool_inspection::{
+    get_decision_source_from_results, InspectionAction, InspectionResult, ToolInspectionManager,
+};
 use crate::tool_monitor::RepetitionInspector;
 use crate::utils::is_token_cancelled;
 use regex::Regex;
@@ -54,16 +63,30 @@ use tokio::sync::{mpsc, Mutex};
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, warn};
 
+use super::content_filter::{ContentFilter, ContentFilters};
 use super::final_output_tool::FinalOutputTool;
 use super::model_selector::autopilot::AutoPilot;
 use super::platform_tools;
-use super::tool_execution::{ToolCallResult, CHAT_MODE_TOOL_SKIPPED_RESPONSE, DECLINED_RESPONSE};
-use crate::agents::subagent_task_config::TaskConfig;
+use super::tool_execution::{
+    dry_run_response, take_within_budget, tool_call_budget_response, ToolCallResult,
+    CHAT_MODE_TOOL_SKIPPED_RESPONSE, DECLINED_RESPONSE, MAX_TOOL_CALLS_PER_TURN_KEY,
+    REPEATED_TOOL_CALL_RESPONSE,
+};
+use super::tool_rate_limiter::ToolRateLimiter;
+use super::tool_result_cache::{is_cacheable, is_read_only, ToolResultCache};
+use crate::agents::subagent_task_config::{SubagentApprovalRequest, TaskConfig};
 use crate::conversation::message::{Message, MessageContent, SystemNotificationType, ToolRequest};
 use crate::session::extension_data::{EnabledExtensionsState, ExtensionState};
-use crate::session::SessionManager;
+use crate::session::{autosave_interval, Autosave, SessionManager};
 
 const DEFAULT_MAX_TURNS: u32 = 1000;
+/// How many times in a row the agent compacts and retries after the provider reports that the
+/// context is too long, before giving up
+const MAX_RECOVERY_COMPACTIONS: u32 = 3;
+/// The number of identical tool calls in a row at which the latest one is refused instead of
+/// run; 0 turns the check off
+const TOOL_REPETITION_LIMIT_KEY: &str = "GOOSE_TOOL_REPETITION_LIMIT";
+const DEFAULT_TOOL_REPETITION_LIMIT: u32 = 3;
 const COMPACTION_THINKING_TEXT: &str = "goose is compacting the conversation...";
 const MANUAL_COMPACT_TRIGGER: &str = "Please compact this conversation";
 
@@ -73,7 +96,7 @@ pub struct ReplyContext {
     pub tools: Vec<Tool>,
     pub toolshim_tools: Vec<Tool>,
     pub system_prompt: String,
-    pub goose_mode: String,
+    pub goose_mode: GooseMode,
     pub initial_messages: Vec<Message>,
     pub config: &'static Config,
 }
@@ -98,20 +121,33 @@ pub struct Agent {
     pub(super) confirmation_rx: Mutex<mpsc::Receiver<(String, PermissionConfirmation)>>,
     pub(super) tool_result_tx: mpsc::Sender<(String, ToolResult<Vec<Content>>)>,
     pub(super) tool_result_rx: ToolResultReceiver,
+    pub(super) subagent_approval_tx: mpsc::Sender<SubagentApprovalRequest>,
+    pub(super) subagent_approval_rx: Mutex<mpsc::Receiver<SubagentApprovalRequest>>,
 
     pub tool_route_manager: Arc<ToolRouteManager>,
     pub(super) scheduler_service: Mutex<Option<Arc<dyn SchedulerTrait>>>,
     pub(super) retry_manager: RetryManager,
     pub(super) tool_inspection_manager: ToolInspectionManager,
     pub(super) autopilot: Mutex<AutoPilot>,
+    pub(super) tool_result_cache: Arc<ToolResultCache>,
+    pub(super) tool_rate_limiter: ToolRateLimiter,
+    pub(super) content_filters: ContentFilters,
+    /// Parent of the cancellation token of every reply, cancelled by `emergency_stop`
+    pub(super) stop_token: std::sync::Mutex<CancellationToken>,
+    pub(super) operations: Arc<OperationRegistry>,
 }
 
 #[derive(Clone, Debug)]
 pub enum AgentEvent {
     Message(Message),
     McpNotification((String, ServerNotification)),
-    ModelChange { model: String, mode: String },
+    ModelChange {
+        model: String,
+        mode: String,
+    },
     HistoryReplaced(Conversation),
+    /// Why the model stopped its last response, e.g. whether it was cut off by the token limit
+    StopReason(StopReason),
 }
 
 impl Default for Agent {
@@ -125,6 +161,14 @@ pub enum ToolStreamItem<T> {
     Result(T),
 }
 
+/// Events the reply loop waits on while tool calls are running
+enum ToolExecutionEvent {
+    Tool(String, ToolStreamItem<ToolResult<Vec<Content>>>),
+    SubagentApproval(SubagentApprovalRequest),
+    /// Time to save the messages of the turn so far
+    Autosave,
+}
+
 pub type ToolStream = Pin<Box<dyn Stream<Item = ToolStreamItem<ToolResult<Vec<Content>>>> + Send>>;
 
 // tool_stream combines a stream of ServerNotifications with a future representing the
@@ -159,6 +203,7 @@ impl Agent {
         // Create channels with buffer size 32 (adjust if needed)
         let (confirm_tx, confirm_rx) = mpsc::channel(32);
         let (tool_tx, tool_rx) = mpsc::channel(32);
+        let (subagent_approval_tx, subagent_approval_rx) = mpsc::channel(32);
 
         Self {
             provider: Mutex::new(None),
@@ -173,11 +218,18 @@ impl Agent {
             confirmation_rx: Mutex::new(confirm_rx),
             tool_result_tx: tool_tx,
             tool_result_rx: Arc::new(Mutex::new(tool_rx)),
+            subagent_approval_tx,
+            subagent_approval_rx: Mutex::new(subagent_approval_rx),
             tool_route_manager: Arc::new(ToolRouteManager::new()),
             scheduler_service: Mutex::new(None),
             retry_manager: RetryManager::new(),
             tool_inspection_manager: Self::create_default_tool_inspection_manager(),
             autopilot: Mutex::new(AutoPilot::new()),
+            tool_result_cache: Arc::new(ToolResultCache::from_config()),
+            tool_rate_limiter: ToolRateLimiter::from_config(),
+            content_filters: ContentFilters::from_config(),
+            stop_token: std::sync::Mutex::new(CancellationToken::new()),
+            operations: Arc::new(OperationRegistry::default()),
         }
     }
 
@@ -191,17 +243,98 @@ impl Agent {
         // Add permission inspector (medium-high priority)
         // Note: mode will be updated dynamically based on session config
         tool_inspection_manager.add_inspector(Box::new(PermissionInspector::new(
-            "smart_approve".to_string(),
+            GooseMode::SmartApprove,
             std::collections::HashSet::new(), // readonly tools - will be populated from extension manager
             std::collections::HashSet::new(), // regular tools - will be populated from extension manager
         )));
 
         // Add repetition inspector (lower priority - basic repetition checking)
-        tool_inspection_manager.add_inspector(Box::new(RepetitionInspector::new(None)));
+        let repetition_limit = Config::global()
+            .get_param::<u32>(TOOL_REPETITION_LIMIT_KEY)
+            .unwrap_or(DEFAULT_TOOL_REPETITION_LIMIT);
+        // The limit counts the call being made, so allow one fewer before it
+        let max_repetitions = (repetition_limit > 0).then(|| repetition_limit.saturating_sub(1));
+        tool_inspection_manager.add_inspector(Box::new(RepetitionInspector::new(max_repetitions)));
 
         tool_inspection_manager
     }
 
+    /// Stop everything this agent is doing: cancel the in-flight tool calls of every running reply,
+    /// which tells extensions to abort the requests, and cancel the subagents those replies
+    /// spawned. Tool calls cut short this way are repaired like any interrupted call when the
+    /// conversation continues, and replies started afterwards are not affected.
+    pub fn emergency_stop(&self) {
+        let stopped = std::mem::take(&mut *self.stop_token.lock().unwrap());
+        warn!("Emergency stop requested, cancelling all running work");
+        stopped.cancel();
+    }
+
+    /// Calls per minute allowed for each of these extensions, on top of and overriding those
+    /// from `GOOSE_TOOL_RATE_LIMITS`. Calls over a limit wait for their turn; 0 removes a limit.
+    pub fn set_tool_rate_limits(&self, calls_per_minute: &HashMap<String, u32>) {
+        self.tool_rate_limiter.set_limits(calls_per_minute);
+    }
+
+    /// Delay the start of a tool call until the rate limit of its extension allows it
+    fn throttle(&self, tool_name: &str, result: ToolCallResult) -> ToolCallResult {
+        let Some(slot) = self.tool_rate_limiter.reserve(tool_name) else {
+            return result;
+        };
+        let delay = slot.saturating_duration_since(tokio::time::Instant::now());
+        if !delay.is_zero() {
+            debug!("Rate limiting {}, starting in {:?}", tool_name, delay);
+        }
+        let call = result.result;
+        ToolCallResult {
+            notification_stream: result.notification_stream,
+            result: Box::new(
+                async move {
+                    tokio::time::sleep_until(slot).await;
+                    call.await
+                }
+                .boxed(),
+            ),
+        }
+    }
+
+    /// Run a filter over the arguments and results of every tool call, after the filters from
+    /// the config and those added before
+    pub fn add_content_filter(&self, filter: Arc<dyn ContentFilter>) {
+        self.content_filters.add(filter);
+    }
+
+    /// The replies this agent is running, oldest first
+    pub fn active_operations(&self) -> Vec<OperationInfo> {
+        self.operations.list()
+    }
+
+    /// Cancel one running reply, like dropping its stream: its tool calls and subagents are
+    /// cancelled and the interrupted calls are repaired when the conversation continues.
+    /// Returns false if no reply with this id is running, e.g. because it already finished.
+    pub fn cancel_operation(&self, id: &str) -> bool {
+        let cancelled = self.operations.cancel(id);
+        if cancelled {
+            info!("Cancelled operation {}", id);
+        }
+        cancelled
+    }
+
+    /// A token for one reply that is cancelled by the caller's token or by `emergency_stop`.
+    /// It is also cancelled when the reply stream is dropped, see `reply`.
+    fn reply_cancel_token(&self, cancel_token: Option<CancellationToken>) -> CancellationToken {
+        let reply_token = self.stop_token.lock().unwrap().child_token();
+        if let Some(cancel_token) = cancel_token {
+            let linked = reply_token.clone();
+            tokio::spawn(async move {
+                tokio::select! {
+                    _ = cancel_token.cancelled() => linked.cancel(),
+                    _ = linked.cancelled() => {}
+                }
+            });
+        }
+        reply_token
+    }
+
     /// Reset the retry attempts counter to 0
     pub async fn reset_retry_attempts(&self) {
         self.retry_manager.reset_attempts().await;
@@ -258,11 +391,11 @@ impl Agent {
         let config = Config::global();
 
         let (tools, toolshim_tools, system_prompt) = self.prepare_tools_and_prompt().await?;
-        let goose_mode = Self::determine_goose_mode(session.as_ref(), config);
+        let goose_mode = Self::determine_goose_mode(session.as_ref());
 
         // Update permission inspector mode to match the session mode
         self.tool_inspection_manager
-            .update_permission_inspector_mode(goose_mode.clone())
+            .update_permission_inspector_mode(goose_mode)
             .await;
 
         Ok(ReplyContext {
@@ -295,6 +428,7 @@ impl Agent {
     async fn handle_approved_and_denied_tools(
         &self,
         permission_check_result: &PermissionCheckResult,
+        inspection_results: &[InspectionResult],
         message_tool_response: Arc<Mutex<Message>>,
         cancel_token: Option<tokio_util::sync::CancellationToken>,
         session: Option<SessionConfig>,
@@ -304,6 +438,13 @@ impl Agent {
         // Handle pre-approved and read-only tools
         for request in &permission_check_result.approved {
             if let Ok(tool_call) = request.tool_call.clone() {
+                self.record_automatic_decision(
+                    session.as_ref(),
+                    request,
+                    Permission::AllowOnce,
+                    inspection_results,
+                )
+                .await;
                 let (req_id, tool_result) = self
                     .dispatch_tool_call(
                         tool_call,
@@ -332,16 +473,66 @@ impl Agent {
 
         // Handle denied tools
         for request in &permission_check_result.denied {
+            self.record_automatic_decision(
+                session.as_ref(),
+                request,
+                Permission::DenyOnce,
+                inspection_results,
+            )
+            .await;
+            let repeated = inspection_results.iter().any(|result| {
+                result.tool_request_id == request.id
+                    && result.inspector_name == "repetition"
+                    && result.action == InspectionAction::Deny
+            });
+            let text = if repeated {
+                REPEATED_TOOL_CALL_RESPONSE
+            } else {
+                DECLINED_RESPONSE
+            };
             let mut response = message_tool_response.lock().await;
             *response = response.clone().with_tool_response(
                 request.id.clone(),
-                Ok(vec![rmcp::model::Content::text(DECLINED_RESPONSE)]),
+                Ok(vec![rmcp::model::Content::text(text)]),
             );
         }
 
         Ok(tool_futures)
     }
 
+    async fn record_automatic_decision(
+        &self,
+        session: Option<&SessionConfig>,
+        request: &ToolRequest,
+        permission: Permission,
+        inspection_results: &[InspectionResult],
+    ) {
+        let Ok(tool_call) = &request.tool_call else {
+            return;
+        };
+        let confirmation = PermissionConfirmation {
+            principal_type: PrincipalType::Tool,
+            permission,
+        };
+        let source = get_decision_source_from_results(&request.id, inspection_results);
+        self.record_permission_decision(
+            session,
+            PermissionDecision::new(
+                request.id.clone(),
+                tool_call.name.to_string(),
+                Value::Object(tool_call.arguments.clone().unwrap_or_default()),
+                &confirmation,
+                source,
+            ),
+        )
+        .await;
+    }
+
+    /// The tool calls a session allowed and denied, oldest first, with what decided each of them
+    pub async fn permission_decisions(&self, session_id: &str) -> Result<Vec<PermissionDecision>> {
+        SessionManager::list_permission_decisions(session_id).await
+    }
+
     /// Set the scheduler service for this agent
     pub async fn set_scheduler(&self, scheduler: Arc<dyn SchedulerTrait>) {
         let mut scheduler_service = self.scheduler_service.lock().await;
@@ -383,6 +574,11 @@ impl Agent {
         sub_recipe_manager.add_sub_recipe_tools(sub_recipes);
     }
 
+    /// Pass the secret parameter values of the session's recipe to the extensions it starts
+    pub async fn set_recipe_secrets(&self, secrets: RecipeSecrets) {
+        self.extension_manager.set_recipe_secrets(secrets).await;
+    }
+
     /// Dispatch a single tool call to the appropriate client
     #[instrument(skip(self, tool_call, request_id), fields(input, output))]
     pub async fn dispatch_tool_call(
@@ -392,6 +588,11 @@ impl Agent {
         cancellation_token: Option<CancellationToken>,
         session: Option<SessionConfig>,
     ) -> (String, Result<ToolCallResult, ErrorData>) {
+        let tool_call = match self.content_filters.filter_input(tool_call) {
+            Ok(tool_call) => tool_call,
+            Err(e) => return (request_id, Err(e)),
+        };
+
         if tool_call.name == PLATFORM_MANAGE_SCHEDULE_TOOL_NAME {
             let arguments = tool_call
                 .arguments
@@ -470,7 +671,8 @@ impl Agent {
             let extensions = self.get_extension_configs().await;
 
             let task_config =
-                TaskConfig::new(provider, parent_session_id, parent_working_dir, extensions);
+                TaskConfig::new(provider, parent_session_id, parent_working_dir, extensions)
+                    .with_approval_channel(self.subagent_approval_tx.clone());
 
             let arguments = match tool_call.arguments.clone() {
                 Some(args) => Value::Object(args),
@@ -553,19 +755,51 @@ impl Agent {
                 Ok(tool_result) => tool_result,
                 Err(e) => return (request_id, Err(e)),
             }
+        } else if let Some(cached) = self.tool_result_cache.get(&tool_call) {
+            debug!("Returning cached result for {}", tool_call.name);
+            ToolCallResult::from(Ok(cached))
         } else {
+            let (cacheable, read_only) = if self.tool_result_cache.is_enabled() {
+                let tool = self.extension_manager.get_tool(&tool_call.name).await;
+                (
+                    is_cacheable(&tool_call.name),
+                    tool.as_ref().is_some_and(is_read_only),
+                )
+            } else {
+                (false, true)
+            };
             // Clone the result to ensure no references to extension_manager are returned
             let result = self
                 .extension_manager
                 .dispatch_tool_call(tool_call.clone(), cancellation_token.unwrap_or_default())
-                .await;
-            result.unwrap_or_else(|e| {
-                ToolCallResult::from(Err(ErrorData::new(
+                .await
+                .map(|result| self.throttle(&tool_call.name, result));
+            match result {
+                Ok(result) if cacheable || !read_only => {
+                    let cache = self.tool_result_cache.clone();
+                    let cached_call = tool_call.clone();
+                    ToolCallResult {
+                        notification_stream: result.notification_stream,
+                        result: Box::new(result.result.map(move |output| {
+                            if let Ok(contents) = &output {
+                                if cacheable {
+                                    cache.insert(&cached_call, contents.clone());
+                                } else {
+                                    // The call may have changed what the cached results show
+                                    cache.clear();
+                                }
+                            }
+                            output
+                        })),
+                    }
+                }
+                Ok(result) => result,
+                Err(e) => ToolCallResult::from(Err(ErrorData::new(
                     ErrorCode::INTERNAL_ERROR,
                     e.to_string(),
                     None,
-                )))
-            })
+                ))),
+            }
         };
 
         debug!("WAITING_TOOL_END: {}", tool_call.name);
@@ -577,6 +811,7 @@ impl Agent {
                 result: Box::new(
                     result
                         .result
+                        .map(self.content_filters.output_filter())
                         .map(super::large_response_handler::process_tool_response),
                 ),
             }),
@@ -605,6 +840,29 @@ impl Agent {
         Ok(())
     }
 
+    /// Start `extensions` concurrently, at most `GOOSE_EXTENSION_STARTUP_CONCURRENCY` at a time.
+    /// An extension that fails to start does not hold up the others; the failures are returned
+    /// together, in the order the extensions were given.
+    pub async fn add_extensions(
+        &self,
+        extensions: Vec<ExtensionConfig>,
+    ) -> Vec<(String, ExtensionError)> {
+        let mut failures: Vec<(usize, String, ExtensionError)> =
+            stream::iter(extensions.into_iter().enumerate())
+                .map(|(index, extension)| async move {
+                    let name = extension.name();
+                    (index, name, self.add_extension(extension).await)
+                })
+                .buffer_unordered(extension_startup_concurrency())
+                .filter_map(|(index, name, result)| async move {
+                    result.err().map(|e| (index, name, e))
+                })
+                .collect()
+                .await;
+        failures.sort_by_key(|(index, _, _)| *index);
+        failures.into_iter().map(|(_, name, e)| (name, e)).collect()
+    }
+
     pub async fn add_extension(&self, extension: ExtensionConfig) -> ExtensionResult<()> {
         match &extension {
             ExtensionConfig::Frontend {
@@ -664,6 +922,16 @@ impl Agent {
         Ok(())
     }
 
+    /// Look up an extension in the registry by id and add it, returning the config that was used
+    pub async fn install_extension_from_registry(
+        &self,
+        id: &str,
+    ) -> ExtensionResult<ExtensionConfig> {
+        let entry = extension_registry::find_extension(id).await?;
+        self.add_extension(entry.config.clone()).await?;
+        Ok(entry.config)
+    }
+
     pub async fn list_tools(&self, extension_name: Option<String>) -> Vec<Tool> {
         let mut prefixed_tools = self
             .extension_manager
@@ -718,6 +986,39 @@ impl Agent {
         Ok(())
     }
 
+    /// Hide an extension's tools from the model and refuse calls to them until
+    /// `enable_extension`, without disconnecting it. Takes effect from the next model request.
+    pub async fn disable_extension(&self, name: &str) -> Result<()> {
+        // The router finds the tools to unindex through the extension, so do this first
+        self.update_router_index(name, "remove").await?;
+        self.extension_manager
+            .set_extension_enabled(name, false)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn enable_extension(&self, name: &str) -> Result<()> {
+        self.extension_manager
+            .set_extension_enabled(name, true)
+            .await?;
+        self.update_router_index(name, "add").await
+    }
+
+    async fn update_router_index(&self, extension_name: &str, action: &str) -> Result<()> {
+        if self.tool_route_manager.is_router_functional().await {
+            if let Some(selector) = self.tool_route_manager.get_router_tool_selector().await {
+                ToolRouterIndexManager::update_extension_tools(
+                    &selector,
+                    &self.extension_manager,
+                    extension_name,
+                    action,
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
     pub async fn list_extensions(&self) -> Vec<String> {
         self.extension_manager
             .list_extensions()
@@ -740,12 +1041,144 @@ impl Agent {
         }
     }
 
+    /// Truncate a stored session to its first `message_count` messages and continue from there,
+    /// e.g. to reproduce a problem with different settings. The truncated conversation is sent
+    /// as a `HistoryReplaced` event before the new reply.
+    #[instrument(skip(self, session, cancel_token), fields(session_id = %session.id))]
+    pub async fn replay_from(
+        &self,
+        session: SessionConfig,
+        message_count: usize,
+        cancel_token: Option<CancellationToken>,
+    ) -> Result<BoxStream<'_, Result<AgentEvent>>> {
+        let stored = SessionManager::get_session(&session.id, true).await?;
+        let last_message = stored
+            .conversation
+            .as_ref()
+            .zip(message_count.checked_sub(1))
+            .and_then(|(conversation, index)| conversation.messages().get(index));
+        if !last_message.is_some_and(|message| message.role == rmcp::model::Role::User) {
+            return Err(anyhow!(
+                "Cannot replay session {} from message {}: the conversation must end with a user message",
+                session.id,
+                message_count
+            ));
+        }
+
+        let conversation =
+            SessionManager::truncate_conversation(&session.id, message_count).await?;
+        let mut reply_stream = self
+            .reply(conversation.clone(), Some(session), cancel_token)
+            .await?;
+
+        Ok(Box::pin(async_stream::try_stream! {
+            yield AgentEvent::HistoryReplaced(conversation);
+            while let Some(event) = reply_stream.next().await {
+                yield event?;
+            }
+        }))
+    }
+
+    /// Run a tool call from a stored session again against the current extensions, e.g. to
+    /// check whether a tool that started failing still fails with the same arguments. The call
+    /// goes through the same inspectors and permission checks as a call from the model, and as
+    /// there is no one to ask here, it is refused unless it is approved without asking. The
+    /// session is left unchanged.
+    pub async fn replay_tool_call(
+        &self,
+        session: SessionConfig,
+        message_id: &str,
+        tool_request_id: &str,
+    ) -> Result<ToolResult<Vec<Content>>> {
+        let stored = SessionManager::get_session(&session.id, true).await?;
+        let request = stored
+            .conversation
+            .as_ref()
+            .and_then(|conversation| conversation.find_tool_request(message_id, tool_request_id))
+            .ok_or_else(|| {
+                anyhow!(
+                    "No tool request {} in message {} of session {}",
+                    tool_request_id,
+                    message_id,
+                    session.id
+                )
+            })?;
+        let tool_call = request.tool_call.clone().map_err(|e| {
+            anyhow!(
+                "Tool request {} is not a valid call: {}",
+                tool_request_id,
+                e
+            )
+        })?;
+
+        let goose_mode = Self::determine_goose_mode(Some(&session));
+        if goose_mode == GooseMode::Chat {
+            return Err(anyhow!("Tool calls are not run in chat mode"));
+        }
+        self.tool_inspection_manager
+            .update_permission_inspector_mode(goose_mode)
+            .await;
+        let requests = [request.clone()];
+        let messages = stored
+            .conversation
+            .as_ref()
+            .map(|conversation| conversation.messages().as_slice())
+            .unwrap_or_default();
+        let inspection_results = self
+            .tool_inspection_manager
+            .inspect_tools(&requests, messages)
+            .await?;
+        let approved = self
+            .tool_inspection_manager
+            .process_inspection_results_with_permission_inspector(&requests, &inspection_results)
+            .is_some_and(|result| result.approved.iter().any(|r| r.id == request.id));
+        if !approved {
+            return Err(anyhow!(
+                "Tool call {} needs approval or was denied, so it is not replayed",
+                tool_call.name
+            ));
+        }
+
+        let (_, result) = self
+            .dispatch_tool_call(tool_call, tool_request_id.to_string(), None, Some(session))
+            .await;
+        Ok(match result {
+            Ok(call_result) => call_result.result.await,
+            Err(e) => Err(e),
+        })
+    }
+
     #[instrument(skip(self, unfixed_conversation, session), fields(user_message))]
     pub async fn reply(
         &self,
         unfixed_conversation: Conversation,
         session: Option<SessionConfig>,
         cancel_token: Option<CancellationToken>,
+    ) -> Result<BoxStream<'_, Result<AgentEvent>>> {
+        let cancel_token = self.reply_cancel_token(cancel_token);
+        // Whatever the reply still has running is cancelled once its stream is dropped
+        let drop_guard = cancel_token.clone().drop_guard();
+        let operation = self.operations.start(
+            session.as_ref().map(|session| session.id.clone()),
+            cancel_token.clone(),
+        );
+        let stream = self
+            .reply_with_compaction(unfixed_conversation, session, Some(cancel_token))
+            .await?;
+        Ok(Box::pin(stream.map(move |event| {
+            let _drop_guard = &drop_guard;
+            if let Ok(AgentEvent::Message(message)) = &event {
+                operation.observe(message);
+            }
+            event
+        })))
+    }
+
+    async fn reply_with_compaction(
+        &self,
+        unfixed_conversation: Conversation,
+        session: Option<SessionConfig>,
+        cancel_token: Option<CancellationToken>,
     ) -> Result<BoxStream<'_, Result<AgentEvent>>> {
         let is_manual_compact = unfixed_conversation.messages().last().is_some_and(|msg| {
             msg.content.iter().any(|c| {
@@ -811,7 +1244,12 @@ impl Agent {
                 )
             );
 
-            match crate::context_mgmt::compact_messages(self, &conversation_to_compact, false).await {
+            let strategy = if is_manual_compact {
+                CompactionStrategy::Summarize
+            } else {
+                CompactionStrategy::AutoCompact
+            };
+            match crate::context_mgmt::compact_messages(self, &conversation_to_compact, false, strategy).await {
                 Ok((compacted_conversation, _token_counts, _summarization_usage)) => {
                     if let Some(session_to_store) = &session {
                         SessionManager::replace_conversation(&session_to_store.id, &compacted_conversation).await?;
@@ -861,6 +1299,10 @@ impl Agent {
         } = context;
         let reply_span = tracing::Span::current();
         self.reset_retry_attempts().await;
+        let request_metadata = session
+            .as_ref()
+            .map(|s| s.provider_request_metadata())
+            .unwrap_or_default();
 
         // This will need further refactoring. In the ideal world we pass the new message into
         // reply and load the existing conversation. Until we get to that point, fetch the conversation
@@ -913,12 +1355,20 @@ impl Agent {
         Ok(Box::pin(async_stream::try_stream! {
             let _ = reply_span.enter();
             let mut turns_taken = 0u32;
+            let mut recovery_compactions = 0u32;
+            let autosave_interval = autosave_interval();
+            let dry_run = session.as_ref().is_some_and(|s| s.dry_run);
             let max_turns = session
                 .as_ref()
                 .and_then(|s| s.max_turns)
                 .unwrap_or_else(|| {
                     config.get_param("GOOSE_MAX_TURNS").unwrap_or(DEFAULT_MAX_TURNS)
                 });
+            let max_tool_calls_per_turn = session
+                .as_ref()
+                .and_then(|s| s.max_tool_calls_per_turn)
+                .or_else(|| config.get_param::<u32>(MAX_TOOL_CALLS_PER_TURN_KEY).ok())
+                .map(|n| n as usize);
 
             loop {
                 if is_token_cancelled(&cancel_token) {
@@ -956,18 +1406,44 @@ impl Agent {
                     }
                 }
 
-                let mut stream = Self::stream_response_from_provider(
-                    self.provider().await?,
-                    &system_prompt,
-                    conversation.messages(),
-                    &tools,
-                    &toolshim_tools,
-                ).await?;
+                // Surface provider backoff (e.g. a 429 with Retry-After) while we wait
+                let (retry_tx, mut retry_rx) = mpsc::unbounded_channel();
+                let mut stream = {
+                    let provider_request = with_retry_notices(
+                        retry_tx,
+                        Self::stream_response_from_provider(
+                            self.provider().await?,
+                            &system_prompt,
+                            conversation.messages(),
+                            &tools,
+                            &toolshim_tools,
+                            request_metadata.clone(),
+                        ),
+                    );
+                    tokio::pin!(provider_request);
+                    loop {
+                        let notice = tokio::select! {
+                            result = &mut provider_request => break result?,
+                            Some(notice) = retry_rx.recv() => notice,
+                        };
+                        yield AgentEvent::Message(
+                            Message::assistant().with_system_notification(
+                                SystemNotificationType::InlineMessage,
+                                notice.to_string(),
+                            )
+                        );
+                    }
+                };
 
                 let mut no_tools_called = true;
                 let mut messages_to_add = Conversation::default();
+                let mut autosave = session
+                    .as_ref()
+                    .map(|session_config| Autosave::new(session_config.id.clone(), autosave_interval));
                 let mut tools_updated = false;
                 let mut did_recovery_compact_this_iteration = false;
+                let mut context_exhausted = false;
+                let mut stop_reason: Option<StopReason> = None;
 
                 while let Some(next) = stream.next().await {
                     if is_token_cancelled(&cancel_token) {
@@ -976,6 +1452,8 @@ impl Agent {
 
                     match next {
                         Ok((response, usage)) => {
+                            recovery_compactions = 0;
+
                             // Emit model change event if provider is lead-worker
                             let provider = self.provider().await?;
                             if let Some(lead_worker) = provider.as_lead_worker() {
@@ -1003,9 +1481,17 @@ impl Agent {
                                     Self::update_session_metrics(session_config, usage).await?;
                                 }
                             }
+                            if let Some(reason) = usage.as_ref().and_then(|u| u.stop_reason.clone()) {
+                                stop_reason = Some(reason);
+                            }
 
                             if let Some(response) = response {
                                 messages_to_add.push(response.clone());
+                                if let Some(autosave) = autosave.as_mut() {
+                                    if let Err(e) = autosave.save_if_due(&messages_to_add).await {
+                                        warn!("Failed to autosave session: {}", e);
+                                    }
+                                }
                                 let ToolCategorizeResult {
                                     frontend_requests,
                                     remaining_requests,
@@ -1027,18 +1513,45 @@ impl Agent {
                                 let message_tool_response = Arc::new(Mutex::new(Message::user().with_id(
                                     format!("msg_{}", Uuid::new_v4())
                                 )));
+                                let mut subagent_messages = Vec::new();
+
+                                // Calls over the budget are answered without running them
+                                let mut budget = max_tool_calls_per_turn;
+                                let (frontend_requests, frontend_over_budget) =
+                                    take_within_budget(frontend_requests, &mut budget);
+                                let (remaining_requests, remaining_over_budget) =
+                                    take_within_budget(remaining_requests, &mut budget);
+                                for request in frontend_over_budget.iter().chain(&remaining_over_budget) {
+                                    let mut response = message_tool_response.lock().await;
+                                    *response = response.clone().with_tool_response(
+                                        request.id.clone(),
+                                        Ok(vec![Content::text(tool_call_budget_response(
+                                            max_tool_calls_per_turn.unwrap_or_default(),
+                                        ))]),
+                                    );
+                                }
 
-                                let mut frontend_tool_stream = self.handle_frontend_tool_requests(
-                                    &frontend_requests,
-                                    message_tool_response.clone(),
-                                );
+                                if !dry_run {
+                                    let mut frontend_tool_stream = self.handle_frontend_tool_requests(
+                                        &frontend_requests,
+                                        message_tool_response.clone(),
+                                    );
 
-                                while let Some(msg) = frontend_tool_stream.try_next().await? {
-                                    yield AgentEvent::Message(msg);
+                                    while let Some(msg) = frontend_tool_stream.try_next().await? {
+                                        yield AgentEvent::Message(msg);
+                                    }
                                 }
 
-                                let mode = goose_mode.clone();
-                                if mode.as_str() == "chat" {
+                                if dry_run {
+                                    // Frontend tools are not run either, so nothing has side effects
+                                    for request in frontend_requests.iter().chain(&remaining_requests) {
+                                        let mut response = message_tool_response.lock().await;
+                                        *response = response.clone().with_tool_response(
+                                            request.id.clone(),
+                                            Ok(vec![Content::text(dry_run_response(request))]),
+                                        );
+                                    }
+                                } else if goose_mode == GooseMode::Chat {
                                     // Skip all tool calls in chat mode
                                     for request in remaining_requests {
                                         let mut response = message_tool_response.lock().await;
@@ -1073,18 +1586,29 @@ impl Agent {
                                             result
                                         });
 
-                                    // Track extension requests for special handling
+                                    // Track extension and subagent requests for special handling
                                     let mut enable_extension_request_ids = vec![];
+                                    let mut subagent_request_ids = vec![];
+                                    let subagent_responses_in_conversation = Config::global()
+                                        .get_param::<bool>(SUBAGENT_RESPONSES_IN_CONVERSATION_KEY)
+                                        .unwrap_or(false);
+                                    let output_scan_mode = ToolOutputScanMode::from_config();
                                     for request in &remaining_requests {
                                         if let Ok(tool_call) = &request.tool_call {
                                             if tool_call.name == MANAGE_EXTENSIONS_TOOL_NAME_COMPLETE {
                                                 enable_extension_request_ids.push(request.id.clone());
                                             }
+                                            if subagent_responses_in_conversation
+                                                && tool_call.name == SUBAGENT_EXECUTE_TASK_TOOL_NAME
+                                            {
+                                                subagent_request_ids.push(request.id.clone());
+                                            }
                                         }
                                     }
 
                                     let mut tool_futures = self.handle_approved_and_denied_tools(
                                         &permission_check_result,
+                                        &inspection_results,
                                         message_tool_response.clone(),
                                         cancel_token.clone(),
                                         session.clone(),
@@ -1120,18 +1644,105 @@ impl Agent {
 
                                     let mut combined = stream::select_all(with_id);
                                     let mut all_install_successful = true;
-
-                                    while let Some((request_id, item)) = combined.next().await {
+                                    let mut subagent_approval_rx = self.subagent_approval_rx.lock().await;
+
+                                    loop {
+                                        let event = tokio::select! {
+                                            next = combined.next() => match next {
+                                                Some((request_id, item)) => ToolExecutionEvent::Tool(request_id, item),
+                                                None => break,
+                                            },
+                                            Some(approval) = subagent_approval_rx.recv() => {
+                                                ToolExecutionEvent::SubagentApproval(approval)
+                                            }
+                                            _ = async {
+                                                match &autosave {
+                                                    Some(autosave) => autosave.wait_until_due().await,
+                                                    None => std::future::pending().await,
+                                                }
+                                            }, if autosave.as_ref().is_some_and(|a| a.has_unsaved(&messages_to_add)) => {
+                                                ToolExecutionEvent::Autosave
+                                            }
+                                        };
                                         if is_token_cancelled(&cancel_token) {
                                             break;
                                         }
+                                        let (request_id, item) = match event {
+                                            ToolExecutionEvent::Tool(request_id, item) => (request_id, item),
+                                            ToolExecutionEvent::SubagentApproval(approval) => {
+                                                let SubagentApprovalRequest { request, response_tx } = approval;
+                                                let prompt = match &request.prompt {
+                                                    Some(security_message) => format!(
+                                                        "A subagent would like to call {}:\n{}",
+                                                        request.tool_name, security_message
+                                                    ),
+                                                    None => format!(
+                                                        "A subagent would like to call {} with arguments:\n{}",
+                                                        request.tool_name,
+                                                        serde_json::to_string_pretty(&request.arguments).unwrap_or_default()
+                                                    ),
+                                                };
+                                                yield AgentEvent::Message(Message::user().with_tool_confirmation_request(
+                                                    request.id.clone(),
+                                                    request.tool_name.clone(),
+                                                    request.arguments.clone(),
+                                                    Some(prompt),
+                                                ));
+
+                                                let confirmation = self.wait_for_confirmation(&request.id).await;
+                                                self.record_permission_decision(
+                                                    session.as_ref(),
+                                                    PermissionDecision::new(
+                                                        request.id.clone(),
+                                                        request.tool_name.clone(),
+                                                        Value::Object(request.arguments.clone()),
+                                                        &confirmation,
+                                                        DecisionSource::Manual,
+                                                    ),
+                                                ).await;
+                                                if response_tx.send(confirmation).is_err() {
+                                                    warn!("Subagent stopped waiting for approval of {}", request.tool_name);
+                                                }
+                                                continue;
+                                            }
+                                            ToolExecutionEvent::Autosave => {
+                                                if let Some(autosave) = autosave.as_mut() {
+                                                    if let Err(e) = autosave.save(&messages_to_add).await {
+                                                        warn!("Failed to autosave session: {}", e);
+                                                    }
+                                                }
+                                                continue;
+                                            }
+                                        };
                                         match item {
                                             ToolStreamItem::Result(output) => {
+                                                let (output, findings) = scan_tool_output(output, output_scan_mode);
+                                                if !findings.is_empty() {
+                                                    let tool_name = remaining_requests
+                                                        .iter()
+                                                        .find(|request| request.id == request_id)
+                                                        .and_then(|request| request.tool_call.as_ref().ok())
+                                                        .map(|call| call.name.to_string())
+                                                        .unwrap_or_default();
+                                                    let warning = injection_warning(&tool_name, &findings);
+                                                    warn!("{}", warning);
+                                                    yield AgentEvent::Message(
+                                                        Message::assistant().with_system_notification(
+                                                            SystemNotificationType::InlineMessage,
+                                                            warning,
+                                                        )
+                                                    );
+                                                }
                                                 if enable_extension_request_ids.contains(&request_id)
                                                     && output.is_err()
                                                 {
                                                     all_install_successful = false;
                                                 }
+                                                if subagent_request_ids.contains(&request_id) {
+                                                    subagent_messages.extend(
+                                                        subagent_execute_task_tool::subagent_response_messages(&output),
+                                                    );
+                                                }
                                                 let mut response = message_tool_response.lock().await;
                                                 *response =
                                                     response.clone().with_tool_response(request_id, output);
@@ -1144,6 +1755,8 @@ impl Agent {
                                         }
                                     }
 
+                                    drop(subagent_approval_rx);
+
                                     if all_install_successful && !enable_extension_request_ids.is_empty() {
                                         if let Some(ref session_config) = session {
                                             if let Err(e) = self.save_extension_state(session_config).await {
@@ -1159,9 +1772,26 @@ impl Agent {
 
                                 no_tools_called = false;
                                 messages_to_add.push(final_message_tool_resp);
+
+                                for message in subagent_messages {
+                                    yield AgentEvent::Message(message.clone());
+                                    messages_to_add.push(message);
+                                }
                             }
                         }
                         Err(ProviderError::ContextLengthExceeded(_error_msg)) => {
+                            if recovery_compactions >= MAX_RECOVERY_COMPACTIONS {
+                                error!("Context still exceeded after {} compactions", recovery_compactions);
+                                yield AgentEvent::Message(Message::assistant().with_text(format!(
+                                    "The conversation is still too long for the model's context window after compacting it {} times. \
+                                    Please start a new session or switch to a model with a larger context window.",
+                                    recovery_compactions
+                                )));
+                                context_exhausted = true;
+                                break;
+                            }
+                            recovery_compactions += 1;
+
                             yield AgentEvent::Message(
                                 Message::assistant().with_system_notification(
                                     SystemNotificationType::InlineMessage,
@@ -1175,11 +1805,19 @@ impl Agent {
                                 )
                             );
 
-                            match crate::context_mgmt::compact_messages(self, &conversation, true).await {
+                            match crate::context_mgmt::compact_messages(
+                                self,
+                                &conversation,
+                                true,
+                                CompactionStrategy::ContextLimitRecovery,
+                            ).await {
                                 Ok((compacted_conversation, _token_counts, _usage)) => {
                                     if let Some(session_to_store) = &session {
                                         SessionManager::replace_conversation(&session_to_store.id, &compacted_conversation).await?
                                     }
+                                    if let Some(autosave) = autosave.as_mut() {
+                                        autosave.reset();
+                                    }
 
                                     conversation = compacted_conversation;
                                     did_recovery_compact_this_iteration = true;
@@ -1205,6 +1843,12 @@ impl Agent {
                         }
                     }
                 }
+                if let Some(reason) = stop_reason {
+                    yield AgentEvent::StopReason(reason);
+                }
+                if context_exhausted {
+                    break;
+                }
                 if tools_updated {
                     (tools, toolshim_tools, system_prompt) = self.prepare_tools_and_prompt().await?;
                 }
@@ -1244,10 +1888,8 @@ impl Agent {
                     }
                 }
 
-                if let Some(session_config) = &session {
-                    for msg in &messages_to_add {
-                        SessionManager::add_message(&session_config.id, msg).await?;
-                    }
+                if let Some(autosave) = autosave.as_mut() {
+                    autosave.save(&messages_to_add).await?;
                 }
                 conversation.extend(messages_to_add);
                 if exit_chat {
@@ -1259,15 +1901,13 @@ impl Agent {
         }))
     }
 
-    fn determine_goose_mode(session: Option<&SessionConfig>, config: &Config) -> String {
+    fn determine_goose_mode(session: Option<&SessionConfig>) -> GooseMode {
         let mode = session.and_then(|s| s.execution_mode.as_deref());
 
         match mode {
-            Some("foreground") => "chat".to_string(),
-            Some("background") => "auto".to_string(),
-            _ => config
-                .get_param("GOOSE_MODE")
-                .unwrap_or_else(|_| "auto".to_string()),
+            Some("foreground") => GooseMode::Chat,
+            Some("background") => GooseMode::Auto,
+            _ => GooseMode::from_config(),
         }
     }
 
@@ -1338,6 +1978,59 @@ impl Agent {
         Err(anyhow!("Prompt '{}' not found", name))
     }
 
+    /// The prompts of all extensions, ordered by extension and name
+    pub async fn list_prompts(&self) -> Result<Vec<PromptDescriptor>> {
+        let prompts = self
+            .extension_manager
+            .list_prompts(CancellationToken::default())
+            .await
+            .map_err(|e| anyhow!("Failed to list prompts: {}", e))?;
+
+        let mut descriptors: Vec<PromptDescriptor> = prompts
+            .into_iter()
+            .flat_map(|(extension, prompts)| {
+                prompts
+                    .into_iter()
+                    .map(move |prompt| PromptDescriptor::new(&extension, prompt))
+            })
+            .collect();
+        descriptors.sort_by(|a, b| (&a.extension, &a.name).cmp(&(&b.extension, &b.name)));
+        Ok(descriptors)
+    }
+
+    /// Check `arguments` against the prompt's declared arguments, then get its messages as a
+    /// conversation that alternates between user and assistant
+    pub async fn run_prompt(
+        &self,
+        name: &str,
+        arguments: HashMap<String, String>,
+    ) -> Result<Conversation> {
+        let descriptor = self
+            .list_prompts()
+            .await?
+            .into_iter()
+            .find(|prompt| prompt.name == name)
+            .ok_or_else(|| anyhow!("Prompt '{}' not found", name))?;
+        descriptor
+            .validate_arguments(&arguments)
+            .map_err(|e| anyhow!(e))?;
+
+        let result = self
+            .extension_manager
+            .get_prompt(
+                &descriptor.extension,
+                name,
+                serde_json::to_value(arguments)?,
+                CancellationToken::default(),
+            )
+            .await?;
+
+        let mut conversation =
+            Conversation::new_unvalidated(result.messages.into_iter().map(Message::from));
+        conversation.normalize_roles(true);
+        Ok(conversation)
+    }
+
     pub async fn get_plan_prompt(&self) -> Result<String> {
         let tools = self.extension_manager.get_prefixed_tools(None).await?;
         let tools_info = tools
@@ -1534,6 +2227,8 @@ impl Agent {
             goose_provider: Some(provider_name.clone()),
             goose_model: Some(model_name.clone()),
             temperature: Some(model_config.temperature.unwrap_or(0.0)),
+            max_output_tokens: None,
+            tool_rate_limits: None,
         };
 
         tracing::debug!(
@@ -1646,4 +2341,22 @@ mod tests {
 
         Ok(())
     }
+    #[tokio::test]
+    async fn test_emergency_stop_cancels_running_replies() {
+        let agent = Agent::new();
+        let caller_token = CancellationToken::new();
+        let running = agent.reply_cancel_token(Some(caller_token.clone()));
+        let other = agent.reply_cancel_token(None);
+
+        agent.emergency_stop();
+        assert!(running.is_cancelled());
+        assert!(other.is_cancelled());
+        assert!(!caller_token.is_cancelled());
+
+        // Replies started after the stop are not affected by it
+        let next = agent.reply_cancel_token(Some(caller_token.clone()));
+        assert!(!next.is_cancelled());
+        caller_token.cancel();
+        next.cancelled().await;
+    }
 }
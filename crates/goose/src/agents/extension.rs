@@ -99,6 +99,8 @@ pub enum ExtensionError {
     InitializeError(#[from] ClientInitializeError),
     #[error("{0}")]
     ProcessExit(#[from] ProcessExit),
+    #[error("extension not found: {0}")]
+    NotFound(String),
 }
 
 pub type ExtensionResult<T> = Result<T, ExtensionError>;
@@ -223,8 +225,11 @@ pub enum ExtensionConfig {
         description: String,
         cmd: String,
         args: Vec<String>,
+        /// Environment variables set to literal values, these take precedence over `env_keys`
         #[serde(default)]
         envs: Envs,
+        /// Names of environment variables whose values are read when the extension starts,
+        /// from goose's own environment or else the secret store. Starting fails if one is missing.
         #[serde(default)]
         env_keys: Vec<String>,
         timeout: Option<u64>,
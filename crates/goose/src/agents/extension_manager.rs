@@ -12,6 +12,7 @@ use rmcp::transport::{
     TokioChildProcess,
 };
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 use std::time::Duration;
@@ -22,7 +23,7 @@ use tokio::sync::Mutex;
 use tokio::task;
 use tokio_stream::wrappers::ReceiverStream;
 use tokio_util::sync::CancellationToken;
-use tracing::{error, warn};
+use tracing::error;
 
 use super::extension::{
     ExtensionConfig, ExtensionError, ExtensionInfo, ExtensionResult, PlatformExtensionContext,
@@ -32,9 +33,10 @@ use super::tool_execution::ToolCallResult;
 use crate::agents::extension::{Envs, ProcessExit};
 use crate::agents::extension_malware_check;
 use crate::agents::mcp_client::{McpClient, McpClientTrait};
-use crate::config::{get_all_extensions, Config};
+use crate::config::{get_all_extensions, Config, ConfigError};
 use crate::oauth::oauth_flow;
 use crate::prompt_template;
+use crate::recipe::RecipeSecrets;
 use rmcp::model::{
     CallToolRequestParam, Content, ErrorCode, ErrorData, GetPromptResult, Prompt, ResourceContents,
     ServerInfo, Tool,
@@ -51,6 +53,8 @@ struct Extension {
     client: McpClientBox,
     server_info: Option<ServerInfo>,
     _temp_dir: Option<tempfile::TempDir>,
+    /// Disabled extensions stay connected but are hidden from the model
+    enabled: bool,
 }
 
 impl Extension {
@@ -65,6 +69,7 @@ impl Extension {
             config,
             server_info,
             _temp_dir: temp_dir,
+            enabled: true,
         }
     }
 
@@ -90,6 +95,8 @@ impl Extension {
 pub struct ExtensionManager {
     extensions: Mutex<HashMap<String, Extension>>,
     context: Mutex<PlatformExtensionContext>,
+    /// Secret recipe parameter values, passed to this session's extension processes only
+    recipe_secrets: Mutex<RecipeSecrets>,
 }
 
 /// A flattened representation of a resource used by the agent to prepare inference
@@ -213,6 +220,178 @@ async fn child_process_client(
     }
 }
 
+/// Build the environment for an extension process. Literal `envs` take precedence; each of the
+/// `env_keys` not set there is read from the goose process environment, then from the secret
+/// store, so recipes can name secrets without embedding them. A key found in neither is an error.
+fn merge_environments(
+    envs: &Envs,
+    env_keys: &[String],
+    ext_name: &str,
+) -> Result<HashMap<String, String>, ExtensionError> {
+    let mut all_envs = envs.get_env();
+    let config_instance = Config::global();
+
+    for key in env_keys {
+        if all_envs.contains_key(key) {
+            continue;
+        }
+
+        // Read the environment directly so values are passed through as-is, rather than
+        // parsed the way config values are
+        if let Ok(value) = std::env::var(key) {
+            all_envs.insert(key.clone(), value);
+            continue;
+        }
+
+        match config_instance.get_secret::<Value>(key) {
+            Ok(Value::String(value)) => {
+                all_envs.insert(key.clone(), value);
+            }
+            Ok(Value::Null) | Err(ConfigError::NotFound(_)) => {
+                return Err(ExtensionError::ConfigError(format!(
+                    "environment variable '{}' required by extension '{}' is not set and was not found in the secret store",
+                    key, ext_name
+                )));
+            }
+            Ok(value) => {
+                all_envs.insert(key.clone(), value.to_string());
+            }
+            Err(e) => {
+                error!(
+                    key = %key,
+                    ext_name = %ext_name,
+                    error = %e,
+                    "Failed to fetch secret from config."
+                );
+                return Err(ExtensionError::ConfigError(format!(
+                    "Failed to fetch secret '{}' from config: {}",
+                    key, e
+                )));
+            }
+        }
+    }
+
+    Ok(all_envs)
+}
+
+/// Replace `${VAR}` references with values from the process environment, so secrets such as
+/// auth tokens don't need to be stored in extension configs
+fn interpolate_env(value: &str) -> ExtensionResult<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start + 2..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 2..start + 2 + len];
+        let resolved = std::env::var(name).map_err(|_| {
+            ExtensionError::ConfigError(format!(
+                "environment variable '{}' referenced in extension config is not set",
+                name
+            ))
+        })?;
+        result.push_str(&rest[..start]);
+        result.push_str(&resolved);
+        rest = &rest[start + 2 + len + 1..];
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Find `cmd` the way the OS would when spawning it: paths are checked directly, bare names
+/// are looked up on PATH
+fn find_executable(cmd: &str) -> Option<PathBuf> {
+    let path = Path::new(cmd);
+    if path.components().count() > 1 {
+        return path.is_file().then(|| path.to_path_buf());
+    }
+
+    let extensions: Vec<String> = if cfg!(windows) {
+        std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".EXE;.CMD;.BAT;.COM".to_string())
+            .split(';')
+            .map(|ext| ext.to_string())
+            .chain(std::iter::once(String::new()))
+            .collect()
+    } else {
+        vec![String::new()]
+    };
+
+    std::env::var_os("PATH").and_then(|paths| {
+        std::env::split_paths(&paths).find_map(|dir| {
+            extensions
+                .iter()
+                .map(|ext| dir.join(format!("{}{}", cmd, ext)))
+                .find(|candidate| candidate.is_file())
+        })
+    })
+}
+
+fn check_command(cmd: &str) -> ExtensionResult<()> {
+    match find_executable(cmd) {
+        Some(_) => Ok(()),
+        None => Err(ExtensionError::ConfigError(format!(
+            "command '{}' not found — is it installed?",
+            cmd
+        ))),
+    }
+}
+
+const PREFLIGHT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub const EXTENSION_STARTUP_CONCURRENCY_KEY: &str = "GOOSE_EXTENSION_STARTUP_CONCURRENCY";
+const DEFAULT_EXTENSION_STARTUP_CONCURRENCY: usize = 8;
+
+/// How many extensions may start at once, from `GOOSE_EXTENSION_STARTUP_CONCURRENCY`
+pub fn extension_startup_concurrency() -> usize {
+    Config::global()
+        .get_param::<usize>(EXTENSION_STARTUP_CONCURRENCY_KEY)
+        .ok()
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_EXTENSION_STARTUP_CONCURRENCY)
+}
+
+/// Check that a remote extension's URL is valid and that its server accepts connections.
+/// Any HTTP response counts as reachable, the handshake reports protocol and auth errors.
+async fn check_url(uri: &str) -> ExtensionResult<()> {
+    let url = url::Url::parse(uri)
+        .map_err(|e| ExtensionError::ConfigError(format!("invalid URL '{}': {}", uri, e)))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(ExtensionError::ConfigError(format!(
+            "invalid URL '{}': expected an http or https URL",
+            uri
+        )));
+    }
+
+    let client = reqwest::Client::builder()
+        .connect_timeout(PREFLIGHT_CONNECT_TIMEOUT)
+        .timeout(PREFLIGHT_CONNECT_TIMEOUT)
+        .build()
+        .map_err(|_| ExtensionError::ConfigError("could not construct http client".to_string()))?;
+    match client.head(url).send().await {
+        Err(e) if e.is_connect() => Err(ExtensionError::SetupError(format!(
+            "could not connect to '{}' — is the server running?",
+            uri
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Catch the common reasons an extension fails to start before attempting the MCP handshake,
+/// whose errors are much harder to act on
+pub(crate) async fn preflight_check(config: &ExtensionConfig) -> ExtensionResult<()> {
+    match config {
+        ExtensionConfig::Stdio { cmd, .. } => check_command(cmd),
+        ExtensionConfig::InlinePython { .. } => check_command("uvx"),
+        ExtensionConfig::Sse { uri, .. } | ExtensionConfig::StreamableHttp { uri, .. } => {
+            check_url(uri).await
+        }
+        ExtensionConfig::Builtin { .. }
+        | ExtensionConfig::Platform { .. }
+        | ExtensionConfig::Frontend { .. } => Ok(()),
+    }
+}
+
 fn extract_auth_error(
     res: &Result<McpClient, ClientInitializeError>,
 ) -> Option<&AuthRequiredError> {
@@ -244,9 +423,15 @@ impl ExtensionManager {
                 extension_manager: None,
                 tool_route_manager: None,
             }),
+            recipe_secrets: Mutex::new(RecipeSecrets::default()),
         }
     }
 
+    /// Set the secret recipe values passed to the extensions started from now on
+    pub async fn set_recipe_secrets(&self, secrets: RecipeSecrets) {
+        *self.recipe_secrets.lock().await = secrets;
+    }
+
     pub async fn set_context(&self, context: PlatformExtensionContext) {
         *self.context.lock().await = context;
     }
@@ -268,62 +453,8 @@ impl ExtensionManager {
         let sanitized_name = normalize(config_name.clone());
         let mut temp_dir = None;
 
-        /// Helper function to merge environment variables from direct envs and keychain-stored env_keys
-        async fn merge_environments(
-            envs: &Envs,
-            env_keys: &[String],
-            ext_name: &str,
-        ) -> Result<HashMap<String, String>, ExtensionError> {
-            let mut all_envs = envs.get_env();
-            let config_instance = Config::global();
-
-            for key in env_keys {
-                // If the Envs payload already contains the key, prefer that value
-                // over looking into the keychain/secret store
-                if all_envs.contains_key(key) {
-                    continue;
-                }
-
-                match config_instance.get(key, true) {
-                    Ok(value) => {
-                        if value.is_null() {
-                            warn!(
-                                key = %key,
-                                ext_name = %ext_name,
-                                "Secret key not found in config (returned null)."
-                            );
-                            continue;
-                        }
-
-                        // Try to get string value
-                        if let Some(str_val) = value.as_str() {
-                            all_envs.insert(key.clone(), str_val.to_string());
-                        } else {
-                            warn!(
-                                key = %key,
-                                ext_name = %ext_name,
-                                value_type = %value.get("type").and_then(|t| t.as_str()).unwrap_or("unknown"),
-                                "Secret value is not a string; skipping."
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        error!(
-                            key = %key,
-                            ext_name = %ext_name,
-                            error = %e,
-                            "Failed to fetch secret from config."
-                        );
-                        return Err(ExtensionError::ConfigError(format!(
-                            "Failed to fetch secret '{}' from config: {}",
-                            key, e
-                        )));
-                    }
-                }
-            }
-
-            Ok(all_envs)
-        }
+        preflight_check(&config).await?;
+        let secret_envs = self.recipe_secrets.lock().await.envs().clone();
 
         let client: Box<dyn McpClientTrait> = match &config {
             ExtensionConfig::Sse { uri, timeout, .. } => {
@@ -358,7 +489,7 @@ impl ExtensionManager {
                         HeaderName::try_from(key).map_err(|_| {
                             ExtensionError::ConfigError(format!("invalid header: {}", key))
                         })?,
-                        value.parse().map_err(|_| {
+                        interpolate_env(value)?.parse().map_err(|_| {
                             ExtensionError::ConfigError(format!("invalid header value: {}", key))
                         })?,
                     );
@@ -415,9 +546,9 @@ impl ExtensionManager {
                 timeout,
                 ..
             } => {
-                let all_envs = merge_environments(envs, env_keys, &sanitized_name).await?;
+                let all_envs = merge_environments(envs, env_keys, &sanitized_name)?;
                 let command = Command::new(cmd).configure(|command| {
-                    command.args(args).envs(all_envs);
+                    command.args(args).envs(&secret_envs).envs(all_envs);
                 });
 
                 // Check for malicious packages before launching the process
@@ -450,7 +581,7 @@ impl ExtensionManager {
                         ))
                     })?;
                 let command = Command::new(cmd).configure(|command| {
-                    command.arg("mcp").arg(name);
+                    command.arg("mcp").arg(name).envs(&secret_envs);
                 });
                 let client = child_process_client(command, timeout).await?;
                 Box::new(client)
@@ -479,7 +610,7 @@ impl ExtensionManager {
                 std::fs::write(&file_path, code)?;
 
                 let command = Command::new("uvx").configure(|command| {
-                    command.arg("--with").arg("mcp");
+                    command.envs(&secret_envs).arg("--with").arg("mcp");
 
                     dependencies.iter().flatten().for_each(|dep| {
                         command.arg("--with").arg(dep);
@@ -532,6 +663,7 @@ impl ExtensionManager {
             .lock()
             .await
             .iter()
+            .filter(|(_, ext)| ext.enabled)
             .map(|(name, ext)| {
                 ExtensionInfo::new(
                     name,
@@ -549,8 +681,36 @@ impl ExtensionManager {
         Ok(())
     }
 
+    /// Hide an extension's tools and instructions from the model and refuse calls to it, or
+    /// undo that. The extension stays connected, so enabling it again is immediate.
+    pub async fn set_extension_enabled(&self, name: &str, enabled: bool) -> ExtensionResult<()> {
+        let sanitized_name = normalize(name.to_string());
+        match self.extensions.lock().await.get_mut(&sanitized_name) {
+            Some(extension) => {
+                extension.enabled = enabled;
+                Ok(())
+            }
+            None => Err(ExtensionError::NotFound(name.to_string())),
+        }
+    }
+
+    pub async fn is_extension_enabled(&self, name: &str) -> bool {
+        let sanitized_name = normalize(name.to_string());
+        self.extensions
+            .lock()
+            .await
+            .get(&sanitized_name)
+            .is_some_and(|extension| extension.enabled)
+    }
+
     pub async fn suggest_disable_extensions_prompt(&self) -> Value {
-        let enabled_extensions_count = self.extensions.lock().await.len();
+        let enabled_extensions_count = self
+            .extensions
+            .lock()
+            .await
+            .values()
+            .filter(|extension| extension.enabled)
+            .count();
 
         let total_tools = self
             .get_prefixed_tools(None)
@@ -605,6 +765,7 @@ impl ExtensionManager {
             .lock()
             .await
             .iter()
+            .filter(|(_, ext)| ext.enabled)
             .filter(|(name, _ext)| {
                 if let Some(ref name_filter) = extension_name {
                     *name == name_filter
@@ -670,6 +831,18 @@ impl ExtensionManager {
         Ok(tools)
     }
 
+    /// Look up a single tool by its prefixed name
+    pub async fn get_tool(&self, name: &str) -> Option<Tool> {
+        let extension_name = name
+            .split_once("__")
+            .map(|(extension, _)| extension.to_string())?;
+        self.get_prefixed_tools(Some(extension_name))
+            .await
+            .ok()?
+            .into_iter()
+            .find(|tool| tool.name == name)
+    }
+
     /// Get the extension prompt including client instructions
     pub async fn get_planning_prompt(&self, tools_info: Vec<ToolInfo>) -> String {
         let mut context: HashMap<&str, Value> = HashMap::new();
@@ -923,6 +1096,17 @@ impl ExtensionManager {
             .to_string();
 
         if let Some(extension) = self.extensions.lock().await.get(&client_name) {
+            if !extension.enabled {
+                return Err(ErrorData::new(
+                    ErrorCode::RESOURCE_NOT_FOUND,
+                    format!(
+                        "Extension '{}' is disabled, so tool '{}' cannot be called",
+                        client_name, tool_name
+                    ),
+                    None,
+                )
+                .into());
+            }
             if !extension.config.is_tool_available(&tool_name) {
                 return Err(ErrorData::new(
                     ErrorCode::RESOURCE_NOT_FOUND,
@@ -1171,6 +1355,78 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_merge_environments() {
+        let key = "GOOSE_TEST_MERGE_ENVIRONMENTS_TOKEN";
+        std::env::set_var(key, "0123");
+
+        let envs = Envs::new(HashMap::from([(
+            "LITERAL".to_string(),
+            "from-envs".to_string(),
+        )]));
+        let merged =
+            merge_environments(&envs, &[key.to_string(), "LITERAL".to_string()], "test").unwrap();
+        // Passed through as a string, not parsed as a number
+        assert_eq!(merged.get(key).map(String::as_str), Some("0123"));
+        // Literal envs win over env_keys
+        assert_eq!(merged.get("LITERAL").map(String::as_str), Some("from-envs"));
+        std::env::remove_var(key);
+
+        let err = merge_environments(
+            &Envs::default(),
+            &["GOOSE_TEST_MERGE_ENVIRONMENTS_MISSING".to_string()],
+            "test",
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("GOOSE_TEST_MERGE_ENVIRONMENTS_MISSING"));
+    }
+
+    #[test]
+    fn test_interpolate_env() {
+        std::env::set_var("GOOSE_TEST_INTERPOLATE_TOKEN", "secret");
+        assert_eq!(
+            interpolate_env("Bearer ${GOOSE_TEST_INTERPOLATE_TOKEN}").unwrap(),
+            "Bearer secret"
+        );
+        assert_eq!(interpolate_env("plain value").unwrap(), "plain value");
+        // Unterminated references are left alone
+        assert_eq!(interpolate_env("cost ${5").unwrap(), "cost ${5");
+        std::env::remove_var("GOOSE_TEST_INTERPOLATE_TOKEN");
+
+        assert!(interpolate_env("${GOOSE_TEST_INTERPOLATE_MISSING}").is_err());
+    }
+
+    #[test]
+    fn test_find_executable() {
+        let dir = tempfile::tempdir().unwrap();
+        let missing = dir.path().join("missing");
+        assert!(find_executable(missing.to_str().unwrap()).is_none());
+
+        let present = dir.path().join("present");
+        std::fs::write(&present, "").unwrap();
+        assert_eq!(find_executable(present.to_str().unwrap()), Some(present));
+
+        let err = check_command("definitely-not-a-real-goose-command").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid config: command 'definitely-not-a-real-goose-command' not found — is it installed?"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_url_rejects_invalid_urls() {
+        assert!(matches!(
+            check_url("not a url").await,
+            Err(ExtensionError::ConfigError(_))
+        ));
+        assert!(matches!(
+            check_url("ftp://example.com/mcp").await,
+            Err(ExtensionError::ConfigError(_))
+        ));
+    }
+
     struct MockClient {}
 
     #[async_trait::async_trait]
@@ -1538,4 +1794,65 @@ mod tests {
 
         assert!(result.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_disabled_extension_is_hidden_and_refused() {
+        let extension_manager = ExtensionManager::new();
+        extension_manager
+            .add_mock_extension(
+                "test_extension".to_string(),
+                Arc::new(Mutex::new(Box::new(MockClient {}))),
+            )
+            .await;
+        let tool_call = CallToolRequestParam {
+            name: "test_extension__tool".to_string().into(),
+            arguments: Some(object!({})),
+        };
+
+        extension_manager
+            .set_extension_enabled("test_extension", false)
+            .await
+            .unwrap();
+        assert!(
+            !extension_manager
+                .is_extension_enabled("test_extension")
+                .await
+        );
+        assert!(extension_manager
+            .get_prefixed_tools(None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(extension_manager.get_extensions_info().await.is_empty());
+        assert_eq!(extension_manager.list_extensions().await.unwrap().len(), 1);
+        let Err(err) = extension_manager
+            .dispatch_tool_call(tool_call.clone(), CancellationToken::default())
+            .await
+        else {
+            panic!("Expected calls to a disabled extension to fail");
+        };
+        let tool_err = err.downcast_ref::<ErrorData>().expect("Expected ErrorData");
+        assert!(tool_err.message.contains("is disabled"));
+
+        extension_manager
+            .set_extension_enabled("test_extension", true)
+            .await
+            .unwrap();
+        assert!(!extension_manager
+            .get_prefixed_tools(None)
+            .await
+            .unwrap()
+            .is_empty());
+        assert!(extension_manager
+            .dispatch_tool_call(tool_call, CancellationToken::default())
+            .await
+            .is_ok());
+
+        assert!(matches!(
+            extension_manager
+                .set_extension_enabled("missing", false)
+                .await,
+            Err(ExtensionError::NotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,271 @@
+//! Discovery of extensions that can be added to an agent: the extensions bundled with goose and
+//! community extensions listed in a registry index.
+//!
+//! The index is a JSON document at the URL configured with `GOOSE_EXTENSION_REGISTRY_URL`:
+//!
+//! ```json
+//! {
+//!   "extensions": [
+//!     {
+//!       "id": "fetch",
+//!       "name": "Fetch",
+//!       "description": "Fetch web pages as markdown",
+//!       "config": { "type": "stdio", "name": "fetch", "description": "", "cmd": "uvx", "args": ["mcp-server-fetch"], "timeout": 300 }
+//!     }
+//!   ]
+//! }
+//! ```
+
+use crate::agents::extension::{
+    ExtensionConfig, ExtensionError, ExtensionResult, PLATFORM_EXTENSIONS,
+};
+use crate::config::{Config, DEFAULT_EXTENSION_TIMEOUT};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+pub const REGISTRY_URL_KEY: &str = "GOOSE_EXTENSION_REGISTRY_URL";
+
+const REGISTRY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Builtin extensions served by goose itself, as (id, display name, description)
+const BUILTIN_EXTENSIONS: &[(&str, &str, &str)] = &[
+    (
+        "autovisualiser",
+        "Auto Visualiser",
+        "Data visualisation and UI generation tools",
+    ),
+    (
+        "computercontroller",
+        "Computer Controller",
+        "Controls for webscraping, file caching, and automations",
+    ),
+    (
+        "developer",
+        "Developer Tools",
+        "Code editing and shell access",
+    ),
+    (
+        "memory",
+        "Memory",
+        "Tools to save and retrieve durable memories",
+    ),
+    (
+        "tutorial",
+        "Tutorial",
+        "Access interactive tutorials and guides",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrySource {
+    /// Ships with goose
+    Bundled,
+    /// Listed in the configured registry index
+    Community,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default = "community_source")]
+    pub source: RegistrySource,
+    /// The config used to add the extension
+    pub config: ExtensionConfig,
+}
+
+fn community_source() -> RegistrySource {
+    RegistrySource::Community
+}
+
+impl RegistryEntry {
+    /// A short description of what adding the extension runs or connects to
+    pub fn install_command(&self) -> String {
+        match &self.config {
+            ExtensionConfig::Stdio { cmd, args, .. } => std::iter::once(cmd.as_str())
+                .chain(args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" "),
+            ExtensionConfig::Sse { uri, .. } | ExtensionConfig::StreamableHttp { uri, .. } => {
+                uri.clone()
+            }
+            ExtensionConfig::Builtin { name, .. } => format!("builtin {}", name),
+            ExtensionConfig::Platform { name, .. } => format!("platform {}", name),
+            ExtensionConfig::Frontend { name, .. } => format!("frontend {}", name),
+            ExtensionConfig::InlinePython { name, .. } => format!("inline python {}", name),
+        }
+    }
+
+    fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        [&self.id, &self.name, &self.description]
+            .iter()
+            .any(|field| field.to_lowercase().contains(&query))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct RegistryIndex {
+    extensions: Vec<RegistryEntry>,
+}
+
+/// The extensions that ship with goose
+pub fn bundled_extensions() -> Vec<RegistryEntry> {
+    let builtins = BUILTIN_EXTENSIONS
+        .iter()
+        .map(|(id, name, description)| RegistryEntry {
+            id: id.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            source: RegistrySource::Bundled,
+            config: ExtensionConfig::Builtin {
+                name: id.to_string(),
+                description: description.to_string(),
+                display_name: Some(name.to_string()),
+                timeout: Some(DEFAULT_EXTENSION_TIMEOUT),
+                bundled: Some(true),
+                available_tools: Vec::new(),
+            },
+        });
+
+    let mut platform: Vec<RegistryEntry> = PLATFORM_EXTENSIONS
+        .values()
+        .map(|def| RegistryEntry {
+            id: def.name.to_string(),
+            name: def.name.to_string(),
+            description: def.description.to_string(),
+            source: RegistrySource::Bundled,
+            config: ExtensionConfig::Platform {
+                name: def.name.to_string(),
+                description: def.description.to_string(),
+                bundled: Some(true),
+                available_tools: Vec::new(),
+            },
+        })
+        .collect();
+    platform.sort_by(|a, b| a.id.cmp(&b.id));
+
+    builtins.chain(platform).collect()
+}
+
+/// Fetch the community extensions listed in the index at `url`
+pub async fn fetch_registry(url: &str) -> ExtensionResult<Vec<RegistryEntry>> {
+    let url = url::Url::parse(url).map_err(|e| {
+        ExtensionError::ConfigError(format!("invalid extension registry URL {}: {}", url, e))
+    })?;
+    let setup_error =
+        |e: reqwest::Error| ExtensionError::SetupError(format!("extension registry: {}", e));
+
+    let client = reqwest::Client::builder()
+        .timeout(REGISTRY_TIMEOUT)
+        .build()
+        .map_err(setup_error)?;
+    let index: RegistryIndex = client
+        .get(url)
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(setup_error)?
+        .json()
+        .await
+        .map_err(setup_error)?;
+
+    Ok(index
+        .extensions
+        .into_iter()
+        // Only goose can vouch for bundled extensions
+        .map(|entry| RegistryEntry {
+            source: RegistrySource::Community,
+            ..entry
+        })
+        .collect())
+}
+
+/// All known extensions: the bundled ones, then the community registry if one is configured.
+/// Community entries that reuse a bundled id are dropped.
+pub async fn list_extensions() -> ExtensionResult<Vec<RegistryEntry>> {
+    let mut entries = bundled_extensions();
+
+    if let Ok(url) = Config::global().get_param::<String>(REGISTRY_URL_KEY) {
+        for entry in fetch_registry(&url).await? {
+            if !entries.iter().any(|existing| existing.id == entry.id) {
+                entries.push(entry);
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Entries whose id, name or description contain `query`, ignoring case
+pub fn search<'a>(entries: &'a [RegistryEntry], query: &str) -> Vec<&'a RegistryEntry> {
+    entries
+        .iter()
+        .filter(|entry| entry.matches(query))
+        .collect()
+}
+
+/// Look up a single extension by id
+pub async fn find_extension(id: &str) -> ExtensionResult<RegistryEntry> {
+    list_extensions()
+        .await?
+        .into_iter()
+        .find(|entry| entry.id == id)
+        .ok_or_else(|| {
+            ExtensionError::ConfigError(format!("extension '{}' not found in the registry", id))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundled_extensions() {
+        let entries = bundled_extensions();
+        let developer = entries.iter().find(|e| e.id == "developer").unwrap();
+        assert_eq!(developer.source, RegistrySource::Bundled);
+        assert_eq!(developer.install_command(), "builtin developer");
+        assert!(entries
+            .iter()
+            .any(|e| matches!(e.config, ExtensionConfig::Platform { .. })));
+    }
+
+    #[test]
+    fn test_parse_index_and_search() {
+        let index: RegistryIndex = serde_json::from_str(
+            r#"{
+                "extensions": [
+                    {
+                        "id": "fetch",
+                        "name": "Fetch",
+                        "description": "Fetch web pages as markdown",
+                        "config": {
+                            "type": "stdio",
+                            "name": "fetch",
+                            "description": "",
+                            "cmd": "uvx",
+                            "args": ["mcp-server-fetch"],
+                            "timeout": 300
+                        }
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let fetch = &index.extensions[0];
+        assert_eq!(fetch.source, RegistrySource::Community);
+        assert_eq!(fetch.install_command(), "uvx mcp-server-fetch");
+
+        let mut entries = bundled_extensions();
+        entries.extend(index.extensions);
+        let ids: Vec<&str> = search(&entries, "WEB")
+            .iter()
+            .map(|e| e.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["computercontroller", "fetch"]);
+    }
+}
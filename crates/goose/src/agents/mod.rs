@@ -1,12 +1,15 @@
 mod agent;
+pub mod content_filter;
 pub mod extension;
 pub mod extension_malware_check;
 pub mod extension_manager;
 pub mod extension_manager_extension;
+pub mod extension_registry;
 pub mod final_output_tool;
 mod large_response_handler;
 pub mod mcp_client;
 pub mod model_selector;
+mod operations;
 pub mod platform_tools;
 pub mod prompt_manager;
 pub mod recipe_tools;
@@ -21,13 +24,17 @@ pub mod subagent_handler;
 mod subagent_task_config;
 pub(crate) mod todo_extension;
 mod tool_execution;
+mod tool_rate_limiter;
+mod tool_result_cache;
 mod tool_route_manager;
 mod tool_router_index_manager;
 pub mod types;
 
 pub use agent::{Agent, AgentEvent};
+pub use content_filter::{ContentFilter, FilterOutcome, FilterStage};
 pub use extension::ExtensionConfig;
 pub use extension_manager::ExtensionManager;
+pub use operations::OperationInfo;
 pub use prompt_manager::PromptManager;
 pub use subagent_task_config::TaskConfig;
-pub use types::{FrontendTool, RetryConfig, SessionConfig, SuccessCheck};
+pub use types::{FrontendTool, PromptDescriptor, RetryConfig, SessionConfig, SuccessCheck};
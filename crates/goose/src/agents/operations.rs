@@ -0,0 +1,156 @@
+//! The replies an agent is running, so they can be listed and cancelled one at a time
+
+use crate::conversation::message::{Message, MessageContent};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::CancellationToken;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A reply that is still running
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct OperationInfo {
+    /// Identifies the reply for `Agent::cancel_operation`
+    pub id: String,
+    pub session_id: Option<String>,
+    pub started_at: DateTime<Utc>,
+    /// The tools the reply is waiting on, empty while the model is responding
+    pub current_tools: Vec<String>,
+}
+
+struct Operation {
+    info: OperationInfo,
+    cancel_token: CancellationToken,
+}
+
+#[derive(Default)]
+pub(super) struct OperationRegistry {
+    operations: Mutex<HashMap<String, Operation>>,
+}
+
+impl OperationRegistry {
+    /// Track a reply until the returned guard is dropped
+    pub fn start(
+        self: &Arc<Self>,
+        session_id: Option<String>,
+        cancel_token: CancellationToken,
+    ) -> OperationGuard {
+        let id = Uuid::new_v4().to_string();
+        let info = OperationInfo {
+            id: id.clone(),
+            session_id,
+            started_at: Utc::now(),
+            current_tools: Vec::new(),
+        };
+        self.operations
+            .lock()
+            .unwrap()
+            .insert(id.clone(), Operation { info, cancel_token });
+        OperationGuard {
+            registry: self.clone(),
+            id,
+        }
+    }
+
+    /// Oldest first
+    pub fn list(&self) -> Vec<OperationInfo> {
+        let mut operations: Vec<OperationInfo> = self
+            .operations
+            .lock()
+            .unwrap()
+            .values()
+            .map(|operation| operation.info.clone())
+            .collect();
+        operations.sort_by_key(|info| info.started_at);
+        operations
+    }
+
+    /// Whether an operation with this id was running
+    pub fn cancel(&self, id: &str) -> bool {
+        match self.operations.lock().unwrap().get(id) {
+            Some(operation) => {
+                operation.cancel_token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn update(&self, id: &str, update: impl FnOnce(&mut OperationInfo)) {
+        if let Some(operation) = self.operations.lock().unwrap().get_mut(id) {
+            update(&mut operation.info);
+        }
+    }
+}
+
+/// Removes its operation from the registry when dropped, along with the reply stream
+pub(super) struct OperationGuard {
+    registry: Arc<OperationRegistry>,
+    id: String,
+}
+
+impl OperationGuard {
+    /// Follow which tools the reply is waiting on from the messages it streams: a tool request
+    /// starts them and the matching response means they are done
+    pub fn observe(&self, message: &Message) {
+        let requested: Vec<String> = message
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                MessageContent::ToolRequest(request) => request.tool_call.as_ref().ok(),
+                _ => None,
+            })
+            .map(|call| call.name.to_string())
+            .collect();
+        if !requested.is_empty() {
+            self.registry
+                .update(&self.id, |info| info.current_tools = requested);
+        } else if message.is_tool_response() {
+            self.registry
+                .update(&self.id, |info| info.current_tools.clear());
+        }
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        self.registry.operations.lock().unwrap().remove(&self.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::CallToolRequestParam;
+
+    #[test]
+    fn test_operations_are_tracked_until_dropped() {
+        let registry = Arc::new(OperationRegistry::default());
+        let token = CancellationToken::new();
+        let guard = registry.start(Some("session".to_string()), token.clone());
+
+        guard.observe(&Message::assistant().with_tool_request(
+            "call",
+            Ok(CallToolRequestParam {
+                name: "developer__shell".into(),
+                arguments: None,
+            }),
+        ));
+        let operations = registry.list();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(operations[0].session_id.as_deref(), Some("session"));
+        assert_eq!(operations[0].current_tools, vec!["developer__shell"]);
+
+        guard.observe(&Message::user().with_tool_response("call", Ok(vec![])));
+        assert!(registry.list()[0].current_tools.is_empty());
+
+        assert!(!registry.cancel("unknown"));
+        assert!(registry.cancel(&operations[0].id));
+        assert!(token.is_cancelled());
+
+        drop(guard);
+        assert!(registry.list().is_empty());
+    }
+}
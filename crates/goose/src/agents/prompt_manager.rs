@@ -7,7 +7,7 @@ use std::collections::HashMap;
 use crate::agents::extension::ExtensionInfo;
 use crate::agents::recipe_tools::dynamic_task_tools::should_enabled_subagents;
 use crate::agents::router_tools::llm_search_tool_prompt;
-use crate::{config::Config, prompt_template, utils::sanitize_unicode_tags};
+use crate::{config::GooseMode, prompt_template, utils::sanitize_unicode_tags};
 
 pub struct PromptManager {
     system_prompt_override: Option<String>,
@@ -105,9 +105,8 @@ impl PromptManager {
             Value::String(suggest_disable_extensions_prompt.to_string()),
         );
 
-        let config = Config::global();
-        let goose_mode = config.get_param("GOOSE_MODE").unwrap_or("auto".to_string());
-        context.insert("goose_mode", Value::String(goose_mode.clone()));
+        let goose_mode = GooseMode::from_config();
+        context.insert("goose_mode", Value::String(goose_mode.to_string()));
         context.insert(
             "enable_subagents",
             Value::Bool(should_enabled_subagents(model_name)),
@@ -124,7 +123,7 @@ impl PromptManager {
         });
 
         let mut system_prompt_extras = self.system_prompt_extras.clone();
-        if goose_mode == "chat" {
+        if goose_mode == GooseMode::Chat {
             system_prompt_extras.push(
                 "Right now you are in the chat only mode, no access to any tool use and system."
                     .to_string(),
@@ -3,21 +3,108 @@ use std::sync::Arc;
 
 use async_stream::try_stream;
 use futures::stream::StreamExt;
-use tracing::debug;
+use std::ops::Deref;
+use tracing::{debug, warn, Instrument};
 
 use super::super::agents::Agent;
 use crate::conversation::message::{Message, MessageContent, ToolRequest};
 use crate::conversation::Conversation;
-use crate::providers::base::{stream_from_single_message, MessageStream, Provider, ProviderUsage};
+use crate::providers::base::{
+    stream_from_single_message, with_request_metadata, MessageStream, Provider,
+    ProviderCapabilities, ProviderUsage, RequestMetadata,
+};
 use crate::providers::errors::ProviderError;
 use crate::providers::toolshim::{
     augment_message_with_tool_calls, convert_tool_messages_to_text,
     modify_system_prompt_for_tool_json, OllamaInterpreter,
 };
 
+use crate::agents::final_output_tool::FINAL_OUTPUT_TOOL_NAME;
 use crate::agents::recipe_tools::dynamic_task_tools::should_enabled_subagents;
-use crate::session::SessionManager;
-use rmcp::model::Tool;
+use crate::context_mgmt::dedup;
+use crate::session::{ExtensionState, SessionManager, TokenUsageState, TurnUsage};
+use rmcp::model::{Content, RawContent, Tool};
+
+const IMAGE_OMITTED: &str = "[An image was omitted because the model does not accept images]";
+
+/// Replace images in messages and tool results with a note, for providers without vision
+fn omit_images(messages: Conversation) -> Conversation {
+    let messages = messages
+        .messages()
+        .iter()
+        .map(|message| {
+            let mut message = message.clone();
+            for content in message.content.iter_mut() {
+                match content {
+                    MessageContent::Image(_) => *content = MessageContent::text(IMAGE_OMITTED),
+                    MessageContent::ToolResponse(response) => {
+                        if let Ok(contents) = response.tool_result.as_mut() {
+                            for item in contents.iter_mut() {
+                                if matches!(item.deref(), RawContent::Image(_)) {
+                                    *item = Content::text(IMAGE_OMITTED);
+                                }
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            message
+        })
+        .collect();
+    Conversation::new_unvalidated(messages)
+}
+
+/// Tools the agent cannot work without, kept when a provider limits how many tools it accepts
+fn is_essential_tool(tool: &Tool) -> bool {
+    tool.name.starts_with("platform__") || tool.name == FINAL_OUTPUT_TOOL_NAME
+}
+
+/// Drop what a provider has said it cannot accept, rather than send a request that errors
+fn adapt_to_capabilities(
+    messages: Conversation,
+    mut tools: Vec<Tool>,
+    capabilities: &ProviderCapabilities,
+) -> (Conversation, Vec<Tool>) {
+    let messages = if capabilities.vision {
+        messages
+    } else {
+        omit_images(messages)
+    };
+
+    if !capabilities.tools {
+        tools.clear();
+    } else if let Some(max_tools) = capabilities.max_tools {
+        if tools.len() > max_tools {
+            // Essential tools are always kept, the others fill the remaining slots in order
+            let essential = tools.iter().filter(|tool| is_essential_tool(tool)).count();
+            let mut other_slots = max_tools.saturating_sub(essential);
+            let (kept, dropped): (Vec<Tool>, Vec<Tool>) = tools.into_iter().partition(|tool| {
+                if is_essential_tool(tool) {
+                    return true;
+                }
+                if other_slots == 0 {
+                    return false;
+                }
+                other_slots -= 1;
+                true
+            });
+            warn!(
+                "Provider accepts at most {} tools, dropping {} of them from the request: {}",
+                max_tools,
+                dropped.len(),
+                dropped
+                    .iter()
+                    .map(|tool| tool.name.as_ref())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            tools = kept;
+        }
+    }
+
+    (messages, tools)
+}
 
 async fn toolshim_postprocess(
     response: Message,
@@ -106,8 +193,10 @@ impl Agent {
         messages: &[Message],
         tools: &[Tool],
         toolshim_tools: &[Tool],
+        request_metadata: RequestMetadata,
     ) -> Result<MessageStream, ProviderError> {
         let config = provider.get_model_config();
+        let span = tracing::info_span!("provider_request", request_metadata = ?request_metadata);
 
         // Convert tool messages to text if toolshim is enabled
         let messages_for_provider = if config.toolshim {
@@ -115,10 +204,21 @@ impl Agent {
         } else {
             Conversation::new_unvalidated(messages.to_vec())
         };
+        let (messages_for_provider, tools) = adapt_to_capabilities(
+            messages_for_provider,
+            tools.to_owned(),
+            &provider.capabilities(),
+        );
+        let messages_for_provider = if dedup::is_enabled() {
+            Conversation::new_unvalidated(dedup::dedup_repeated_content(
+                messages_for_provider.messages(),
+            ))
+        } else {
+            messages_for_provider
+        };
 
         // Clone owned data to move into the async stream
         let system_prompt = system_prompt.to_owned();
-        let tools = tools.to_owned();
         let toolshim_tools = toolshim_tools.to_owned();
         let provider = provider.clone();
 
@@ -126,24 +226,30 @@ impl Agent {
         // so they can be handled by the existing error handling logic in the agent
         let stream_result = if provider.supports_streaming() {
             debug!("WAITING_LLM_STREAM_START");
-            let result = provider
-                .stream(
+            let result = with_request_metadata(
+                request_metadata,
+                provider.stream(
                     system_prompt.as_str(),
                     messages_for_provider.messages(),
                     &tools,
-                )
-                .await;
+                ),
+            )
+            .instrument(span)
+            .await;
             debug!("WAITING_LLM_STREAM_END");
             result
         } else {
             debug!("WAITING_LLM_START");
-            let complete_result = provider
-                .complete(
+            let complete_result = with_request_metadata(
+                request_metadata,
+                provider.complete(
                     system_prompt.as_str(),
                     messages_for_provider.messages(),
                     &tools,
-                )
-                .await;
+                ),
+            )
+            .instrument(span)
+            .await;
             debug!("WAITING_LLM_END");
 
             match complete_result {
@@ -273,6 +379,17 @@ impl Agent {
         let accumulated_output =
             accumulate(session.accumulated_output_tokens, usage.usage.output_tokens);
 
+        let mut extension_data = session.extension_data;
+        let mut token_usage =
+            TokenUsageState::from_extension_data(&extension_data).unwrap_or_default();
+        token_usage.record(TurnUsage {
+            message_count: session.message_count,
+            input_tokens: usage.usage.input_tokens,
+            output_tokens: usage.usage.output_tokens,
+            total_tokens: usage.usage.total_tokens,
+        });
+        token_usage.to_extension_data(&mut extension_data)?;
+
         SessionManager::update_session(session_id)
             .schedule_id(session_config.schedule_id.clone())
             .total_tokens(usage.usage.total_tokens)
@@ -281,6 +398,7 @@ impl Agent {
             .accumulated_total_tokens(accumulated_total)
             .accumulated_input_tokens(accumulated_input)
             .accumulated_output_tokens(accumulated_output)
+            .extension_data(extension_data)
             .apply()
             .await?;
 
@@ -291,6 +409,7 @@ impl Agent {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agents::platform_tools::PLATFORM_MANAGE_SCHEDULE_TOOL_NAME;
     use crate::conversation::message::Message;
     use crate::model::ModelConfig;
     use crate::providers::base::{Provider, ProviderUsage, Usage};
@@ -380,4 +499,82 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn adapt_to_capabilities_omits_images_and_limits_tools() {
+        let messages = Conversation::new_unvalidated(vec![
+            Message::user()
+                .with_text("what is this?")
+                .with_image("aGVsbG8=", "image/png"),
+            Message::user().with_tool_response(
+                "1",
+                Ok(vec![
+                    Content::image("aGVsbG8=", "image/png"),
+                    Content::text("done"),
+                ]),
+            ),
+        ]);
+        let tools: Vec<Tool> = ["a", "b", "c"]
+            .iter()
+            .map(|name| Tool::new(name.to_string(), "", object!({ "type": "object" })))
+            .collect();
+        let capabilities = ProviderCapabilities {
+            vision: false,
+            tools: true,
+            streaming: false,
+            embeddings: false,
+            json_mode: false,
+            max_tools: Some(2),
+        };
+
+        let (adapted, adapted_tools) =
+            adapt_to_capabilities(messages.clone(), tools.clone(), &capabilities);
+        assert_eq!(
+            adapted.messages()[0].content[1].as_text(),
+            Some(IMAGE_OMITTED)
+        );
+        let tool_result = adapted.messages()[1].content[0]
+            .as_tool_response()
+            .unwrap()
+            .tool_result
+            .clone()
+            .unwrap();
+        assert_eq!(tool_result[0].as_text().unwrap().text, IMAGE_OMITTED);
+        assert_eq!(adapted_tools.len(), 2);
+
+        // Platform and final output tools survive the limit, even at the end of the list
+        let mut with_essential = tools.clone();
+        with_essential.push(Tool::new(
+            PLATFORM_MANAGE_SCHEDULE_TOOL_NAME,
+            "",
+            object!({ "type": "object" }),
+        ));
+        with_essential.push(Tool::new(
+            FINAL_OUTPUT_TOOL_NAME,
+            "",
+            object!({ "type": "object" }),
+        ));
+        let (_, adapted_tools) =
+            adapt_to_capabilities(messages.clone(), with_essential, &capabilities);
+        let names: Vec<_> = adapted_tools
+            .iter()
+            .map(|tool| tool.name.as_ref())
+            .collect();
+        assert_eq!(
+            names,
+            vec![PLATFORM_MANAGE_SCHEDULE_TOOL_NAME, FINAL_OUTPUT_TOOL_NAME]
+        );
+
+        let capabilities = ProviderCapabilities {
+            vision: true,
+            tools: false,
+            ..capabilities
+        };
+        let (adapted, adapted_tools) = adapt_to_capabilities(messages, tools, &capabilities);
+        assert!(matches!(
+            adapted.messages()[0].content[1],
+            MessageContent::Image(_)
+        ));
+        assert!(adapted_tools.is_empty());
+    }
 }
@@ -4,10 +4,11 @@ use crate::agents::subagent_execution_tool::lib::{
 use crate::agents::subagent_execution_tool::task_execution_tracker::{
     DisplayMode, TaskExecutionTracker,
 };
-use crate::agents::subagent_execution_tool::tasks::process_task;
+use crate::agents::subagent_execution_tool::tasks::{cancelled_task_result, process_task};
 use crate::agents::subagent_execution_tool::workers::spawn_worker;
 use crate::agents::subagent_task_config::TaskConfig;
 use rmcp::model::ServerNotification;
+use std::collections::HashSet;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 use tokio::sync::mpsc;
@@ -16,6 +17,7 @@ use tokio::time::Instant;
 use tokio_util::sync::CancellationToken;
 
 const EXECUTION_STATUS_COMPLETED: &str = "completed";
+const EXECUTION_STATUS_CANCELLED: &str = "cancelled";
 const DEFAULT_MAX_WORKERS: usize = 10;
 
 pub async fn execute_single_task(
@@ -73,11 +75,23 @@ pub async fn execute_tasks_in_parallel(
         return create_empty_response();
     }
 
+    let cancellation_token = cancellation_token.unwrap_or_default();
+    if cancellation_token.is_cancelled() {
+        let results: Vec<TaskResult> = tasks.iter().map(cancelled_task_result).collect();
+        task_execution_tracker.send_tasks_cancelled(&results).await;
+        let stats = calculate_stats(&results, start_time.elapsed().as_millis());
+        return ExecutionResponse {
+            status: EXECUTION_STATUS_CANCELLED.to_string(),
+            results,
+            stats,
+        };
+    }
+
     task_execution_tracker.refresh_display().await;
 
     let (task_tx, task_rx, result_tx, mut result_rx) = create_channels(task_count);
 
-    if let Err(e) = send_tasks_to_channel(tasks, task_tx).await {
+    if let Err(e) = send_tasks_to_channel(tasks.clone(), task_tx).await {
         tracing::error!("Task execution failed: {}", e);
         return create_error_response(e);
     }
@@ -86,7 +100,7 @@ pub async fn execute_tasks_in_parallel(
         task_rx,
         result_tx,
         task_execution_tracker.clone(),
-        cancellation_token.unwrap_or_default(),
+        cancellation_token.clone(),
     );
 
     let worker_count = std::cmp::min(task_count, DEFAULT_MAX_WORKERS);
@@ -96,7 +110,17 @@ pub async fn execute_tasks_in_parallel(
         worker_handles.push(handle);
     }
 
-    let results = collect_results(&mut result_rx, task_execution_tracker.clone(), task_count).await;
+    // Only the workers should keep the result channel open, so that cancellation
+    // can't leave us waiting on results that will never arrive
+    drop(shared_state);
+
+    let mut results = collect_results(
+        &mut result_rx,
+        task_execution_tracker.clone(),
+        task_count,
+        &cancellation_token,
+    )
+    .await;
 
     for handle in worker_handles {
         if let Err(e) = handle.await {
@@ -104,18 +128,39 @@ pub async fn execute_tasks_in_parallel(
         }
     }
 
-    task_execution_tracker.send_tasks_complete().await;
+    let status = if cancellation_token.is_cancelled() {
+        // Keep results of tasks that finished while we were shutting down
+        while let Ok(result) = result_rx.try_recv() {
+            results.push(result);
+        }
+        mark_remaining_as_cancelled(&tasks, &mut results);
+        task_execution_tracker.send_tasks_cancelled(&results).await;
+        EXECUTION_STATUS_CANCELLED
+    } else {
+        task_execution_tracker.send_tasks_complete().await;
+        EXECUTION_STATUS_COMPLETED
+    };
 
     let execution_time = start_time.elapsed().as_millis();
     let stats = calculate_stats(&results, execution_time);
 
     ExecutionResponse {
-        status: EXECUTION_STATUS_COMPLETED.to_string(),
+        status: status.to_string(),
         results,
         stats,
     }
 }
 
+fn mark_remaining_as_cancelled(tasks: &[Task], results: &mut Vec<TaskResult>) {
+    let reported: HashSet<String> = results.iter().map(|r| r.task_id.clone()).collect();
+    results.extend(
+        tasks
+            .iter()
+            .filter(|task| !reported.contains(&task.id))
+            .map(cancelled_task_result),
+    );
+}
+
 fn calculate_stats(results: &[TaskResult], execution_time_ms: u128) -> ExecutionStats {
     let completed = results
         .iter()
@@ -125,11 +170,16 @@ fn calculate_stats(results: &[TaskResult], execution_time_ms: u128) -> Execution
         .iter()
         .filter(|r| matches!(r.status, TaskStatus::Failed))
         .count();
+    let cancelled = results
+        .iter()
+        .filter(|r| matches!(r.status, TaskStatus::Cancelled))
+        .count();
 
     ExecutionStats {
         total_tasks: results.len(),
         completed,
         failed,
+        cancelled,
         execution_time_ms,
     }
 }
@@ -183,6 +233,7 @@ fn create_empty_response() -> ExecutionResponse {
             total_tasks: 0,
             completed: 0,
             failed: 0,
+            cancelled: 0,
             execution_time_ms: 0,
         },
     }
@@ -191,9 +242,17 @@ async fn collect_results(
     result_rx: &mut mpsc::Receiver<TaskResult>,
     task_execution_tracker: Arc<TaskExecutionTracker>,
     expected_count: usize,
+    cancellation_token: &CancellationToken,
 ) -> Vec<TaskResult> {
     let mut results = Vec::new();
-    while let Some(result) = result_rx.recv().await {
+    loop {
+        let result = tokio::select! {
+            result = result_rx.recv() => match result {
+                Some(result) => result,
+                None => break,
+            },
+            _ = cancellation_token.cancelled() => break,
+        };
         task_execution_tracker
             .complete_task(&result.task_id, result.clone())
             .await;
@@ -215,6 +274,7 @@ fn create_error_response(error: String) -> ExecutionResponse {
             total_tasks: 0,
             completed: 0,
             failed: 1,
+            cancelled: 0,
             execution_time_ms: 0,
         },
     }
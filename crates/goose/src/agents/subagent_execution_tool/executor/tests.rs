@@ -13,6 +13,7 @@ fn create_test_task_result(task_id: &str, status: TaskStatus) -> TaskResult {
         } else {
             None
         },
+        task_description: None,
     }
 }
 
@@ -74,6 +75,22 @@ fn test_calculate_stats_all_failed() {
     assert_eq!(stats.execution_time_ms, 1200);
 }
 
+#[test]
+fn test_calculate_stats_with_cancelled() {
+    let results = vec![
+        create_test_task_result("task1", TaskStatus::Completed),
+        create_test_task_result("task2", TaskStatus::Cancelled),
+        create_test_task_result("task3", TaskStatus::Cancelled),
+    ];
+
+    let stats = calculate_stats(&results, 300);
+
+    assert_eq!(stats.total_tasks, 3);
+    assert_eq!(stats.completed, 1);
+    assert_eq!(stats.failed, 0);
+    assert_eq!(stats.cancelled, 2);
+}
+
 #[test]
 fn test_create_empty_response() {
     let response = create_empty_response();
@@ -6,8 +6,12 @@ use crate::agents::subagent_execution_tool::{
     tasks_manager::TasksManager,
 };
 use crate::agents::subagent_task_config::TaskConfig;
+use crate::config::paths::Paths;
+use crate::config::Config;
 use rmcp::model::ServerNotification;
 use serde_json::{json, Value};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use tokio::sync::mpsc::Sender;
 use tokio_util::sync::CancellationToken;
 
@@ -20,6 +24,7 @@ pub async fn execute_tasks(
     cancellation_token: Option<CancellationToken>,
 ) -> Result<Value, String> {
     let tasks = tasks_manager.get_tasks(&task_ids).await?;
+    let output_dir = subagent_output_dir(&task_config.parent_session_id);
 
     let task_count = tasks.len();
     match execution_mode {
@@ -27,7 +32,7 @@ pub async fn execute_tasks(
             if task_count == 1 {
                 let response =
                     execute_single_task(&tasks[0], notifier, task_config, cancellation_token).await;
-                handle_response(response)
+                handle_response(response, &output_dir)
             } else {
                 Err("Sequential execution mode requires exactly one task".to_string())
             }
@@ -49,21 +54,128 @@ pub async fn execute_tasks(
                     cancellation_token,
                 )
                 .await;
-                handle_response(response)
+                handle_response(response, &output_dir)
             }
         }
     }
 }
 
-fn extract_failed_tasks(results: &[TaskResult]) -> Vec<String> {
+const DEFAULT_MAX_PARTIAL_OUTPUT_CHARS: usize = 4_000;
+
+fn max_partial_output_chars() -> usize {
+    Config::global()
+        .get_param::<usize>("GOOSE_SUBAGENT_MAX_PARTIAL_OUTPUT_CHARS")
+        .unwrap_or(DEFAULT_MAX_PARTIAL_OUTPUT_CHARS)
+}
+
+/// Most full outputs kept for a session; the oldest are removed past this
+const MAX_SAVED_OUTPUTS_PER_SESSION: usize = 20;
+
+/// Where the full output of failed tasks is saved, separate for each session
+fn subagent_output_dir(session_id: &str) -> PathBuf {
+    Paths::in_state_dir("subagent_outputs").join(sanitize_file_name(session_id))
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn extract_failed_tasks(results: &[TaskResult], output_dir: &Path) -> Vec<String> {
+    let max_chars = max_partial_output_chars();
     results
         .iter()
-        .filter(|r| matches!(r.status, TaskStatus::Failed))
-        .map(format_failed_task_error)
+        .filter(|r| matches!(r.status, TaskStatus::Failed | TaskStatus::Cancelled))
+        .map(|r| format_failed_task_error(r, max_chars, output_dir))
         .collect()
 }
 
-fn format_failed_task_error(result: &TaskResult) -> String {
+/// Keeps the tail of the output, which is usually closest to the failure. The full
+/// output is written to a file in `output_dir` so it can still be inspected.
+fn truncate_partial_output(
+    task_id: &str,
+    output: &str,
+    max_chars: usize,
+    output_dir: &Path,
+) -> String {
+    let char_count = output.chars().count();
+    if char_count <= max_chars {
+        return output.to_string();
+    }
+
+    let tail: String = output.chars().skip(char_count - max_chars).collect();
+    let omitted = char_count - max_chars;
+    match write_full_output_to_file(task_id, output, output_dir) {
+        Ok(path) => format!(
+            "[... {} characters truncated, full output saved to {}]\n{}",
+            omitted,
+            path.display(),
+            tail
+        ),
+        Err(e) => {
+            tracing::warn!("Failed to save full output for task {}: {}", task_id, e);
+            format!("[... {} characters truncated]\n{}", omitted, tail)
+        }
+    }
+}
+
+/// Write `output` to a new file only the owner can read, then drop the oldest files in
+/// `output_dir` beyond [`MAX_SAVED_OUTPUTS_PER_SESSION`]
+fn write_full_output_to_file(
+    task_id: &str,
+    output: &str,
+    output_dir: &Path,
+) -> std::io::Result<PathBuf> {
+    create_private_dir(output_dir)?;
+
+    let mut file = tempfile::Builder::new()
+        .prefix(&format!("task_{}_", sanitize_file_name(task_id)))
+        .suffix(".txt")
+        .tempfile_in(output_dir)?;
+    file.write_all(output.as_bytes())?;
+    let (_, path) = file.keep().map_err(|e| e.error)?;
+
+    remove_old_outputs(output_dir, MAX_SAVED_OUTPUTS_PER_SESSION);
+    Ok(path)
+}
+
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    let mut builder = std::fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    builder.create(dir)
+}
+
+fn remove_old_outputs(dir: &Path, keep: usize) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((modified, entry.path()))
+        })
+        .collect();
+    if files.len() <= keep {
+        return;
+    }
+    files.sort();
+    for (_, path) in &files[..files.len() - keep] {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+fn format_failed_task_error(
+    result: &TaskResult,
+    max_partial_output_chars: usize,
+    output_dir: &Path,
+) -> String {
     let error_msg = result.error.as_deref().unwrap_or("Unknown error");
     let partial_output = result
         .data
@@ -71,7 +183,8 @@ fn format_failed_task_error(result: &TaskResult) -> String {
         .and_then(|d| d.get("partial_output"))
         .and_then(|v| v.as_str())
         .filter(|s| !s.trim().is_empty())
-        .unwrap_or("No output captured");
+        .map(|s| truncate_partial_output(&result.task_id, s, max_partial_output_chars, output_dir))
+        .unwrap_or_else(|| "No output captured".to_string());
 
     format!(
         "Task '{}' ({}): {}\nOutput: {}",
@@ -95,9 +208,38 @@ fn format_error_summary(
     )
 }
 
-fn handle_response(response: ExecutionResponse) -> Result<Value, String> {
+fn format_cancelled_summary(
+    cancelled_count: usize,
+    failed_count: usize,
+    total_count: usize,
+    unfinished_tasks: Vec<String>,
+) -> String {
+    let failed = if failed_count > 0 {
+        format!(" and {} failed", failed_count)
+    } else {
+        String::new()
+    };
+    format!(
+        "Execution was cancelled: {}/{} tasks were cancelled{}:\n{}",
+        cancelled_count,
+        total_count,
+        failed,
+        unfinished_tasks.join("\n")
+    )
+}
+
+fn handle_response(response: ExecutionResponse, output_dir: &Path) -> Result<Value, String> {
+    if response.stats.cancelled > 0 {
+        let failed_tasks = extract_failed_tasks(&response.results, output_dir);
+        return Err(format_cancelled_summary(
+            response.stats.cancelled,
+            response.stats.failed,
+            response.stats.total_tasks,
+            failed_tasks,
+        ));
+    }
     if response.stats.failed > 0 {
-        let failed_tasks = extract_failed_tasks(&response.results);
+        let failed_tasks = extract_failed_tasks(&response.results, output_dir);
         let error_summary = format_error_summary(
             response.stats.failed,
             response.stats.total_tasks,
@@ -109,5 +251,38 @@ fn handle_response(response: ExecutionResponse) -> Result<Value, String> {
 }
 
 fn get_task_description(result: &TaskResult) -> String {
-    format!("ID: {}", result.task_id)
+    result
+        .task_description
+        .clone()
+        .unwrap_or_else(|| format!("ID: {}", result.task_id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_output_file_is_private_and_old_files_are_removed() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let session_dir = output_dir.path().join("session");
+
+        let path = write_full_output_to_file("task/8", "full output", &session_dir).unwrap();
+        assert!(path.starts_with(&session_dir));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "full output");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = |path: &std::path::Path| {
+                std::fs::metadata(path).unwrap().permissions().mode() & 0o777
+            };
+            assert_eq!(mode(&session_dir), 0o700);
+            assert_eq!(mode(&path), 0o600);
+        }
+
+        for _ in 0..4 {
+            write_full_output_to_file("task8", "full output", &session_dir).unwrap();
+        }
+        remove_old_outputs(&session_dir, 2);
+        assert_eq!(std::fs::read_dir(&session_dir).unwrap().count(), 2);
+    }
 }
@@ -1,6 +1,6 @@
 use super::{
-    extract_failed_tasks, format_error_summary, format_failed_task_error, get_task_description,
-    handle_response,
+    extract_failed_tasks, format_cancelled_summary, format_error_summary, format_failed_task_error,
+    get_task_description, handle_response, truncate_partial_output,
 };
 use crate::agents::sub_recipe_execution_tool::lib::{
     ExecutionResponse, ExecutionStats, TaskResult, TaskStatus,
@@ -13,6 +13,7 @@ fn create_test_task_result(task_id: &str, status: TaskStatus, error: Option<Stri
         status,
         data: Some(json!({"partial_output": "test output"})),
         error,
+        task_description: None,
     }
 }
 
@@ -27,6 +28,7 @@ fn create_test_execution_response(
             total_tasks: results.len(),
             completed: results.len() - failed_count,
             failed: failed_count,
+            cancelled: 0,
             execution_time_ms: 1000,
         },
     }
@@ -34,6 +36,7 @@ fn create_test_execution_response(
 
 #[test]
 fn test_extract_failed_tasks() {
+    let output_dir = tempfile::tempdir().unwrap();
     let results = vec![
         create_test_task_result("task1", TaskStatus::Completed, None),
         create_test_task_result(
@@ -49,7 +52,7 @@ fn test_extract_failed_tasks() {
         ),
     ];
 
-    let failed_tasks = extract_failed_tasks(&results);
+    let failed_tasks = extract_failed_tasks(&results, output_dir.path());
 
     assert_eq!(failed_tasks.len(), 2);
     assert!(failed_tasks[0].contains("task2"));
@@ -60,25 +63,27 @@ fn test_extract_failed_tasks() {
 
 #[test]
 fn test_extract_failed_tasks_empty() {
+    let output_dir = tempfile::tempdir().unwrap();
     let results = vec![
         create_test_task_result("task1", TaskStatus::Completed, None),
         create_test_task_result("task2", TaskStatus::Completed, None),
     ];
 
-    let failed_tasks = extract_failed_tasks(&results);
+    let failed_tasks = extract_failed_tasks(&results, output_dir.path());
 
     assert_eq!(failed_tasks.len(), 0);
 }
 
 #[test]
 fn test_format_failed_task_error_with_error_message() {
+    let output_dir = tempfile::tempdir().unwrap();
     let result = create_test_task_result(
         "task1",
         TaskStatus::Failed,
         Some("Test error message".to_string()),
     );
 
-    let formatted = format_failed_task_error(&result);
+    let formatted = format_failed_task_error(&result, 4_000, output_dir.path());
 
     assert!(formatted.contains("task1"));
     assert!(formatted.contains("Test error message"));
@@ -88,9 +93,10 @@ fn test_format_failed_task_error_with_error_message() {
 
 #[test]
 fn test_format_failed_task_error_without_error_message() {
+    let output_dir = tempfile::tempdir().unwrap();
     let result = create_test_task_result("task2", TaskStatus::Failed, None);
 
-    let formatted = format_failed_task_error(&result);
+    let formatted = format_failed_task_error(&result, 4_000, output_dir.path());
 
     assert!(formatted.contains("task2"));
     assert!(formatted.contains("Unknown error"));
@@ -99,33 +105,36 @@ fn test_format_failed_task_error_without_error_message() {
 
 #[test]
 fn test_format_failed_task_error_empty_partial_output() {
+    let output_dir = tempfile::tempdir().unwrap();
     let mut result =
         create_test_task_result("task3", TaskStatus::Failed, Some("Error".to_string()));
     result.data = Some(json!({"partial_output": ""}));
 
-    let formatted = format_failed_task_error(&result);
+    let formatted = format_failed_task_error(&result, 4_000, output_dir.path());
 
     assert!(formatted.contains("No output captured"));
 }
 
 #[test]
 fn test_format_failed_task_error_no_partial_output() {
+    let output_dir = tempfile::tempdir().unwrap();
     let mut result =
         create_test_task_result("task4", TaskStatus::Failed, Some("Error".to_string()));
     result.data = Some(json!({}));
 
-    let formatted = format_failed_task_error(&result);
+    let formatted = format_failed_task_error(&result, 4_000, output_dir.path());
 
     assert!(formatted.contains("No output captured"));
 }
 
 #[test]
 fn test_format_failed_task_error_no_data() {
+    let output_dir = tempfile::tempdir().unwrap();
     let mut result =
         create_test_task_result("task5", TaskStatus::Failed, Some("Error".to_string()));
     result.data = None;
 
-    let formatted = format_failed_task_error(&result);
+    let formatted = format_failed_task_error(&result, 4_000, output_dir.path());
 
     assert!(formatted.contains("No output captured"));
 }
@@ -156,13 +165,14 @@ fn test_format_error_summary_single_failure() {
 
 #[test]
 fn test_handle_response_success() {
+    let output_dir = tempfile::tempdir().unwrap();
     let results = vec![
         create_test_task_result("task1", TaskStatus::Completed, None),
         create_test_task_result("task2", TaskStatus::Completed, None),
     ];
     let response = create_test_execution_response(results, 0);
 
-    let result = handle_response(response);
+    let result = handle_response(response, output_dir.path());
 
     assert!(result.is_ok());
     let value = result.unwrap();
@@ -172,13 +182,14 @@ fn test_handle_response_success() {
 
 #[test]
 fn test_handle_response_with_failures() {
+    let output_dir = tempfile::tempdir().unwrap();
     let results = vec![
         create_test_task_result("task1", TaskStatus::Completed, None),
         create_test_task_result("task2", TaskStatus::Failed, Some("Test error".to_string())),
     ];
     let response = create_test_execution_response(results, 1);
 
-    let result = handle_response(response);
+    let result = handle_response(response, output_dir.path());
 
     assert!(result.is_err());
     let error = result.unwrap_err();
@@ -189,13 +200,14 @@ fn test_handle_response_with_failures() {
 
 #[test]
 fn test_handle_response_all_failures() {
+    let output_dir = tempfile::tempdir().unwrap();
     let results = vec![
         create_test_task_result("task1", TaskStatus::Failed, Some("Error 1".to_string())),
         create_test_task_result("task2", TaskStatus::Failed, Some("Error 2".to_string())),
     ];
     let response = create_test_execution_response(results, 2);
 
-    let result = handle_response(response);
+    let result = handle_response(response, output_dir.path());
 
     assert!(result.is_err());
     let error = result.unwrap_err();
@@ -214,3 +226,56 @@ fn test_get_task_description() {
 
     assert_eq!(description, "ID: test_task_123");
 }
+
+#[test]
+fn test_get_task_description_uses_task_description() {
+    let mut result = create_test_task_result("test_task_123", TaskStatus::Failed, None);
+    result.task_description = Some("sub-recipe 'weather' with city=Paris".to_string());
+
+    let description = get_task_description(&result);
+
+    assert_eq!(description, "sub-recipe 'weather' with city=Paris");
+}
+
+#[test]
+fn test_format_failed_task_error_truncates_large_partial_output() {
+    let output_dir = tempfile::tempdir().unwrap();
+    let mut result =
+        create_test_task_result("task6", TaskStatus::Failed, Some("Error".to_string()));
+    let output = format!("{}the end", "x".repeat(10_000));
+    result.data = Some(json!({ "partial_output": output }));
+
+    let formatted = format_failed_task_error(&result, 100, output_dir.path());
+
+    assert!(formatted.contains("characters truncated"));
+    assert!(formatted.contains("the end"));
+    assert!(formatted.len() < 1_000);
+}
+
+#[test]
+fn test_truncate_partial_output_keeps_small_output() {
+    let output_dir = tempfile::tempdir().unwrap();
+    assert_eq!(
+        truncate_partial_output("task7", "short", 100, output_dir.path()),
+        "short"
+    );
+}
+
+#[test]
+fn test_format_cancelled_summary() {
+    let tasks = vec![
+        "Task 'task1': Task cancelled before it started\nOutput: No output captured".to_string(),
+    ];
+
+    let summary = format_cancelled_summary(1, 0, 3, tasks.clone());
+    assert_eq!(
+        summary,
+        "Execution was cancelled: 1/3 tasks were cancelled:\nTask 'task1': Task cancelled before it started\nOutput: No output captured"
+    );
+    assert!(!summary.contains("failed"));
+
+    let summary = format_cancelled_summary(1, 1, 3, tasks);
+    assert!(
+        summary.starts_with("Execution was cancelled: 1/3 tasks were cancelled and 1 failed:\n")
+    );
+}
@@ -6,6 +6,8 @@ use crate::agents::{
     subagent_execution_tool::task_types::ExecutionMode,
     subagent_execution_tool::tasks_manager::TasksManager, tool_execution::ToolCallResult,
 };
+use crate::conversation::message::{Message, MessageMetadata};
+use crate::mcp_utils::ToolResult;
 use rmcp::model::{Content, ErrorCode, ErrorData, ServerNotification, Tool, ToolAnnotations};
 use rmcp::object;
 use tokio::sync::mpsc;
@@ -13,6 +15,12 @@ use tokio_stream;
 use tokio_util::sync::CancellationToken;
 
 pub const SUBAGENT_EXECUTE_TASK_TOOL_NAME: &str = "subagent__execute_task";
+
+/// When enabled, the final response of each completed subagent task is also added to the parent
+/// conversation as its own message, tagged with the task, so the parent model can reason over it
+/// directly instead of digging it out of the tool result
+pub const SUBAGENT_RESPONSES_IN_CONVERSATION_KEY: &str = "GOOSE_SUBAGENT_RESPONSES_IN_CONVERSATION";
+
 pub fn create_subagent_execute_task_tool() -> Tool {
     Tool::new(
         SUBAGENT_EXECUTE_TASK_TOOL_NAME,
@@ -100,3 +108,84 @@ pub async fn run_tasks(
         notification_stream: Some(Box::new(notification_stream)),
     }
 }
+
+/// The final responses of the completed tasks in a subagent__execute_task result, each as an
+/// agent-only message tagged with the task description
+pub fn subagent_response_messages(result: &ToolResult<Vec<Content>>) -> Vec<Message> {
+    let Ok(contents) = result else {
+        return Vec::new();
+    };
+
+    contents
+        .iter()
+        .filter_map(|content| content.as_text())
+        .filter_map(|text| serde_json::from_str::<serde_json::Value>(&text.text).ok())
+        .flat_map(|response| {
+            response
+                .get("results")
+                .and_then(|results| results.as_array())
+                .cloned()
+                .unwrap_or_default()
+        })
+        .filter(|result| result.get("status").and_then(|s| s.as_str()) == Some("Completed"))
+        .filter_map(|result| {
+            let text = result.get("data")?.get("result")?.as_str()?;
+            let name = result
+                .get("task_description")
+                .and_then(|d| d.as_str())
+                .map(str::to_string)
+                .or_else(|| {
+                    result
+                        .get("task_id")
+                        .and_then(|id| id.as_str())
+                        .map(|id| format!("task {}", id))
+                })?;
+            Some(
+                Message::user()
+                    .with_text(format!("[subagent {}]\n{}", name, text))
+                    .with_metadata(MessageMetadata::agent_only()),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_subagent_response_messages() {
+        let output = json!({
+            "status": "completed",
+            "results": [
+                {
+                    "task_id": "a",
+                    "status": "Completed",
+                    "data": { "result": "The weather is sunny" },
+                    "task_description": "sub-recipe 'weather'"
+                },
+                {
+                    "task_id": "b",
+                    "status": "Failed",
+                    "error": "boom"
+                }
+            ],
+            "stats": {}
+        });
+        let result = Ok(vec![Content::text(output.to_string())]);
+
+        let messages = subagent_response_messages(&result);
+        assert_eq!(messages.len(), 1);
+        assert_eq!(
+            messages[0].as_concat_text(),
+            "[subagent sub-recipe 'weather']\nThe weather is sunny"
+        );
+        assert!(!messages[0].is_user_visible());
+        assert!(messages[0].is_agent_visible());
+
+        let error: ToolResult<Vec<Content>> =
+            Err(ErrorData::new(ErrorCode::INTERNAL_ERROR, "x", None));
+        assert!(subagent_response_messages(&error).is_empty());
+    }
+}
@@ -167,7 +167,11 @@ impl TaskExecutionTracker {
     pub async fn send_live_output(&self, task_id: &str, line: &str) {
         match self.display_mode {
             DisplayMode::SingleTaskOutput => {
-                let tasks = self.tasks.read().await;
+                let mut tasks = self.tasks.write().await;
+                if let Some(task_info) = tasks.get_mut(task_id) {
+                    task_info.current_output.push_str(line);
+                    task_info.current_output.push('\n');
+                }
                 let task_info = tasks.get(task_id);
 
                 let formatted_line = self.format_line(task_info, line).await;
@@ -211,6 +215,11 @@ impl TaskExecutionTracker {
             return;
         }
 
+        let event = self.tasks_update_event().await;
+        self.try_send_notification(event, "tasks update");
+    }
+
+    async fn tasks_update_event(&self) -> TaskExecutionNotificationEvent {
         let tasks = self.tasks.read().await;
         let task_list: Vec<_> = tasks.values().collect();
         let (total, pending, running, completed, failed) = count_by_status(&tasks);
@@ -241,9 +250,31 @@ impl TaskExecutionTracker {
             })
             .collect();
 
-        let event = TaskExecutionNotificationEvent::tasks_update(stats, event_tasks);
+        TaskExecutionNotificationEvent::tasks_update(stats, event_tasks)
+    }
 
-        self.try_send_notification(event, "tasks update");
+    /// Record the results of a cancelled execution and send a final update, so the dashboard
+    /// stops showing unfinished tasks as pending or running. Unlike other updates this is sent
+    /// after cancellation.
+    pub async fn send_tasks_cancelled(&self, results: &[TaskResult]) {
+        if self.display_mode != DisplayMode::MultipleTasksOutput {
+            return;
+        }
+
+        let mut tasks = self.tasks.write().await;
+        for result in results {
+            if let Some(task_info) = tasks.get_mut(&result.task_id) {
+                if task_info.result.is_none() {
+                    task_info.status = result.status.clone();
+                    task_info.end_time = Some(Instant::now());
+                    task_info.result = Some(result.clone());
+                }
+            }
+        }
+        drop(tasks);
+
+        let event = self.tasks_update_event().await;
+        self.try_send_notification(event, "tasks cancelled");
     }
 
     pub async fn refresh_display(&self) {
@@ -301,3 +332,40 @@ impl TaskExecutionTracker {
         sleep(Duration::from_millis(COMPLETION_NOTIFICATION_DELAY_MS)).await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agents::subagent_execution_tool::task_types::TaskType;
+    use crate::agents::subagent_execution_tool::tasks::cancelled_task_result;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_send_tasks_cancelled_reports_unfinished_tasks() {
+        let task = Task {
+            id: "task1".to_string(),
+            task_type: TaskType::InlineRecipe,
+            payload: json!({}),
+        };
+        let (notifier, mut notifications) = mpsc::channel(10);
+        let cancellation_token = CancellationToken::new();
+        cancellation_token.cancel();
+        let tracker = TaskExecutionTracker::new(
+            vec![task.clone()],
+            DisplayMode::MultipleTasksOutput,
+            notifier,
+            Some(cancellation_token),
+        );
+
+        tracker
+            .send_tasks_cancelled(&[cancelled_task_result(&task)])
+            .await;
+
+        let Ok(ServerNotification::LoggingMessageNotification(notification)) =
+            notifications.try_recv()
+        else {
+            panic!("expected a tasks update");
+        };
+        assert_eq!(notification.params.data["tasks"][0]["status"], "Cancelled");
+    }
+}
@@ -69,6 +69,43 @@ impl Task {
             .and_then(|sr| sr.get("recipe_path"))
             .and_then(|path| path.as_str())
     }
+
+    pub fn get_inline_recipe_title(&self) -> Option<&str> {
+        matches!(self.task_type, TaskType::InlineRecipe)
+            .then(|| self.payload.get("recipe")?.get("title")?.as_str())
+            .flatten()
+    }
+
+    /// Human readable description of the task, used when reporting failures.
+    /// Falls back to the task id when the payload carries nothing better.
+    pub fn get_description(&self) -> String {
+        let description = match self.task_type {
+            TaskType::SubRecipe => self.get_sub_recipe_name().map(|name| {
+                let params = self
+                    .get_command_parameters()
+                    .map(|params| {
+                        params
+                            .iter()
+                            .map(|(key, value)| match value {
+                                Value::String(s) => format!("{}={}", key, s),
+                                _ => format!("{}={}", key, value),
+                            })
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .unwrap_or_default();
+                if params.is_empty() {
+                    format!("sub-recipe '{}'", name)
+                } else {
+                    format!("sub-recipe '{}' with {}", name, params)
+                }
+            }),
+            TaskType::InlineRecipe => self
+                .get_inline_recipe_title()
+                .map(|title| format!("inline recipe '{}'", title)),
+        };
+        description.unwrap_or_else(|| format!("ID: {}", self.id))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -79,6 +116,8 @@ pub struct TaskResult {
     pub data: Option<Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub task_description: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -87,6 +126,7 @@ pub enum TaskStatus {
     Running,
     Completed,
     Failed,
+    Cancelled,
 }
 
 impl std::fmt::Display for TaskStatus {
@@ -96,6 +136,7 @@ impl std::fmt::Display for TaskStatus {
             TaskStatus::Running => write!(f, "Running"),
             TaskStatus::Completed => write!(f, "Completed"),
             TaskStatus::Failed => write!(f, "Failed"),
+            TaskStatus::Cancelled => write!(f, "Cancelled"),
         }
     }
 }
@@ -143,6 +184,7 @@ pub struct ExecutionStats {
     pub total_tasks: usize,
     pub completed: usize,
     pub failed: usize,
+    pub cancelled: usize,
     pub execution_time_ms: u128,
 }
 
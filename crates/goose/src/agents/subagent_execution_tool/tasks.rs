@@ -10,6 +10,16 @@ use crate::agents::subagent_execution_tool::task_types::{Task, TaskResult, TaskS
 use crate::agents::subagent_execution_tool::utils::strip_ansi_codes;
 use crate::agents::subagent_task_config::TaskConfig;
 
+pub fn cancelled_task_result(task: &Task) -> TaskResult {
+    TaskResult {
+        task_id: task.id.clone(),
+        status: TaskStatus::Cancelled,
+        data: None,
+        error: Some("Task cancelled before it started".to_string()),
+        task_description: Some(task.get_description()),
+    }
+}
+
 pub async fn process_task(
     task: &Task,
     task_execution_tracker: Arc<TaskExecutionTracker>,
@@ -18,9 +28,9 @@ pub async fn process_task(
 ) -> TaskResult {
     match get_task_result(
         task.clone(),
-        task_execution_tracker,
+        task_execution_tracker.clone(),
         task_config,
-        cancellation_token,
+        cancellation_token.clone(),
     )
     .await
     {
@@ -29,12 +39,22 @@ pub async fn process_task(
             status: TaskStatus::Completed,
             data: Some(data),
             error: None,
+            task_description: Some(task.get_description()),
         },
         Err(error) => TaskResult {
             task_id: task.id.clone(),
-            status: TaskStatus::Failed,
-            data: None,
+            status: if cancellation_token.is_cancelled() {
+                TaskStatus::Cancelled
+            } else {
+                TaskStatus::Failed
+            },
+            data: task_execution_tracker
+                .get_current_output(&task.id)
+                .await
+                .filter(|output| !output.trim().is_empty())
+                .map(|output| serde_json::json!({ "partial_output": output })),
             error: Some(error),
+            task_description: Some(task.get_description()),
         },
     }
 }
@@ -107,6 +127,7 @@ async fn handle_inline_recipe_task(
             instruction,
             task_config,
             return_last_only,
+            cancellation_token.child_token(),
         ) => result,
         _ = cancellation_token.cancelled() => {
             return Err("Task cancelled".to_string());
@@ -158,6 +179,8 @@ fn build_command(task: &Task) -> Result<(Command, String), String> {
 
     command.stdout(Stdio::piped());
     command.stderr(Stdio::piped());
+    // A sub-recipe must not outlive the task running it, e.g. after an emergency stop
+    command.kill_on_drop(true);
 
     Ok((command, format!("sub-recipe {}", sub_recipe_name)))
 }
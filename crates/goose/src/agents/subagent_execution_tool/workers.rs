@@ -22,6 +22,11 @@ pub fn spawn_worker(
 
 async fn worker_loop(state: Arc<SharedState>, _worker_id: usize, task_config: TaskConfig) {
     loop {
+        if state.cancellation_token.is_cancelled() {
+            tracing::debug!("Worker cancelled");
+            break;
+        }
+
         tokio::select! {
             task_option = receive_task(&state) => {
                 match task_option {
@@ -1,7 +1,14 @@
 use crate::{
-    agents::{subagent_task_config::TaskConfig, AgentEvent, SessionConfig},
-    conversation::{message::Message, Conversation},
+    agents::{
+        subagent_task_config::{SubagentApprovalRequest, SubagentCommunicationMode, TaskConfig},
+        AgentEvent, SessionConfig,
+    },
+    conversation::{
+        message::{Message, ToolConfirmationRequest},
+        Conversation,
+    },
     execution::manager::AgentManager,
+    permission::{permission_confirmation::PrincipalType, Permission, PermissionConfirmation},
     session::SessionManager,
 };
 use anyhow::{anyhow, Result};
@@ -9,6 +16,8 @@ use futures::StreamExt;
 use rmcp::model::{ErrorCode, ErrorData};
 use std::future::Future;
 use std::pin::Pin;
+use tokio::sync::{mpsc, oneshot};
+use tokio_util::sync::CancellationToken;
 use tracing::debug;
 
 /// Standalone function to run a complete subagent task with output options
@@ -16,8 +25,9 @@ pub async fn run_complete_subagent_task(
     text_instruction: String,
     task_config: TaskConfig,
     return_last_only: bool,
+    cancel_token: CancellationToken,
 ) -> Result<String, anyhow::Error> {
-    let messages = get_agent_messages(text_instruction, task_config)
+    let messages = get_agent_messages(text_instruction, task_config, cancel_token)
         .await
         .map_err(|e| {
             ErrorData::new(
@@ -94,6 +104,7 @@ pub async fn run_complete_subagent_task(
 fn get_agent_messages(
     text_instruction: String,
     task_config: TaskConfig,
+    cancel_token: CancellationToken,
 ) -> Pin<Box<dyn Future<Output = Result<Conversation>> + Send>> {
     Box::pin(async move {
         let agent_manager = AgentManager::instance()
@@ -101,6 +112,8 @@ fn get_agent_messages(
             .map_err(|e| anyhow!("Failed to create AgentManager: {}", e))?;
         let parent_session_id = task_config.parent_session_id;
         let working_dir = task_config.parent_working_dir;
+        let communication_mode = task_config.communication_mode;
+        let approval_tx = task_config.approval_tx;
         let session = SessionManager::create_session(
             working_dir.clone(),
             format!("Subagent task for: {}", parent_session_id),
@@ -117,14 +130,8 @@ fn get_agent_messages(
             .await
             .map_err(|e| anyhow!("Failed to set provider on sub agent: {}", e))?;
 
-        for extension in task_config.extensions {
-            if let Err(e) = agent.add_extension(extension.clone()).await {
-                debug!(
-                    "Failed to add extension '{}' to subagent: {}",
-                    extension.name(),
-                    e
-                );
-            }
+        for (name, e) in agent.add_extensions(task_config.extensions).await {
+            debug!("Failed to add extension '{}' to subagent: {}", name, e);
         }
 
         let mut conversation =
@@ -135,19 +142,47 @@ fn get_agent_messages(
             id: session.id,
             working_dir,
             schedule_id: None,
-            execution_mode: None,
+            // "background" runs every tool without asking, which is what autonomous means
+            execution_mode: match communication_mode {
+                SubagentCommunicationMode::Autonomous => Some("background".to_string()),
+                SubagentCommunicationMode::Interactive => None,
+            },
             max_turns: task_config.max_turns.map(|v| v as u32),
+            max_tool_calls_per_turn: None,
             retry_config: None,
+            request_metadata: Default::default(),
+            dry_run: false,
         };
 
         let mut stream = agent
-            .reply(conversation.clone(), Some(session_config), None)
+            .reply(
+                conversation.clone(),
+                Some(session_config),
+                Some(cancel_token),
+            )
             .await
             .map_err(|e| anyhow!("Failed to get reply from agent: {}", e))?;
         while let Some(message_result) = stream.next().await {
             match message_result {
-                Ok(AgentEvent::Message(msg)) => conversation.push(msg),
-                Ok(AgentEvent::McpNotification(_)) | Ok(AgentEvent::ModelChange { .. }) => {}
+                Ok(AgentEvent::Message(msg)) => {
+                    let confirmation_request = msg
+                        .content
+                        .iter()
+                        .find_map(|content| content.as_tool_confirmation_request())
+                        .cloned();
+                    match confirmation_request {
+                        Some(request) => {
+                            let request_id = request.id.clone();
+                            let confirmation =
+                                request_parent_approval(approval_tx.as_ref(), request).await;
+                            agent.handle_confirmation(request_id, confirmation).await;
+                        }
+                        None => conversation.push(msg),
+                    }
+                }
+                Ok(AgentEvent::McpNotification(_))
+                | Ok(AgentEvent::ModelChange { .. })
+                | Ok(AgentEvent::StopReason(_)) => {}
                 Ok(AgentEvent::HistoryReplaced(updated_conversation)) => {
                     conversation = updated_conversation;
                 }
@@ -161,3 +196,37 @@ fn get_agent_messages(
         Ok(conversation)
     })
 }
+
+/// Forward a subagent's confirmation request to the parent session and wait for the answer.
+/// Without a parent to ask, the tool call is denied rather than left waiting forever.
+async fn request_parent_approval(
+    approval_tx: Option<&mpsc::Sender<SubagentApprovalRequest>>,
+    request: ToolConfirmationRequest,
+) -> PermissionConfirmation {
+    let deny = PermissionConfirmation {
+        principal_type: PrincipalType::Tool,
+        permission: Permission::DenyOnce,
+    };
+
+    let Some(approval_tx) = approval_tx else {
+        debug!(
+            "No parent session to approve subagent tool call '{}', denying",
+            request.tool_name
+        );
+        return deny;
+    };
+
+    let (response_tx, response_rx) = oneshot::channel();
+    if approval_tx
+        .send(SubagentApprovalRequest {
+            request,
+            response_tx,
+        })
+        .await
+        .is_err()
+    {
+        return deny;
+    }
+
+    response_rx.await.unwrap_or(deny)
+}
@@ -1,9 +1,14 @@
 use crate::agents::ExtensionConfig;
+use crate::conversation::message::ToolConfirmationRequest;
+use crate::permission::PermissionConfirmation;
 use crate::providers::base::Provider;
+use serde::{Deserialize, Serialize};
 use std::env;
 use std::fmt;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::sync::Arc;
+use tokio::sync::{mpsc, oneshot};
 
 /// Default maximum number of turns for task execution
 pub const DEFAULT_SUBAGENT_MAX_TURNS: usize = 25;
@@ -11,6 +16,48 @@ pub const DEFAULT_SUBAGENT_MAX_TURNS: usize = 25;
 /// Environment variable name for configuring max turns
 pub const GOOSE_SUBAGENT_MAX_TURNS_ENV_VAR: &str = "GOOSE_SUBAGENT_MAX_TURNS";
 
+/// Environment variable name for configuring how subagents handle tool approvals
+pub const GOOSE_SUBAGENT_COMMUNICATION_MODE_ENV_VAR: &str = "GOOSE_SUBAGENT_COMMUNICATION_MODE";
+
+/// How a subagent deals with tool calls that require approval.
+///
+/// In `Interactive` mode the subagent follows the configured `GOOSE_MODE`. Whenever a tool
+/// call needs approval, the confirmation request is forwarded to the parent session, which
+/// renders it like any other confirmation, and the subagent blocks until it is answered.
+/// In `Autonomous` mode the subagent never prompts and runs its tools as in `auto` mode.
+///
+/// Only inline recipe tasks run in-process; sub-recipe tasks run as a separate `goose run`
+/// process and are not affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubagentCommunicationMode {
+    #[default]
+    Interactive,
+    Autonomous,
+}
+
+impl FromStr for SubagentCommunicationMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "interactive" => Ok(Self::Interactive),
+            "autonomous" => Ok(Self::Autonomous),
+            _ => Err(format!(
+                "Invalid subagent communication mode '{}', expected 'interactive' or 'autonomous'",
+                s
+            )),
+        }
+    }
+}
+
+/// A tool confirmation request raised by an interactive subagent, waiting on the parent
+/// session for an answer
+pub struct SubagentApprovalRequest {
+    pub request: ToolConfirmationRequest,
+    pub response_tx: oneshot::Sender<PermissionConfirmation>,
+}
+
 /// Configuration for task execution with all necessary dependencies
 #[derive(Clone)]
 pub struct TaskConfig {
@@ -19,6 +66,8 @@ pub struct TaskConfig {
     pub parent_working_dir: PathBuf,
     pub extensions: Vec<ExtensionConfig>,
     pub max_turns: Option<usize>,
+    pub communication_mode: SubagentCommunicationMode,
+    pub approval_tx: Option<mpsc::Sender<SubagentApprovalRequest>>,
 }
 
 impl fmt::Debug for TaskConfig {
@@ -29,6 +78,7 @@ impl fmt::Debug for TaskConfig {
             .field("parent_working_dir", &self.parent_working_dir)
             .field("max_turns", &self.max_turns)
             .field("extensions", &self.extensions)
+            .field("communication_mode", &self.communication_mode)
             .finish()
     }
 }
@@ -52,6 +102,20 @@ impl TaskConfig {
                     .and_then(|val| val.parse::<usize>().ok())
                     .unwrap_or(DEFAULT_SUBAGENT_MAX_TURNS),
             ),
+            communication_mode: env::var(GOOSE_SUBAGENT_COMMUNICATION_MODE_ENV_VAR)
+                .ok()
+                .and_then(|val| val.parse().ok())
+                .unwrap_or_default(),
+            approval_tx: None,
         }
     }
+
+    /// Route approval requests of interactive subagents to the given channel
+    pub fn with_approval_channel(
+        mut self,
+        approval_tx: mpsc::Sender<SubagentApprovalRequest>,
+    ) -> Self {
+        self.approval_tx = Some(approval_tx);
+        self
+    }
 }
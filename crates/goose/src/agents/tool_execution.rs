@@ -1,5 +1,6 @@
 use std::future::Future;
 use std::sync::Arc;
+use std::time::Duration;
 
 use async_stream::try_stream;
 use futures::stream::{self, BoxStream};
@@ -9,8 +10,11 @@ use tokio_util::sync::CancellationToken;
 
 use crate::config::permission::PermissionLevel;
 use crate::mcp_utils::ToolResult;
-use crate::permission::Permission;
+use crate::permission::permission_confirmation::PrincipalType;
+use crate::permission::{DecisionSource, Permission, PermissionConfirmation, PermissionDecision};
+use crate::session::SessionManager;
 use rmcp::model::{Content, ServerNotification};
+use serde_json::Value;
 
 // ToolCallResult combines the result of a tool call with an optional notification stream that
 // can be used to receive notifications from the tool.
@@ -37,6 +41,55 @@ pub const DECLINED_RESPONSE: &str = "The user has declined to run this tool. \
     DO NOT attempt to call this tool again. \
     If there are no alternative methods to proceed, clearly explain the situation and STOP.";
 
+pub const REPEATED_TOOL_CALL_RESPONSE: &str = "This tool call was not run because it repeats your \
+    previous calls with exactly the same arguments, which returned the results above. \
+    DO NOT call it again with the same arguments. \
+    Use the results you already have, try a different approach, or explain to the user why you are stuck.";
+
+pub const MAX_TOOL_CALLS_PER_TURN_KEY: &str = "GOOSE_MAX_TOOL_CALLS_PER_TURN";
+
+/// What the calls over the per-turn tool call budget are answered with
+pub fn tool_call_budget_response(budget: usize) -> String {
+    format!(
+        "This tool call was not run because your response made more than {} tool calls, \
+        the most allowed in one response. Look at the results of the calls that did run \
+        and make any further calls in your next response.",
+        budget
+    )
+}
+
+/// Split `requests` into those that fit in what is left of the budget and those that do not,
+/// taking the ones that fit from the budget. No budget means no limit.
+pub fn take_within_budget(
+    requests: Vec<ToolRequest>,
+    budget: &mut Option<usize>,
+) -> (Vec<ToolRequest>, Vec<ToolRequest>) {
+    let Some(left) = budget.as_mut() else {
+        return (requests, Vec::new());
+    };
+    let mut requests = requests;
+    let over = requests.split_off((*left).min(requests.len()));
+    *left -= requests.len();
+    (requests, over)
+}
+
+/// What a tool call is answered with in a dry run, see `SessionConfig::dry_run`
+pub fn dry_run_response(request: &ToolRequest) -> String {
+    let call = match &request.tool_call {
+        Ok(call) => format!(
+            "{} with arguments {}",
+            call.name,
+            Value::Object(call.arguments.clone().unwrap_or_default())
+        ),
+        Err(e) => format!("an invalid tool call ({})", e),
+    };
+    format!(
+        "[dry run] Would execute {}. Nothing was run because this is a dry run; \
+        continue with the next step you would take, without assuming what the call returned.",
+        call
+    )
+}
+
 pub const CHAT_MODE_TOOL_SKIPPED_RESPONSE: &str = "Let the user know the tool call was skipped in goose chat mode. \
                                         DO NOT apologize for skipping the tool call. DO NOT say sorry. \
                                         Provide an explanation of what the tool call would do, structured as a \
@@ -81,6 +134,18 @@ impl Agent {
                     let mut rx = self.confirmation_rx.lock().await;
                     while let Some((req_id, confirmation)) = rx.recv().await {
                         if req_id == request.id {
+                            self.record_permission_decision(
+                                session.as_ref(),
+                                PermissionDecision::new(
+                                    request.id.clone(),
+                                    tool_call.name.to_string(),
+                                    Value::Object(tool_call.arguments.clone().unwrap_or_default()),
+                                    &confirmation,
+                                    DecisionSource::Manual,
+                                ),
+                            )
+                            .await;
+
                             // Log user decision if this was a security alert
                             if let Some(finding_id) = get_security_finding_id_from_results(&request.id, inspection_results) {
                                 tracing::info!(
@@ -90,7 +155,7 @@ impl Agent {
                                 );
                             }
 
-                            if confirmation.permission == Permission::AllowOnce || confirmation.permission == Permission::AlwaysAllow {
+                            if confirmation.permission.allows() {
                                 let (req_id, tool_result) = self.dispatch_tool_call(tool_call.clone(), request.id.clone(), cancellation_token.clone(), session.clone()).await;
                                 let mut futures = tool_futures.lock().await;
 
@@ -111,6 +176,11 @@ impl Agent {
                                         .update_permission_manager(&tool_call.name, PermissionLevel::AlwaysAllow)
                                         .await;
                                 }
+                                if let Permission::AllowForMinutes(minutes) = confirmation.permission {
+                                    self.tool_inspection_manager
+                                        .allow_tool_for(&tool_call.name, Duration::from_secs(u64::from(minutes) * 60))
+                                        .await;
+                                }
                             } else {
                                 // User declined - add declined response
                                 let mut response = message_tool_response.lock().await;
@@ -127,6 +197,39 @@ impl Agent {
         }.boxed()
     }
 
+    /// Add an allowed or denied tool call to the session's audit trail. Failing to record it
+    /// does not stop the call, since the decision has been made either way.
+    pub(crate) async fn record_permission_decision(
+        &self,
+        session: Option<&SessionConfig>,
+        decision: PermissionDecision,
+    ) {
+        let Some(session) = session else {
+            return;
+        };
+        if let Err(e) = SessionManager::record_permission_decision(&session.id, &decision).await {
+            tracing::warn!(
+                "Failed to record permission decision for {}: {}",
+                decision.tool_name,
+                e
+            );
+        }
+    }
+
+    /// Wait for the confirmation answering `request_id`. A closed channel counts as a denial.
+    pub(crate) async fn wait_for_confirmation(&self, request_id: &str) -> PermissionConfirmation {
+        let mut rx = self.confirmation_rx.lock().await;
+        while let Some((req_id, confirmation)) = rx.recv().await {
+            if req_id == request_id {
+                return confirmation;
+            }
+        }
+        PermissionConfirmation {
+            principal_type: PrincipalType::Tool,
+            permission: Permission::DenyOnce,
+        }
+    }
+
     pub(crate) fn handle_frontend_tool_requests<'a>(
         &'a self,
         tool_requests: &'a [ToolRequest],
@@ -153,3 +256,35 @@ impl Agent {
         .boxed()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::CallToolRequestParam;
+
+    fn request(id: &str) -> ToolRequest {
+        ToolRequest {
+            id: id.to_string(),
+            tool_call: Ok(CallToolRequestParam {
+                name: "developer__shell".into(),
+                arguments: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_take_within_budget() {
+        let mut budget = Some(3);
+        let (run, over) = take_within_budget(vec![request("a"), request("b")], &mut budget);
+        assert_eq!((run.len(), over.len()), (2, 0));
+
+        let (run, over) = take_within_budget(vec![request("c"), request("d")], &mut budget);
+        assert_eq!(run[0].id, "c");
+        assert_eq!(over[0].id, "d");
+        assert_eq!(budget, Some(0));
+
+        let mut unlimited = None;
+        let (run, over) = take_within_budget(vec![request("e")], &mut unlimited);
+        assert_eq!((run.len(), over.len()), (1, 0));
+    }
+}
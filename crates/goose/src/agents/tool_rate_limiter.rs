@@ -0,0 +1,95 @@
+//! Throttling of tool calls per extension, for extensions that call external services which
+//! ban clients that send too many requests, such as web scraping.
+//!
+//! Limits are in calls per minute by extension name, from `GOOSE_TOOL_RATE_LIMITS` or a
+//! recipe's `settings.tool_rate_limits`. Calls over the limit are delayed, never refused: each
+//! call is given the next free slot, so calls to one extension are at least a minute divided
+//! by its limit apart.
+
+use crate::config::Config;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio::time::{Duration, Instant};
+
+pub const TOOL_RATE_LIMITS_KEY: &str = "GOOSE_TOOL_RATE_LIMITS";
+
+struct Limit {
+    spacing: Duration,
+    next_slot: Instant,
+}
+
+#[derive(Default)]
+pub struct ToolRateLimiter {
+    limits: Mutex<HashMap<String, Limit>>,
+}
+
+impl ToolRateLimiter {
+    pub fn from_config() -> Self {
+        let limiter = Self::default();
+        if let Ok(limits) = Config::global().get_param::<HashMap<String, u32>>(TOOL_RATE_LIMITS_KEY)
+        {
+            limiter.set_limits(&limits);
+        }
+        limiter
+    }
+
+    /// Set the calls per minute of these extensions, keeping the limits of others. A limit of
+    /// 0 removes the extension's limit.
+    pub fn set_limits(&self, calls_per_minute: &HashMap<String, u32>) {
+        let mut limits = self.limits.lock().unwrap();
+        for (extension, &calls) in calls_per_minute {
+            if calls == 0 {
+                limits.remove(extension);
+                continue;
+            }
+            limits.insert(
+                extension.clone(),
+                Limit {
+                    spacing: Duration::from_secs(60) / calls,
+                    next_slot: Instant::now(),
+                },
+            );
+        }
+    }
+
+    /// Reserve the next slot for a call of `tool_name`, returning when it may start, or None
+    /// if its extension is not limited
+    pub fn reserve(&self, tool_name: &str) -> Option<Instant> {
+        let mut limits = self.limits.lock().unwrap();
+        let limit = limits
+            .iter_mut()
+            .find(|(extension, _)| {
+                tool_name
+                    .strip_prefix(extension.as_str())
+                    .is_some_and(|rest| rest.starts_with("__"))
+            })
+            .map(|(_, limit)| limit)?;
+
+        let slot = limit.next_slot.max(Instant::now());
+        limit.next_slot = slot + limit.spacing;
+        Some(slot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calls_are_spaced_by_limit() {
+        let limiter = ToolRateLimiter::default();
+        limiter.set_limits(&HashMap::from([("computercontroller".to_string(), 60)]));
+
+        let first = limiter.reserve("computercontroller__web_scrape").unwrap();
+        let second = limiter.reserve("computercontroller__web_scrape").unwrap();
+        let third = limiter.reserve("computercontroller__xlsx_tool").unwrap();
+        assert_eq!(second - first, Duration::from_secs(1));
+        assert_eq!(third - second, Duration::from_secs(1));
+
+        assert!(limiter.reserve("developer__shell").is_none());
+        assert!(limiter.reserve("computercontrollerx__web_scrape").is_none());
+
+        limiter.set_limits(&HashMap::from([("computercontroller".to_string(), 0)]));
+        assert!(limiter.reserve("computercontroller__web_scrape").is_none());
+    }
+}
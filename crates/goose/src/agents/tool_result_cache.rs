@@ -0,0 +1,202 @@
+//! Opt-in cache of tool results for the lifetime of an agent, and so of its session.
+//!
+//! Only the tools listed in [`CACHEABLE_TOOLS`] are cached; the MCP read-only and idempotent
+//! hints say a call changes nothing, not that repeating it returns the same result. Any
+//! successful call to a tool that is not marked read-only clears the cache, since it may have
+//! changed what the cached calls would see. Set `GOOSE_TOOL_RESULT_CACHE_TTL_SECONDS` to enable
+//! the cache; entries expire after that long.
+
+use crate::config::Config;
+use rmcp::model::{CallToolRequestParam, Content, Tool};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+pub const TOOL_RESULT_CACHE_TTL_KEY: &str = "GOOSE_TOOL_RESULT_CACHE_TTL_SECONDS";
+
+/// Prefixed names of the tools whose results may be reused for an identical call
+pub const CACHEABLE_TOOLS: &[&str] = &["computercontroller__web_scrape"];
+
+pub fn is_cacheable(tool_name: &str) -> bool {
+    CACHEABLE_TOOLS.contains(&tool_name)
+}
+
+/// Whether the tool declares that it does not modify its environment
+pub fn is_read_only(tool: &Tool) -> bool {
+    tool.annotations
+        .as_ref()
+        .is_some_and(|annotations| annotations.read_only_hint == Some(true))
+}
+
+pub struct ToolResultCache {
+    ttl: Option<Duration>,
+    entries: Mutex<HashMap<String, (Instant, Vec<Content>)>>,
+}
+
+impl ToolResultCache {
+    /// A cache keeping results for `ttl`, or a disabled cache for None
+    pub fn new(ttl: Option<Duration>) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_config() -> Self {
+        let ttl = Config::global()
+            .get_param::<u64>(TOOL_RESULT_CACHE_TTL_KEY)
+            .ok()
+            .filter(|seconds| *seconds > 0)
+            .map(Duration::from_secs);
+        Self::new(ttl)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.ttl.is_some()
+    }
+
+    /// The result of an identical earlier call that has not expired, with a note saying so
+    pub fn get(&self, tool_call: &CallToolRequestParam) -> Option<Vec<Content>> {
+        let ttl = self.ttl?;
+        let key = cache_key(tool_call);
+        let mut entries = self.entries.lock().unwrap();
+
+        let age = entries.get(&key)?.0.elapsed();
+        if age >= ttl {
+            entries.remove(&key);
+            return None;
+        }
+        let contents = &entries[&key].1;
+
+        let note = Content::text(format!(
+            "Note: this is the cached result of an identical {} call made {}s ago.",
+            tool_call.name,
+            age.as_secs()
+        ));
+        Some(
+            std::iter::once(note)
+                .chain(contents.iter().cloned())
+                .collect(),
+        )
+    }
+
+    pub fn insert(&self, tool_call: &CallToolRequestParam, contents: Vec<Content>) {
+        if self.ttl.is_none() {
+            return;
+        }
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(cache_key(tool_call), (Instant::now(), contents));
+    }
+
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
+
+/// The tool name and its arguments with object keys sorted, so that argument order does not matter
+fn cache_key(tool_call: &CallToolRequestParam) -> String {
+    let arguments = tool_call
+        .arguments
+        .clone()
+        .map(Value::Object)
+        .unwrap_or(Value::Null);
+    format!("{}:{}", tool_call.name, normalize(&arguments))
+}
+
+fn normalize(value: &Value) -> String {
+    match value {
+        Value::Object(map) => {
+            let mut fields: Vec<(&String, &Value)> = map.iter().collect();
+            fields.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = fields
+                .into_iter()
+                .map(|(key, value)| format!("{}:{}", Value::String(key.clone()), normalize(value)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(normalize).collect();
+            format!("[{}]", items.join(","))
+        }
+        // An empty object and no arguments at all are the same call
+        Value::Null => "{}".to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::ToolAnnotations;
+    use rmcp::object;
+
+    fn call(arguments: Value) -> CallToolRequestParam {
+        CallToolRequestParam {
+            name: "computercontroller__web_scrape".into(),
+            arguments: arguments.as_object().cloned(),
+        }
+    }
+
+    #[test]
+    fn test_cache_hit_ignores_argument_order() {
+        let cache = ToolResultCache::new(Some(Duration::from_secs(60)));
+        cache.insert(
+            &call(serde_json::json!({"url": "https://example.com", "save_as": "text"})),
+            vec![Content::text("page")],
+        );
+
+        let cached = cache
+            .get(&call(
+                serde_json::json!({"save_as": "text", "url": "https://example.com"}),
+            ))
+            .unwrap();
+        assert_eq!(cached.len(), 2);
+        assert_eq!(cached[1].as_text().unwrap().text, "page");
+
+        assert!(cache
+            .get(&call(serde_json::json!({"url": "https://example.org"})))
+            .is_none());
+    }
+
+    #[test]
+    fn test_cache_expiry_and_disabled() {
+        let cache = ToolResultCache::new(Some(Duration::ZERO));
+        cache.insert(&call(serde_json::json!({})), vec![Content::text("page")]);
+        assert!(cache.get(&call(serde_json::json!({}))).is_none());
+
+        let disabled = ToolResultCache::new(None);
+        disabled.insert(&call(serde_json::json!({})), vec![Content::text("page")]);
+        assert!(!disabled.is_enabled());
+        assert!(disabled.get(&call(serde_json::json!({}))).is_none());
+    }
+
+    #[test]
+    fn test_clear() {
+        let cache = ToolResultCache::new(Some(Duration::from_secs(60)));
+        cache.insert(&call(serde_json::json!({})), vec![Content::text("page")]);
+        cache.clear();
+        assert!(cache.get(&call(serde_json::json!({}))).is_none());
+    }
+
+    #[test]
+    fn test_is_cacheable() {
+        assert!(is_cacheable("computercontroller__web_scrape"));
+        assert!(!is_cacheable("developer__shell"));
+
+        // Read-only and idempotent hints alone don't make a tool cacheable
+        let tool = Tool::new("developer__list_windows", "", object!({"type": "object"}));
+        assert!(!is_read_only(&tool));
+        let tool = tool.annotate(ToolAnnotations {
+            title: None,
+            read_only_hint: Some(true),
+            destructive_hint: Some(false),
+            idempotent_hint: Some(true),
+            open_world_hint: Some(false),
+        });
+        assert!(is_read_only(&tool));
+        assert!(!is_cacheable(&tool.name));
+    }
+}
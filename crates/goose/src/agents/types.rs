@@ -1,6 +1,8 @@
 use crate::mcp_utils::ToolResult;
-use rmcp::model::{Content, Tool};
+use crate::providers::base::{RequestMetadata, REQUEST_METADATA_SESSION_ID};
+use rmcp::model::{Content, Prompt, PromptArgument, Tool};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::{mpsc, Mutex};
@@ -90,7 +92,151 @@ pub struct SessionConfig {
     pub execution_mode: Option<String>,
     /// Maximum number of turns (iterations) allowed without user input
     pub max_turns: Option<u32>,
+    /// Maximum number of tool calls run for a single model response; further calls in the
+    /// response are not run and the model is told why
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tool_calls_per_turn: Option<u32>,
     /// Retry configuration for automated validation and recovery
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry_config: Option<RetryConfig>,
+    /// Metadata attached to every provider request, e.g. user id or recipe name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub request_metadata: HashMap<String, String>,
+    /// Answer tool calls with a note saying what would have run instead of running them, to
+    /// see what the agent would do without any side effects
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl SessionConfig {
+    /// The request metadata for this session, including its id
+    pub fn provider_request_metadata(&self) -> RequestMetadata {
+        let mut metadata = self.request_metadata.clone();
+        metadata
+            .entry(REQUEST_METADATA_SESSION_ID.to_string())
+            .or_insert_with(|| self.id.clone());
+        metadata
+    }
+}
+
+/// A prompt offered by an extension, with the arguments it accepts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PromptDescriptor {
+    pub name: String,
+    pub description: Option<String>,
+    pub arguments: Vec<PromptArgument>,
+    /// The extension that provides the prompt
+    pub extension: String,
+}
+
+impl PromptDescriptor {
+    pub fn new(extension: &str, prompt: Prompt) -> Self {
+        Self {
+            name: prompt.name,
+            description: prompt.description,
+            arguments: prompt.arguments.unwrap_or_default(),
+            extension: extension.to_string(),
+        }
+    }
+
+    /// Check that every required argument is given and that no unknown ones are, listing all
+    /// of the problems found. Prompt arguments are strings, so there are no types to check.
+    pub fn validate_arguments(&self, arguments: &HashMap<String, String>) -> Result<(), String> {
+        let mut problems = Vec::new();
+
+        let missing: Vec<&str> = self
+            .arguments
+            .iter()
+            .filter(|arg| arg.required.unwrap_or(false) && !arguments.contains_key(&arg.name))
+            .map(|arg| arg.name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            problems.push(format!(
+                "missing required arguments: {}",
+                missing.join(", ")
+            ));
+        }
+
+        let mut unknown: Vec<&str> = arguments
+            .keys()
+            .filter(|name| !self.arguments.iter().any(|arg| &arg.name == *name))
+            .map(String::as_str)
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort();
+            let expected: Vec<&str> = self.arguments.iter().map(|arg| arg.name.as_str()).collect();
+            problems.push(format!(
+                "unknown arguments: {} (expected: {})",
+                unknown.join(", "),
+                if expected.is_empty() {
+                    "none".to_string()
+                } else {
+                    expected.join(", ")
+                }
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Invalid arguments for prompt '{}': {}",
+                self.name,
+                problems.join("; ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_prompt_arguments() {
+        let descriptor = PromptDescriptor {
+            name: "review".to_string(),
+            description: None,
+            arguments: vec![
+                PromptArgument {
+                    name: "file".to_string(),
+                    title: None,
+                    description: None,
+                    required: Some(true),
+                },
+                PromptArgument {
+                    name: "focus".to_string(),
+                    title: None,
+                    description: None,
+                    required: None,
+                },
+            ],
+            extension: "developer".to_string(),
+        };
+        let args = |pairs: &[(&str, &str)]| -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect()
+        };
+
+        assert!(descriptor
+            .validate_arguments(&args(&[("file", "a.rs")]))
+            .is_ok());
+        assert!(descriptor
+            .validate_arguments(&args(&[("focus", "tests")]))
+            .unwrap_err()
+            .contains("missing required arguments: file"));
+        assert!(descriptor
+            .validate_arguments(&args(&[("file", "a.rs"), ("colour", "red")]))
+            .unwrap_err()
+            .contains("unknown arguments: colour (expected: file, focus)"));
+        assert_eq!(
+            descriptor
+                .validate_arguments(&args(&[("colour", "red")]))
+                .unwrap_err(),
+            "Invalid arguments for prompt 'review': missing required arguments: file; \
+unknown arguments: colour (expected: file, focus)"
+        );
+    }
 }
@@ -15,6 +15,27 @@ use thiserror::Error;
 const KEYRING_SERVICE: &str = "goose";
 const KEYRING_USERNAME: &str = "secrets";
 
+/// When enabled, values set through `set_param` whose key looks like a secret are stored
+/// in the system keyring instead of the plaintext config file.
+pub const SECRETS_IN_KEYRING_KEY: &str = "GOOSE_SECRETS_IN_KEYRING";
+
+const SECRET_KEY_SUFFIXES: &[&str] = &[
+    "API_KEY",
+    "_ACCESS_KEY",
+    "_SECRET",
+    "_SECRET_KEY",
+    "_TOKEN",
+    "_PASSWORD",
+];
+
+/// Whether a config key names a sensitive value such as an API key or token
+pub fn is_secret_key(key: &str) -> bool {
+    let upper = key.to_uppercase();
+    SECRET_KEY_SUFFIXES
+        .iter()
+        .any(|suffix| upper.ends_with(suffix))
+}
+
 #[cfg(test)]
 const TEST_KEYRING_SERVICE: &str = "goose-test";
 
@@ -101,7 +122,7 @@ impl From<keyring::Error> for ConfigError {
 pub struct Config {
     config_path: PathBuf,
     secrets: SecretStorage,
-    guard: Mutex<()>,
+    pub(super) guard: Mutex<()>,
 }
 
 enum SecretStorage {
@@ -577,10 +598,18 @@ impl Config {
         let values = self.load_values()?;
 
         // Then check our stored values
-        values
-            .get(key)
-            .ok_or_else(|| ConfigError::NotFound(key.to_string()))
-            .and_then(|v| Ok(serde_json::from_value(v.clone())?))
+        if let Some(value) = values.get(key) {
+            return Ok(serde_json::from_value(value.clone())?);
+        }
+
+        // Secret-like values may have been moved to the keyring by set_param
+        if is_secret_key(key) && self.secrets_in_keyring(&values) {
+            if let Some(value) = self.load_secrets().ok().and_then(|s| s.get(key).cloned()) {
+                return Ok(serde_json::from_value(value)?);
+            }
+        }
+
+        Err(ConfigError::NotFound(key.to_string()))
     }
 
     /// Set a configuration value in the config file (non-secret).
@@ -588,6 +617,11 @@ impl Config {
     /// This will immediately write the value to the config file. The value
     /// can be any type that can be serialized to JSON/YAML.
     ///
+    /// If `GOOSE_SECRETS_IN_KEYRING` is enabled and the key looks like a secret
+    /// (see [`is_secret_key`]), the value goes to the system keyring instead and
+    /// `get_param` reads it back from there. When the keyring is unavailable the
+    /// value is written to the config file in plaintext with a warning.
+    ///
     /// Note that this does not affect environment variables - those can only
     /// be set through the system environment.
     ///
@@ -603,6 +637,22 @@ impl Config {
         // Load current values with recovery if needed
         let mut values = self.load_values()?;
 
+        if is_secret_key(key) && self.secrets_in_keyring(&values) {
+            match self.move_to_keyring(key, value.clone()) {
+                Ok(()) => {
+                    values.remove(key);
+                    return self.save_values(values);
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "Could not store {} in the system keyring, saving it in plaintext: {}",
+                        key,
+                        e
+                    );
+                }
+            }
+        }
+
         // Modify values
         values.insert(key.to_string(), value);
 
@@ -610,6 +660,79 @@ impl Config {
         self.save_values(values)
     }
 
+    fn secrets_in_keyring(&self, values: &HashMap<String, Value>) -> bool {
+        let value = match env::var(SECRETS_IN_KEYRING_KEY) {
+            Ok(val) => Self::parse_env_value(&val).ok(),
+            Err(_) => values.get(SECRETS_IN_KEYRING_KEY).cloned(),
+        };
+        matches!(value, Some(Value::Bool(true)))
+    }
+
+    // Store a single value in the keyring; the caller must hold the guard
+    fn move_to_keyring(&self, key: &str, value: Value) -> Result<(), ConfigError> {
+        if !matches!(self.secrets, SecretStorage::Keyring { .. }) {
+            return Err(ConfigError::KeyringError(
+                "keyring is disabled by GOOSE_DISABLE_KEYRING".to_string(),
+            ));
+        }
+        let mut secrets = self.load_secrets()?;
+        secrets.insert(key.to_string(), value);
+        self.save_secrets(&secrets)
+    }
+
+    /// Move secret-like values stored in plaintext in the config file into the keyring.
+    ///
+    /// Returns the keys that were moved. Nothing is moved when the keyring is disabled or
+    /// `GOOSE_SECRETS_IN_KEYRING` is off.
+    pub fn protect_secrets(&self) -> Result<Vec<String>, ConfigError> {
+        let _guard = self.guard.lock().unwrap();
+
+        if !matches!(self.secrets, SecretStorage::Keyring { .. }) {
+            return Ok(Vec::new());
+        }
+
+        let mut values = self.load_values()?;
+        // get_param only looks in the keyring for them while this is enabled
+        if !self.secrets_in_keyring(&values) {
+            return Ok(Vec::new());
+        }
+        let mut keys: Vec<String> = values
+            .iter()
+            .filter(|(key, value)| is_secret_key(key) && value.is_string())
+            .map(|(key, _)| key.clone())
+            .collect();
+        if keys.is_empty() {
+            return Ok(keys);
+        }
+        keys.sort();
+
+        let mut secrets = self.load_secrets()?;
+        for key in &keys {
+            if let Some(value) = values.remove(key) {
+                secrets.insert(key.clone(), value);
+            }
+        }
+        // Write the keyring first so a failure leaves the config file untouched
+        self.save_secrets(&secrets)?;
+        self.save_values(values)?;
+        Ok(keys)
+    }
+
+    fn save_secrets(&self, values: &HashMap<String, Value>) -> Result<(), ConfigError> {
+        match &self.secrets {
+            SecretStorage::Keyring { service } => {
+                let json_value = serde_json::to_string(values)?;
+                let entry = Entry::new(service, KEYRING_USERNAME)?;
+                entry.set_password(&json_value)?;
+            }
+            SecretStorage::File { path } => {
+                let yaml_value = serde_yaml::to_string(values)?;
+                std::fs::write(path, yaml_value)?;
+            }
+        };
+        Ok(())
+    }
+
     /// Delete a configuration value in the config file.
     ///
     /// This will immediately write the value to the config file. The value
@@ -686,18 +809,7 @@ impl Config {
         let mut values = self.load_secrets()?;
         values.insert(key.to_string(), value);
 
-        match &self.secrets {
-            SecretStorage::Keyring { service } => {
-                let json_value = serde_json::to_string(&values)?;
-                let entry = Entry::new(service, KEYRING_USERNAME)?;
-                entry.set_password(&json_value)?;
-            }
-            SecretStorage::File { path } => {
-                let yaml_value = serde_yaml::to_string(&values)?;
-                std::fs::write(path, yaml_value)?;
-            }
-        };
-        Ok(())
+        self.save_secrets(&values)
     }
 
     /// Delete a secret from the system keyring.
@@ -717,18 +829,7 @@ impl Config {
         let mut values = self.load_secrets()?;
         values.remove(key);
 
-        match &self.secrets {
-            SecretStorage::Keyring { service } => {
-                let json_value = serde_json::to_string(&values)?;
-                let entry = Entry::new(service, KEYRING_USERNAME)?;
-                entry.set_password(&json_value)?;
-            }
-            SecretStorage::File { path } => {
-                let yaml_value = serde_yaml::to_string(&values)?;
-                std::fs::write(path, yaml_value)?;
-            }
-        };
-        Ok(())
+        self.save_secrets(&values)
     }
 }
 
@@ -910,6 +1011,57 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_is_secret_key() {
+        assert!(is_secret_key("OPENAI_API_KEY"));
+        assert!(is_secret_key("github_token"));
+        assert!(is_secret_key("DATABRICKS_PASSWORD"));
+        assert!(!is_secret_key("OPENAI_HOST"));
+        assert!(!is_secret_key("GOOSE_MAX_TOKENS_PER_TURN"));
+    }
+
+    #[test]
+    #[serial]
+    fn test_set_param_keeps_plaintext_without_keyring() -> Result<(), ConfigError> {
+        let config_file = NamedTempFile::new().unwrap();
+        let secrets_file = NamedTempFile::new().unwrap();
+        let config = Config::new_with_file_secrets(config_file.path(), secrets_file.path())?;
+
+        std::env::set_var(SECRETS_IN_KEYRING_KEY, "true");
+        let result = config.set_param("TEST_API_KEY", Value::String("sk-123".to_string()));
+        std::env::remove_var(SECRETS_IN_KEYRING_KEY);
+        result?;
+
+        let content = std::fs::read_to_string(config_file.path())?;
+        assert!(content.contains("TEST_API_KEY: sk-123"));
+        assert!(config.protect_secrets()?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[serial]
+    fn test_get_param_reads_secret_like_keys_from_secret_storage() -> Result<(), ConfigError> {
+        let config_file = NamedTempFile::new().unwrap();
+        let secrets_file = NamedTempFile::new().unwrap();
+        let config = Config::new_with_file_secrets(config_file.path(), secrets_file.path())?;
+
+        config.set_secret("STORED_API_KEY", Value::String("sk-456".to_string()))?;
+        config.set_secret("stored_host", Value::String("localhost".to_string()))?;
+
+        // Only looked up in secret storage when the keyring option is on
+        let result: Result<String, ConfigError> = config.get_param("STORED_API_KEY");
+        assert!(matches!(result, Err(ConfigError::NotFound(_))));
+
+        config.set_param(SECRETS_IN_KEYRING_KEY, Value::Bool(true))?;
+        let value: String = config.get_param("STORED_API_KEY")?;
+        assert_eq!(value, "sk-456");
+        let result: Result<String, ConfigError> = config.get_param("stored_host");
+        assert!(matches!(result, Err(ConfigError::NotFound(_))));
+
+        Ok(())
+    }
+
     #[test]
     #[serial]
     fn test_secret_management() -> Result<(), ConfigError> {
@@ -0,0 +1,98 @@
+use crate::config::Config;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+use tracing::warn;
+
+pub const GOOSE_MODE_KEY: &str = "GOOSE_MODE";
+
+/// How goose decides whether a tool call may run, set with `GOOSE_MODE`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GooseMode {
+    /// Run every tool call
+    #[default]
+    Auto,
+    /// Ask before every tool call
+    Approve,
+    /// Run no tool calls, only talk about them
+    Chat,
+    /// Ask before tool calls that may change something
+    SmartApprove,
+}
+
+impl GooseMode {
+    pub const ALL: [GooseMode; 4] = [
+        GooseMode::Auto,
+        GooseMode::Approve,
+        GooseMode::Chat,
+        GooseMode::SmartApprove,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GooseMode::Auto => "auto",
+            GooseMode::Approve => "approve",
+            GooseMode::Chat => "chat",
+            GooseMode::SmartApprove => "smart_approve",
+        }
+    }
+
+    /// The valid values of `GOOSE_MODE`, comma separated
+    pub fn names() -> String {
+        GooseMode::ALL.map(|mode| mode.as_str()).join(", ")
+    }
+
+    /// The configured mode, auto if none is set. An invalid mode is treated as approve, so a
+    /// typo asks before tool calls rather than running all of them.
+    pub fn from_config() -> Self {
+        match Config::global().get_param::<String>(GOOSE_MODE_KEY) {
+            Ok(value) => value.parse().unwrap_or_else(|e| {
+                warn!("{}, asking before every tool call", e);
+                GooseMode::Approve
+            }),
+            Err(_) => GooseMode::default(),
+        }
+    }
+}
+
+impl fmt::Display for GooseMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invalid goose mode '{0}'. Mode must be one of: {names}", names = GooseMode::names())]
+pub struct ParseGooseModeError(pub String);
+
+impl FromStr for GooseMode {
+    type Err = ParseGooseModeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        GooseMode::ALL
+            .into_iter()
+            .find(|mode| mode.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseGooseModeError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_goose_mode() {
+        for mode in GooseMode::ALL {
+            assert_eq!(mode.to_string().parse::<GooseMode>(), Ok(mode));
+        }
+        assert_eq!("Smart_Approve ".parse(), Ok(GooseMode::SmartApprove));
+
+        let err = "aprove".parse::<GooseMode>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid goose mode 'aprove'. Mode must be one of: auto, approve, chat, smart_approve"
+        );
+    }
+}
@@ -2,10 +2,12 @@ pub mod base;
 pub mod declarative_providers;
 mod experiments;
 pub mod extensions;
+pub mod goose_mode;
 pub mod paths;
 pub mod permission;
 pub mod signup_openrouter;
 pub mod signup_tetrate;
+pub mod validation;
 
 pub use crate::agents::ExtensionConfig;
 pub use base::{Config, ConfigError};
@@ -15,9 +17,11 @@ pub use extensions::{
     get_all_extension_names, get_all_extensions, get_enabled_extensions, get_extension_by_name,
     is_extension_enabled, remove_extension, set_extension, set_extension_enabled, ExtensionEntry,
 };
+pub use goose_mode::GooseMode;
 pub use permission::PermissionManager;
 pub use signup_openrouter::configure_openrouter;
 pub use signup_tetrate::configure_tetrate;
+pub use validation::{ConfigIssue, ConfigIssueKind, ConfigMigration};
 
 pub use extensions::DEFAULT_DISPLAY_NAME;
 pub use extensions::DEFAULT_EXTENSION;
@@ -0,0 +1,402 @@
+use crate::agents::content_filter;
+use crate::config::base::{Config, ConfigError};
+use crate::config::GooseMode;
+use crate::permission::PermissionRule;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fmt;
+
+/// goose settings that may appear in the config file, covering every `GOOSE_` key read through
+/// `get_param` in the workspace. Keys with the `GOOSE_` prefix that are not in this list are
+/// reported as unknown, since they are most likely typos.
+const KNOWN_GOOSE_KEYS: &[&str] = &[
+    "GOOSE_AUTO_COMPACT_THRESHOLD",
+    "GOOSE_CA_CERT_PATH",
+    "GOOSE_CLIENT_CERT_PATH",
+    "GOOSE_CLIENT_KEY_PATH",
+    "GOOSE_CLI_MIN_PRIORITY",
+    "GOOSE_CLI_SHOW_COST",
+    "GOOSE_CLI_SHOW_THINKING",
+    "GOOSE_CLI_THEME",
+    "GOOSE_COMPACTION_CHUNK_TOKENS",
+    "GOOSE_CONTENT_FILTERS",
+    "GOOSE_CONTEXT_DEDUP",
+    "GOOSE_CONTEXT_LIMIT",
+    "GOOSE_DEBUG",
+    "GOOSE_EDITOR_API_KEY",
+    "GOOSE_EDITOR_HOST",
+    "GOOSE_EDITOR_MODEL",
+    "GOOSE_EMBEDDING_MODEL",
+    "GOOSE_EMBEDDING_PROVIDER",
+    "GOOSE_ENABLE_ROUTER",
+    "GOOSE_EXTENSION_REGISTRY_URL",
+    "GOOSE_EXTENSION_STARTUP_CONCURRENCY",
+    "GOOSE_HTTP_POOL_IDLE_TIMEOUT",
+    "GOOSE_HTTP_POOL_MAX_IDLE_PER_HOST",
+    "GOOSE_HTTP_TCP_KEEPALIVE",
+    "GOOSE_LEAD_CONTEXT_LIMIT",
+    "GOOSE_LEAD_FAILURE_THRESHOLD",
+    "GOOSE_LEAD_FALLBACK_TURNS",
+    "GOOSE_LEAD_MODEL",
+    "GOOSE_LEAD_PROVIDER",
+    "GOOSE_LEAD_TURNS",
+    "GOOSE_LOG_FORMAT",
+    "GOOSE_LOG_MAX_FILES",
+    "GOOSE_LOG_MAX_FILE_SIZE_MB",
+    "GOOSE_LOG_MAX_TOTAL_SIZE_MB",
+    "GOOSE_MAX_OUTPUT_TOKENS",
+    "GOOSE_MAX_TOOL_CALLS_PER_TURN",
+    "GOOSE_MAX_TURNS",
+    "GOOSE_MOCK_PROVIDER_SCRIPT",
+    "GOOSE_MODE",
+    "GOOSE_MODEL",
+    "GOOSE_PERMISSION_RULES",
+    "GOOSE_PLANNER_CONTEXT_LIMIT",
+    "GOOSE_PLANNER_FALLBACK_MODELS",
+    "GOOSE_PLANNER_MODEL",
+    "GOOSE_PLANNER_PROVIDER",
+    "GOOSE_PROVIDER",
+    "GOOSE_PROVIDER_CASSETTE",
+    "GOOSE_PROVIDER_CASSETTE_MODE",
+    "GOOSE_PROVIDER_REQUEST_ATTRIBUTION",
+    "GOOSE_RECIPE_GITHUB_REPO",
+    "GOOSE_RECIPE_ON_FAILURE_TIMEOUT_SECONDS",
+    "GOOSE_RECIPE_RETRY_TIMEOUT_SECONDS",
+    "GOOSE_SCHEDULER_TYPE",
+    "GOOSE_SECRETS_IN_KEYRING",
+    "GOOSE_SESSION_AUTOSAVE_SECONDS",
+    "GOOSE_SESSION_IDLE_TIMEOUT",
+    "GOOSE_SUBAGENT_COMMUNICATION_MODE",
+    "GOOSE_SUBAGENT_MAX_PARTIAL_OUTPUT_CHARS",
+    "GOOSE_SUBAGENT_MAX_TURNS",
+    "GOOSE_SUBAGENT_RESPONSES_IN_CONVERSATION",
+    "GOOSE_SYSTEM_PROMPT_FILE_PATH",
+    "GOOSE_TEMPERATURE",
+    "GOOSE_THEME",
+    "GOOSE_TODO_MAX_CHARS",
+    "GOOSE_TOOL_OUTPUT_SCAN",
+    "GOOSE_TOOL_RATE_LIMITS",
+    "GOOSE_TOOL_REPETITION_LIMIT",
+    "GOOSE_TOOL_RESULT_CACHE_TTL_SECONDS",
+    "GOOSE_TOOLSHIM",
+    "GOOSE_TOOLSHIM_OLLAMA_MODEL",
+    "GOOSE_VERBOSITY",
+    "GOOSE_WORKER_CONTEXT_LIMIT",
+];
+
+/// Old key names and the keys that replaced them
+const RENAMED_KEYS: &[(&str, &str)] = &[
+    ("GOOSE_PROVIDER__TYPE", "GOOSE_PROVIDER"),
+    ("GOOSE_PROVIDER__MODEL", "GOOSE_MODEL"),
+];
+
+const VALID_SCHEDULER_TYPES: &[&str] = &["legacy", "temporal"];
+const VALID_CLI_THEMES: &[&str] = &["light", "dark", "ansi"];
+const VALID_LOG_FORMATS: &[&str] = &["pretty", "text", "json"];
+const VALID_VERBOSITIES: &[&str] = &["quiet", "normal", "verbose", "debug"];
+
+const POSITIVE_INTEGER_KEYS: &[&str] = &[
+    "GOOSE_COMPACTION_CHUNK_TOKENS",
+    "GOOSE_CONTEXT_LIMIT",
+    "GOOSE_EXTENSION_STARTUP_CONCURRENCY",
+    "GOOSE_HTTP_POOL_MAX_IDLE_PER_HOST",
+    "GOOSE_LEAD_CONTEXT_LIMIT",
+    "GOOSE_LEAD_FAILURE_THRESHOLD",
+    "GOOSE_LEAD_FALLBACK_TURNS",
+    "GOOSE_LEAD_TURNS",
+    "GOOSE_LOG_MAX_FILE_SIZE_MB",
+    "GOOSE_LOG_MAX_TOTAL_SIZE_MB",
+    "GOOSE_MAX_OUTPUT_TOKENS",
+    "GOOSE_MAX_TOOL_CALLS_PER_TURN",
+    "GOOSE_MAX_TURNS",
+    "GOOSE_PLANNER_CONTEXT_LIMIT",
+    "GOOSE_SUBAGENT_MAX_PARTIAL_OUTPUT_CHARS",
+    "GOOSE_SUBAGENT_MAX_TURNS",
+    "GOOSE_TOOL_RESULT_CACHE_TTL_SECONDS",
+    "GOOSE_WORKER_CONTEXT_LIMIT",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigIssueKind {
+    /// A `GOOSE_` key goose does not know about, usually a typo
+    UnknownKey,
+    /// The value has the wrong type or is not one of the allowed values
+    InvalidValue,
+    /// The key was renamed; `migrate` can fix this
+    Deprecated,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub key: String,
+    pub kind: ConfigIssueKind,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// A key that was renamed by `migrate`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigMigration {
+    pub from: String,
+    pub to: String,
+}
+
+fn check_one_of(key: &str, value: &Value, allowed: &[&str]) -> Option<ConfigIssue> {
+    match value.as_str() {
+        Some(s) if allowed.contains(&s) => None,
+        _ => Some(ConfigIssue {
+            key: key.to_string(),
+            kind: ConfigIssueKind::InvalidValue,
+            message: format!("expected one of {}, got {}", allowed.join(", "), value),
+        }),
+    }
+}
+
+// Values may have been written as strings, e.g. by `goose configure`
+fn as_number(value: &Value) -> Option<f64> {
+    match value {
+        Value::Number(n) => n.as_f64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+fn is_whole_number(value: &Value) -> bool {
+    as_number(value).is_some_and(|n| n >= 0.0 && n.fract() == 0.0)
+}
+
+fn check_value(key: &str, value: &Value) -> Option<ConfigIssue> {
+    let invalid = |message: String| {
+        Some(ConfigIssue {
+            key: key.to_string(),
+            kind: ConfigIssueKind::InvalidValue,
+            message,
+        })
+    };
+
+    match key {
+        "GOOSE_MODE" => check_one_of(key, value, &GooseMode::ALL.map(|mode| mode.as_str())),
+        "GOOSE_SCHEDULER_TYPE" => check_one_of(key, value, VALID_SCHEDULER_TYPES),
+        "GOOSE_CLI_THEME" | "GOOSE_THEME" => check_one_of(key, value, VALID_CLI_THEMES),
+        "GOOSE_LOG_FORMAT" => check_one_of(key, value, VALID_LOG_FORMATS),
+        "GOOSE_VERBOSITY" => check_one_of(key, value, VALID_VERBOSITIES),
+        "GOOSE_PROVIDER_CASSETTE_MODE" => check_one_of(key, value, &["record", "replay"]),
+        "GOOSE_TOOL_OUTPUT_SCAN" => check_one_of(key, value, &["off", "warn", "neutralize"]),
+        "GOOSE_TEMPERATURE" => match as_number(value) {
+            Some(_) => None,
+            None => invalid(format!("expected a number, got {}", value)),
+        },
+        "GOOSE_AUTO_COMPACT_THRESHOLD" => match as_number(value) {
+            Some(n) if (0.0..=1.0).contains(&n) => None,
+            _ => invalid(format!("expected a number between 0 and 1, got {}", value)),
+        },
+        "GOOSE_TOOL_RATE_LIMITS" => match value.as_object() {
+            Some(limits) if limits.values().all(is_whole_number) => None,
+            _ => invalid(format!(
+                "expected calls per minute by extension name, got {}",
+                value
+            )),
+        },
+        content_filter::CONTENT_FILTERS_KEY => content_filter::validate_config(value)
+            .err()
+            .and_then(invalid),
+        "GOOSE_PERMISSION_RULES"
+            if serde_json::from_value::<Vec<PermissionRule>>(value.clone()).is_err() =>
+        {
+            invalid(format!(
+                "expected a list of rules with a tool, optional arguments and a level, got {}",
+                value
+            ))
+        }
+        "GOOSE_SESSION_AUTOSAVE_SECONDS" if !is_whole_number(value) => {
+            invalid(format!("expected a whole number of seconds, got {}", value))
+        }
+        _ if POSITIVE_INTEGER_KEYS.contains(&key) => match as_number(value) {
+            Some(n) if n >= 1.0 && n.fract() == 0.0 => None,
+            _ => invalid(format!("expected a positive integer, got {}", value)),
+        },
+        _ => None,
+    }
+}
+
+/// Check config values for unknown goose keys, invalid values and deprecated names
+pub fn validate_values(values: &HashMap<String, Value>) -> Vec<ConfigIssue> {
+    let mut keys: Vec<&String> = values.keys().collect();
+    keys.sort();
+
+    let mut issues = Vec::new();
+    for key in keys {
+        if let Some((_, new_key)) = RENAMED_KEYS.iter().find(|(old, _)| old == key) {
+            issues.push(ConfigIssue {
+                key: key.clone(),
+                kind: ConfigIssueKind::Deprecated,
+                message: format!("renamed to {}", new_key),
+            });
+            continue;
+        }
+
+        if key.starts_with("GOOSE_") && !KNOWN_GOOSE_KEYS.contains(&key.as_str()) {
+            issues.push(ConfigIssue {
+                key: key.clone(),
+                kind: ConfigIssueKind::UnknownKey,
+                message: "unknown goose setting, it will be ignored".to_string(),
+            });
+            continue;
+        }
+
+        if let Some(issue) = check_value(key, &values[key]) {
+            issues.push(issue);
+        }
+    }
+    issues
+}
+
+/// Rename deprecated keys in place. When both the old and the new key are set, the new
+/// key wins and the old one is dropped.
+pub fn migrate_values(values: &mut HashMap<String, Value>) -> Vec<ConfigMigration> {
+    let mut migrations = Vec::new();
+    for (old_key, new_key) in RENAMED_KEYS {
+        if let Some(value) = values.remove(*old_key) {
+            values.entry(new_key.to_string()).or_insert(value);
+            migrations.push(ConfigMigration {
+                from: old_key.to_string(),
+                to: new_key.to_string(),
+            });
+        }
+    }
+    migrations
+}
+
+impl Config {
+    /// Validate the config file, see [`validate_values`]
+    pub fn validate(&self) -> Result<Vec<ConfigIssue>, ConfigError> {
+        Ok(validate_values(&self.load_values()?))
+    }
+
+    /// Upgrade deprecated key names in the config file, returning what was renamed
+    pub fn migrate(&self) -> Result<Vec<ConfigMigration>, ConfigError> {
+        let _guard = self.guard.lock().unwrap();
+        let mut values = self.load_values()?;
+        let migrations = migrate_values(&mut values);
+        if !migrations.is_empty() {
+            self.save_values(values)?;
+        }
+        Ok(migrations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn values(pairs: &[(&str, Value)]) -> HashMap<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_valid_config_has_no_issues() {
+        let values = values(&[
+            ("GOOSE_MODE", json!("smart_approve")),
+            ("GOOSE_MAX_TURNS", json!(100)),
+            ("GOOSE_CONTEXT_LIMIT", json!("128000")),
+            ("OPENAI_HOST", json!("https://api.openai.com")),
+            ("extensions", json!({})),
+        ]);
+        assert!(validate_values(&values).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_and_unknown_keys() {
+        let values = values(&[
+            ("GOOSE_MODE", json!("yolo")),
+            ("GOOSE_MODLE", json!("gpt-4o")),
+            ("GOOSE_MAX_TURNS", json!(-3)),
+            ("GOOSE_AUTO_COMPACT_THRESHOLD", json!(1.5)),
+        ]);
+        let issues = validate_values(&values);
+        let kinds: Vec<(&str, &ConfigIssueKind)> =
+            issues.iter().map(|i| (i.key.as_str(), &i.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    "GOOSE_AUTO_COMPACT_THRESHOLD",
+                    &ConfigIssueKind::InvalidValue
+                ),
+                ("GOOSE_MAX_TURNS", &ConfigIssueKind::InvalidValue),
+                ("GOOSE_MODE", &ConfigIssueKind::InvalidValue),
+                ("GOOSE_MODLE", &ConfigIssueKind::UnknownKey),
+            ]
+        );
+    }
+
+    fn collect_get_param_keys(
+        dir: &std::path::Path,
+        pattern: &regex::Regex,
+        keys: &mut Vec<String>,
+    ) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                if path.file_name().is_some_and(|name| name != "target") {
+                    collect_get_param_keys(&path, pattern, keys);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                let source = std::fs::read_to_string(&path).unwrap();
+                keys.extend(pattern.captures_iter(&source).map(|c| c[1].to_string()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_known_keys_cover_every_get_param_call() {
+        let pattern =
+            regex::Regex::new(r#"get_param(?:::<[^>]*>)?\(\s*"(GOOSE_[A-Z0-9_]+)""#).unwrap();
+        let crates_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap();
+        let mut keys = Vec::new();
+        collect_get_param_keys(crates_dir, &pattern, &mut keys);
+
+        assert!(keys.contains(&"GOOSE_MODEL".to_string()));
+        let missing: Vec<&String> = keys
+            .iter()
+            .filter(|key| !KNOWN_GOOSE_KEYS.contains(&key.as_str()))
+            .collect();
+        assert!(missing.is_empty(), "add {:?} to KNOWN_GOOSE_KEYS", missing);
+    }
+
+    #[test]
+    fn test_migrate_renames_deprecated_keys() {
+        let mut values = values(&[
+            ("GOOSE_PROVIDER__TYPE", json!("openai")),
+            ("GOOSE_PROVIDER__MODEL", json!("old-model")),
+            ("GOOSE_MODEL", json!("gpt-4o")),
+        ]);
+        assert_eq!(
+            validate_values(&values)
+                .iter()
+                .filter(|i| i.kind == ConfigIssueKind::Deprecated)
+                .count(),
+            2
+        );
+
+        let migrations = migrate_values(&mut values);
+
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(values.get("GOOSE_PROVIDER"), Some(&json!("openai")));
+        // The existing new key is kept
+        assert_eq!(values.get("GOOSE_MODEL"), Some(&json!("gpt-4o")));
+        assert!(!values.contains_key("GOOSE_PROVIDER__MODEL"));
+        assert!(validate_values(&values).is_empty());
+    }
+}
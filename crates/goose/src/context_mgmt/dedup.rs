@@ -0,0 +1,152 @@
+//! Lossless context reduction: a large block of content that appears again later in the
+//! conversation, such as a file read twice or a command run again with the same output, is sent
+//! to the model once, with later copies replaced by a reference to the first.
+//!
+//! Only identical blocks are replaced, so no information is lost. The pass is applied to the
+//! messages of each request rather than to the stored conversation, which keeps every block in
+//! the session history. Set `GOOSE_CONTEXT_DEDUP` to enable it.
+
+use crate::config::Config;
+use crate::conversation::message::{Message, MessageContent};
+use rmcp::model::{Content, RawContent};
+use std::collections::HashMap;
+use std::ops::Deref;
+
+pub const CONTEXT_DEDUP_KEY: &str = "GOOSE_CONTEXT_DEDUP";
+
+/// Smaller blocks cost less than the reference that would replace them is worth
+const MIN_DEDUP_CHARS: usize = 2000;
+
+pub fn is_enabled() -> bool {
+    Config::global()
+        .get_param::<bool>(CONTEXT_DEDUP_KEY)
+        .unwrap_or(false)
+}
+
+/// Replace the later copies of each large text block in agent visible messages with a reference
+/// to its first occurrence. Messages the agent cannot see are left alone.
+pub fn dedup_repeated_content(messages: &[Message]) -> Vec<Message> {
+    let mut first_seen: HashMap<&str, String> = HashMap::new();
+    let mut deduped = Vec::with_capacity(messages.len());
+    // Positions count only the messages the model is sent
+    let mut position = 0;
+
+    for message in messages {
+        if !message.is_agent_visible() {
+            deduped.push(message.clone());
+            continue;
+        }
+        position += 1;
+
+        let mut message_copy = message.clone();
+        for (content, original) in message_copy.content.iter_mut().zip(&message.content) {
+            match (content, original) {
+                (MessageContent::Text(text), MessageContent::Text(original)) => {
+                    let origin = format!("message {}", position);
+                    if let Some(reference) = dedup_block(&mut first_seen, &original.text, origin) {
+                        text.text = reference;
+                    }
+                }
+                (
+                    MessageContent::ToolResponse(response),
+                    MessageContent::ToolResponse(original),
+                ) => {
+                    let (Ok(contents), Ok(originals)) =
+                        (response.tool_result.as_mut(), original.tool_result.as_ref())
+                    else {
+                        continue;
+                    };
+                    for (item, original_item) in contents.iter_mut().zip(originals) {
+                        if let RawContent::Text(text) = original_item.deref() {
+                            let origin = format!("the result of tool call {}", original.id);
+                            if let Some(reference) =
+                                dedup_block(&mut first_seen, &text.text, origin)
+                            {
+                                *item = Content::text(reference);
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        deduped.push(message_copy);
+    }
+
+    deduped
+}
+
+/// The reference to send instead of `text` when it was seen before, or None after recording
+/// where it was first seen
+fn dedup_block<'a>(
+    first_seen: &mut HashMap<&'a str, String>,
+    text: &'a str,
+    origin: String,
+) -> Option<String> {
+    if text.len() < MIN_DEDUP_CHARS {
+        return None;
+    }
+    match first_seen.get(text) {
+        Some(first) => Some(format!(
+            "[Identical to the content of {} earlier in this conversation, omitted to save \
+context]",
+            first
+        )),
+        None => {
+            first_seen.insert(text, origin);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conversation::message::MessageMetadata;
+
+    #[test]
+    fn test_dedup_repeated_tool_output() {
+        let file = "fn main() {}\n".repeat(200);
+        let messages = vec![
+            Message::user().with_tool_response("call_1", Ok(vec![Content::text(file.clone())])),
+            Message::assistant().with_text("short reply"),
+            Message::user().with_tool_response(
+                "call_2",
+                Ok(vec![
+                    Content::text(file.clone()),
+                    Content::text("exit code 0"),
+                ]),
+            ),
+            Message::user().with_text(file.clone()),
+        ];
+
+        let deduped = dedup_repeated_content(&messages);
+        assert_eq!(deduped[0], messages[0]);
+        assert_eq!(deduped[1], messages[1]);
+
+        let response = deduped[2].content[0].as_tool_response().unwrap();
+        let contents = response.tool_result.as_ref().unwrap();
+        assert!(contents[0]
+            .as_text()
+            .unwrap()
+            .text
+            .contains("the result of tool call call_1"));
+        assert_eq!(contents[1].as_text().unwrap().text, "exit code 0");
+        assert!(deduped[3].as_concat_text().starts_with("[Identical"));
+    }
+
+    #[test]
+    fn test_dedup_leaves_small_and_invisible_content() {
+        let file = "x".repeat(MIN_DEDUP_CHARS);
+        let messages = vec![
+            Message::user()
+                .with_text(file.clone())
+                .with_metadata(MessageMetadata::user_only()),
+            Message::user().with_text(file.clone()),
+            Message::user().with_text("ok"),
+            Message::user().with_text("ok"),
+        ];
+
+        assert_eq!(dedup_repeated_content(&messages), messages);
+    }
+}
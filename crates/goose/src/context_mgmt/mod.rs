@@ -10,8 +10,42 @@ use serde::Serialize;
 use std::sync::Arc;
 use tracing::{debug, info};
 
+pub mod dedup;
+
 pub const DEFAULT_COMPACTION_THRESHOLD: f64 = 0.8;
 
+/// Maximum number of tokens of history sent in a single summarization request. Longer
+/// histories are summarized in chunks, and the chunk summaries are then summarized together.
+/// Defaults to half of the model's context limit.
+pub const COMPACTION_CHUNK_TOKENS_KEY: &str = "GOOSE_COMPACTION_CHUNK_TOKENS";
+
+const SUMMARIZE_HISTORY_REQUEST: &str =
+    "Please summarize the conversation history provided in the system prompt.";
+const COMBINE_SUMMARIES_REQUEST: &str =
+    "The system prompt contains summaries of consecutive parts of one conversation, in order. \
+Please combine them into a single summary of the whole conversation.";
+
+/// What triggered a compaction, used to tag the compaction metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompactionStrategy {
+    /// Explicitly requested by the user
+    Summarize,
+    /// The session crossed GOOSE_AUTO_COMPACT_THRESHOLD
+    AutoCompact,
+    /// The provider rejected the request for exceeding its context length
+    ContextLimitRecovery,
+}
+
+impl CompactionStrategy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompactionStrategy::Summarize => "summarize",
+            CompactionStrategy::AutoCompact => "auto_compact",
+            CompactionStrategy::ContextLimitRecovery => "context_limit_recovery",
+        }
+    }
+}
+
 #[derive(Serialize)]
 struct SummarizeContext {
     messages: String,
@@ -27,6 +61,7 @@ struct SummarizeContext {
 /// * `agent` - The agent to use for context management
 /// * `conversation` - The current conversation history
 /// * `preserve_last_user_message` - If true and last message is not a user message, copy the most recent user message to the end
+/// * `strategy` - What triggered the compaction, recorded with the `goose.context.compactions` metric
 ///
 /// # Returns
 /// * A tuple containing:
@@ -37,6 +72,7 @@ pub async fn compact_messages(
     agent: &Agent,
     conversation: &Conversation,
     preserve_last_user_message: bool,
+    strategy: CompactionStrategy,
 ) -> Result<(Conversation, Vec<usize>, Option<ProviderUsage>)> {
     info!("Performing message compaction");
 
@@ -93,7 +129,12 @@ pub async fn compact_messages(
         final_token_counts.push(0);
     }
 
-    // Add the summary message (agent_visible=true, user_visible=false)
+    // Add the summary message (agent_visible=true, user_visible=false), followed by anything
+    // the user pinned so that it survives compaction word for word
+    let summary_message = match pinned_messages_text(messages_to_compact) {
+        Some(pinned) => summary_message.with_text(pinned),
+        None => summary_message,
+    };
     let summary_msg = summary_message.with_metadata(MessageMetadata::agent_only());
     // For token counting purposes, we use the output tokens (the actual summary content)
     // since that's what will be in the context going forward
@@ -101,6 +142,13 @@ pub async fn compact_messages(
         .as_ref()
         .and_then(|usage| usage.usage.output_tokens)
         .unwrap_or(0) as usize;
+    // The summarization requests together carried the whole agent-visible history, so their
+    // input size is a good measure of the context before compaction
+    let tokens_before = summarization_usage
+        .as_ref()
+        .and_then(|usage| usage.usage.input_tokens)
+        .unwrap_or(0) as usize;
+    record_compaction(strategy, tokens_before, summary_tokens);
     final_messages.push(summary_msg);
     final_token_counts.push(summary_tokens);
 
@@ -128,6 +176,32 @@ Just continue the conversation naturally based on the summarized context"
     ))
 }
 
+/// The text of the pinned messages, formatted to follow a summary
+fn pinned_messages_text(messages: &[Message]) -> Option<String> {
+    let pinned: Vec<String> = messages
+        .iter()
+        .filter(|msg| msg.metadata.pinned)
+        .map(format_message_for_compacting)
+        .collect();
+    if pinned.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "The user pinned these messages. They still apply exactly as written:\n{}",
+        pinned.join("\n")
+    ))
+}
+
+fn record_compaction(strategy: CompactionStrategy, tokens_before: usize, tokens_after: usize) {
+    tracing::info!(
+        counter.goose.context.compactions = 1,
+        histogram.goose.context.compaction_tokens_before = tokens_before as u64,
+        histogram.goose.context.compaction_tokens_after = tokens_after as u64,
+        strategy = strategy.as_str(),
+        "Context compacted"
+    );
+}
+
 /// Check if messages exceed the auto-compaction threshold
 pub async fn check_if_compaction_needed(
     agent: &Agent,
@@ -189,26 +263,81 @@ async fn do_compact(
     provider: Arc<dyn Provider>,
     messages: &[Message],
 ) -> Result<Option<(Message, ProviderUsage)>, anyhow::Error> {
-    let agent_visible_messages: Vec<&Message> = messages
+    let messages_text: Vec<String> = messages
         .iter()
         .filter(|msg| msg.is_agent_visible())
+        .map(format_message_for_compacting)
         .collect();
 
-    let messages_text = agent_visible_messages
-        .iter()
-        .map(|&msg| format_message_for_compacting(msg))
-        .collect::<Vec<_>>()
-        .join("\n");
+    let chunk_tokens = Config::global()
+        .get_param::<usize>(COMPACTION_CHUNK_TOKENS_KEY)
+        .unwrap_or_else(|_| provider.get_model_config().context_limit() / 2)
+        .max(1);
+    let token_counter = create_token_counter()
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create token counter: {}", e))?;
+
+    // Map: summarize the history one chunk at a time, so that no single request is larger than
+    // the context window. A history that fits in one chunk takes a single request.
+    let mut chunks = chunk_texts(&messages_text, chunk_tokens, |text| {
+        token_counter.count_tokens(text)
+    });
+    if chunks.is_empty() {
+        chunks.push(String::new());
+    }
+    let mut request = SUMMARIZE_HISTORY_REQUEST;
+    let mut total_usage: Option<ProviderUsage> = None;
+
+    loop {
+        let mut summaries = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            let (summary, usage) = summarize_chunk(provider.as_ref(), chunk, request).await?;
+            total_usage = Some(match total_usage {
+                Some(total) => total.combine_with(&usage),
+                None => usage,
+            });
+            summaries.push(summary);
+        }
 
+        if summaries.len() == 1 {
+            let summary = summaries.pop().expect("one summary");
+            return Ok(total_usage.map(|usage| (summary, usage)));
+        }
+
+        // Reduce: summarize the summaries, chunking again if they still do not fit together
+        debug!("Combining {} partial summaries", summaries.len());
+        let summary_texts: Vec<String> = summaries
+            .iter()
+            .enumerate()
+            .map(|(i, summary)| {
+                format!("[summary of part {}]: {}", i + 1, summary.as_concat_text())
+            })
+            .collect();
+        let next_chunks = chunk_texts(&summary_texts, chunk_tokens, |text| {
+            token_counter.count_tokens(text)
+        });
+        // Summaries too long to pair up would never converge; combine them in one request
+        chunks = if next_chunks.len() < chunks.len() {
+            next_chunks
+        } else {
+            vec![summary_texts.join("\n")]
+        };
+        request = COMBINE_SUMMARIES_REQUEST;
+    }
+}
+
+async fn summarize_chunk(
+    provider: &dyn Provider,
+    messages_text: &str,
+    request: &str,
+) -> Result<(Message, ProviderUsage), anyhow::Error> {
     let context = SummarizeContext {
-        messages: messages_text,
+        messages: messages_text.to_string(),
     };
 
     let system_prompt = render_global_file("summarize_oneshot.md", &context)?;
 
-    let user_message = Message::user()
-        .with_text("Please summarize the conversation history provided in the system prompt.");
-    let summarization_request = vec![user_message];
+    let summarization_request = vec![Message::user().with_text(request)];
 
     let (mut response, mut provider_usage) = provider
         .complete_fast(&system_prompt, &summarization_request, &[])
@@ -221,7 +350,49 @@ async fn do_compact(
         .await
         .map_err(|e| anyhow::anyhow!("Failed to ensure usage tokens: {}", e))?;
 
-    Ok(Some((response, provider_usage)))
+    Ok((response, provider_usage))
+}
+
+/// Group consecutive texts into newline-joined chunks of at most `chunk_tokens` tokens each.
+/// A text that is too long for a chunk on its own is truncated to fit.
+fn chunk_texts(
+    texts: &[String],
+    chunk_tokens: usize,
+    count_tokens: impl Fn(&str) -> usize,
+) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current: Vec<String> = Vec::new();
+    let mut current_tokens = 0;
+
+    for text in texts {
+        let mut text = text.clone();
+        let mut tokens = count_tokens(&text);
+        if tokens > chunk_tokens {
+            text = truncate_to_tokens(&text, tokens, chunk_tokens);
+            tokens = chunk_tokens;
+        }
+        if !current.is_empty() && current_tokens + tokens > chunk_tokens {
+            chunks.push(current.join("\n"));
+            current.clear();
+            current_tokens = 0;
+        }
+        current.push(text);
+        current_tokens += tokens;
+    }
+    if !current.is_empty() {
+        chunks.push(current.join("\n"));
+    }
+
+    chunks
+}
+
+/// Cut `text` down to roughly `max_tokens`, assuming tokens are spread evenly over its characters
+fn truncate_to_tokens(text: &str, tokens: usize, max_tokens: usize) -> String {
+    let char_count = text.chars().count();
+    let keep = char_count * max_tokens / tokens.max(1);
+    let mut truncated: String = text.chars().take(keep).collect();
+    truncated.push_str(" [truncated]");
+    truncated
 }
 
 fn format_message_for_compacting(msg: &Message) -> String {
@@ -290,3 +461,37 @@ fn format_message_for_compacting(msg: &Message) -> String {
         format!("[{}]: {}", role_str, content_parts.join("\n"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word_count(text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+
+    #[test]
+    fn test_chunk_texts() {
+        let texts: Vec<String> = ["one two", "three four five", "six", "seven eight"]
+            .iter()
+            .map(|t| t.to_string())
+            .collect();
+
+        let chunks = chunk_texts(&texts, 5, word_count);
+        assert_eq!(chunks, vec!["one two\nthree four five", "six\nseven eight"]);
+
+        let chunks = chunk_texts(&texts, 100, word_count);
+        assert_eq!(chunks.len(), 1);
+    }
+
+    #[test]
+    fn test_chunk_texts_truncates_oversized_text() {
+        let texts = vec!["a ".repeat(20), "b".to_string()];
+
+        let chunks = chunk_texts(&texts, 5, word_count);
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks[0].ends_with("[truncated]"));
+        assert!(chunks[0].chars().count() < texts[0].chars().count());
+        assert_eq!(chunks[1], "b");
+    }
+}
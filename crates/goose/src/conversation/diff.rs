@@ -0,0 +1,356 @@
+//! Compare two conversations turn by turn, for example the sessions of a recipe before and
+//! after a prompt change.
+
+use crate::conversation::message::{Message, MessageContent};
+use crate::conversation::Conversation;
+use rmcp::model::Role;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TurnDiff {
+    Same {
+        a_index: usize,
+        b_index: usize,
+        message: Message,
+    },
+    Removed {
+        a_index: usize,
+        message: Message,
+    },
+    Added {
+        b_index: usize,
+        message: Message,
+    },
+    /// A turn by the same role whose content differs
+    Changed {
+        a_index: usize,
+        b_index: usize,
+        before: Message,
+        after: Message,
+        tool_calls: Vec<ToolCallDiff>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ToolCallDiff {
+    Removed { name: String, arguments: Value },
+    Added { name: String, arguments: Value },
+}
+
+/// The turns of two aligned conversations, in order
+#[derive(Debug, Clone, Serialize)]
+pub struct ConversationDiff {
+    pub turns: Vec<TurnDiff>,
+}
+
+impl ConversationDiff {
+    /// Whether the conversations have the same turns
+    pub fn is_empty(&self) -> bool {
+        self.turns
+            .iter()
+            .all(|turn| matches!(turn, TurnDiff::Same { .. }))
+    }
+}
+
+/// The diff as text in the style of a unified diff, each turn shown with its role
+impl fmt::Display for ConversationDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = String::from("--- a\n+++ b\n");
+        for turn in &self.turns {
+            match turn {
+                TurnDiff::Same { message, .. } => push_lines(&mut out, ' ', message),
+                TurnDiff::Removed { message, .. } => push_lines(&mut out, '-', message),
+                TurnDiff::Added { message, .. } => push_lines(&mut out, '+', message),
+                TurnDiff::Changed {
+                    a_index,
+                    b_index,
+                    before,
+                    after,
+                    ..
+                } => {
+                    out.push_str(&format!("@@ a:{} b:{} @@\n", a_index + 1, b_index + 1));
+                    push_lines(&mut out, '-', before);
+                    push_lines(&mut out, '+', after);
+                }
+            }
+        }
+        write!(f, "{}", out)
+    }
+}
+
+/// Align the messages of `a` and `b` on their longest common sequence of identical turns and
+/// report the rest as removed, added or, for a removed and an added turn by the same role,
+/// changed. Message ids, timestamps and tool call ids are ignored, since they differ between
+/// any two sessions.
+pub fn diff(a: &Conversation, b: &Conversation) -> ConversationDiff {
+    let a_keys: Vec<String> = a.iter().map(turn_key).collect();
+    let b_keys: Vec<String> = b.iter().map(turn_key).collect();
+
+    // lengths[i][j] is the length of the common sequence of a[i..] and b[j..]
+    let mut lengths = vec![vec![0usize; b_keys.len() + 1]; a_keys.len() + 1];
+    for i in (0..a_keys.len()).rev() {
+        for j in (0..b_keys.len()).rev() {
+            lengths[i][j] = if a_keys[i] == b_keys[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut turns = Vec::new();
+    let (mut removed, mut added) = (Vec::new(), Vec::new());
+    let (mut i, mut j) = (0, 0);
+    while i < a_keys.len() || j < b_keys.len() {
+        if i < a_keys.len() && j < b_keys.len() && a_keys[i] == b_keys[j] {
+            flush_changes(a, b, &mut removed, &mut added, &mut turns);
+            turns.push(TurnDiff::Same {
+                a_index: i,
+                b_index: j,
+                message: a.messages()[i].clone(),
+            });
+            i += 1;
+            j += 1;
+        } else if j < b_keys.len() && (i == a_keys.len() || lengths[i][j + 1] >= lengths[i + 1][j])
+        {
+            added.push(j);
+            j += 1;
+        } else {
+            removed.push(i);
+            i += 1;
+        }
+    }
+    flush_changes(a, b, &mut removed, &mut added, &mut turns);
+
+    ConversationDiff { turns }
+}
+
+/// Emit a run of removed and added turns, pairing those by the same role as changed turns
+fn flush_changes(
+    a: &Conversation,
+    b: &Conversation,
+    removed: &mut Vec<usize>,
+    added: &mut Vec<usize>,
+    turns: &mut Vec<TurnDiff>,
+) {
+    let added = std::mem::take(added);
+    let push_added = |turns: &mut Vec<TurnDiff>, b_indexes: &[usize]| {
+        for &b_index in b_indexes {
+            turns.push(TurnDiff::Added {
+                b_index,
+                message: b.messages()[b_index].clone(),
+            });
+        }
+    };
+
+    // Added turns before the one a removed turn is paired with stay additions
+    let mut next_added = 0;
+    for a_index in std::mem::take(removed) {
+        let before = &a.messages()[a_index];
+        let pair = added[next_added..]
+            .iter()
+            .position(|&b_index| b.messages()[b_index].role == before.role);
+        match pair {
+            Some(offset) => {
+                push_added(turns, &added[next_added..next_added + offset]);
+                let b_index = added[next_added + offset];
+                let after = &b.messages()[b_index];
+                turns.push(TurnDiff::Changed {
+                    a_index,
+                    b_index,
+                    before: before.clone(),
+                    after: after.clone(),
+                    tool_calls: tool_call_diff(before, after),
+                });
+                next_added += offset + 1;
+            }
+            None => turns.push(TurnDiff::Removed {
+                a_index,
+                message: before.clone(),
+            }),
+        }
+    }
+    push_added(turns, &added[next_added..]);
+}
+
+fn tool_calls(message: &Message) -> Vec<(String, Value)> {
+    message
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            MessageContent::ToolRequest(request) => request.tool_call.as_ref().ok(),
+            _ => None,
+        })
+        .map(|call| {
+            (
+                call.name.to_string(),
+                Value::Object(call.arguments.clone().unwrap_or_default()),
+            )
+        })
+        .collect()
+}
+
+fn tool_call_diff(before: &Message, after: &Message) -> Vec<ToolCallDiff> {
+    let (before, after) = (tool_calls(before), tool_calls(after));
+    let removed = before
+        .iter()
+        .filter(|call| !after.contains(call))
+        .map(|(name, arguments)| ToolCallDiff::Removed {
+            name: name.clone(),
+            arguments: arguments.clone(),
+        });
+    let added = after
+        .iter()
+        .filter(|call| !before.contains(call))
+        .map(|(name, arguments)| ToolCallDiff::Added {
+            name: name.clone(),
+            arguments: arguments.clone(),
+        });
+    removed.chain(added).collect()
+}
+
+/// The lines a turn is compared and shown by
+fn turn_lines(message: &Message) -> Vec<String> {
+    message
+        .content
+        .iter()
+        .flat_map(|content| {
+            let text = match content {
+                MessageContent::ToolRequest(request) => match &request.tool_call {
+                    Ok(call) => format!(
+                        "[tool call] {} {}",
+                        call.name,
+                        Value::Object(call.arguments.clone().unwrap_or_default())
+                    ),
+                    Err(e) => format!("[tool call] invalid: {}", e),
+                },
+                MessageContent::ToolResponse(response) => match &response.tool_result {
+                    Ok(contents) => {
+                        let text: Vec<&str> = contents
+                            .iter()
+                            .filter_map(|c| c.as_text().map(|t| t.text.as_str()))
+                            .collect();
+                        format!("[tool result] {}", text.join("\n"))
+                    }
+                    Err(e) => format!("[tool result] error: {}", e),
+                },
+                other => other.to_string(),
+            };
+            text.lines().map(str::to_string).collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn role_name(role: &Role) -> &'static str {
+    match role {
+        Role::User => "user",
+        Role::Assistant => "assistant",
+    }
+}
+
+fn turn_key(message: &Message) -> String {
+    format!(
+        "{}\n{}",
+        role_name(&message.role),
+        turn_lines(message).join("\n")
+    )
+}
+
+fn push_lines(out: &mut String, prefix: char, message: &Message) {
+    out.push_str(&format!("{}[{}]\n", prefix, role_name(&message.role)));
+    for line in turn_lines(message) {
+        out.push_str(&format!("{}  {}\n", prefix, line));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::CallToolRequestParam;
+    use rmcp::object;
+
+    fn tool_call(name: &str, path: &str) -> Message {
+        Message::assistant().with_tool_request(
+            "call",
+            Ok(CallToolRequestParam {
+                name: name.to_string().into(),
+                arguments: Some(object!({"path": path})),
+            }),
+        )
+    }
+
+    #[test]
+    fn test_identical_conversations() {
+        let a = Conversation::new_unvalidated(vec![
+            Message::user().with_text("Summarize the README"),
+            tool_call("read", "README.md"),
+        ]);
+        let b = Conversation::new_unvalidated(vec![
+            Message::user().with_text("Summarize the README"),
+            tool_call("read", "README.md").with_id("other"),
+        ]);
+
+        assert!(diff(&a, &b).is_empty());
+    }
+
+    #[test]
+    fn test_changed_added_and_removed_turns() {
+        let a = Conversation::new_unvalidated(vec![
+            Message::user().with_text("Summarize the README"),
+            tool_call("read", "README.md"),
+            Message::assistant().with_text("It is a CLI."),
+        ]);
+        let b = Conversation::new_unvalidated(vec![
+            Message::user().with_text("Summarize the README"),
+            tool_call("read", "docs/README.md"),
+            Message::assistant().with_text("It is a CLI."),
+            Message::user().with_text("Thanks"),
+        ]);
+
+        let diff = diff(&a, &b);
+        assert!(!diff.is_empty());
+        assert_eq!(diff.turns.len(), 4);
+        match &diff.turns[1] {
+            TurnDiff::Changed {
+                a_index,
+                b_index,
+                tool_calls,
+                ..
+            } => {
+                assert_eq!((*a_index, *b_index), (1, 1));
+                assert_eq!(
+                    tool_calls,
+                    &vec![
+                        ToolCallDiff::Removed {
+                            name: "read".to_string(),
+                            arguments: serde_json::json!({"path": "README.md"}),
+                        },
+                        ToolCallDiff::Added {
+                            name: "read".to_string(),
+                            arguments: serde_json::json!({"path": "docs/README.md"}),
+                        },
+                    ]
+                );
+            }
+            other => panic!("expected a changed turn, got {:?}", other),
+        }
+        assert!(matches!(diff.turns[3], TurnDiff::Added { b_index: 3, .. }));
+
+        let unified = diff.to_string();
+        assert!(unified.starts_with("--- a\n+++ b\n [user]\n   Summarize the README\n"));
+        assert!(unified.contains(
+            "@@ a:2 b:2 @@\n-[assistant]\n-  [tool call] read {\"path\":\"README.md\"}\n"
+        ));
+        assert!(unified.ends_with("+[user]\n+  Thanks\n"));
+
+        let reverse = super::diff(&b, &a);
+        assert!(matches!(
+            reverse.turns[3],
+            TurnDiff::Removed { a_index: 3, .. }
+        ));
+    }
+}
@@ -394,6 +394,9 @@ pub struct MessageMetadata {
     pub user_visible: bool,
     /// Whether the message should be included in the agent's context window
     pub agent_visible: bool,
+    /// Whether the message is kept verbatim in the agent's context when the conversation is compacted
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub pinned: bool,
 }
 
 impl Default for MessageMetadata {
@@ -401,6 +404,7 @@ impl Default for MessageMetadata {
         MessageMetadata {
             user_visible: true,
             agent_visible: true,
+            pinned: false,
         }
     }
 }
@@ -411,6 +415,7 @@ impl MessageMetadata {
         MessageMetadata {
             user_visible: false,
             agent_visible: true,
+            pinned: false,
         }
     }
 
@@ -419,6 +424,7 @@ impl MessageMetadata {
         MessageMetadata {
             user_visible: true,
             agent_visible: false,
+            pinned: false,
         }
     }
 
@@ -427,6 +433,7 @@ impl MessageMetadata {
         MessageMetadata {
             user_visible: false,
             agent_visible: false,
+            pinned: false,
         }
     }
 
@@ -461,6 +468,11 @@ impl MessageMetadata {
             ..self
         }
     }
+
+    /// Return a copy with pinned set to the given value
+    pub fn with_pinned(self, pinned: bool) -> Self {
+        Self { pinned, ..self }
+    }
 }
 
 #[derive(ToSchema, Clone, PartialEq, Serialize, Deserialize, Debug)]
@@ -1,13 +1,16 @@
-use crate::conversation::message::{Message, MessageContent, MessageMetadata};
+use crate::conversation::message::{Message, MessageContent, MessageMetadata, ToolRequest};
 use rmcp::model::Role;
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 use thiserror::Error;
 use utoipa::ToSchema;
 
+pub mod diff;
 pub mod message;
 mod tool_result_serde;
 
+pub use diff::{diff, ConversationDiff, ToolCallDiff, TurnDiff};
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
 pub struct Conversation(Vec<Message>);
 
@@ -122,6 +125,75 @@ impl Conversation {
         self.filtered_messages(|meta| meta.user_visible)
     }
 
+    /// The tool request `request_id` in the message with id `message_id`
+    pub fn find_tool_request(&self, message_id: &str, request_id: &str) -> Option<&ToolRequest> {
+        self.0
+            .iter()
+            .filter(|message| message.id.as_deref() == Some(message_id))
+            .flat_map(|message| message.content.iter())
+            .find_map(|content| match content {
+                MessageContent::ToolRequest(request) if request.id == request_id => Some(request),
+                _ => None,
+            })
+    }
+
+    /// Pin or unpin the message at `index`, returning false if there is no such message
+    pub fn set_pinned(&mut self, index: usize, pinned: bool) -> bool {
+        match self.0.get_mut(index) {
+            Some(message) => {
+                message.metadata = message.metadata.with_pinned(pinned);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Repair the damage an interrupted run can leave in a stored conversation: orphaned tool
+    /// requests and responses, tool content on the wrong role and consecutive messages from
+    /// the same role. Unlike [`fix_conversation`] the first and last messages are left alone,
+    /// so this is safe to apply when loading a session. Returns the repaired conversation and
+    /// a description of each fix.
+    pub fn validate_and_repair(self) -> (Self, Vec<String>) {
+        fix_agent_visible(self, repair_messages)
+    }
+
+    /// Make the agent-visible messages alternate between user and assistant, as some providers
+    /// require, by merging consecutive messages from the same role. With `insert_placeholders`
+    /// a placeholder user message is also added before a leading assistant message. Returns
+    /// whether anything changed.
+    pub fn normalize_roles(&mut self, insert_placeholders: bool) -> bool {
+        // Merging and inserting change the number of agent-visible messages, so rather than
+        // mapping them back into their old slots the conversation is rebuilt in one pass, with
+        // messages the agent doesn't see kept where they are
+        let mut normalized: Vec<Message> = Vec::with_capacity(self.0.len());
+        let mut last_visible: Option<usize> = None;
+        let mut changed = false;
+
+        for message in std::mem::take(&mut self.0) {
+            if !message.metadata.agent_visible {
+                normalized.push(message);
+                continue;
+            }
+            match last_visible {
+                Some(index) if normalized[index].role == message.role => {
+                    normalized[index].content.extend(message.content);
+                    changed = true;
+                    continue;
+                }
+                None if insert_placeholders && message.role == Role::Assistant => {
+                    normalized.push(Message::user().with_text(PLACEHOLDER_USER_MESSAGE));
+                    changed = true;
+                }
+                _ => {}
+            }
+            normalized.push(message);
+            last_visible = Some(normalized.len() - 1);
+        }
+
+        self.0 = normalized;
+        changed
+    }
+
     fn validate(self) -> Result<Self, InvalidConversation> {
         let (_messages, issues) = fix_messages(self.0.clone());
         if !issues.is_empty() {
@@ -162,6 +234,14 @@ impl<'a> IntoIterator for &'a Conversation {
 /// Fix a conversation that we're about to send to an LLM. So the last and first
 /// messages should always be from the user.
 pub fn fix_conversation(conversation: Conversation) -> (Conversation, Vec<String>) {
+    fix_agent_visible(conversation, fix_messages)
+}
+
+/// Apply `fix` to the agent-visible messages, keeping the others in place
+fn fix_agent_visible(
+    conversation: Conversation,
+    fix: fn(Vec<Message>) -> (Vec<Message>, Vec<String>),
+) -> (Conversation, Vec<String>) {
     let all_messages = conversation.messages();
 
     // Create a shadow map: track each message as either Visible or NonVisible with its index
@@ -185,7 +265,7 @@ pub fn fix_conversation(conversation: Conversation) -> (Conversation, Vec<String
         .collect();
 
     // Fix only the agent-visible messages
-    let (fixed_visible, issues) = fix_messages(agent_visible_messages);
+    let (fixed_visible, issues) = fix(agent_visible_messages);
 
     // Reconstruct using shadow map: replace Visible slots with fixed messages
     let final_messages: Vec<Message> = shadow_map
@@ -219,6 +299,13 @@ fn fix_messages(messages: Vec<Message>) -> (Vec<Message>, Vec<String>) {
     )
 }
 
+fn repair_messages(messages: Vec<Message>) -> (Vec<Message>, Vec<String>) {
+    let (messages, mut issues) = fix_tool_calling(messages);
+    let (messages, merged) = merge_consecutive_messages(messages);
+    issues.extend(merged);
+    (messages, issues)
+}
+
 fn merge_text_content_in_message(mut msg: Message) -> Message {
     if msg.role != Role::Assistant {
         return msg;
@@ -497,6 +584,154 @@ mod tests {
         };
     }
 
+    #[test]
+    fn test_validate_and_repair_interrupted_session() {
+        let call = CallToolRequestParam {
+            name: "developer__shell".into(),
+            arguments: Some(object!({"command": "ls"})),
+        };
+        let conversation = Conversation::new_unvalidated(vec![
+            Message::user().with_text("list the files"),
+            Message::user().with_tool_response("stale", Ok(vec![])),
+            Message::assistant().with_text("Here they are"),
+            Message::user().with_text("now run it again"),
+            // The run crashed before the tool responded
+            Message::assistant()
+                .with_text("Running ls")
+                .with_tool_request("pending", Ok(call)),
+        ]);
+
+        let (repaired, issues) = conversation.validate_and_repair();
+
+        assert_eq!(
+            issues,
+            vec![
+                "Removed orphaned tool response 'stale'",
+                "Removed orphaned tool request 'pending'",
+                "Removed empty message",
+            ]
+        );
+        let texts: Vec<(Role, String)> = repaired
+            .iter()
+            .map(|m| (m.role.clone(), m.as_concat_text()))
+            .collect();
+        // Unlike fix_conversation, the trailing assistant message is kept
+        assert_eq!(
+            texts,
+            vec![
+                (Role::User, "list the files".to_string()),
+                (Role::Assistant, "Here they are".to_string()),
+                (Role::User, "now run it again".to_string()),
+                (Role::Assistant, "Running ls".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_tool_request() {
+        let call = CallToolRequestParam {
+            name: "shell".into(),
+            arguments: Some(object!({"command": "ls"})),
+        };
+        let conversation = Conversation::new_unvalidated(vec![
+            Message::user().with_text("List files").with_id("msg_1"),
+            Message::assistant()
+                .with_tool_request("call_1", Ok(call.clone()))
+                .with_id("msg_2"),
+        ]);
+
+        let request = conversation.find_tool_request("msg_2", "call_1").unwrap();
+        assert_eq!(request.tool_call.as_ref().unwrap(), &call);
+        assert!(conversation.find_tool_request("msg_1", "call_1").is_none());
+        assert!(conversation.find_tool_request("msg_2", "call_2").is_none());
+    }
+
+    #[test]
+    fn test_normalize_roles() {
+        let mut conversation = Conversation::new_unvalidated(vec![
+            Message::assistant().with_text("I can help with that."),
+            Message::user().with_text("First"),
+            Message::user().with_text("Second"),
+            Message::assistant().with_text("Reply"),
+        ]);
+
+        let mut merged = conversation.clone();
+        assert!(merged.normalize_roles(false));
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged.messages()[1].content.len(), 2);
+        assert!(!merged.normalize_roles(false));
+
+        assert!(conversation.normalize_roles(true));
+        let roles: Vec<Role> = conversation.iter().map(|m| m.role.clone()).collect();
+        assert_eq!(
+            roles,
+            vec![Role::User, Role::Assistant, Role::User, Role::Assistant]
+        );
+        assert_eq!(conversation.messages()[0].as_concat_text(), "Hello");
+        assert!(!conversation.normalize_roles(true));
+    }
+
+    #[test]
+    fn test_normalize_roles_keeps_last_message_when_inserting_placeholder() {
+        let mut conversation = Conversation::new_unvalidated(vec![
+            Message::assistant().with_text("I can help with that."),
+            Message::user().with_text("Thanks"),
+        ]);
+
+        assert!(conversation.normalize_roles(true));
+        let texts: Vec<String> = conversation.iter().map(|m| m.as_concat_text()).collect();
+        assert_eq!(texts, vec!["Hello", "I can help with that.", "Thanks"]);
+    }
+
+    #[test]
+    fn test_normalize_roles_keeps_hidden_messages_in_place() {
+        let mut conversation = Conversation::new_unvalidated(vec![
+            Message::user().with_text("First"),
+            Message::user()
+                .with_text("Only shown to the user")
+                .user_only(),
+            Message::user().with_text("Second"),
+            Message::assistant().with_text("Reply"),
+        ]);
+
+        assert!(conversation.normalize_roles(false));
+        let texts: Vec<String> = conversation.iter().map(|m| m.as_concat_text()).collect();
+        assert_eq!(
+            texts,
+            vec!["First\nSecond", "Only shown to the user", "Reply"]
+        );
+    }
+
+    #[test]
+    fn test_pinned_messages() {
+        let pinned_texts = |conversation: &Conversation| -> Vec<String> {
+            conversation
+                .iter()
+                .filter(|message| message.metadata.pinned)
+                .map(|message| message.as_concat_text())
+                .collect()
+        };
+        let mut conversation = Conversation::new_unvalidated(vec![
+            Message::user().with_text("Always answer in French"),
+            Message::assistant().with_text("D'accord"),
+        ]);
+        assert!(pinned_texts(&conversation).is_empty());
+
+        assert!(conversation.set_pinned(0, true));
+        assert!(!conversation.set_pinned(5, true));
+        assert_eq!(pinned_texts(&conversation), vec!["Always answer in French"]);
+
+        // Only pinned messages carry the flag when serialized
+        let json = serde_json::to_value(&conversation).unwrap();
+        assert_eq!(json[0]["metadata"]["pinned"], true);
+        assert!(json[1]["metadata"].get("pinned").is_none());
+        let restored: Conversation = serde_json::from_value(json).unwrap();
+        assert_eq!(pinned_texts(&restored).len(), 1);
+
+        conversation.set_pinned(0, false);
+        assert!(pinned_texts(&conversation).is_empty());
+    }
+
     fn run_verify(messages: Vec<Message>) -> (Vec<Message>, Vec<String>) {
         let (fixed, issues) = fix_conversation(Conversation::new_unvalidated(messages.clone()));
 
@@ -1,7 +1,14 @@
 use crate::config::paths::Paths;
+use crate::config::Config;
 use anyhow::{Context, Result};
-use std::fs;
-use std::path::PathBuf;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+const DEFAULT_MAX_FILE_SIZE_MB: u64 = 50;
+const DEFAULT_MAX_FILES: usize = 5;
+const DEFAULT_MAX_TOTAL_SIZE_MB: u64 = 1024;
+const BYTES_PER_MB: u64 = 1024 * 1024;
 
 /// Returns the directory where log files should be stored for a specific component.
 /// Creates the directory structure if it doesn't exist.
@@ -29,6 +36,237 @@ pub fn get_log_directory(component: &str, use_date_subdir: bool) -> Result<PathB
     Ok(log_dir)
 }
 
+/// Output format for goose logs, selected with GOOSE_LOG_FORMAT
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable output
+    #[default]
+    Pretty,
+    /// One JSON object per line with the event fields flattened to the top level
+    /// (`timestamp`, `level`, `target`, `message`, ...) for log aggregators
+    Json,
+}
+
+impl LogFormat {
+    pub fn from_config() -> Self {
+        match Config::global().get_param::<String>("GOOSE_LOG_FORMAT") {
+            Ok(format) => format.parse().unwrap_or_else(|_| {
+                eprintln!("Unknown GOOSE_LOG_FORMAT '{}', using pretty", format);
+                LogFormat::Pretty
+            }),
+            Err(_) => LogFormat::Pretty,
+        }
+    }
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "json" => Ok(LogFormat::Json),
+            "pretty" | "text" => Ok(LogFormat::Pretty),
+            _ => Err(anyhow::anyhow!("Unknown log format: {}", s)),
+        }
+    }
+}
+
+/// Limits applied to goose log files
+#[derive(Debug, Clone, Copy)]
+pub struct LogRotationConfig {
+    /// Size in bytes after which the active log file is rotated
+    pub max_file_size: u64,
+    /// How many rotated files to keep next to the active one
+    pub max_files: usize,
+    /// Size in bytes of all goose logs after which the oldest are deleted
+    pub max_total_size: u64,
+}
+
+impl Default for LogRotationConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE_MB * BYTES_PER_MB,
+            max_files: DEFAULT_MAX_FILES,
+            max_total_size: DEFAULT_MAX_TOTAL_SIZE_MB * BYTES_PER_MB,
+        }
+    }
+}
+
+impl LogRotationConfig {
+    /// Read the limits from GOOSE_LOG_MAX_FILE_SIZE_MB, GOOSE_LOG_MAX_FILES and
+    /// GOOSE_LOG_MAX_TOTAL_SIZE_MB, falling back to the defaults
+    pub fn from_config() -> Self {
+        let config = Config::global();
+        let defaults = Self::default();
+        Self {
+            max_file_size: config
+                .get_param::<u64>("GOOSE_LOG_MAX_FILE_SIZE_MB")
+                .map(|mb| mb.max(1) * BYTES_PER_MB)
+                .unwrap_or(defaults.max_file_size),
+            max_files: config
+                .get_param::<usize>("GOOSE_LOG_MAX_FILES")
+                .unwrap_or(defaults.max_files),
+            max_total_size: config
+                .get_param::<u64>("GOOSE_LOG_MAX_TOTAL_SIZE_MB")
+                .map(|mb| mb * BYTES_PER_MB)
+                .unwrap_or(defaults.max_total_size),
+        }
+    }
+}
+
+/// A log file writer that rotates by size.
+///
+/// When the active file would exceed `max_file_size` it is renamed to `<name>.1`, older
+/// rotations shift up by one and anything past `max_files` is deleted. After each rotation
+/// the total size of the goose log files in the logs directory is capped at `max_total_size`.
+pub struct RotatingFileWriter {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    config: LogRotationConfig,
+}
+
+impl RotatingFileWriter {
+    pub fn new(path: impl Into<PathBuf>, config: LogRotationConfig) -> Result<Self> {
+        let path = path.into();
+        let file = Self::open(&path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        let writer = Self {
+            path,
+            file,
+            written,
+            config,
+        };
+        writer.enforce_total_size();
+        Ok(writer)
+    }
+
+    fn open(path: &Path) -> io::Result<File> {
+        OpenOptions::new().create(true).append(true).open(path)
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.as_os_str().to_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+
+        if self.config.max_files == 0 {
+            fs::remove_file(&self.path)?;
+        } else {
+            let _ = fs::remove_file(self.rotated_path(self.config.max_files));
+            for index in (1..self.config.max_files).rev() {
+                let from = self.rotated_path(index);
+                if from.exists() {
+                    fs::rename(&from, self.rotated_path(index + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+        }
+
+        self.file = Self::open(&self.path)?;
+        self.written = 0;
+        self.enforce_total_size();
+        Ok(())
+    }
+
+    fn enforce_total_size(&self) {
+        let logs_dir = Paths::in_state_dir("logs");
+        let root = if self.path.starts_with(&logs_dir) {
+            logs_dir
+        } else {
+            match self.path.parent() {
+                Some(parent) => parent.to_path_buf(),
+                None => return,
+            }
+        };
+        if let Err(e) = enforce_total_size_cap(&root, self.config.max_total_size, &self.path) {
+            eprintln!("Failed to enforce log size cap: {}", e);
+        }
+    }
+}
+
+impl Write for RotatingFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.written > 0 && self.written + buf.len() as u64 > self.config.max_file_size {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn collect_log_files(dir: &Path, files: &mut Vec<(PathBuf, u64, std::time::SystemTime)>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_log_files(&path, files);
+        } else if is_goose_log_file(&path) {
+            if let Ok(modified) = metadata.modified() {
+                files.push((path, metadata.len(), modified));
+            }
+        }
+    }
+}
+
+/// Whether `path` is a log file goose writes: `<name>.log` or a rotation of it, `<name>.log.<n>`
+fn is_goose_log_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+        return false;
+    };
+    match name.rsplit_once(".log") {
+        Some((stem, "")) => !stem.is_empty(),
+        Some((stem, rotation)) => {
+            !stem.is_empty()
+                && rotation
+                    .strip_prefix('.')
+                    .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        }
+        None => false,
+    }
+}
+
+/// Delete the oldest goose log files under `dir` until their total size is at most
+/// `max_total_size`. Other files are left alone and not counted. The file at `keep` is
+/// never deleted.
+pub fn enforce_total_size_cap(dir: &Path, max_total_size: u64, keep: &Path) -> Result<()> {
+    let mut files = Vec::new();
+    collect_log_files(dir, &mut files);
+
+    let mut total: u64 = files.iter().map(|(_, size, _)| size).sum();
+    if total <= max_total_size {
+        return Ok(());
+    }
+
+    files.sort_by_key(|(_, _, modified)| *modified);
+    for (path, size, _) in files {
+        if total <= max_total_size {
+            break;
+        }
+        if path == keep {
+            continue;
+        }
+        fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove old log file {}", path.display()))?;
+        total = total.saturating_sub(size);
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,4 +378,94 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_log_format_parsing() {
+        assert_eq!("json".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!(" JSON ".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("pretty".parse::<LogFormat>().unwrap(), LogFormat::Pretty);
+        assert!("xml".parse::<LogFormat>().is_err());
+    }
+
+    #[test]
+    fn test_rotating_file_writer_rotates_and_retains() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.log");
+        let config = LogRotationConfig {
+            max_file_size: 10,
+            max_files: 2,
+            max_total_size: u64::MAX,
+        };
+        let mut writer = RotatingFileWriter::new(&path, config).unwrap();
+
+        for line in ["aaaaaaaa\n", "bbbbbbbb\n", "cccccccc\n", "dddddddd\n"] {
+            writer.write_all(line.as_bytes()).unwrap();
+        }
+        writer.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "dddddddd\n");
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log.1")).unwrap(),
+            "cccccccc\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("test.log.2")).unwrap(),
+            "bbbbbbbb\n"
+        );
+        assert!(!dir.path().join("test.log.3").exists());
+    }
+
+    #[test]
+    fn test_enforce_total_size_cap_deletes_oldest() {
+        let dir = tempfile::tempdir().unwrap();
+        let old = dir.path().join("old.log");
+        let newer = dir.path().join("sub").join("newer.log");
+        let active = dir.path().join("active.log");
+        fs::create_dir_all(newer.parent().unwrap()).unwrap();
+
+        fs::write(&old, "0123456789").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(&newer, "0123456789").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(&active, "0123456789").unwrap();
+
+        enforce_total_size_cap(dir.path(), 20, &active).unwrap();
+
+        assert!(!old.exists());
+        assert!(newer.exists());
+        assert!(active.exists());
+    }
+
+    #[test]
+    fn test_enforce_total_size_cap_only_touches_goose_logs() {
+        let dir = tempfile::tempdir().unwrap();
+        let notes = dir.path().join("notes.txt");
+        let requests = dir.path().join("llm_request.0.jsonl");
+        let rotated = dir.path().join("cli.log.3");
+        let active = dir.path().join("cli.log");
+
+        fs::write(&notes, "0123456789").unwrap();
+        fs::write(&requests, "0123456789").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(&rotated, "0123456789").unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        fs::write(&active, "0123456789").unwrap();
+
+        enforce_total_size_cap(dir.path(), 10, &active).unwrap();
+
+        assert!(notes.exists());
+        assert!(requests.exists());
+        assert!(!rotated.exists());
+        assert!(active.exists());
+    }
+
+    #[test]
+    fn test_is_goose_log_file() {
+        assert!(is_goose_log_file(Path::new("20250101_120000.log")));
+        assert!(is_goose_log_file(Path::new("cli.log.12")));
+        assert!(!is_goose_log_file(Path::new(".log")));
+        assert!(!is_goose_log_file(Path::new("cli.log.old")));
+        assert!(!is_goose_log_file(Path::new("cli.logs")));
+        assert!(!is_goose_log_file(Path::new("llm_request.0.jsonl")));
+    }
 }
@@ -1,5 +1,7 @@
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
 use thiserror::Error;
 
 const DEFAULT_CONTEXT_LIMIT: usize = 128_000;
@@ -67,11 +69,18 @@ static MODEL_SPECIFIC_LIMITS: Lazy<Vec<(&'static str, usize)>> = Lazy::new(|| {
     ]
 });
 
+/// Context limits providers reported for their models, which take precedence over the table
+/// above. Kept outside of ModelConfig so providers built before the lookup pick them up too.
+static DETECTED_CONTEXT_LIMITS: Lazy<RwLock<HashMap<String, usize>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelConfig {
     pub model_name: String,
     pub context_limit: Option<usize>,
     pub temperature: Option<f32>,
+    /// The most tokens the model may produce in one response, set with GOOSE_MAX_OUTPUT_TOKENS
+    /// or a session's `max_output_tokens` setting
     pub max_tokens: Option<i32>,
     pub toolshim: bool,
     pub toolshim_model: Option<String>,
@@ -95,6 +104,7 @@ impl ModelConfig {
     ) -> Result<Self, ConfigError> {
         let context_limit = Self::parse_context_limit(&model_name, None, context_env_var)?;
         let temperature = Self::parse_temperature()?;
+        let max_tokens = Self::parse_max_tokens()?;
         let toolshim = Self::parse_toolshim()?;
         let toolshim_model = Self::parse_toolshim_model()?;
 
@@ -102,7 +112,7 @@ impl ModelConfig {
             model_name,
             context_limit,
             temperature,
-            max_tokens: None,
+            max_tokens,
             toolshim,
             toolshim_model,
             fast_model: None,
@@ -183,6 +193,36 @@ impl ModelConfig {
         }
     }
 
+    fn parse_max_tokens() -> Result<Option<i32>, ConfigError> {
+        let key = "GOOSE_MAX_OUTPUT_TOKENS";
+        let config = crate::config::Config::global();
+        let val = match config.get_param::<serde_json::Value>("GOOSE_MAX_OUTPUT_TOKENS") {
+            Ok(val) => val,
+            Err(crate::config::ConfigError::NotFound(_)) => return Ok(None),
+            Err(e) => {
+                return Err(ConfigError::InvalidValue(
+                    key.to_string(),
+                    String::new(),
+                    e.to_string(),
+                ))
+            }
+        };
+        let tokens = val
+            .as_i64()
+            .and_then(|tokens| i32::try_from(tokens).ok())
+            .ok_or_else(|| {
+                ConfigError::InvalidValue(
+                    key.to_string(),
+                    val.to_string(),
+                    "must be a positive integer".to_string(),
+                )
+            })?;
+        if tokens <= 0 {
+            return Err(ConfigError::InvalidRange(key.to_string(), val.to_string()));
+        }
+        Ok(Some(tokens))
+    }
+
     fn parse_toolshim() -> Result<bool, ConfigError> {
         if let Ok(val) = std::env::var("GOOSE_TOOLSHIM") {
             match val.to_lowercase().as_str() {
@@ -218,6 +258,22 @@ impl ModelConfig {
             .map(|(_, limit)| *limit)
     }
 
+    /// Use the context limit a provider reported for a model wherever none is configured
+    pub fn set_detected_context_limit(model_name: &str, limit: usize) {
+        DETECTED_CONTEXT_LIMITS
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(model_name.to_string(), limit);
+    }
+
+    fn detected_context_limit(model_name: &str) -> Option<usize> {
+        DETECTED_CONTEXT_LIMITS
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .get(model_name)
+            .copied()
+    }
+
     pub fn get_all_model_limits() -> Vec<ModelLimitConfig> {
         MODEL_SPECIFIC_LIMITS
             .iter()
@@ -271,6 +327,13 @@ impl ModelConfig {
     }
 
     pub fn context_limit(&self) -> usize {
+        // What the provider reported beats the static table, but not a configured limit
+        if !self.has_configured_context_limit() {
+            if let Some(limit) = Self::detected_context_limit(&self.model_name) {
+                return limit;
+            }
+        }
+
         // If we have an explicit context limit set, use it
         if let Some(limit) = self.context_limit {
             return limit;
@@ -290,6 +353,15 @@ impl ModelConfig {
         }
     }
 
+    /// Whether the context limit was chosen by the user, through GOOSE_CONTEXT_LIMIT or
+    /// `with_context_limit`, rather than taken from the table of known models
+    pub fn has_configured_context_limit(&self) -> bool {
+        std::env::var("GOOSE_CONTEXT_LIMIT").is_ok()
+            || self.context_limit.is_some_and(|limit| {
+                Self::get_model_specific_limit(&self.model_name) != Some(limit)
+            })
+    }
+
     pub fn new_or_fail(model_name: &str) -> ModelConfig {
         ModelConfig::new(model_name)
             .unwrap_or_else(|_| panic!("Failed to create model config for {}", model_name))
@@ -366,6 +438,31 @@ mod tests {
         });
     }
 
+    #[test]
+    #[serial]
+    fn test_max_output_tokens() {
+        with_var("GOOSE_MAX_OUTPUT_TOKENS", None::<&str>, || {
+            let config = ModelConfig::new("test-model").unwrap();
+            assert_eq!(config.max_tokens, None);
+        });
+
+        with_var("GOOSE_MAX_OUTPUT_TOKENS", Some("512"), || {
+            let config = ModelConfig::new("test-model").unwrap();
+            assert_eq!(config.max_tokens, Some(512));
+        });
+
+        with_var("GOOSE_MAX_OUTPUT_TOKENS", Some("0"), || {
+            assert!(matches!(
+                ModelConfig::new("test-model").unwrap_err(),
+                ConfigError::InvalidRange(_, _)
+            ));
+        });
+
+        with_var("GOOSE_MAX_OUTPUT_TOKENS", Some("lots"), || {
+            assert!(ModelConfig::new("test-model").is_err());
+        });
+    }
+
     #[test]
     #[serial]
     fn test_invalid_toolshim() {
@@ -416,4 +513,21 @@ mod tests {
             });
         });
     }
+
+    #[test]
+    #[serial]
+    fn test_detected_context_limit() {
+        with_var("GOOSE_CONTEXT_LIMIT", None::<&str>, || {
+            let built = ModelConfig::new("gpt-4o-detected").unwrap();
+            let table_limit = built.context_limit();
+            ModelConfig::set_detected_context_limit("gpt-4o-detected", 200_000);
+
+            // Applies to configs built before the limit was detected
+            assert_ne!(table_limit, 200_000);
+            assert_eq!(built.context_limit(), 200_000);
+
+            let configured = built.with_context_limit(Some(50_000));
+            assert_eq!(configured.context_limit(), 50_000);
+        });
+    }
 }
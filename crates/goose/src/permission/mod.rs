@@ -1,9 +1,13 @@
+pub mod permission_audit;
 pub mod permission_confirmation;
 pub mod permission_inspector;
 pub mod permission_judge;
+pub mod permission_rules;
 pub mod permission_store;
 
+pub use permission_audit::{DecisionSource, PermissionDecision};
 pub use permission_confirmation::{Permission, PermissionConfirmation};
 pub use permission_inspector::PermissionInspector;
 pub use permission_judge::detect_read_only_tools;
+pub use permission_rules::PermissionRule;
 pub use permission_store::ToolPermissionStore;
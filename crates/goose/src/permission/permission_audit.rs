@@ -0,0 +1,57 @@
+use crate::permission::permission_confirmation::PrincipalType;
+use crate::permission::{Permission, PermissionConfirmation};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// What decided whether a tool call could run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DecisionSource {
+    /// The user, answering a confirmation now or earlier with always allow
+    Manual,
+    /// A matching permission rule
+    Rule,
+    /// Smart approve found the tool read-only or pre-approved
+    SmartApprove,
+    /// The goose mode, e.g. auto approving everything
+    Mode,
+    /// An inspector other than the permission one, such as the security or repetition check
+    Inspector,
+}
+
+/// One allow or deny, as kept in the session's audit trail
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PermissionDecision {
+    pub timestamp: DateTime<Utc>,
+    pub tool_request_id: String,
+    pub principal_type: PrincipalType,
+    pub tool_name: String,
+    pub arguments: Value,
+    pub permission: Permission,
+    pub source: DecisionSource,
+}
+
+impl PermissionDecision {
+    pub fn new(
+        tool_request_id: impl Into<String>,
+        tool_name: impl Into<String>,
+        arguments: Value,
+        confirmation: &PermissionConfirmation,
+        source: DecisionSource,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            tool_request_id: tool_request_id.into(),
+            principal_type: confirmation.principal_type.clone(),
+            tool_name: tool_name.into(),
+            arguments,
+            permission: confirmation.permission.clone(),
+            source,
+        }
+    }
+
+    pub fn allowed(&self) -> bool {
+        self.permission.allows()
+    }
+}
@@ -5,10 +5,21 @@ use utoipa::ToSchema;
 pub enum Permission {
     AlwaysAllow,
     AllowOnce,
+    /// Allow this call and any call to the same tool for the next few minutes
+    AllowForMinutes(u32),
     Cancel,
     DenyOnce,
 }
 
+impl Permission {
+    pub fn allows(&self) -> bool {
+        matches!(
+            self,
+            Permission::AlwaysAllow | Permission::AllowOnce | Permission::AllowForMinutes(_)
+        )
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, ToSchema)]
 pub enum PrincipalType {
     Extension,
@@ -1,26 +1,33 @@
 use crate::agents::extension_manager_extension::MANAGE_EXTENSIONS_TOOL_NAME_COMPLETE;
 use crate::config::permission::PermissionLevel;
-use crate::config::PermissionManager;
+use crate::config::{GooseMode, PermissionManager};
 use crate::conversation::message::{Message, ToolRequest};
 use crate::permission::permission_judge::PermissionCheckResult;
+use crate::permission::permission_rules::{
+    builtin_permission_rules, evaluate_rules, load_permission_rules,
+};
+use crate::permission::DecisionSource;
 use crate::tool_inspection::{InspectionAction, InspectionResult, ToolInspector};
 use anyhow::Result;
 use async_trait::async_trait;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 
 /// Permission Inspector that handles tool permission checking
 pub struct PermissionInspector {
-    mode: Arc<Mutex<String>>,
+    mode: Arc<Mutex<GooseMode>>,
     readonly_tools: HashSet<String>,
     regular_tools: HashSet<String>,
     pub permission_manager: Arc<Mutex<PermissionManager>>,
+    /// Tools the user allowed for a while, until when
+    allowed_until: Mutex<HashMap<String, Instant>>,
 }
 
 impl PermissionInspector {
     pub fn new(
-        mode: String,
+        mode: GooseMode,
         readonly_tools: HashSet<String>,
         regular_tools: HashSet<String>,
     ) -> Self {
@@ -29,11 +36,12 @@ impl PermissionInspector {
             readonly_tools,
             regular_tools,
             permission_manager: Arc::new(Mutex::new(PermissionManager::default())),
+            allowed_until: Mutex::new(HashMap::new()),
         }
     }
 
     pub fn with_permission_manager(
-        mode: String,
+        mode: GooseMode,
         readonly_tools: HashSet<String>,
         regular_tools: HashSet<String>,
         permission_manager: Arc<Mutex<PermissionManager>>,
@@ -43,15 +51,25 @@ impl PermissionInspector {
             readonly_tools,
             regular_tools,
             permission_manager,
+            allowed_until: Mutex::new(HashMap::new()),
         }
     }
 
     /// Update the mode of this permission inspector
-    pub async fn update_mode(&self, new_mode: String) {
+    pub async fn update_mode(&self, new_mode: GooseMode) {
         let mut mode = self.mode.lock().await;
         *mode = new_mode;
     }
 
+    /// Allow calls to a tool without asking until `duration` has passed. Permission rules that
+    /// deny or ask about a call still apply.
+    pub async fn allow_for(&self, tool_name: &str, duration: Duration) {
+        self.allowed_until
+            .lock()
+            .await
+            .insert(tool_name.to_string(), Instant::now() + duration);
+    }
+
     /// Process inspection results into permission decisions
     /// This method takes all inspection results and converts them into a PermissionCheckResult
     /// that can be used by the agent to determine which tools to approve, deny, or ask for approval
@@ -134,17 +152,37 @@ impl ToolInspector for PermissionInspector {
         let mut results = Vec::new();
         let permission_manager = self.permission_manager.lock().await;
         let mode = self.mode.lock().await;
+        let mut rules = load_permission_rules();
+        rules.extend(builtin_permission_rules());
+        let mut allowed_until = self.allowed_until.lock().await;
+        let now = Instant::now();
+        allowed_until.retain(|_, until| *until > now);
 
         for request in tool_requests {
             if let Ok(tool_call) = &request.tool_call {
                 let tool_name = &tool_call.name;
+                let rule_level = evaluate_rules(&rules, tool_call);
+                // A tool the user never allows stays denied whatever the rules allow
+                let denied_by_user = *mode != GooseMode::Auto
+                    && permission_manager.get_user_permission(tool_name)
+                        == Some(PermissionLevel::NeverAllow);
 
                 // Handle different modes
-                let action = if *mode == "chat" {
+                let action = if *mode == GooseMode::Chat {
                     // In chat mode, all tools are skipped (handled elsewhere)
                     continue;
-                } else if *mode == "auto" {
-                    // In auto mode, all tools are approved
+                } else if denied_by_user {
+                    InspectionAction::Deny
+                } else if let Some(level) = &rule_level {
+                    // Permission rules apply in every mode, so they can also gate auto mode
+                    match level {
+                        PermissionLevel::AlwaysAllow => InspectionAction::Allow,
+                        PermissionLevel::NeverAllow => InspectionAction::Deny,
+                        PermissionLevel::AskBefore => InspectionAction::RequireApproval(None),
+                    }
+                } else if *mode == GooseMode::Auto || allowed_until.contains_key(tool_name.as_ref())
+                {
+                    // In auto mode, all tools are approved, as are tools the user allowed for now
                     InspectionAction::Allow
                 } else {
                     // Smart mode - check permissions
@@ -176,9 +214,15 @@ impl ToolInspector for PermissionInspector {
                 };
 
                 let reason = match &action {
+                    _ if denied_by_user => "User permission denies this tool".to_string(),
+                    _ if rule_level.is_some() => {
+                        format!("Decided by a permission rule for {}", tool_name)
+                    }
                     InspectionAction::Allow => {
-                        if *mode == "auto" {
+                        if *mode == GooseMode::Auto {
                             "Auto mode - all tools approved".to_string()
+                        } else if allowed_until.contains_key(tool_name.as_ref()) {
+                            "User allowed this tool for a limited time".to_string()
                         } else if self.readonly_tools.contains(tool_name.as_ref()) {
                             "Tool marked as read-only".to_string()
                         } else if self.regular_tools.contains(tool_name.as_ref()) {
@@ -197,6 +241,20 @@ impl ToolInspector for PermissionInspector {
                     }
                 };
 
+                // Calls that need approval are decided by the user's answer instead
+                let decision_source = match &action {
+                    InspectionAction::RequireApproval(_) => None,
+                    _ if denied_by_user => Some(DecisionSource::Manual),
+                    _ if rule_level.is_some() => Some(DecisionSource::Rule),
+                    _ if *mode == GooseMode::Auto => Some(DecisionSource::Mode),
+                    _ if allowed_until.contains_key(tool_name.as_ref())
+                        || permission_manager.get_user_permission(tool_name).is_some() =>
+                    {
+                        Some(DecisionSource::Manual)
+                    }
+                    _ => Some(DecisionSource::SmartApprove),
+                };
+
                 results.push(InspectionResult {
                     tool_request_id: request.id.clone(),
                     action,
@@ -204,6 +262,7 @@ impl ToolInspector for PermissionInspector {
                     confidence: 1.0, // Permission decisions are definitive
                     inspector_name: self.name().to_string(),
                     finding_id: None,
+                    decision_source,
                 });
             }
         }
@@ -211,3 +270,91 @@ impl ToolInspector for PermissionInspector {
         Ok(results)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::CallToolRequestParam;
+    use rmcp::object;
+
+    #[tokio::test]
+    async fn test_allow_for_lets_calls_run_until_it_expires() {
+        let inspector =
+            PermissionInspector::new(GooseMode::Approve, HashSet::new(), HashSet::new());
+        let request = ToolRequest {
+            id: "req_1".to_string(),
+            tool_call: Ok(CallToolRequestParam {
+                name: "test__write".into(),
+                arguments: Some(object!({})),
+            }),
+        };
+
+        let action = |results: Vec<InspectionResult>| results[0].action.clone();
+        let results = inspector.inspect(&[request.clone()], &[]).await.unwrap();
+        assert_eq!(action(results), InspectionAction::RequireApproval(None));
+
+        inspector
+            .allow_for("test__write", Duration::from_secs(600))
+            .await;
+        let results = inspector.inspect(&[request.clone()], &[]).await.unwrap();
+        assert_eq!(results[0].decision_source, Some(DecisionSource::Manual));
+        assert_eq!(action(results), InspectionAction::Allow);
+
+        inspector.allow_for("test__write", Duration::ZERO).await;
+        let results = inspector.inspect(&[request], &[]).await.unwrap();
+        assert_eq!(action(results), InspectionAction::RequireApproval(None));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_user_never_allow_beats_allow_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut permission_manager = PermissionManager::new(dir.path().join("permission.yaml"));
+        permission_manager.update_user_permission("test__write", PermissionLevel::NeverAllow);
+        let inspector = PermissionInspector::with_permission_manager(
+            GooseMode::SmartApprove,
+            HashSet::new(),
+            HashSet::new(),
+            Arc::new(Mutex::new(permission_manager)),
+        );
+        let request = ToolRequest {
+            id: "req_1".to_string(),
+            tool_call: Ok(CallToolRequestParam {
+                name: "test__write".into(),
+                arguments: Some(object!({})),
+            }),
+        };
+
+        let rules_key = crate::permission::permission_rules::PERMISSION_RULES_KEY;
+        std::env::set_var(
+            rules_key,
+            r#"[{"tool": "test__*", "level": "always_allow"}]"#,
+        );
+        let results = inspector.inspect(&[request], &[]).await;
+        std::env::remove_var(rules_key);
+
+        let results = results.unwrap();
+        assert_eq!(results[0].action, InspectionAction::Deny);
+        assert_eq!(results[0].decision_source, Some(DecisionSource::Manual));
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_hard_reset_asks_even_in_auto_mode() {
+        let inspector = PermissionInspector::new(GooseMode::Auto, HashSet::new(), HashSet::new());
+        let request = |id: &str, mode: &str| ToolRequest {
+            id: id.to_string(),
+            tool_call: Ok(CallToolRequestParam {
+                name: "developer__git_reset".into(),
+                arguments: Some(object!({"mode": mode})),
+            }),
+        };
+
+        let results = inspector
+            .inspect(&[request("hard", "hard"), request("soft", "soft")], &[])
+            .await
+            .unwrap();
+        assert_eq!(results[0].action, InspectionAction::RequireApproval(None));
+        assert_eq!(results[1].action, InspectionAction::Allow);
+    }
+}
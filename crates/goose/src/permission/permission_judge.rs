@@ -1,6 +1,6 @@
 use crate::agents::extension_manager_extension::MANAGE_EXTENSIONS_TOOL_NAME_COMPLETE;
 use crate::config::permission::PermissionLevel;
-use crate::config::PermissionManager;
+use crate::config::{GooseMode, PermissionManager};
 use crate::conversation::message::{Message, MessageContent, ToolRequest};
 use crate::conversation::Conversation;
 use crate::prompt_template::render_global_file;
@@ -169,7 +169,7 @@ pub struct PermissionCheckResult {
 
 pub async fn check_tool_permissions(
     candidate_requests: &[ToolRequest],
-    mode: &str,
+    mode: GooseMode,
     tools_with_readonly_annotation: HashSet<String>,
     tools_without_annotation: HashSet<String>,
     permission_manager: &mut PermissionManager,
@@ -183,9 +183,9 @@ pub async fn check_tool_permissions(
 
     for request in candidate_requests {
         if let Ok(tool_call) = request.tool_call.clone() {
-            if mode == "chat" {
+            if mode == GooseMode::Chat {
                 continue;
-            } else if mode == "auto" {
+            } else if mode == GooseMode::Auto {
                 approved.push(request.clone());
             } else {
                 if tool_call.name == MANAGE_EXTENSIONS_TOOL_NAME_COMPLETE {
@@ -204,10 +204,10 @@ pub async fn check_tool_permissions(
 
                 // 2. Fallback based on mode
                 match mode {
-                    "approve" => {
+                    GooseMode::Approve => {
                         needs_approval.push(request.clone());
                     }
-                    "smart_approve" => {
+                    GooseMode::SmartApprove => {
                         if let Some(level) =
                             permission_manager.get_smart_approve_permission(&tool_call.name)
                         {
@@ -236,7 +236,7 @@ pub async fn check_tool_permissions(
     }
 
     // 3. LLM detect
-    if !llm_detect_candidates.is_empty() && mode == "smart_approve" {
+    if !llm_detect_candidates.is_empty() && mode == GooseMode::SmartApprove {
         let detected_readonly_tools =
             detect_read_only_tools(provider, llm_detect_candidates.iter().collect()).await;
         for request in llm_detect_candidates {
@@ -0,0 +1,278 @@
+use crate::config::permission::PermissionLevel;
+use crate::config::{Config, ConfigError};
+use regex::Regex;
+use rmcp::model::CallToolRequestParam;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use utoipa::ToSchema;
+
+pub const PERMISSION_RULES_KEY: &str = "GOOSE_PERMISSION_RULES";
+
+/// Decides tool calls by tool name and arguments before the user would be asked, e.g. always
+/// allow `developer__text_editor` with a `path` of `*/src/*`, or never allow
+/// `developer__shell` with a `command` of `*rm *`.
+///
+/// Patterns match the whole value, with `*` for any run of characters and `?` for a single
+/// one. Arguments that are not strings are matched against their JSON text. Path arguments
+/// (`path`, `cwd`, `*_path`, `*_dir`, `*_file`) are normalized first, and in them `*` and `?`
+/// stop at `/` while `**` crosses directories, so `./src/*` does not allow
+/// `./src/../../etc/passwd`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PermissionRule {
+    /// Pattern for the prefixed tool name
+    pub tool: String,
+    /// Patterns by argument name, all of which have to match
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub arguments: HashMap<String, String>,
+    pub level: PermissionLevel,
+}
+
+fn pattern_matches(pattern: &str, text: &str) -> bool {
+    let pattern = regex::escape(pattern)
+        .replace(r"\*", ".*")
+        .replace(r"\?", ".");
+    Regex::new(&format!("(?s)^{}$", pattern)).is_ok_and(|regex| regex.is_match(text))
+}
+
+fn is_path_argument(name: &str) -> bool {
+    matches!(name, "path" | "cwd" | "file" | "dir" | "directory")
+        || name.ends_with("_path")
+        || name.ends_with("_dir")
+        || name.ends_with("_file")
+}
+
+/// Resolve `.` and `..` components and repeated separators without touching the filesystem.
+/// A relative path keeps the `..` components that climb above its start.
+fn normalize_path(path: &str) -> String {
+    let path = path.replace('\\', "/");
+    let absolute = path.starts_with('/');
+    let mut parts: Vec<&str> = Vec::new();
+    for part in path.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                if parts.last().is_some_and(|last| *last != "..") {
+                    parts.pop();
+                } else if !absolute {
+                    parts.push("..");
+                }
+            }
+            part => parts.push(part),
+        }
+    }
+    let joined = parts.join("/");
+    if absolute {
+        format!("/{}", joined)
+    } else {
+        joined
+    }
+}
+
+fn path_pattern_matches(pattern: &str, path: &str) -> bool {
+    let pattern = normalize_path(pattern);
+    let path = normalize_path(path);
+    // A path that still climbs out of its start only matches a pattern that says so
+    if path.split('/').any(|part| part == "..") && !pattern.split('/').any(|part| part == "..") {
+        return false;
+    }
+    let pattern = regex::escape(&pattern)
+        .replace(r"\*\*", ".*")
+        .replace(r"\*", "[^/]*")
+        .replace(r"\?", "[^/]");
+    Regex::new(&format!("(?s)^{}$", pattern)).is_ok_and(|regex| regex.is_match(&path))
+}
+
+impl PermissionRule {
+    pub fn matches(&self, tool_call: &CallToolRequestParam) -> bool {
+        if !pattern_matches(&self.tool, &tool_call.name) {
+            return false;
+        }
+        self.arguments.iter().all(|(name, pattern)| {
+            match tool_call.arguments.as_ref().and_then(|args| args.get(name)) {
+                Some(Value::String(value)) if is_path_argument(name) => {
+                    path_pattern_matches(pattern, value)
+                }
+                Some(Value::String(value)) => pattern_matches(pattern, value),
+                Some(value) => pattern_matches(pattern, &value.to_string()),
+                None => false,
+            }
+        })
+    }
+}
+
+fn restrictiveness(level: &PermissionLevel) -> u8 {
+    match level {
+        PermissionLevel::AlwaysAllow => 0,
+        PermissionLevel::AskBefore => 1,
+        PermissionLevel::NeverAllow => 2,
+    }
+}
+
+/// The level of the most restrictive rule matching the call, so an allow rule never opens up
+/// a call that another rule denies or asks about
+pub fn evaluate_rules(
+    rules: &[PermissionRule],
+    tool_call: &CallToolRequestParam,
+) -> Option<PermissionLevel> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches(tool_call))
+        .map(|rule| rule.level.clone())
+        .max_by_key(restrictiveness)
+}
+
+/// Rules that apply on top of the configured ones, in every mode, for calls that throw away
+/// work: a hard `git_reset` and a `git_push` with `force` always ask first
+pub fn builtin_permission_rules() -> Vec<PermissionRule> {
+    let ask = |tool: &str, argument: &str, pattern: &str| PermissionRule {
+        tool: tool.to_string(),
+        arguments: HashMap::from([(argument.to_string(), pattern.to_string())]),
+        level: PermissionLevel::AskBefore,
+    };
+    vec![
+        ask("*__git_reset", "mode", "hard"),
+        ask("*__git_push", "force", "true"),
+    ]
+}
+
+/// The rules in `GOOSE_PERMISSION_RULES`, in the order they were added
+pub fn load_permission_rules() -> Vec<PermissionRule> {
+    Config::global()
+        .get_param(PERMISSION_RULES_KEY)
+        .unwrap_or_default()
+}
+
+pub fn save_permission_rules(rules: &[PermissionRule]) -> Result<(), ConfigError> {
+    Config::global().set_param(PERMISSION_RULES_KEY, serde_json::to_value(rules)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::JsonObject;
+    use rmcp::object;
+
+    fn call(name: &str, arguments: JsonObject) -> CallToolRequestParam {
+        CallToolRequestParam {
+            name: name.to_string().into(),
+            arguments: Some(arguments),
+        }
+    }
+
+    fn rule(tool: &str, arguments: &[(&str, &str)], level: PermissionLevel) -> PermissionRule {
+        PermissionRule {
+            tool: tool.to_string(),
+            arguments: arguments
+                .iter()
+                .map(|(name, pattern)| (name.to_string(), pattern.to_string()))
+                .collect(),
+            level,
+        }
+    }
+
+    #[test]
+    fn test_evaluate_rules() {
+        let rules = vec![
+            rule(
+                "developer__text_editor",
+                &[("path", "./src/*")],
+                PermissionLevel::AlwaysAllow,
+            ),
+            rule("developer__shell", &[], PermissionLevel::AlwaysAllow),
+            rule(
+                "developer__shell",
+                &[("command", "*rm *")],
+                PermissionLevel::NeverAllow,
+            ),
+        ];
+
+        let read_src = call(
+            "developer__text_editor",
+            object!({"command": "view", "path": "./src/main.rs"}),
+        );
+        assert_eq!(
+            evaluate_rules(&rules, &read_src),
+            Some(PermissionLevel::AlwaysAllow)
+        );
+
+        let read_home = call(
+            "developer__text_editor",
+            object!({"command": "view", "path": "/home/me/.ssh/id_rsa"}),
+        );
+        assert_eq!(evaluate_rules(&rules, &read_home), None);
+
+        let list = call("developer__shell", object!({"command": "ls -la"}));
+        assert_eq!(
+            evaluate_rules(&rules, &list),
+            Some(PermissionLevel::AlwaysAllow)
+        );
+
+        let remove = call(
+            "developer__shell",
+            object!({"command": "cd /tmp && rm -rf build"}),
+        );
+        assert_eq!(
+            evaluate_rules(&rules, &remove),
+            Some(PermissionLevel::NeverAllow)
+        );
+    }
+
+    #[test]
+    fn test_patterns_match_whole_value() {
+        assert!(pattern_matches("developer__*", "developer__shell"));
+        assert!(!pattern_matches("developer", "developer__shell"));
+        assert!(pattern_matches("file?.txt", "file1.txt"));
+        assert!(pattern_matches("a.b", "a.b"));
+        assert!(!pattern_matches("a.b", "axb"));
+    }
+
+    #[test]
+    fn test_path_patterns_stay_in_their_directory() {
+        assert!(path_pattern_matches("./src/*", "src/main.rs"));
+        assert!(path_pattern_matches("./src/*", "./src/./lib.rs"));
+        assert!(!path_pattern_matches("./src/*", "./src/../../etc/passwd"));
+        assert!(!path_pattern_matches("./src/*", "./src/nested/mod.rs"));
+        assert!(path_pattern_matches("./src/**", "./src/nested/mod.rs"));
+        assert!(!path_pattern_matches("*/src/*", "../src/main.rs"));
+        assert!(path_pattern_matches(
+            "/repo/src/*",
+            "/repo/src/../src/main.rs"
+        ));
+        assert!(!path_pattern_matches(
+            "/repo/src/**",
+            "/repo/src/../../etc/passwd"
+        ));
+
+        let rule = rule(
+            "developer__text_editor",
+            &[("path", "./src/*")],
+            PermissionLevel::AlwaysAllow,
+        );
+        assert!(!rule.matches(&call(
+            "developer__text_editor",
+            object!({"path": "./src/../../etc/passwd"}),
+        )));
+    }
+
+    #[test]
+    fn test_builtin_rules_ask_before_destructive_git_calls() {
+        let rules = builtin_permission_rules();
+
+        let hard_reset = call("developer__git_reset", object!({"mode": "hard"}));
+        assert_eq!(
+            evaluate_rules(&rules, &hard_reset),
+            Some(PermissionLevel::AskBefore)
+        );
+        let soft_reset = call("developer__git_reset", object!({"mode": "soft"}));
+        assert_eq!(evaluate_rules(&rules, &soft_reset), None);
+
+        let force_push = call("developer__git_push", object!({"force": true}));
+        assert_eq!(
+            evaluate_rules(&rules, &force_push),
+            Some(PermissionLevel::AskBefore)
+        );
+        let push = call("developer__git_push", object!({}));
+        assert_eq!(evaluate_rules(&rules, &push), None);
+    }
+}
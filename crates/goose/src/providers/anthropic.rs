@@ -14,7 +14,10 @@ use super::errors::ProviderError;
 use super::formats::anthropic::{
     create_request, get_usage, response_to_message, response_to_streaming_message,
 };
-use super::utils::{get_model, map_http_error_to_provider_error};
+use super::utils::{
+    get_model, get_stop_reason, map_http_error_to_provider_error, parse_retry_after,
+    with_retry_after,
+};
 use crate::config::declarative_providers::DeclarativeProviderConfig;
 use crate::conversation::message::Message;
 use crate::model::ModelConfig;
@@ -140,9 +143,9 @@ impl AnthropicProvider {
                         }
                     }
                 }
-                Err(map_http_error_to_provider_error(
-                    response.status,
-                    response.payload,
+                Err(with_retry_after(
+                    map_http_error_to_provider_error(response.status, response.payload),
+                    response.retry_after,
                 ))
             }
         }
@@ -207,7 +210,8 @@ impl Provider for AnthropicProvider {
         let response_model = get_model(&json_response);
         let mut log = RequestLog::start(&self.model, &payload)?;
         log.write(&json_response, Some(&usage))?;
-        let provider_usage = ProviderUsage::new(response_model, usage);
+        let provider_usage = ProviderUsage::new(response_model, usage)
+            .with_stop_reason(get_stop_reason(&json_response));
         tracing::debug!(
             "🔍 Anthropic non-streaming returning ProviderUsage: {:?}",
             provider_usage
@@ -219,9 +223,9 @@ impl Provider for AnthropicProvider {
         let response = self.api_client.api_get("v1/models").await?;
 
         if response.status != StatusCode::OK {
-            return Err(map_http_error_to_provider_error(
-                response.status,
-                response.payload,
+            return Err(with_retry_after(
+                map_http_error_to_provider_error(response.status, response.payload),
+                response.retry_after,
             ));
         }
 
@@ -271,9 +275,13 @@ impl Provider for AnthropicProvider {
         })?;
         if !response.status().is_success() {
             let status = response.status();
+            let retry_after = parse_retry_after(response.headers());
             let error_text = response.text().await.unwrap_or_default();
             let error_json = serde_json::from_str::<Value>(&error_text).ok();
-            let error = map_http_error_to_provider_error(status, error_json);
+            let error = with_retry_after(
+                map_http_error_to_provider_error(status, error_json),
+                retry_after,
+            );
             let _ = log.error(&error);
             return Err(error);
         }
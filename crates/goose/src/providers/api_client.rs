@@ -10,13 +10,16 @@ use std::fs::read_to_string;
 use std::path::PathBuf;
 use std::time::Duration;
 
+pub const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 32;
+pub const DEFAULT_POOL_IDLE_TIMEOUT_SECS: u64 = 90;
+pub const DEFAULT_TCP_KEEPALIVE_SECS: u64 = 60;
+
 pub struct ApiClient {
     client: Client,
     host: String,
     auth: AuthMethod,
     default_headers: HeaderMap,
     timeout: Duration,
-    tls_config: Option<TlsConfig>,
 }
 
 pub enum AuthMethod {
@@ -30,13 +33,13 @@ pub enum AuthMethod {
     Custom(Box<dyn AuthProvider>),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TlsCertKeyPair {
     pub cert_path: PathBuf,
     pub key_path: PathBuf,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TlsConfig {
     pub client_identity: Option<TlsCertKeyPair>,
     pub ca_cert_path: Option<PathBuf>,
@@ -151,6 +154,73 @@ impl Default for TlsConfig {
     }
 }
 
+/// Connection pool and keep-alive tuning applied to all provider HTTP clients.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HttpPoolConfig {
+    pub max_idle_per_host: usize,
+    /// `None` keeps idle connections open indefinitely.
+    pub idle_timeout: Option<Duration>,
+    /// `None` disables TCP keep-alive probes.
+    pub tcp_keepalive: Option<Duration>,
+}
+
+impl HttpPoolConfig {
+    /// Reads GOOSE_HTTP_POOL_MAX_IDLE_PER_HOST, GOOSE_HTTP_POOL_IDLE_TIMEOUT and
+    /// GOOSE_HTTP_TCP_KEEPALIVE (both in seconds, 0 disables) from config or env.
+    pub fn from_config() -> Self {
+        let config = crate::config::Config::global();
+        let defaults = Self::default();
+
+        let max_idle_per_host = config
+            .get_param::<usize>("GOOSE_HTTP_POOL_MAX_IDLE_PER_HOST")
+            .unwrap_or(defaults.max_idle_per_host);
+        let idle_timeout = config
+            .get_param::<u64>("GOOSE_HTTP_POOL_IDLE_TIMEOUT")
+            .map(|secs| (secs > 0).then_some(Duration::from_secs(secs)))
+            .unwrap_or(defaults.idle_timeout);
+        let tcp_keepalive = config
+            .get_param::<u64>("GOOSE_HTTP_TCP_KEEPALIVE")
+            .map(|secs| (secs > 0).then_some(Duration::from_secs(secs)))
+            .unwrap_or(defaults.tcp_keepalive);
+
+        Self {
+            max_idle_per_host,
+            idle_timeout,
+            tcp_keepalive,
+        }
+    }
+
+    pub fn apply(&self, client_builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        client_builder
+            .pool_max_idle_per_host(self.max_idle_per_host)
+            .pool_idle_timeout(self.idle_timeout)
+            .tcp_keepalive(self.tcp_keepalive)
+    }
+}
+
+impl Default for HttpPoolConfig {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            idle_timeout: Some(Duration::from_secs(DEFAULT_POOL_IDLE_TIMEOUT_SECS)),
+            tcp_keepalive: Some(Duration::from_secs(DEFAULT_TCP_KEEPALIVE_SECS)),
+        }
+    }
+}
+
+/// Builds a pooled HTTP client for the given request timeout, applying the configured TLS
+/// and pool settings. Each provider keeps its own client rather than sharing one process-wide:
+/// pooled connections are bound to the Tokio runtime that opened them, and providers are
+/// created from more than one runtime.
+pub fn pooled_http_client(timeout: Duration) -> Result<Client> {
+    let mut client_builder =
+        HttpPoolConfig::from_config().apply(Client::builder().timeout(timeout));
+    if let Some(ref tls_config) = TlsConfig::from_config()? {
+        client_builder = ApiClient::configure_tls(client_builder, tls_config)?;
+    }
+    Ok(client_builder.build()?)
+}
+
 pub struct OAuthConfig {
     pub host: String,
     pub client_id: String,
@@ -166,6 +236,7 @@ pub trait AuthProvider: Send + Sync {
 pub struct ApiResponse {
     pub status: StatusCode,
     pub payload: Option<Value>,
+    pub retry_after: Option<Duration>,
 }
 
 impl fmt::Debug for AuthMethod {
@@ -186,8 +257,13 @@ impl fmt::Debug for AuthMethod {
 impl ApiResponse {
     pub async fn from_response(response: Response) -> Result<Self> {
         let status = response.status();
+        let retry_after = super::utils::parse_retry_after(response.headers());
         let payload = response.json().await.ok();
-        Ok(Self { status, payload })
+        Ok(Self {
+            status,
+            payload,
+            retry_after,
+        })
     }
 }
 
@@ -203,15 +279,7 @@ impl ApiClient {
     }
 
     pub fn with_timeout(host: String, auth: AuthMethod, timeout: Duration) -> Result<Self> {
-        let mut client_builder = Client::builder().timeout(timeout);
-
-        // Configure TLS if needed
-        let tls_config = TlsConfig::from_config()?;
-        if let Some(ref config) = tls_config {
-            client_builder = Self::configure_tls(client_builder, config)?;
-        }
-
-        let client = client_builder.build()?;
+        let client = pooled_http_client(timeout)?;
 
         Ok(Self {
             client,
@@ -219,24 +287,9 @@ impl ApiClient {
             auth,
             default_headers: HeaderMap::new(),
             timeout,
-            tls_config,
         })
     }
 
-    fn rebuild_client(&mut self) -> Result<()> {
-        let mut client_builder = Client::builder()
-            .timeout(self.timeout)
-            .default_headers(self.default_headers.clone());
-
-        // Configure TLS if needed
-        if let Some(ref tls_config) = self.tls_config {
-            client_builder = Self::configure_tls(client_builder, tls_config)?;
-        }
-
-        self.client = client_builder.build()?;
-        Ok(())
-    }
-
     /// Configure TLS settings on a reqwest ClientBuilder
     fn configure_tls(
         mut client_builder: reqwest::ClientBuilder,
@@ -257,9 +310,10 @@ impl ApiClient {
         Ok(client_builder)
     }
 
+    // Default headers are applied per request rather than baked into the client so
+    // that the underlying connection pool can be shared across providers.
     pub fn with_headers(mut self, headers: HeaderMap) -> Result<Self> {
         self.default_headers = headers;
-        self.rebuild_client()?;
         Ok(self)
     }
 
@@ -267,7 +321,6 @@ impl ApiClient {
         let header_name = HeaderName::from_bytes(key.as_bytes())?;
         let header_value = HeaderValue::from_str(value)?;
         self.default_headers.insert(header_name, header_value);
-        self.rebuild_client()?;
         Ok(self)
     }
 
@@ -367,6 +420,7 @@ impl<'a> ApiRequestBuilder<'a> {
     {
         let url = self.client.build_url(self.path)?;
         let mut request = request_builder(url, &self.client.client);
+        request = request.headers(self.client.default_headers.clone());
         request = request.headers(self.headers.clone());
 
         request = match &self.client.auth {
@@ -6,10 +6,11 @@ use serde_json::Value;
 use super::api_client::{ApiClient, AuthMethod, AuthProvider};
 use super::azureauth::{AuthError, AzureAuth};
 use super::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage, Usage};
+use super::embedding::{EmbeddingCapable, EmbeddingRequest, EmbeddingResponse};
 use super::errors::ProviderError;
 use super::formats::openai::{create_request, get_usage, response_to_message};
 use super::retry::ProviderRetry;
-use super::utils::{get_model, handle_response_openai_compat, ImageFormat};
+use super::utils::{get_model, get_stop_reason, handle_response_openai_compat, ImageFormat};
 use crate::conversation::message::Message;
 use crate::model::ModelConfig;
 use crate::providers::utils::RequestLog;
@@ -25,6 +26,8 @@ pub const AZURE_OPENAI_KNOWN_MODELS: &[&str] = &["gpt-4o", "gpt-4o-mini", "gpt-4
 pub struct AzureProvider {
     api_client: ApiClient,
     deployment_name: String,
+    /// Deployment of an embedding model, when embeddings should go through Azure too
+    embedding_deployment_name: Option<String>,
     api_version: String,
     model: ModelConfig,
 }
@@ -35,8 +38,9 @@ impl Serialize for AzureProvider {
         S: serde::Serializer,
     {
         use serde::ser::SerializeStruct;
-        let mut state = serializer.serialize_struct("AzureProvider", 2)?;
+        let mut state = serializer.serialize_struct("AzureProvider", 3)?;
         state.serialize_field("deployment_name", &self.deployment_name)?;
+        state.serialize_field("embedding_deployment_name", &self.embedding_deployment_name)?;
         state.serialize_field("api_version", &self.api_version)?;
         state.end()
     }
@@ -73,6 +77,10 @@ impl AzureProvider {
         let config = crate::config::Config::global();
         let endpoint: String = config.get_param("AZURE_OPENAI_ENDPOINT")?;
         let deployment_name: String = config.get_param("AZURE_OPENAI_DEPLOYMENT_NAME")?;
+        let embedding_deployment_name = config
+            .get_param("AZURE_OPENAI_EMBEDDING_DEPLOYMENT_NAME")
+            .ok()
+            .filter(|name: &String| !name.is_empty());
         let api_version: String = config
             .get_param("AZURE_OPENAI_API_VERSION")
             .unwrap_or_else(|_| AZURE_DEFAULT_API_VERSION.to_string());
@@ -92,6 +100,7 @@ impl AzureProvider {
         Ok(Self {
             api_client,
             deployment_name,
+            embedding_deployment_name,
             api_version,
             model,
         })
@@ -124,6 +133,7 @@ impl Provider for AzureProvider {
                 ConfigKey::new("AZURE_OPENAI_DEPLOYMENT_NAME", true, false, None),
                 ConfigKey::new("AZURE_OPENAI_API_VERSION", true, false, Some("2024-10-21")),
                 ConfigKey::new("AZURE_OPENAI_API_KEY", true, true, Some("")),
+                ConfigKey::new("AZURE_OPENAI_EMBEDDING_DEPLOYMENT_NAME", false, false, None),
             ],
         )
     }
@@ -132,6 +142,16 @@ impl Provider for AzureProvider {
         self.model.clone()
     }
 
+    fn supports_embeddings(&self) -> bool {
+        self.embedding_deployment_name.is_some()
+    }
+
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, ProviderError> {
+        EmbeddingCapable::create_embeddings(self, texts)
+            .await
+            .map_err(|e| ProviderError::ExecutionError(e.to_string()))
+    }
+
     #[tracing::instrument(
         skip(self, model_config, system, messages, tools),
         fields(model_config, input, output, input_tokens, output_tokens, total_tokens)
@@ -159,6 +179,54 @@ impl Provider for AzureProvider {
         let response_model = get_model(&response);
         let mut log = RequestLog::start(model_config, &payload)?;
         log.write(&response, Some(&usage))?;
-        Ok((message, ProviderUsage::new(response_model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
+    }
+}
+
+#[async_trait]
+impl EmbeddingCapable for AzureProvider {
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(vec![]);
+        }
+        let deployment_name = self.embedding_deployment_name.as_ref().ok_or_else(|| {
+            anyhow::anyhow!("Set AZURE_OPENAI_EMBEDDING_DEPLOYMENT_NAME to create embeddings")
+        })?;
+
+        // Azure picks the model from the deployment, the model field is only sent for the schema
+        let request = EmbeddingRequest {
+            input: texts,
+            model: deployment_name.clone(),
+        };
+        let path = format!(
+            "openai/deployments/{}/embeddings?api-version={}",
+            deployment_name, self.api_version
+        );
+        let response = self
+            .api_client
+            .api_post(&path, &serde_json::to_value(request)?)
+            .await?;
+
+        if !response.status.is_success() {
+            return Err(anyhow::anyhow!(
+                "Embedding API error: HTTP {}",
+                response.status
+            ));
+        }
+
+        let embedding_response: EmbeddingResponse = serde_json::from_value(
+            response
+                .payload
+                .ok_or_else(|| anyhow::anyhow!("Empty response body"))?,
+        )?;
+
+        Ok(embedding_response
+            .data
+            .into_iter()
+            .map(|d| d.embedding)
+            .collect())
     }
 }
@@ -12,6 +12,9 @@ use rmcp::model::Tool;
 use utoipa::ToSchema;
 
 use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
 use std::ops::{Add, AddAssign};
 use std::pin::Pin;
 use std::sync::Mutex;
@@ -33,6 +36,55 @@ pub fn get_current_model() -> Option<String> {
 
 pub static MSG_COUNT_FOR_SESSION_NAME_GENERATION: usize = 3;
 
+/// Metadata attached to provider requests for spend attribution and observability
+pub type RequestMetadata = HashMap<String, String>;
+
+pub const REQUEST_METADATA_USER_ID: &str = "user_id";
+pub const REQUEST_METADATA_SESSION_ID: &str = "session_id";
+pub const REQUEST_METADATA_RECIPE_NAME: &str = "recipe_name";
+
+tokio::task_local! {
+    static REQUEST_METADATA: RequestMetadata;
+}
+
+/// Run a provider call with `metadata` attached to the requests it builds.
+///
+/// Providers read it back with [`current_request_attribution`] while building their payload
+/// and map it to their native field, e.g. OpenAI's `user` or Anthropic's `metadata.user_id`.
+pub async fn with_request_metadata<F: Future>(metadata: RequestMetadata, f: F) -> F::Output {
+    REQUEST_METADATA.scope(metadata, f).await
+}
+
+/// The metadata for the request being built, empty outside of [`with_request_metadata`]
+pub fn current_request_metadata() -> RequestMetadata {
+    REQUEST_METADATA
+        .try_with(|metadata| metadata.clone())
+        .unwrap_or_default()
+}
+
+/// The identifier providers should attribute a request to: the user if known, else the session
+pub fn request_attribution_id(metadata: &RequestMetadata) -> Option<&str> {
+    metadata
+        .get(REQUEST_METADATA_USER_ID)
+        .or_else(|| metadata.get(REQUEST_METADATA_SESSION_ID))
+        .map(String::as_str)
+}
+
+/// Opt in to sending the attribution id with provider requests. Off by default, since the
+/// provider then sees which requests belong to the same user or session.
+pub const REQUEST_ATTRIBUTION_KEY: &str = "GOOSE_PROVIDER_REQUEST_ATTRIBUTION";
+
+/// The attribution id for the request being built, None unless attribution is turned on
+pub fn current_request_attribution() -> Option<String> {
+    let enabled = crate::config::Config::global()
+        .get_param::<bool>(REQUEST_ATTRIBUTION_KEY)
+        .unwrap_or(false);
+    if !enabled {
+        return None;
+    }
+    request_attribution_id(&current_request_metadata()).map(str::to_string)
+}
+
 /// Information about a model's capabilities
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, PartialEq)]
 pub struct ModelInfo {
@@ -215,15 +267,78 @@ impl ConfigKey {
     }
 }
 
+/// Why the model stopped generating a response
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StopReason {
+    /// The model finished its turn
+    EndTurn,
+    /// The response was cut off by the output token limit
+    MaxTokens,
+    /// The model stopped to call tools
+    ToolUse,
+    /// The model produced one of the requested stop sequences
+    StopSequence,
+    /// The provider withheld or cut off the response for safety or policy reasons
+    ContentFilter,
+    /// A reason the provider reported that goose does not know
+    Other(String),
+}
+
+impl StopReason {
+    /// Map the reasons the OpenAI, Anthropic and Google APIs report onto a StopReason
+    pub fn from_provider(reason: &str) -> Self {
+        match reason {
+            "stop" | "end_turn" | "STOP" | "COMPLETE" => StopReason::EndTurn,
+            "length" | "max_tokens" | "MAX_TOKENS" => StopReason::MaxTokens,
+            "tool_calls" | "function_call" | "tool_use" => StopReason::ToolUse,
+            "stop_sequence" => StopReason::StopSequence,
+            "content_filter" | "refusal" | "SAFETY" | "RECITATION" | "BLOCKLIST"
+            | "PROHIBITED_CONTENT" | "SPII" => StopReason::ContentFilter,
+            other => StopReason::Other(other.to_string()),
+        }
+    }
+
+    /// Whether the response is probably incomplete
+    pub fn is_truncation(&self) -> bool {
+        matches!(self, StopReason::MaxTokens | StopReason::ContentFilter)
+    }
+}
+
+impl fmt::Display for StopReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopReason::EndTurn => write!(f, "end of turn"),
+            StopReason::MaxTokens => write!(f, "output token limit reached"),
+            StopReason::ToolUse => write!(f, "tool use"),
+            StopReason::StopSequence => write!(f, "stop sequence"),
+            StopReason::ContentFilter => write!(f, "content filter"),
+            StopReason::Other(reason) => write!(f, "{}", reason),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderUsage {
     pub model: String,
     pub usage: Usage,
+    /// Why the model stopped, when the provider reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_reason: Option<StopReason>,
 }
 
 impl ProviderUsage {
     pub fn new(model: String, usage: Usage) -> Self {
-        Self { model, usage }
+        Self {
+            model,
+            usage,
+            stop_reason: None,
+        }
+    }
+
+    pub fn with_stop_reason(mut self, stop_reason: Option<StopReason>) -> Self {
+        self.stop_reason = stop_reason;
+        self
     }
 
     /// Ensures this ProviderUsage has token counts, estimating them if necessary
@@ -251,6 +366,10 @@ impl ProviderUsage {
         ProviderUsage {
             model: self.model.clone(),
             usage: self.usage + other.usage,
+            stop_reason: other
+                .stop_reason
+                .clone()
+                .or_else(|| self.stop_reason.clone()),
         }
     }
 }
@@ -260,6 +379,12 @@ pub struct Usage {
     pub input_tokens: Option<i32>,
     pub output_tokens: Option<i32>,
     pub total_tokens: Option<i32>,
+    /// Input tokens read from the provider's prompt cache, already counted in input_tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_input_tokens: Option<i32>,
+    /// Input tokens written to the provider's prompt cache, already counted in input_tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_input_tokens: Option<i32>,
 }
 
 fn sum_optionals<T>(a: Option<T>, b: Option<T>) -> Option<T>
@@ -282,6 +407,14 @@ impl Add for Usage {
             input_tokens: sum_optionals(self.input_tokens, other.input_tokens),
             output_tokens: sum_optionals(self.output_tokens, other.output_tokens),
             total_tokens: sum_optionals(self.total_tokens, other.total_tokens),
+            cache_read_input_tokens: sum_optionals(
+                self.cache_read_input_tokens,
+                other.cache_read_input_tokens,
+            ),
+            cache_write_input_tokens: sum_optionals(
+                self.cache_write_input_tokens,
+                other.cache_write_input_tokens,
+            ),
         }
     }
 }
@@ -302,12 +435,56 @@ impl Usage {
             input_tokens,
             output_tokens,
             total_tokens,
+            cache_read_input_tokens: None,
+            cache_write_input_tokens: None,
         }
     }
+
+    pub fn with_cache_tokens(
+        mut self,
+        cache_read_input_tokens: Option<i32>,
+        cache_write_input_tokens: Option<i32>,
+    ) -> Self {
+        self.cache_read_input_tokens = cache_read_input_tokens;
+        self.cache_write_input_tokens = cache_write_input_tokens;
+        self
+    }
 }
 
 use async_trait::async_trait;
 
+/// What a provider can accept, so that callers can adapt a request rather than send one that fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ProviderCapabilities {
+    /// Images in messages and tool results are understood
+    pub vision: bool,
+    /// Tools are passed to the model and it can call them
+    pub tools: bool,
+    pub streaming: bool,
+    pub embeddings: bool,
+    /// Responses can be constrained to JSON
+    pub json_mode: bool,
+    /// The most tools a single request may carry, when the API has a limit
+    pub max_tools: Option<usize>,
+}
+
+impl ProviderCapabilities {
+    /// What both providers can do, for wrappers that send requests to either
+    pub fn intersect(&self, other: &ProviderCapabilities) -> ProviderCapabilities {
+        ProviderCapabilities {
+            vision: self.vision && other.vision,
+            tools: self.tools && other.tools,
+            streaming: self.streaming && other.streaming,
+            embeddings: self.embeddings && other.embeddings,
+            json_mode: self.json_mode && other.json_mode,
+            max_tools: match (self.max_tools, other.max_tools) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            },
+        }
+    }
+}
+
 /// Trait for LeadWorkerProvider-specific functionality
 pub trait LeadWorkerProviderTrait {
     /// Get information about the lead and worker models for logging
@@ -391,6 +568,12 @@ pub trait Provider: Send + Sync {
         Ok(None)
     }
 
+    /// Optional hook to look up how many tokens of context a model accepts, from the
+    /// provider's model metadata. None when the provider does not publish it.
+    async fn fetch_context_limit(&self, _model_name: &str) -> Result<Option<usize>, ProviderError> {
+        Ok(None)
+    }
+
     /// Check if this provider supports embeddings
     fn supports_embeddings(&self) -> bool {
         false
@@ -429,6 +612,19 @@ pub trait Provider: Send + Sync {
         false
     }
 
+    /// What this provider can accept. The default assumes images and tools work, which is
+    /// what goose has always sent, so providers only override what they know to be different.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            vision: true,
+            tools: true,
+            streaming: self.supports_streaming(),
+            embeddings: self.supports_embeddings(),
+            json_mode: false,
+            max_tools: None,
+        }
+    }
+
     /// Get the currently active model name
     /// For regular providers, this returns the configured model
     /// For LeadWorkerProvider, this returns the currently active model (lead or worker)
@@ -555,6 +751,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_usage_add_sums_cache_tokens() {
+        let first = Usage::new(Some(100), Some(10), Some(110)).with_cache_tokens(Some(80), None);
+        let second = Usage::new(Some(50), Some(5), Some(55)).with_cache_tokens(Some(40), Some(10));
+
+        let total = first + second;
+        assert_eq!(total.input_tokens, Some(150));
+        assert_eq!(total.cache_read_input_tokens, Some(120));
+        assert_eq!(total.cache_write_input_tokens, Some(10));
+
+        let plain = Usage::new(Some(1), Some(1), Some(2)) + Usage::default();
+        assert_eq!(plain.cache_read_input_tokens, None);
+    }
+
     #[test]
     fn test_set_and_get_current_model() {
         // Set the model
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
 
 use super::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage};
+use super::embedding::EmbeddingCapable;
 use super::errors::ProviderError;
-use super::retry::{ProviderRetry, RetryConfig};
+use super::retry::{ProviderRetry, RetryConfig, DEFAULT_MAX_TOTAL_RETRY_WAIT_MS};
 use crate::conversation::message::Message;
 use crate::model::ModelConfig;
 use crate::providers::utils::RequestLog;
@@ -28,8 +29,16 @@ pub const BEDROCK_KNOWN_MODELS: &[&str] = &[
     "anthropic.claude-3-7-sonnet-20250219-v1:0",
     "anthropic.claude-opus-4-20250514-v1:0",
     "anthropic.claude-opus-4-1-20250805-v1:0",
+    "meta.llama3-3-70b-instruct-v1:0",
+    "amazon.nova-pro-v1:0",
 ];
 
+pub const BEDROCK_DEFAULT_EMBEDDING_MODEL: &str = "amazon.titan-embed-text-v2:0";
+
+/// Context limits by model, as a map from a part of the model id to the number of tokens,
+/// e.g. `{"llama3-3": 128000}`. The longest matching entry wins.
+pub const BEDROCK_CONTEXT_LIMITS_KEY: &str = "BEDROCK_CONTEXT_LIMITS";
+
 pub const BEDROCK_DEFAULT_MAX_RETRIES: usize = 6;
 pub const BEDROCK_DEFAULT_INITIAL_RETRY_INTERVAL_MS: u64 = 2000;
 pub const BEDROCK_DEFAULT_BACKOFF_MULTIPLIER: f64 = 2.0;
@@ -103,6 +112,7 @@ impl BedrockProvider {
             initial_interval_ms,
             backoff_multiplier,
             max_interval_ms,
+            max_total_wait_ms: DEFAULT_MAX_TOTAL_RETRY_WAIT_MS,
         }
     }
 
@@ -131,6 +141,14 @@ impl BedrockProvider {
             request = request.tool_config(to_bedrock_tool_config(tools)?);
         }
 
+        if let Some(max_tokens) = self.model.max_tokens {
+            request = request.inference_config(
+                bedrock::InferenceConfiguration::builder()
+                    .max_tokens(max_tokens)
+                    .build(),
+            );
+        }
+
         let response = request
             .send()
             .await
@@ -192,6 +210,23 @@ impl Provider for BedrockProvider {
         self.model.clone()
     }
 
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, ProviderError> {
+        EmbeddingCapable::create_embeddings(self, texts)
+            .await
+            .map_err(|e| ProviderError::ExecutionError(e.to_string()))
+    }
+
+    async fn fetch_context_limit(&self, model_name: &str) -> Result<Option<usize>, ProviderError> {
+        let limits = crate::config::Config::global()
+            .get_param::<HashMap<String, usize>>(BEDROCK_CONTEXT_LIMITS_KEY)
+            .unwrap_or_default();
+        Ok(configured_context_limit(&limits, model_name))
+    }
+
     #[tracing::instrument(
         skip(self, model_config, system, messages, tools),
         fields(model_config, input, output, input_tokens, output_tokens, total_tokens)
@@ -232,3 +267,70 @@ impl Provider for BedrockProvider {
         Ok((message, provider_usage))
     }
 }
+
+fn configured_context_limit(limits: &HashMap<String, usize>, model_name: &str) -> Option<usize> {
+    limits
+        .iter()
+        .filter(|(pattern, _)| model_name.contains(pattern.as_str()))
+        .max_by_key(|(pattern, _)| pattern.len())
+        .map(|(_, limit)| *limit)
+}
+
+#[async_trait]
+impl EmbeddingCapable for BedrockProvider {
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let embedding_model = std::env::var("GOOSE_EMBEDDING_MODEL")
+            .unwrap_or_else(|_| BEDROCK_DEFAULT_EMBEDDING_MODEL.to_string());
+
+        // Titan embeds one text per request
+        let mut embeddings = Vec::with_capacity(texts.len());
+        for text in texts {
+            let body = serde_json::to_vec(&serde_json::json!({ "inputText": text }))?;
+            let response = self
+                .client
+                .invoke_model()
+                .model_id(embedding_model.clone())
+                .content_type("application/json")
+                .accept("application/json")
+                .body(aws_smithy_types::Blob::new(body))
+                .send()
+                .await
+                .map_err(|err| {
+                    anyhow::anyhow!("Failed to call Bedrock: {:?}", err.into_service_error())
+                })?;
+
+            let payload: Value = serde_json::from_slice(response.body.as_ref())?;
+            let embedding = payload
+                .get("embedding")
+                .cloned()
+                .ok_or_else(|| anyhow::anyhow!("No embedding in Bedrock response"))?;
+            embeddings.push(serde_json::from_value(embedding)?);
+        }
+        Ok(embeddings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_configured_context_limit_prefers_longest_match() {
+        let limits = HashMap::from([
+            ("llama".to_string(), 128_000),
+            ("llama3-2-1b".to_string(), 32_000),
+        ]);
+        assert_eq!(
+            configured_context_limit(&limits, "meta.llama3-2-1b-instruct-v1:0"),
+            Some(32_000)
+        );
+        assert_eq!(
+            configured_context_limit(&limits, "meta.llama3-3-70b-instruct-v1:0"),
+            Some(128_000)
+        );
+        assert_eq!(
+            configured_context_limit(&limits, "amazon.nova-pro-v1:0"),
+            None
+        );
+    }
+}
@@ -7,10 +7,12 @@ use std::process::Stdio;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 
-use super::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage, Usage};
+use super::base::{
+    ConfigKey, Provider, ProviderCapabilities, ProviderMetadata, ProviderUsage, Usage,
+};
 use super::errors::ProviderError;
 use super::utils::RequestLog;
-use crate::config::Config;
+use crate::config::{Config, GooseMode};
 use crate::conversation::message::{Message, MessageContent};
 use crate::model::ModelConfig;
 use rmcp::model::Tool;
@@ -336,7 +338,7 @@ impl ClaudeCodeProvider {
         // Add permission mode based on GOOSE_MODE setting
         let config = Config::global();
         if let Ok(goose_mode) = config.get_param::<String>("GOOSE_MODE") {
-            if goose_mode.as_str() == "auto" {
+            if goose_mode.parse() == Ok(GooseMode::Auto) {
                 cmd.arg("--permission-mode").arg("acceptEdits");
             }
         }
@@ -468,6 +470,18 @@ impl Provider for ClaudeCodeProvider {
         self.model.clone()
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            vision: false,
+            // The CLI runs its own tools rather than those goose offers
+            tools: false,
+            streaming: self.supports_streaming(),
+            embeddings: self.supports_embeddings(),
+            json_mode: false,
+            max_tools: None,
+        }
+    }
+
     #[tracing::instrument(
         skip(self, model_config, system, messages, tools),
         fields(model_config, input, output, input_tokens, output_tokens, total_tokens)
@@ -17,8 +17,8 @@ use super::formats::databricks::{create_request, response_to_message};
 use super::oauth;
 use super::retry::ProviderRetry;
 use super::utils::{
-    get_model, handle_response_openai_compat, map_http_error_to_provider_error, ImageFormat,
-    RequestLog,
+    get_model, get_stop_reason, handle_response_openai_compat, map_http_error_to_provider_error,
+    parse_retry_after, with_retry_after, ImageFormat, RequestLog,
 };
 use crate::config::ConfigError;
 use crate::conversation::message::Message;
@@ -26,7 +26,7 @@ use crate::model::ModelConfig;
 use crate::providers::formats::openai::{get_usage, response_to_streaming_message};
 use crate::providers::retry::{
     RetryConfig, DEFAULT_BACKOFF_MULTIPLIER, DEFAULT_INITIAL_RETRY_INTERVAL_MS,
-    DEFAULT_MAX_RETRIES, DEFAULT_MAX_RETRY_INTERVAL_MS,
+    DEFAULT_MAX_RETRIES, DEFAULT_MAX_RETRY_INTERVAL_MS, DEFAULT_MAX_TOTAL_RETRY_WAIT_MS,
 };
 use rmcp::model::Tool;
 use serde_json::json;
@@ -206,6 +206,7 @@ impl DatabricksProvider {
             initial_interval_ms,
             backoff_multiplier,
             max_interval_ms,
+            max_total_wait_ms: DEFAULT_MAX_TOTAL_RETRY_WAIT_MS,
         }
     }
 
@@ -300,7 +301,10 @@ impl Provider for DatabricksProvider {
         let response_model = get_model(&response);
         log.write(&response, Some(&usage))?;
 
-        Ok((message, ProviderUsage::new(response_model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
     }
 
     async fn stream(
@@ -330,11 +334,15 @@ impl Provider for DatabricksProvider {
                 let resp = self.api_client.response_post(&path, &payload).await?;
                 if !resp.status().is_success() {
                     let status = resp.status();
+                    let retry_after = parse_retry_after(resp.headers());
                     let error_text = resp.text().await.unwrap_or_default();
 
                     // Parse as JSON if possible to pass to map_http_error_to_provider_error
                     let json_payload = serde_json::from_str::<Value>(&error_text).ok();
-                    return Err(map_http_error_to_provider_error(status, json_payload));
+                    return Err(with_retry_after(
+                        map_http_error_to_provider_error(status, json_payload),
+                        retry_after,
+                    ));
                 }
                 Ok(resp)
             })
@@ -1,9 +1,11 @@
-use std::sync::{Arc, RwLock};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, Instant};
 
 use super::{
     anthropic::AnthropicProvider,
     azure::AzureProvider,
-    base::{Provider, ProviderMetadata},
+    base::{ModelInfo, Provider, ProviderMetadata},
     bedrock::BedrockProvider,
     claude_code::ClaudeCodeProvider,
     cursor_agent::CursorAgentProvider,
@@ -14,12 +16,14 @@ use super::{
     google::GoogleProvider,
     lead_worker::LeadWorkerProvider,
     litellm::LiteLLMProvider,
+    mock::{MockProvider, MOCK_PROVIDER_NAME},
     ollama::OllamaProvider,
     openai::OpenAiProvider,
     openrouter::OpenRouterProvider,
     provider_registry::ProviderRegistry,
     sagemaker_tgi::SageMakerTgiProvider,
     snowflake::SnowflakeProvider,
+    testprovider::TestProvider,
     tetrate::TetrateProvider,
     venice::VeniceProvider,
     xai::XaiProvider,
@@ -28,14 +32,38 @@ use crate::config::declarative_providers::register_declarative_providers;
 use crate::model::ModelConfig;
 use crate::providers::base::ProviderType;
 use anyhow::Result;
+use once_cell::sync::Lazy;
 use tokio::sync::OnceCell;
 
 const DEFAULT_LEAD_TURNS: usize = 3;
 const DEFAULT_FAILURE_THRESHOLD: usize = 2;
 const DEFAULT_FALLBACK_TURNS: usize = 2;
 
+pub const EMBEDDING_PROVIDER_KEY: &str = "GOOSE_EMBEDDING_PROVIDER";
+pub const EMBEDDING_MODEL_KEY: &str = "GOOSE_EMBEDDING_MODEL";
+
+/// Path of a cassette file to record provider responses to, or replay them from
+pub const PROVIDER_CASSETTE_KEY: &str = "GOOSE_PROVIDER_CASSETTE";
+/// `record` or `replay`; defaults to replaying when the cassette exists
+pub const PROVIDER_CASSETTE_MODE_KEY: &str = "GOOSE_PROVIDER_CASSETTE_MODE";
+
 static REGISTRY: OnceCell<RwLock<ProviderRegistry>> = OnceCell::const_new();
 
+/// Context limit lookups by provider and model, so each answer is only asked for once per
+/// process. A lookup that failed is tried again after CONTEXT_LIMIT_RETRY_INTERVAL.
+static CONTEXT_LIMIT_LOOKUPS: Lazy<Mutex<HashMap<(String, String), ContextLimitLookup>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+const CONTEXT_LIMIT_DETECTION_TIMEOUT: Duration = Duration::from_secs(5);
+const CONTEXT_LIMIT_RETRY_INTERVAL: Duration = Duration::from_secs(300);
+
+#[derive(Debug, Clone, Copy)]
+enum ContextLimitLookup {
+    /// The provider answered, None if it does not publish a limit for the model
+    Answered(Option<usize>),
+    FailedAt(Instant),
+}
+
 async fn init_registry() -> RwLock<ProviderRegistry> {
     let mut registry = ProviderRegistry::new().with_providers(|registry| {
         registry
@@ -98,6 +126,65 @@ pub async fn providers() -> Vec<(ProviderMetadata, ProviderType)> {
         .all_metadata_with_types()
 }
 
+/// A provider available in this build along with its static model catalog
+#[derive(Debug, Clone)]
+pub struct ProviderInfo {
+    pub name: String,
+    pub display_name: String,
+    pub provider_type: ProviderType,
+    pub default_model: String,
+    pub models: Vec<ModelInfo>,
+}
+
+pub async fn list_providers() -> Vec<ProviderInfo> {
+    providers()
+        .await
+        .into_iter()
+        .map(|(metadata, provider_type)| ProviderInfo {
+            name: metadata.name,
+            display_name: metadata.display_name,
+            provider_type,
+            default_model: metadata.default_model,
+            models: metadata.known_models,
+        })
+        .collect()
+}
+
+/// List the models a provider supports.
+///
+/// Querying needs an instance of the provider, so it must be configured (e.g. have its API
+/// key set) even for providers that end up returning their static catalog; otherwise this
+/// returns the provider's configuration error. Only the provider itself is built, without
+/// what `create` adds on top such as lead/worker setup, cassettes or context limit lookups.
+/// Providers that can query their models (e.g. OpenAI, OpenRouter) are asked directly,
+/// the others return their static catalog.
+pub async fn list_models(name: &str) -> Result<Vec<ModelInfo>> {
+    let metadata = providers()
+        .await
+        .into_iter()
+        .map(|(metadata, _)| metadata)
+        .find(|metadata| metadata.name == name)
+        .ok_or_else(|| anyhow::anyhow!("Unknown provider: {}", name))?;
+
+    let provider =
+        construct_from_registry(name, ModelConfig::new(&metadata.default_model)?).await?;
+    let Some(model_names) = provider.fetch_supported_models().await? else {
+        return Ok(metadata.known_models);
+    };
+
+    let mut models = Vec::with_capacity(model_names.len());
+    for model_name in model_names {
+        match metadata.known_models.iter().find(|m| m.name == model_name) {
+            Some(known) => models.push(known.clone()),
+            None => {
+                let context_limit = ModelConfig::new(&model_name)?.context_limit();
+                models.push(ModelInfo::new(model_name, context_limit));
+            }
+        }
+    }
+    Ok(models)
+}
+
 pub async fn refresh_custom_providers() -> Result<()> {
     let registry = get_registry().await;
     registry.write().unwrap().remove_custom_providers();
@@ -112,13 +199,63 @@ pub async fn refresh_custom_providers() -> Result<()> {
 }
 
 pub async fn create(name: &str, model: ModelConfig) -> Result<Arc<dyn Provider>> {
+    // The mock provider is for tests only, so it is not listed in the registry
+    if name == MOCK_PROVIDER_NAME {
+        return Ok(Arc::new(MockProvider::from_env(model).await?));
+    }
+
     let config = crate::config::Config::global();
 
-    if let Ok(lead_model_name) = config.get_param::<String>("GOOSE_LEAD_MODEL") {
+    let cassette = config.get_param::<String>(PROVIDER_CASSETTE_KEY).ok();
+    if let Some(path) = &cassette {
+        if cassette_replay_mode(path)? {
+            tracing::info!("Replaying provider responses from {}", path);
+            return Ok(Arc::new(
+                TestProvider::new_replaying(path)?.with_tool_matching(),
+            ));
+        }
+    }
+
+    let provider = if let Ok(lead_model_name) = config.get_param::<String>("GOOSE_LEAD_MODEL") {
         tracing::info!("Creating lead/worker provider from environment variables");
-        return create_lead_worker_from_env(name, &model, &lead_model_name).await;
+        create_lead_worker_from_env(name, &model, &lead_model_name).await?
+    } else {
+        create_from_registry(name, model).await?
+    };
+
+    match cassette {
+        Some(path) => {
+            tracing::info!("Recording provider responses to {}", path);
+            Ok(Arc::new(
+                TestProvider::new_recording(provider, path)?
+                    .with_tool_matching()
+                    .with_autosave(),
+            ))
+        }
+        None => Ok(provider),
+    }
+}
+
+fn cassette_replay_mode(path: &str) -> Result<bool> {
+    let config = crate::config::Config::global();
+    match config
+        .get_param::<String>(PROVIDER_CASSETTE_MODE_KEY)
+        .ok()
+        .as_deref()
+    {
+        Some("record") => Ok(false),
+        Some("replay") => Ok(true),
+        None => Ok(std::path::Path::new(path).exists()),
+        Some(other) => Err(anyhow::anyhow!(
+            "Invalid {}: '{}', expected 'record' or 'replay'",
+            PROVIDER_CASSETTE_MODE_KEY,
+            other
+        )),
     }
+}
 
+/// Run the registered constructor of a provider, and nothing else
+async fn construct_from_registry(name: &str, model: ModelConfig) -> Result<Arc<dyn Provider>> {
     let registry = get_registry().await;
     let constructor = {
         let guard = registry.read().unwrap();
@@ -132,6 +269,75 @@ pub async fn create(name: &str, model: ModelConfig) -> Result<Arc<dyn Provider>>
     constructor(model).await
 }
 
+async fn create_from_registry(name: &str, model: ModelConfig) -> Result<Arc<dyn Provider>> {
+    let provider = construct_from_registry(name, model.clone()).await?;
+    if model.has_configured_context_limit() {
+        return Ok(provider);
+    }
+
+    // The provider knows its models better than the static table, so prefer what it reports.
+    // The provider's model config picks this up, so it does not have to be built again.
+    if let Some(limit) = detect_context_limit(name, provider.as_ref(), &model.model_name).await {
+        if limit != model.context_limit() {
+            tracing::info!(
+                "Using the {} token context limit {} reports for {}",
+                limit,
+                name,
+                model.model_name
+            );
+        }
+        ModelConfig::set_detected_context_limit(&model.model_name, limit);
+    }
+    Ok(provider)
+}
+
+async fn detect_context_limit(
+    provider_name: &str,
+    provider: &dyn Provider,
+    model_name: &str,
+) -> Option<usize> {
+    let key = (provider_name.to_string(), model_name.to_string());
+    let cached = CONTEXT_LIMIT_LOOKUPS.lock().unwrap().get(&key).copied();
+    match cached {
+        Some(ContextLimitLookup::Answered(limit)) => return limit,
+        Some(ContextLimitLookup::FailedAt(at)) if at.elapsed() < CONTEXT_LIMIT_RETRY_INTERVAL => {
+            return None
+        }
+        _ => {}
+    }
+
+    let lookup = match tokio::time::timeout(
+        CONTEXT_LIMIT_DETECTION_TIMEOUT,
+        provider.fetch_context_limit(model_name),
+    )
+    .await
+    {
+        Ok(Ok(limit)) => ContextLimitLookup::Answered(limit),
+        Ok(Err(e)) => {
+            tracing::debug!(
+                "Could not look up the context limit of {}/{}: {}",
+                provider_name,
+                model_name,
+                e
+            );
+            ContextLimitLookup::FailedAt(Instant::now())
+        }
+        Err(_) => {
+            tracing::debug!(
+                "Timed out looking up the context limit of {}/{}",
+                provider_name,
+                model_name
+            );
+            ContextLimitLookup::FailedAt(Instant::now())
+        }
+    };
+    CONTEXT_LIMIT_LOOKUPS.lock().unwrap().insert(key, lookup);
+    match lookup {
+        ContextLimitLookup::Answered(limit) => limit,
+        ContextLimitLookup::FailedAt(_) => None,
+    }
+}
+
 pub async fn create_with_named_model(
     provider_name: &str,
     model_name: &str,
@@ -140,6 +346,80 @@ pub async fn create_with_named_model(
     create(provider_name, config).await
 }
 
+/// Create the provider used for embeddings, configured with `GOOSE_EMBEDDING_PROVIDER` and
+/// `GOOSE_EMBEDDING_MODEL` so that bulk embedding can use a cheaper model than chat.
+///
+/// Either setting falls back to the main provider and model. If the embedding provider cannot be
+/// created or does not support embeddings, the main provider is used instead.
+pub async fn create_embedding_provider() -> Result<Arc<dyn Provider>> {
+    let config = crate::config::Config::global();
+    let main_provider = config.get_param::<String>("GOOSE_PROVIDER")?;
+    let main_model = config.get_param::<String>("GOOSE_MODEL")?;
+    let embedding_provider = config.get_param::<String>(EMBEDDING_PROVIDER_KEY).ok();
+    let embedding_model = config.get_param::<String>(EMBEDDING_MODEL_KEY).ok();
+
+    if let Some((provider_name, model_name)) = embedding_provider_and_model(
+        embedding_provider,
+        embedding_model,
+        &main_provider,
+        &main_model,
+    ) {
+        let model_name = match model_name {
+            Some(model_name) => model_name,
+            None => default_model(&provider_name).await?,
+        };
+        let embedder = match ModelConfig::new(&model_name) {
+            Ok(model_config) => create_from_registry(&provider_name, model_config).await,
+            Err(e) => Err(e.into()),
+        };
+        match embedder {
+            Ok(embedder) if embedder.supports_embeddings() => return Ok(embedder),
+            Ok(_) => tracing::warn!(
+                "{}/{} does not support embeddings, using the main provider",
+                provider_name,
+                model_name
+            ),
+            Err(e) => tracing::warn!(
+                "Could not create embedding provider {}/{}, using the main provider: {}",
+                provider_name,
+                model_name,
+                e
+            ),
+        }
+    }
+
+    create_with_named_model(&main_provider, &main_model).await
+}
+
+/// The provider and model to embed with, or None when neither differs from the main provider.
+/// The model is None when a different provider is configured without a model, in which case
+/// that provider's default model is used.
+fn embedding_provider_and_model(
+    embedding_provider: Option<String>,
+    embedding_model: Option<String>,
+    main_provider: &str,
+    main_model: &str,
+) -> Option<(String, Option<String>)> {
+    match (embedding_provider, embedding_model) {
+        (None, None) => None,
+        (None, Some(model)) => Some((main_provider.to_string(), Some(model))),
+        (Some(provider), model) if provider == main_provider => Some((
+            provider,
+            Some(model.unwrap_or_else(|| main_model.to_string())),
+        )),
+        (Some(provider), model) => Some((provider, model)),
+    }
+}
+
+async fn default_model(provider_name: &str) -> Result<String> {
+    providers()
+        .await
+        .into_iter()
+        .find(|(metadata, _)| metadata.name == provider_name)
+        .map(|(metadata, _)| metadata.default_model)
+        .ok_or_else(|| anyhow::anyhow!("Unknown provider: {}", provider_name))
+}
+
 async fn create_lead_worker_from_env(
     default_provider_name: &str,
     default_model: &ModelConfig,
@@ -341,6 +621,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_embedding_provider_and_model() {
+        assert_eq!(
+            embedding_provider_and_model(None, None, "openai", "gpt-4o"),
+            None
+        );
+        assert_eq!(
+            embedding_provider_and_model(
+                None,
+                Some("text-embedding-3-small".to_string()),
+                "openai",
+                "gpt-4o"
+            ),
+            Some((
+                "openai".to_string(),
+                Some("text-embedding-3-small".to_string())
+            ))
+        );
+        assert_eq!(
+            embedding_provider_and_model(Some("openai".to_string()), None, "openai", "gpt-4o"),
+            Some(("openai".to_string(), Some("gpt-4o".to_string())))
+        );
+        assert_eq!(
+            embedding_provider_and_model(Some("ollama".to_string()), None, "openai", "gpt-4o"),
+            Some(("ollama".to_string(), None))
+        );
+    }
+
     #[test]
     fn test_worker_model_preserves_original_context_limit() {
         let _guard = EnvVarGuard::new(&[
@@ -362,4 +670,120 @@ mod tests {
         _guard.set("GOOSE_CONTEXT_LIMIT", "64000");
         let _result = create_lead_worker_from_env("openai", &default_model, "gpt-4o");
     }
+
+    struct ContextLimitProvider {
+        model: ModelConfig,
+        lookups: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl Provider for ContextLimitProvider {
+        fn metadata() -> ProviderMetadata {
+            ProviderMetadata::empty()
+        }
+
+        fn get_model_config(&self) -> ModelConfig {
+            self.model.clone()
+        }
+
+        async fn complete_with_model(
+            &self,
+            _model_config: &ModelConfig,
+            _system: &str,
+            _messages: &[crate::conversation::message::Message],
+            _tools: &[rmcp::model::Tool],
+        ) -> Result<
+            (
+                crate::conversation::message::Message,
+                crate::providers::base::ProviderUsage,
+            ),
+            crate::providers::errors::ProviderError,
+        > {
+            Err(crate::providers::errors::ProviderError::ExecutionError(
+                "this provider only looks up context limits".to_string(),
+            ))
+        }
+
+        async fn fetch_context_limit(
+            &self,
+            model_name: &str,
+        ) -> Result<Option<usize>, crate::providers::errors::ProviderError> {
+            self.lookups
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if model_name == "broken-model" {
+                return Err(crate::providers::errors::ProviderError::ServerError(
+                    "unavailable".to_string(),
+                ));
+            }
+            Ok((model_name == "big-model").then_some(1_000_000))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_detect_context_limit_is_cached() {
+        let provider = ContextLimitProvider {
+            model: ModelConfig::new_or_fail("big-model"),
+            lookups: std::sync::atomic::AtomicUsize::new(0),
+        };
+        let lookups = || provider.lookups.load(std::sync::atomic::Ordering::SeqCst);
+
+        for _ in 0..2 {
+            assert_eq!(
+                detect_context_limit("test-detect", &provider, "big-model").await,
+                Some(1_000_000)
+            );
+            assert_eq!(
+                detect_context_limit("test-detect", &provider, "other-model").await,
+                None
+            );
+        }
+        assert_eq!(lookups(), 2);
+
+        // Failures are not remembered as "no limit", but retried after a while
+        assert_eq!(
+            detect_context_limit("test-detect", &provider, "broken-model").await,
+            None
+        );
+        assert_eq!(
+            detect_context_limit("test-detect", &provider, "broken-model").await,
+            None
+        );
+        assert_eq!(lookups(), 3);
+        let key = ("test-detect".to_string(), "broken-model".to_string());
+        CONTEXT_LIMIT_LOOKUPS.lock().unwrap().insert(
+            key,
+            ContextLimitLookup::FailedAt(Instant::now() - CONTEXT_LIMIT_RETRY_INTERVAL),
+        );
+        detect_context_limit("test-detect", &provider, "broken-model").await;
+        assert_eq!(lookups(), 4);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_has_configured_context_limit() {
+        let _guard = EnvVarGuard::new(&["GOOSE_CONTEXT_LIMIT"]);
+
+        assert!(!ModelConfig::new_or_fail("gpt-4o").has_configured_context_limit());
+        assert!(ModelConfig::new_or_fail("gpt-4o")
+            .with_context_limit(Some(16_000))
+            .has_configured_context_limit());
+
+        _guard.set("GOOSE_CONTEXT_LIMIT", "64000");
+        assert!(ModelConfig::new_or_fail("gpt-4o").has_configured_context_limit());
+    }
+
+    #[tokio::test]
+    async fn test_list_providers_includes_static_catalogs() {
+        let providers = list_providers().await;
+
+        let openai = providers
+            .iter()
+            .find(|p| p.name == "openai")
+            .expect("openai should be registered");
+        assert!(!openai.models.is_empty());
+        assert!(openai.models.iter().all(|m| m.context_limit > 0));
+
+        let result = list_models("not-a-provider").await;
+        assert!(result.is_err());
+    }
 }
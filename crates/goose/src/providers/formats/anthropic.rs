@@ -1,6 +1,6 @@
 use crate::conversation::message::{Message, MessageContent};
 use crate::model::ModelConfig;
-use crate::providers::base::Usage;
+use crate::providers::base::{current_request_attribution, StopReason, Usage};
 use crate::providers::errors::ProviderError;
 use anyhow::{anyhow, Result};
 use rmcp::model::{object, CallToolRequestParam, ErrorCode, ErrorData, JsonObject, Role, Tool};
@@ -274,6 +274,10 @@ pub fn response_to_message(response: &Value) -> Result<Message> {
     Ok(message)
 }
 
+fn clamp_tokens(tokens: u64) -> i32 {
+    tokens.min(i32::MAX as u64) as i32
+}
+
 /// Extract usage information from Anthropic's API response
 pub fn get_usage(data: &Value) -> Result<Usage> {
     // Extract usage data if available
@@ -313,6 +317,10 @@ pub fn get_usage(data: &Value) -> Result<Usage> {
             Some(total_input_i32),
             Some(output_tokens_i32),
             Some(total_tokens_i32),
+        )
+        .with_cache_tokens(
+            Some(clamp_tokens(cache_read_tokens)),
+            Some(clamp_tokens(cache_creation_tokens)),
         ))
     } else if data.as_object().is_some() {
         // Check if the data itself is the usage object (for message_delta events that might have usage at top level)
@@ -356,6 +364,10 @@ pub fn get_usage(data: &Value) -> Result<Usage> {
                 Some(total_input_i32),
                 Some(output_tokens_i32),
                 Some(total_tokens_i32),
+            )
+            .with_cache_tokens(
+                Some(clamp_tokens(cache_read_tokens)),
+                Some(clamp_tokens(cache_creation_tokens)),
             ))
         } else {
             tracing::debug!("🔍 Anthropic no token data found in object");
@@ -446,6 +458,13 @@ pub fn create_request(
         );
     }
 
+    if let Some(user_id) = current_request_attribution() {
+        payload
+            .as_object_mut()
+            .unwrap()
+            .insert("metadata".to_string(), json!({ "user_id": user_id }));
+    }
+
     Ok(payload)
 }
 
@@ -478,6 +497,7 @@ where
         let mut accumulated_tool_calls: std::collections::HashMap<String, (String, String)> = std::collections::HashMap::new();
         let mut current_tool_id: Option<String> = None;
         let mut final_usage: Option<crate::providers::base::ProviderUsage> = None;
+        let mut stop_reason: Option<StopReason> = None;
         let mut message_id: Option<String> = None;
 
         while let Some(line_result) = stream.next().await {
@@ -616,6 +636,9 @@ where
                 "message_delta" => {
                     // Message metadata delta (like stop_reason) and cumulative usage
                     tracing::debug!("🔍 Anthropic message_delta event data: {}", serde_json::to_string_pretty(&event.data).unwrap_or_else(|_| format!("{:?}", event.data)));
+                    if let Some(reason) = event.data.pointer("/delta/stop_reason").and_then(|v| v.as_str()) {
+                        stop_reason = Some(StopReason::from_provider(reason));
+                    }
                     if let Some(usage_data) = event.data.get("usage") {
                         tracing::debug!("🔍 Anthropic message_delta usage data (cumulative): {}", serde_json::to_string_pretty(usage_data).unwrap_or_else(|_| format!("{:?}", usage_data)));
                         let delta_usage = get_usage(usage_data).unwrap_or_default();
@@ -680,7 +703,7 @@ where
 
         // Yield final usage information if available
         if let Some(usage) = final_usage {
-            yield (None, Some(usage));
+            yield (None, Some(usage.with_stop_reason(stop_reason)));
         } else {
             tracing::debug!("🔍 Anthropic no final usage to yield");
         }
@@ -992,6 +1015,8 @@ mod tests {
         assert_eq!(usage.input_tokens, Some(15007));
         assert_eq!(usage.output_tokens, Some(50));
         assert_eq!(usage.total_tokens, Some(15057)); // 15007 + 50
+        assert_eq!(usage.cache_read_input_tokens, Some(5000));
+        assert_eq!(usage.cache_write_input_tokens, Some(10000));
 
         Ok(())
     }
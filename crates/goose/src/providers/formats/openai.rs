@@ -1,6 +1,6 @@
 use crate::conversation::message::{Message, MessageContent};
 use crate::model::ModelConfig;
-use crate::providers::base::{ProviderUsage, Usage};
+use crate::providers::base::{current_request_attribution, ProviderUsage, StopReason, Usage};
 use crate::providers::utils::{
     convert_image, detect_image_path, is_valid_function_name, load_image_file, safely_parse_json,
     sanitize_function_name, ImageFormat,
@@ -451,6 +451,9 @@ where
     try_stream! {
         use futures::StreamExt;
 
+        // Chunks after the one with the finish reason may still carry usage, so remember it
+        let mut stop_reason: Option<StopReason> = None;
+
         'outer: while let Some(response) = stream.next().await {
             if response.as_ref().is_ok_and(|s| s == "data: [DONE]") {
                 break 'outer;
@@ -466,12 +469,14 @@ where
                 .ok_or_else(|| anyhow!("unexpected stream format"))?)
                 .map_err(|e| anyhow!("Failed to parse streaming chunk: {}: {:?}", e, &line))?;
 
+            if let Some(reason) = chunk.choices.first().and_then(|c| c.finish_reason.as_deref()) {
+                stop_reason = Some(StopReason::from_provider(reason));
+            }
+
             let usage = chunk.usage.as_ref().and_then(|u| {
                 chunk.model.as_ref().map(|model| {
-                    ProviderUsage {
-                        usage: get_usage(u),
-                        model: model.clone(),
-                    }
+                    ProviderUsage::new(model.clone(), get_usage(u))
+                        .with_stop_reason(stop_reason.clone())
                 })
             });
 
@@ -518,6 +523,9 @@ where
                                         done = true;
                                     }
 
+                                    if let Some(reason) = tool_chunk.choices[0].finish_reason.as_deref() {
+                                        stop_reason = Some(StopReason::from_provider(reason));
+                                    }
                                     if tool_chunk.choices[0].finish_reason == Some("tool_calls".to_string()) {
                                         done = true;
                                     }
@@ -579,7 +587,7 @@ where
 
                 yield (
                     Some(msg),
-                    usage,
+                    usage.map(|u| u.with_stop_reason(stop_reason.clone())),
                 )
             } else if chunk.choices[0].delta.content.is_some() {
                 let text = chunk.choices[0].delta.content.as_ref().unwrap();
@@ -706,6 +714,13 @@ pub fn create_request(
             .unwrap()
             .insert(key.to_string(), json!(tokens));
     }
+
+    if let Some(user) = current_request_attribution() {
+        payload
+            .as_object_mut()
+            .unwrap()
+            .insert("user".to_string(), json!(user));
+    }
     Ok(payload)
 }
 
@@ -1267,6 +1282,43 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_create_request_includes_request_metadata() -> anyhow::Result<()> {
+        use crate::providers::base::{
+            with_request_metadata, REQUEST_ATTRIBUTION_KEY, REQUEST_METADATA_SESSION_ID,
+            REQUEST_METADATA_USER_ID,
+        };
+        use std::collections::HashMap;
+
+        let model_config = ModelConfig::new_or_fail("gpt-4o");
+        let build = || create_request(&model_config, "system", &[], &[], &ImageFormat::OpenAi);
+
+        assert!(build()?.get("user").is_none());
+
+        let session_only = HashMap::from([(
+            REQUEST_METADATA_SESSION_ID.to_string(),
+            "session-1".to_string(),
+        )]);
+
+        // Nothing is sent until attribution is turned on
+        std::env::remove_var(REQUEST_ATTRIBUTION_KEY);
+        let request = with_request_metadata(session_only.clone(), async { build() }).await?;
+        assert!(request.get("user").is_none());
+
+        std::env::set_var(REQUEST_ATTRIBUTION_KEY, "true");
+        let request = with_request_metadata(session_only.clone(), async { build() }).await;
+        let mut with_user = session_only;
+        with_user.insert(REQUEST_METADATA_USER_ID.to_string(), "user-1".to_string());
+        let request_with_user = with_request_metadata(with_user, async { build() }).await;
+        std::env::remove_var(REQUEST_ATTRIBUTION_KEY);
+
+        assert_eq!(request?["user"], json!("session-1"));
+        assert_eq!(request_with_user?["user"], json!("user-1"));
+
+        Ok(())
+    }
+
     #[test]
     fn test_create_request_o1_default() -> anyhow::Result<()> {
         // Test default medium reasoning effort for O1 model
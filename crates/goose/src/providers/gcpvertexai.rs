@@ -10,6 +10,7 @@ use url::Url;
 
 use crate::conversation::message::Message;
 use crate::model::ModelConfig;
+use crate::providers::api_client::pooled_http_client;
 use crate::providers::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage};
 
 use crate::providers::errors::ProviderError;
@@ -92,9 +93,7 @@ impl GcpVertexAIProvider {
         let location = Self::determine_location(config)?;
         let host = format!("https://{}-aiplatform.googleapis.com", location);
 
-        let client = Client::builder()
-            .timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECS))
-            .build()?;
+        let client = pooled_http_client(Duration::from_secs(DEFAULT_TIMEOUT_SECS))?;
 
         let auth = GcpAuth::new().await?;
 
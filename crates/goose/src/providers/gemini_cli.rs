@@ -6,7 +6,7 @@ use std::process::Stdio;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 
-use super::base::{Provider, ProviderMetadata, ProviderUsage, Usage};
+use super::base::{Provider, ProviderCapabilities, ProviderMetadata, ProviderUsage, Usage};
 use super::errors::ProviderError;
 use super::utils::RequestLog;
 use crate::conversation::message::{Message, MessageContent};
@@ -316,6 +316,18 @@ impl Provider for GeminiCliProvider {
         self.model.clone()
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            vision: false,
+            // The CLI runs its own tools rather than those goose offers
+            tools: false,
+            streaming: self.supports_streaming(),
+            embeddings: self.supports_embeddings(),
+            json_mode: false,
+            max_tools: None,
+        }
+    }
+
     #[tracing::instrument(
         skip(self, _model_config, system, messages, tools),
         fields(model_config, input, output, input_tokens, output_tokens, total_tokens)
@@ -11,11 +11,14 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use super::api_client::pooled_http_client;
 use super::base::{Provider, ProviderMetadata, ProviderUsage, Usage};
 use super::errors::ProviderError;
 use super::formats::openai::{create_request, get_usage, response_to_message};
 use super::retry::ProviderRetry;
-use super::utils::{get_model, handle_response_openai_compat, ImageFormat, RequestLog};
+use super::utils::{
+    get_model, get_stop_reason, handle_response_openai_compat, ImageFormat, RequestLog,
+};
 
 use crate::config::{Config, ConfigError};
 use crate::conversation::message::Message;
@@ -117,9 +120,7 @@ pub struct GithubCopilotProvider {
 
 impl GithubCopilotProvider {
     pub async fn from_env(model: ModelConfig) -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(600))
-            .build()?;
+        let client = pooled_http_client(Duration::from_secs(600))?;
         let cache = DiskCache::new();
         let mu = tokio::sync::Mutex::new(RefCell::new(None));
         Ok(Self {
@@ -426,7 +427,10 @@ impl Provider for GithubCopilotProvider {
         });
         let response_model = get_model(&response);
         log.write(&response, Some(&usage))?;
-        Ok((message, ProviderUsage::new(response_model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
     }
 
     /// Fetch supported models from GitHub Copliot; returns Err on failure, Ok(None) if not present
@@ -1,20 +1,26 @@
 use super::api_client::{ApiClient, AuthMethod};
+use super::embedding::EmbeddingCapable;
 use super::errors::ProviderError;
 use super::retry::ProviderRetry;
-use super::utils::{handle_response_google_compat, unescape_json_values, RequestLog};
+use super::utils::{
+    get_stop_reason, handle_response_google_compat, unescape_json_values, RequestLog,
+};
 use crate::conversation::message::Message;
 
 use crate::model::ModelConfig;
-use crate::providers::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage};
+use crate::providers::base::{
+    ConfigKey, Provider, ProviderCapabilities, ProviderMetadata, ProviderUsage,
+};
 use crate::providers::formats::google::{create_request, get_usage, response_to_message};
 use anyhow::Result;
 use async_trait::async_trait;
 use rmcp::model::Tool;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 pub const GOOGLE_API_HOST: &str = "https://generativelanguage.googleapis.com";
 pub const GOOGLE_DEFAULT_MODEL: &str = "gemini-2.5-pro";
 pub const GOOGLE_DEFAULT_FAST_MODEL: &str = "gemini-2.5-flash";
+pub const GOOGLE_DEFAULT_EMBEDDING_MODEL: &str = "gemini-embedding-001";
 pub const GOOGLE_KNOWN_MODELS: &[&str] = &[
     "gemini-2.5-pro",
     "gemini-2.5-pro-preview-06-05",
@@ -90,6 +96,27 @@ impl Provider for GoogleProvider {
         self.model.clone()
     }
 
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, ProviderError> {
+        EmbeddingCapable::create_embeddings(self, texts)
+            .await
+            .map_err(|e| ProviderError::ExecutionError(e.to_string()))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            vision: true,
+            tools: true,
+            streaming: self.supports_streaming(),
+            embeddings: self.supports_embeddings(),
+            json_mode: true,
+            max_tools: None,
+        }
+    }
+
     #[tracing::instrument(
         skip(self, model_config, system, messages, tools),
         fields(model_config, input, output, input_tokens, output_tokens, total_tokens)
@@ -118,7 +145,8 @@ impl Provider for GoogleProvider {
             None => model_config.model_name.clone(),
         };
         log.write(&response, Some(&usage))?;
-        let provider_usage = ProviderUsage::new(response_model, usage);
+        let provider_usage =
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response));
         Ok((message, provider_usage))
     }
 
@@ -137,4 +165,71 @@ impl Provider for GoogleProvider {
         models.sort();
         Ok(Some(models))
     }
+
+    async fn fetch_context_limit(&self, model_name: &str) -> Result<Option<usize>, ProviderError> {
+        let response = self
+            .api_client
+            .response_get(&format!("v1beta/models/{}", model_name))
+            .await?;
+        let json: Value = response.json().await?;
+        Ok(json
+            .get("inputTokenLimit")
+            .and_then(|v| v.as_u64())
+            .map(|limit| limit as usize))
+    }
+}
+
+#[async_trait]
+impl EmbeddingCapable for GoogleProvider {
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let embedding_model = std::env::var("GOOSE_EMBEDDING_MODEL")
+            .unwrap_or_else(|_| GOOGLE_DEFAULT_EMBEDDING_MODEL.to_string());
+
+        let requests: Vec<Value> = texts
+            .iter()
+            .map(|text| {
+                json!({
+                    "model": format!("models/{}", embedding_model),
+                    "content": {"parts": [{"text": text}]},
+                })
+            })
+            .collect();
+
+        let response = self
+            .api_client
+            .api_post(
+                &format!("v1beta/models/{}:batchEmbedContents", embedding_model),
+                &json!({ "requests": requests }),
+            )
+            .await?;
+
+        if !response.status.is_success() {
+            return Err(anyhow::anyhow!(
+                "Embedding API error: HTTP {}",
+                response.status
+            ));
+        }
+
+        let payload = response
+            .payload
+            .ok_or_else(|| anyhow::anyhow!("Empty response body"))?;
+        let embeddings = payload
+            .get("embeddings")
+            .and_then(|e| e.as_array())
+            .ok_or_else(|| anyhow::anyhow!("No embeddings in response"))?;
+
+        embeddings
+            .iter()
+            .map(|embedding| {
+                serde_json::from_value::<Vec<f32>>(
+                    embedding.get("values").cloned().unwrap_or_default(),
+                )
+                .map_err(anyhow::Error::from)
+            })
+            .collect()
+    }
 }
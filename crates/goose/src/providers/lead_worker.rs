@@ -4,7 +4,9 @@ use std::ops::Deref;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
-use super::base::{LeadWorkerProviderTrait, Provider, ProviderMetadata, ProviderUsage};
+use super::base::{
+    LeadWorkerProviderTrait, Provider, ProviderCapabilities, ProviderMetadata, ProviderUsage,
+};
 use super::errors::ProviderError;
 use crate::conversation::message::{Message, MessageContent};
 use crate::model::ModelConfig;
@@ -428,6 +430,19 @@ impl Provider for LeadWorkerProvider {
         }
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        // Turns may go to either provider, so only claim what both can do
+        let capabilities = self
+            .lead_provider
+            .capabilities()
+            .intersect(&self.worker_provider.capabilities());
+        ProviderCapabilities {
+            streaming: self.supports_streaming(),
+            embeddings: self.supports_embeddings(),
+            ..capabilities
+        }
+    }
+
     fn supports_embeddings(&self) -> bool {
         // Support embeddings if either provider supports them
         self.lead_provider.supports_embeddings() || self.worker_provider.supports_embeddings()
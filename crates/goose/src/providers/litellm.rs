@@ -8,7 +8,9 @@ use super::base::{ConfigKey, ModelInfo, Provider, ProviderMetadata, ProviderUsag
 use super::embedding::EmbeddingCapable;
 use super::errors::ProviderError;
 use super::retry::ProviderRetry;
-use super::utils::{get_model, handle_response_openai_compat, ImageFormat, RequestLog};
+use super::utils::{
+    get_model, get_stop_reason, handle_response_openai_compat, ImageFormat, RequestLog,
+};
 use crate::conversation::message::Message;
 
 use crate::model::ModelConfig;
@@ -190,7 +192,10 @@ impl Provider for LiteLLMProvider {
         let response_model = get_model(&response);
         let mut log = RequestLog::start(model_config, &payload)?;
         log.write(&response, Some(&usage))?;
-        Ok((message, ProviderUsage::new(response_model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
     }
 
     fn supports_embeddings(&self) -> bool {
@@ -226,7 +231,8 @@ impl Provider for LiteLLMProvider {
 #[async_trait]
 impl EmbeddingCapable for LiteLLMProvider {
     async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, anyhow::Error> {
-        let embedding_model = std::env::var("GOOSE_EMBEDDING_MODEL")
+        let embedding_model = crate::config::Config::global()
+            .get_param::<String>(super::EMBEDDING_MODEL_KEY)
             .unwrap_or_else(|_| "text-embedding-3-small".to_string());
 
         let payload = json!({
@@ -0,0 +1,265 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+use std::path::Path;
+use uuid::Uuid;
+
+use super::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage, Usage};
+use super::errors::ProviderError;
+use crate::conversation::message::{Message, MessageContent};
+use crate::model::ModelConfig;
+use rmcp::model::{object, CallToolRequestParam, Tool};
+
+/// Name to pass to `providers::create` to get a [`MockProvider`]
+pub const MOCK_PROVIDER_NAME: &str = "mock";
+pub const MOCK_PROVIDER_SCRIPT_KEY: &str = "GOOSE_MOCK_PROVIDER_SCRIPT";
+pub const MOCK_DEFAULT_MODEL: &str = "mock-model";
+
+/// A tool call the mock provider asks the agent to make
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockToolCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: Value,
+}
+
+/// One scripted reply, used when `pattern` matches the text of the latest message
+#[derive(Debug, Clone, Deserialize)]
+pub struct MockResponse {
+    pub pattern: String,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Vec<MockToolCall>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MockScript {
+    responses: Vec<MockResponse>,
+}
+
+/// Provider that answers from a script instead of a model, for deterministic tests.
+///
+/// The script is a YAML or JSON file of `responses`, each with a regex `pattern` that is
+/// matched against the text of the latest message (including tool results). The first
+/// matching response is returned; if none match the call fails.
+///
+/// ```yaml
+/// responses:
+///   - pattern: "list the files"
+///     tool_calls:
+///       - name: developer__shell
+///         arguments: { command: ls }
+///   - pattern: "Cargo.toml"
+///     text: "This is a Rust project."
+/// ```
+pub struct MockProvider {
+    model: ModelConfig,
+    responses: Vec<(Regex, MockResponse)>,
+}
+
+impl MockProvider {
+    pub async fn from_env(model: ModelConfig) -> Result<Self> {
+        let config = crate::config::Config::global();
+        let script_path: String =
+            config
+                .get_param(MOCK_PROVIDER_SCRIPT_KEY)
+                .with_context(|| {
+                    format!(
+                        "The mock provider needs a script; set {}",
+                        MOCK_PROVIDER_SCRIPT_KEY
+                    )
+                })?;
+        Self::from_script(script_path, model)
+    }
+
+    pub fn from_script(path: impl AsRef<Path>, model: ModelConfig) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read mock script {}", path.display()))?;
+        let script: MockScript = serde_yaml::from_str(&content)
+            .with_context(|| format!("Invalid mock script {}", path.display()))?;
+        Self::new(script.responses, model)
+    }
+
+    pub fn new(responses: Vec<MockResponse>, model: ModelConfig) -> Result<Self> {
+        let responses = responses
+            .into_iter()
+            .map(|response| {
+                let regex = Regex::new(&response.pattern)
+                    .with_context(|| format!("Invalid mock pattern '{}'", response.pattern))?;
+                Ok((regex, response))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { model, responses })
+    }
+
+    fn message_text(message: &Message) -> String {
+        message
+            .content
+            .iter()
+            .flat_map(|content| match content {
+                MessageContent::Text(text) => vec![text.text.clone()],
+                MessageContent::ToolResponse(response) => match &response.tool_result {
+                    Ok(contents) => contents
+                        .iter()
+                        .filter_map(|c| c.as_text().map(|t| t.text.clone()))
+                        .collect(),
+                    Err(e) => vec![e.to_string()],
+                },
+                _ => vec![],
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn to_message(response: &MockResponse) -> Message {
+        let mut message = Message::assistant();
+        if let Some(text) = &response.text {
+            message = message.with_text(text);
+        }
+        for call in &response.tool_calls {
+            let arguments = match &call.arguments {
+                Value::Object(map) => Some(map.clone()),
+                Value::Null => None,
+                other => Some(object(serde_json::json!({ "value": other }))),
+            };
+            message = message.with_tool_request(
+                format!("mock_{}", Uuid::new_v4()),
+                Ok(CallToolRequestParam {
+                    name: call.name.clone().into(),
+                    arguments,
+                }),
+            );
+        }
+        message
+    }
+}
+
+#[async_trait]
+impl Provider for MockProvider {
+    fn metadata() -> ProviderMetadata {
+        ProviderMetadata::new(
+            MOCK_PROVIDER_NAME,
+            "Mock Provider",
+            "Scripted responses for testing agents and recipes",
+            MOCK_DEFAULT_MODEL,
+            vec![MOCK_DEFAULT_MODEL],
+            "",
+            vec![ConfigKey::new(MOCK_PROVIDER_SCRIPT_KEY, true, false, None)],
+        )
+    }
+
+    fn get_model_config(&self) -> ModelConfig {
+        self.model.clone()
+    }
+
+    async fn complete_with_model(
+        &self,
+        model_config: &ModelConfig,
+        _system: &str,
+        messages: &[Message],
+        _tools: &[Tool],
+    ) -> Result<(Message, ProviderUsage), ProviderError> {
+        let latest = messages.last().map(Self::message_text).unwrap_or_default();
+
+        let response = self
+            .responses
+            .iter()
+            .find(|(regex, _)| regex.is_match(&latest))
+            .map(|(_, response)| response)
+            .ok_or_else(|| {
+                ProviderError::ExecutionError(format!(
+                    "No mock response matches the latest message: {}",
+                    latest
+                ))
+            })?;
+
+        Ok((
+            Self::to_message(response),
+            ProviderUsage::new(model_config.model_name.clone(), Usage::default()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rmcp::model::Content;
+
+    fn provider(script: &str) -> MockProvider {
+        let script: MockScript = serde_yaml::from_str(script).unwrap();
+        MockProvider::new(
+            script.responses,
+            ModelConfig::new_or_fail(MOCK_DEFAULT_MODEL),
+        )
+        .unwrap()
+    }
+
+    const SCRIPT: &str = r#"
+responses:
+  - pattern: "list the files"
+    text: "Listing"
+    tool_calls:
+      - name: developer__shell
+        arguments: { command: ls }
+  - pattern: "Cargo.toml"
+    text: "This is a Rust project."
+"#;
+
+    #[tokio::test]
+    async fn test_scripted_tool_call_then_answer() {
+        let provider = provider(SCRIPT);
+
+        let (message, _) = provider
+            .complete(
+                "",
+                &[Message::user().with_text("please list the files")],
+                &[],
+            )
+            .await
+            .unwrap();
+        assert_eq!(message.as_concat_text(), "Listing");
+        let request = message
+            .content
+            .iter()
+            .find_map(|c| c.as_tool_request())
+            .expect("tool request");
+        let call = request.tool_call.as_ref().unwrap();
+        assert_eq!(call.name, "developer__shell");
+        assert_eq!(
+            call.arguments.as_ref().unwrap().get("command"),
+            Some(&Value::from("ls"))
+        );
+
+        let tool_result = Message::user().with_tool_response(
+            request.id.clone(),
+            Ok(vec![Content::text("Cargo.toml\nsrc")]),
+        );
+        let (message, _) = provider.complete("", &[tool_result], &[]).await.unwrap();
+        assert_eq!(message.as_concat_text(), "This is a Rust project.");
+    }
+
+    #[tokio::test]
+    async fn test_unmatched_message_fails() {
+        let provider = provider(SCRIPT);
+        let result = provider
+            .complete("", &[Message::user().with_text("hello")], &[])
+            .await;
+        assert!(matches!(result, Err(ProviderError::ExecutionError(_))));
+    }
+
+    #[test]
+    fn test_invalid_pattern_is_rejected() {
+        let responses = vec![MockResponse {
+            pattern: "(".to_string(),
+            text: None,
+            tool_calls: vec![],
+        }];
+        assert!(
+            MockProvider::new(responses, ModelConfig::new_or_fail(MOCK_DEFAULT_MODEL)).is_err()
+        );
+    }
+}
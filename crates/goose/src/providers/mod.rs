@@ -18,6 +18,7 @@ pub mod githubcopilot;
 pub mod google;
 pub mod lead_worker;
 pub mod litellm;
+pub mod mock;
 pub mod oauth;
 pub mod ollama;
 pub mod openai;
@@ -36,4 +37,9 @@ pub mod utils_universal_openai_stream;
 pub mod venice;
 pub mod xai;
 
-pub use factory::{create, create_with_named_model, providers, refresh_custom_providers};
+pub use factory::{
+    create, create_embedding_provider, create_with_named_model, list_models, list_providers,
+    providers, refresh_custom_providers, ProviderInfo, EMBEDDING_MODEL_KEY, EMBEDDING_PROVIDER_KEY,
+    PROVIDER_CASSETTE_KEY, PROVIDER_CASSETTE_MODE_KEY,
+};
+pub use retry::{with_retry_notices, RetryNotice};
@@ -1,11 +1,17 @@
 use super::api_client::{ApiClient, AuthMethod};
-use super::base::{ConfigKey, MessageStream, Provider, ProviderMetadata, ProviderUsage, Usage};
+use super::base::{
+    ConfigKey, MessageStream, Provider, ProviderCapabilities, ProviderMetadata, ProviderUsage,
+    Usage,
+};
+use super::embedding::{EmbeddingCapable, EmbeddingRequest, EmbeddingResponse};
 use super::errors::ProviderError;
 use super::retry::ProviderRetry;
 use super::utils::{
-    get_model, handle_response_openai_compat, handle_status_openai_compat, RequestLog,
+    get_model, get_stop_reason, handle_response_openai_compat, handle_status_openai_compat,
+    RequestLog,
 };
 use crate::config::declarative_providers::DeclarativeProviderConfig;
+use crate::config::GooseMode;
 use crate::conversation::message::Message;
 use crate::conversation::Conversation;
 
@@ -40,6 +46,7 @@ pub const OLLAMA_KNOWN_MODELS: &[&str] = &[
     "qwen3-coder:480b-cloud",
 ];
 pub const OLLAMA_DOC_URL: &str = "https://ollama.com/library";
+pub const OLLAMA_DEFAULT_EMBEDDING_MODEL: &str = "nomic-embed-text";
 
 #[derive(serde::Serialize)]
 pub struct OllamaProvider {
@@ -134,6 +141,17 @@ impl OllamaProvider {
         })
     }
 
+    // model_info keys are prefixed with the architecture, e.g. "llama.context_length"
+    fn parse_context_length(show_response: &Value) -> Option<usize> {
+        show_response
+            .get("model_info")?
+            .as_object()?
+            .iter()
+            .find(|(key, _)| key.ends_with(".context_length"))
+            .and_then(|(_, value)| value.as_u64())
+            .map(|limit| limit as usize)
+    }
+
     async fn post(&self, payload: &Value) -> Result<Value, ProviderError> {
         let response = self
             .api_client
@@ -180,6 +198,17 @@ impl Provider for OllamaProvider {
         self.model.clone()
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            vision: true,
+            tools: true,
+            streaming: self.supports_streaming(),
+            embeddings: self.supports_embeddings(),
+            json_mode: true,
+            max_tools: None,
+        }
+    }
+
     #[tracing::instrument(
         skip(self, model_config, system, messages, tools),
         fields(model_config, input, output, input_tokens, output_tokens, total_tokens)
@@ -191,9 +220,8 @@ impl Provider for OllamaProvider {
         messages: &[Message],
         tools: &[Tool],
     ) -> Result<(Message, ProviderUsage), ProviderError> {
-        let config = crate::config::Config::global();
-        let goose_mode = config.get_param("GOOSE_MODE").unwrap_or("auto".to_string());
-        let filtered_tools = if goose_mode == "chat" { &[] } else { tools };
+        let chat_only = GooseMode::from_config() == GooseMode::Chat;
+        let filtered_tools = if chat_only { &[] } else { tools };
 
         let payload = create_request(
             &self.model,
@@ -217,7 +245,10 @@ impl Provider for OllamaProvider {
         let response_model = get_model(&response);
         let mut log = RequestLog::start(model_config, &payload)?;
         log.write(&response, Some(&usage))?;
-        Ok((message, ProviderUsage::new(response_model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
     }
 
     /// Generate a session name based on the conversation history
@@ -242,6 +273,32 @@ impl Provider for OllamaProvider {
         Ok(safe_truncate(&description, 100))
     }
 
+    /// Local models vary widely, so ask the server via `/api/show` rather than guess by name.
+    /// This runs when the provider is created through the factory, with a short timeout.
+    async fn fetch_context_limit(&self, model_name: &str) -> Result<Option<usize>, ProviderError> {
+        let response = self
+            .api_client
+            .api_post("api/show", &json!({ "model": model_name }))
+            .await?;
+        if !response.status.is_success() {
+            return Ok(None);
+        }
+        Ok(response
+            .payload
+            .as_ref()
+            .and_then(Self::parse_context_length))
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        true
+    }
+
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, ProviderError> {
+        EmbeddingCapable::create_embeddings(self, texts)
+            .await
+            .map_err(|e| ProviderError::ExecutionError(e.to_string()))
+    }
+
     fn supports_streaming(&self) -> bool {
         self.supports_streaming
     }
@@ -323,6 +380,47 @@ impl Provider for OllamaProvider {
     }
 }
 
+#[async_trait]
+impl EmbeddingCapable for OllamaProvider {
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        if texts.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let embedding_model = std::env::var("GOOSE_EMBEDDING_MODEL")
+            .unwrap_or_else(|_| OLLAMA_DEFAULT_EMBEDDING_MODEL.to_string());
+
+        let request = EmbeddingRequest {
+            input: texts,
+            model: embedding_model,
+        };
+
+        let response = self
+            .api_client
+            .api_post("v1/embeddings", &serde_json::to_value(request)?)
+            .await?;
+
+        if !response.status.is_success() {
+            return Err(anyhow::anyhow!(
+                "Embedding API error: HTTP {}",
+                response.status
+            ));
+        }
+
+        let embedding_response: EmbeddingResponse = serde_json::from_value(
+            response
+                .payload
+                .ok_or_else(|| anyhow::anyhow!("Empty response body"))?,
+        )?;
+
+        Ok(embedding_response
+            .data
+            .into_iter()
+            .map(|d| d.embedding)
+            .collect())
+    }
+}
+
 impl OllamaProvider {
     /// Filter out reasoning tokens and thinking patterns from model responses
     fn filter_reasoning_tokens(text: &str) -> String {
@@ -366,3 +464,24 @@ impl OllamaProvider {
         filtered
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_context_length() {
+        let show_response = json!({
+            "model_info": {
+                "general.architecture": "qwen3",
+                "qwen3.context_length": 40960,
+                "qwen3.embedding_length": 4096
+            }
+        });
+        assert_eq!(
+            OllamaProvider::parse_context_length(&show_response),
+            Some(40960)
+        );
+        assert_eq!(OllamaProvider::parse_context_length(&json!({})), None);
+    }
+}
@@ -7,8 +7,8 @@ use super::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage, Usage};
 use super::errors::ProviderError;
 use super::retry::ProviderRetry;
 use super::utils::{
-    get_model, handle_response_google_compat, handle_response_openai_compat, is_google_model,
-    RequestLog,
+    get_model, get_stop_reason, handle_response_google_compat, handle_response_openai_compat,
+    is_google_model, RequestLog,
 };
 use crate::conversation::message::Message;
 
@@ -277,7 +277,10 @@ impl Provider for OpenRouterProvider {
         });
         let response_model = get_model(&response);
         log.write(&response, Some(&usage))?;
-        Ok((message, ProviderUsage::new(response_model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
     }
 
     /// Fetch supported models from OpenRouter API (only models with tool support)
@@ -357,6 +360,22 @@ impl Provider for OpenRouterProvider {
         Ok(Some(models))
     }
 
+    async fn fetch_context_limit(&self, model_name: &str) -> Result<Option<usize>, ProviderError> {
+        let response = self.api_client.response_get("api/v1/models").await?;
+        let json: Value = response.json().await?;
+        Ok(json
+            .get("data")
+            .and_then(|v| v.as_array())
+            .and_then(|models| {
+                models
+                    .iter()
+                    .find(|model| model.get("id").and_then(|v| v.as_str()) == Some(model_name))
+            })
+            .and_then(|model| model.get("context_length"))
+            .and_then(|v| v.as_u64())
+            .map(|limit| limit as usize))
+    }
+
     fn supports_cache_control(&self) -> bool {
         self.model
             .model_name
@@ -1,4 +1,6 @@
+use super::api_client::pooled_http_client;
 use anyhow::Result;
+use regex::Regex;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -235,12 +237,7 @@ lazy_static::lazy_static! {
 
 /// Create a properly configured HTTP client for the current runtime
 fn create_http_client() -> Client {
-    Client::builder()
-        .timeout(Duration::from_secs(30))
-        .pool_idle_timeout(Duration::from_secs(90))
-        .pool_max_idle_per_host(10)
-        .build()
-        .expect("Failed to create HTTP client")
+    pooled_http_client(Duration::from_secs(30)).expect("Failed to create HTTP client")
 }
 
 /// OpenRouter model pricing information
@@ -332,6 +329,59 @@ pub async fn get_all_pricing() -> HashMap<String, HashMap<String, PricingInfo>>
     }
 }
 
+/// Estimate the cost in USD of a request with the given token counts, using cached pricing.
+/// Returns `None` when no pricing is known for the model, e.g. when openrouter.ai is unreachable.
+pub async fn estimate_cost(
+    provider: &str,
+    model: &str,
+    input_tokens: usize,
+    output_tokens: usize,
+) -> Option<f64> {
+    // For OpenRouter, parse the model name to extract real provider/model
+    let openrouter_data = if provider == "openrouter" {
+        parse_model_id(model)
+    } else {
+        None
+    };
+
+    let (provider_to_use, model_to_use) = match &openrouter_data {
+        Some((real_provider, real_model)) => (real_provider.as_str(), real_model.as_str()),
+        None => (provider, model),
+    };
+
+    let cleaned_model = normalize_model_name(model_to_use);
+    let pricing = get_model_pricing(provider_to_use, &cleaned_model).await?;
+    Some(cost_for_tokens(&pricing, input_tokens, output_tokens))
+}
+
+fn cost_for_tokens(pricing: &PricingInfo, input_tokens: usize, output_tokens: usize) -> f64 {
+    pricing.input_cost * input_tokens as f64 + pricing.output_cost * output_tokens as f64
+}
+
+/// Map a provider model name onto the naming used by OpenRouter's pricing data
+pub fn normalize_model_name(model: &str) -> String {
+    let mut result = model.to_string();
+
+    // Remove "-latest" suffix
+    if let Some(stripped) = result.strip_suffix("-latest") {
+        result = stripped.to_string();
+    }
+
+    // Remove date-like suffixes: -YYYYMMDD
+    let re_date = Regex::new(r"-\d{8}$").unwrap();
+    if re_date.is_match(&result) {
+        result = re_date.replace(&result, "").to_string();
+    }
+
+    // Convert version numbers like -3-7- to -3.7- (e.g., claude-3-7-sonnet -> claude-3.7-sonnet)
+    let re_version = Regex::new(r"-(\d+)-(\d+)-").unwrap();
+    if re_version.is_match(&result) {
+        result = re_version.replace(&result, "-$1.$2-").to_string();
+    }
+
+    result
+}
+
 /// Convert OpenRouter model ID to provider/model format
 /// e.g., "anthropic/claude-sonnet-4-20250514" -> ("anthropic", "claude-sonnet-4-20250514")
 pub fn parse_model_id(model_id: &str) -> Option<(String, String)> {
@@ -395,6 +445,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_normalize_model_name() {
+        assert_eq!(
+            normalize_model_name("claude-3-7-sonnet-20250219"),
+            "claude-3.7-sonnet"
+        );
+        assert_eq!(normalize_model_name("gpt-4o-latest"), "gpt-4o");
+        assert_eq!(normalize_model_name("gpt-4o"), "gpt-4o");
+    }
+
+    #[test]
+    fn test_cost_for_tokens() {
+        let pricing = PricingInfo {
+            input_cost: 0.000003,
+            output_cost: 0.000015,
+            context_length: None,
+        };
+        let cost = cost_for_tokens(&pricing, 1_000, 100);
+        assert!((cost - 0.0045).abs() < 1e-12);
+    }
+
     #[test]
     fn test_convert_pricing() {
         assert_eq!(convert_pricing("0.000003"), Some(0.000003));
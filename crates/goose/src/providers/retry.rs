@@ -1,14 +1,61 @@
 use super::errors::ProviderError;
 use crate::providers::base::Provider;
 use async_trait::async_trait;
+use std::fmt;
 use std::future::Future;
 use std::time::Duration;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::time::sleep;
 
 pub const DEFAULT_MAX_RETRIES: usize = 3;
 pub const DEFAULT_INITIAL_RETRY_INTERVAL_MS: u64 = 1000;
 pub const DEFAULT_BACKOFF_MULTIPLIER: f64 = 2.0;
 pub const DEFAULT_MAX_RETRY_INTERVAL_MS: u64 = 30_000;
+pub const DEFAULT_MAX_TOTAL_RETRY_WAIT_MS: u64 = 300_000;
+
+tokio::task_local! {
+    static RETRY_NOTICES: UnboundedSender<RetryNotice>;
+}
+
+/// Sent while a provider call is backing off, so callers can tell the user goose
+/// is waiting rather than hung.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryNotice {
+    pub attempt: usize,
+    pub max_retries: usize,
+    pub delay: Duration,
+    pub rate_limited: bool,
+}
+
+impl fmt::Display for RetryNotice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = if self.rate_limited {
+            "Rate limited by the provider"
+        } else {
+            "Provider request failed"
+        };
+        write!(
+            f,
+            "{}, retrying in {}s (attempt {}/{})",
+            reason,
+            self.delay.as_secs_f64().ceil() as u64,
+            self.attempt,
+            self.max_retries
+        )
+    }
+}
+
+/// Run a provider call with retry notices forwarded to `sender`.
+pub async fn with_retry_notices<F: Future>(
+    sender: UnboundedSender<RetryNotice>,
+    f: F,
+) -> F::Output {
+    RETRY_NOTICES.scope(sender, f).await
+}
+
+fn send_retry_notice(notice: RetryNotice) {
+    let _ = RETRY_NOTICES.try_with(|sender| sender.send(notice));
+}
 
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -20,6 +67,8 @@ pub struct RetryConfig {
     pub(crate) backoff_multiplier: f64,
     /// Maximum interval between retries in milliseconds
     pub(crate) max_interval_ms: u64,
+    /// Maximum time spent waiting across all retries in milliseconds
+    pub(crate) max_total_wait_ms: u64,
 }
 
 impl Default for RetryConfig {
@@ -29,6 +78,7 @@ impl Default for RetryConfig {
             initial_interval_ms: DEFAULT_INITIAL_RETRY_INTERVAL_MS,
             backoff_multiplier: DEFAULT_BACKOFF_MULTIPLIER,
             max_interval_ms: DEFAULT_MAX_RETRY_INTERVAL_MS,
+            max_total_wait_ms: DEFAULT_MAX_TOTAL_RETRY_WAIT_MS,
         }
     }
 }
@@ -45,9 +95,15 @@ impl RetryConfig {
             initial_interval_ms,
             backoff_multiplier,
             max_interval_ms,
+            max_total_wait_ms: DEFAULT_MAX_TOTAL_RETRY_WAIT_MS,
         }
     }
 
+    pub fn with_max_total_wait_ms(mut self, max_total_wait_ms: u64) -> Self {
+        self.max_total_wait_ms = max_total_wait_ms;
+        self
+    }
+
     pub fn delay_for_attempt(&self, attempt: usize) -> Duration {
         if attempt == 0 {
             return Duration::from_millis(0);
@@ -81,7 +137,9 @@ pub trait ProviderRetry {
         T: Send,
     {
         let mut attempts = 0;
+        let mut total_wait = Duration::ZERO;
         let config = self.retry_config();
+        let max_total_wait = Duration::from_millis(config.max_total_wait_ms);
 
         loop {
             return match operation().await {
@@ -101,6 +159,7 @@ pub trait ProviderRetry {
                             error
                         );
 
+                        // Honor the provider's Retry-After when it gives one
                         let delay = match &error {
                             ProviderError::RateLimitExceeded {
                                 retry_delay: Some(provider_delay),
@@ -109,6 +168,23 @@ pub trait ProviderRetry {
                             _ => config.delay_for_attempt(attempts),
                         };
 
+                        if total_wait + delay > max_total_wait {
+                            tracing::warn!(
+                                "Giving up: waiting {:?} more would exceed the {:?} retry budget",
+                                delay,
+                                max_total_wait
+                            );
+                            return Err(error);
+                        }
+                        total_wait += delay;
+
+                        send_retry_notice(RetryNotice {
+                            attempt: attempts,
+                            max_retries: config.max_retries,
+                            delay,
+                            rate_limited: matches!(error, ProviderError::RateLimitExceeded { .. }),
+                        });
+
                         tracing::info!("Backing off for {:?} before retry", delay);
                         sleep(delay).await;
                         continue;
@@ -4,11 +4,14 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
 use super::api_client::{ApiClient, AuthMethod};
-use super::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage};
+use super::base::{ConfigKey, Provider, ProviderCapabilities, ProviderMetadata, ProviderUsage};
 use super::errors::ProviderError;
 use super::formats::snowflake::{create_request, get_usage, response_to_message};
 use super::retry::ProviderRetry;
-use super::utils::{get_model, map_http_error_to_provider_error, ImageFormat, RequestLog};
+use super::utils::{
+    get_model, get_stop_reason, map_http_error_to_provider_error, parse_retry_after,
+    with_retry_after, ImageFormat, RequestLog,
+};
 use crate::config::ConfigError;
 use crate::conversation::message::Message;
 
@@ -101,6 +104,7 @@ impl SnowflakeProvider {
             .await?;
 
         let status = response.status();
+        let retry_after = parse_retry_after(response.headers());
         let payload_text: String = response.text().await.ok().unwrap_or_default();
 
         if status.is_success() {
@@ -270,7 +274,10 @@ impl SnowflakeProvider {
             Ok(answer_payload)
         } else {
             let error_json = serde_json::from_str::<Value>(&payload_text).ok();
-            Err(map_http_error_to_provider_error(status, error_json))
+            Err(with_retry_after(
+                map_http_error_to_provider_error(status, error_json),
+                retry_after,
+            ))
         }
     }
 }
@@ -296,6 +303,18 @@ impl Provider for SnowflakeProvider {
         self.model.clone()
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            // Images are dropped from requests
+            vision: false,
+            tools: true,
+            streaming: self.supports_streaming(),
+            embeddings: self.supports_embeddings(),
+            json_mode: false,
+            max_tools: None,
+        }
+    }
+
     #[tracing::instrument(
         skip(self, model_config, system, messages, tools),
         fields(model_config, input, output, input_tokens, output_tokens, total_tokens)
@@ -324,6 +343,9 @@ impl Provider for SnowflakeProvider {
 
         log.write(&response, Some(&usage))?;
 
-        Ok((message, ProviderUsage::new(response_model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
     }
 }
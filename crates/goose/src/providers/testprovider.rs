@@ -1,5 +1,6 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -7,9 +8,9 @@ use std::fs;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 
-use super::base::{Provider, ProviderMetadata, ProviderUsage};
+use super::base::{MessageStream, Provider, ProviderMetadata, ProviderUsage, Usage};
 use super::errors::ProviderError;
-use crate::conversation::message::Message;
+use crate::conversation::message::{Message, MessageContent};
 use crate::model::ModelConfig;
 use rmcp::model::Tool;
 
@@ -32,19 +33,32 @@ struct TestRecord {
     output: TestOutput,
 }
 
+/// Records a provider's responses to a cassette file, or replays them from one.
+///
+/// Requests are matched on their messages, and optionally on the names of the tools offered.
+/// The system prompt is stored but not matched on since it embeds the current date.
 pub struct TestProvider {
     inner: Option<Arc<dyn Provider>>,
     records: Arc<Mutex<HashMap<String, TestRecord>>>,
     file_path: String,
+    match_tools: bool,
+    autosave: bool,
 }
 
 impl TestProvider {
-    pub fn new_recording(inner: Arc<dyn Provider>, file_path: impl Into<String>) -> Self {
-        Self {
+    /// Records on top of whatever the cassette already holds, so recording with a fresh provider
+    /// adds to the file instead of replacing it
+    pub fn new_recording(inner: Arc<dyn Provider>, file_path: impl Into<String>) -> Result<Self> {
+        let file_path = file_path.into();
+        let records = Self::load_records(&file_path)?;
+
+        Ok(Self {
             inner: Some(inner),
-            records: Arc::new(Mutex::new(HashMap::new())),
-            file_path: file_path.into(),
-        }
+            records: Arc::new(Mutex::new(records)),
+            file_path,
+            match_tools: false,
+            autosave: false,
+        })
     }
 
     pub fn new_replaying(file_path: impl Into<String>) -> Result<Self> {
@@ -55,9 +69,24 @@ impl TestProvider {
             inner: None,
             records: Arc::new(Mutex::new(records)),
             file_path,
+            match_tools: false,
+            autosave: false,
         })
     }
 
+    /// Also match requests on the tools offered, so a replay fails if the tool set changed
+    pub fn with_tool_matching(mut self) -> Self {
+        self.match_tools = true;
+        self
+    }
+
+    /// Write the cassette after every recorded response instead of on `finish_recording`,
+    /// for recorders that are never explicitly finished
+    pub fn with_autosave(mut self) -> Self {
+        self.autosave = true;
+        self
+    }
+
     pub fn finish_recording(self) -> Result<()> {
         if self.inner.is_some() {
             self.save_records()?;
@@ -65,7 +94,7 @@ impl TestProvider {
         Ok(())
     }
 
-    fn hash_input(messages: &[Message]) -> String {
+    fn hash_input(&self, messages: &[Message], tools: &[Tool]) -> String {
         let stable_messages: Vec<_> = messages
             .iter()
             .map(|msg| (msg.role.clone(), msg.content.clone()))
@@ -73,6 +102,11 @@ impl TestProvider {
         let serialized = serde_json::to_string(&stable_messages).unwrap_or_default();
         let mut hasher = Sha256::new();
         hasher.update(serialized.as_bytes());
+        if self.match_tools {
+            let mut tool_names: Vec<_> = tools.iter().map(|tool| tool.name.as_ref()).collect();
+            tool_names.sort_unstable();
+            hasher.update(tool_names.join(",").as_bytes());
+        }
         format!("{:x}", hasher.finalize())
     }
 
@@ -87,10 +121,7 @@ impl TestProvider {
     }
 
     pub fn save_records(&self) -> Result<()> {
-        let records = self.records.lock().unwrap();
-        let content = serde_json::to_string_pretty(&*records)?;
-        fs::write(&self.file_path, content)?;
-        Ok(())
+        write_records(&self.records, &self.file_path)
     }
 
     pub fn get_record_count(&self) -> usize {
@@ -119,27 +150,27 @@ impl Provider for TestProvider {
         messages: &[Message],
         tools: &[Tool],
     ) -> Result<(Message, ProviderUsage), ProviderError> {
-        let hash = Self::hash_input(messages);
+        let hash = self.hash_input(messages, tools);
 
         if let Some(inner) = &self.inner {
             let (message, usage) = inner.complete(system, messages, tools).await?;
 
-            let record = TestRecord {
-                input: TestInput {
-                    system: system.to_string(),
-                    messages: messages.to_vec(),
-                    tools: tools.to_vec(),
-                },
-                output: TestOutput {
-                    message: message.clone(),
-                    usage: usage.clone(),
-                },
+            let input = TestInput {
+                system: system.to_string(),
+                messages: messages.to_vec(),
+                tools: tools.to_vec(),
             };
-
-            {
-                let mut records = self.records.lock().unwrap();
-                records.insert(hash, record);
-            }
+            let output = TestOutput {
+                message: message.clone(),
+                usage: usage.clone(),
+            };
+            store_record(
+                &self.records,
+                &self.file_path,
+                self.autosave,
+                hash,
+                TestRecord { input, output },
+            )?;
 
             Ok((message, usage))
         } else {
@@ -148,23 +179,134 @@ impl Provider for TestProvider {
                 Ok((record.output.message.clone(), record.output.usage.clone()))
             } else {
                 Err(ProviderError::ExecutionError(format!(
-                    "No recorded response found for input hash: {}",
-                    hash
+                    "No recorded response found for input hash: {} in {}",
+                    hash, self.file_path
                 )))
             }
         }
     }
 
+    /// Streams from the recorded provider and records the chunks merged into one message once
+    /// the stream ends. A replay has nothing to stream, so it answers through `complete`.
+    async fn stream(
+        &self,
+        system: &str,
+        messages: &[Message],
+        tools: &[Tool],
+    ) -> Result<MessageStream, ProviderError> {
+        let Some(inner) = &self.inner else {
+            return Err(ProviderError::NotImplemented(
+                "streaming is not replayed, use complete".to_string(),
+            ));
+        };
+
+        let mut chunks = inner.stream(system, messages, tools).await?;
+        let hash = self.hash_input(messages, tools);
+        let input = TestInput {
+            system: system.to_string(),
+            messages: messages.to_vec(),
+            tools: tools.to_vec(),
+        };
+        let model_name = inner.get_model_config().model_name;
+        let records = Arc::clone(&self.records);
+        let file_path = self.file_path.clone();
+        let autosave = self.autosave;
+
+        Ok(Box::pin(async_stream::try_stream! {
+            let mut received = Vec::new();
+            let mut last_usage = None;
+            while let Some(chunk) = chunks.next().await {
+                let (message, usage) = chunk?;
+                if let Some(message) = &message {
+                    received.push(message.clone());
+                }
+                if usage.is_some() {
+                    last_usage = usage.clone();
+                }
+                yield (message, usage);
+            }
+
+            if let Some(message) = merge_chunks(received) {
+                let usage = last_usage
+                    .unwrap_or_else(|| ProviderUsage::new(model_name, Usage::default()));
+                let output = TestOutput { message, usage };
+                store_record(&records, &file_path, autosave, hash, TestRecord { input, output })?;
+            }
+        }))
+    }
+
+    fn supports_streaming(&self) -> bool {
+        self.inner
+            .as_ref()
+            .is_some_and(|inner| inner.supports_streaming())
+    }
+
+    fn supports_embeddings(&self) -> bool {
+        self.inner
+            .as_ref()
+            .is_some_and(|inner| inner.supports_embeddings())
+    }
+
+    /// Embeddings are passed through to the recorded provider and not recorded
+    async fn create_embeddings(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>, ProviderError> {
+        match &self.inner {
+            Some(inner) => inner.create_embeddings(texts).await,
+            None => Err(ProviderError::NotImplemented(
+                "embeddings are not recorded, so they cannot be replayed".to_string(),
+            )),
+        }
+    }
+
     fn get_model_config(&self) -> ModelConfig {
-        ModelConfig::new_or_fail("test-model")
+        match &self.inner {
+            Some(inner) => inner.get_model_config(),
+            None => ModelConfig::new_or_fail("test-model"),
+        }
+    }
+}
+
+fn write_records(records: &Mutex<HashMap<String, TestRecord>>, file_path: &str) -> Result<()> {
+    let records = records.lock().unwrap();
+    let content = serde_json::to_string_pretty(&*records)?;
+    fs::write(file_path, content)?;
+    Ok(())
+}
+
+fn store_record(
+    records: &Mutex<HashMap<String, TestRecord>>,
+    file_path: &str,
+    autosave: bool,
+    hash: String,
+    record: TestRecord,
+) -> Result<(), ProviderError> {
+    records.lock().unwrap().insert(hash, record);
+    if autosave {
+        write_records(records, file_path)
+            .map_err(|e| ProviderError::ExecutionError(e.to_string()))?;
+    }
+    Ok(())
+}
+
+/// Joins streamed chunks back into the single message `complete` would have returned
+fn merge_chunks(chunks: Vec<Message>) -> Option<Message> {
+    let mut chunks = chunks.into_iter();
+    let mut merged = chunks.next()?;
+    for chunk in chunks {
+        for content in chunk.content {
+            match (merged.content.last_mut(), content) {
+                (Some(MessageContent::Text(last)), MessageContent::Text(text)) => {
+                    last.text.push_str(&text.text)
+                }
+                (_, content) => merged.content.push(content),
+            }
+        }
     }
+    Some(merged)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::conversation::message::{Message, MessageContent};
-    use crate::providers::base::{ProviderUsage, Usage};
     use chrono::Utc;
     use rmcp::model::{RawTextContent, Role, TextContent};
     use std::env;
@@ -231,7 +373,7 @@ mod tests {
         });
 
         {
-            let test_provider = TestProvider::new_recording(mock, &temp_file);
+            let test_provider = TestProvider::new_recording(mock, &temp_file).unwrap();
 
             let result = test_provider.complete("You are helpful", &[], &[]).await;
 
@@ -284,4 +426,88 @@ mod tests {
 
         let _ = fs::remove_file(temp_file);
     }
+
+    #[tokio::test]
+    async fn test_tool_matching_requires_same_tools() {
+        let temp_file = format!(
+            "{}/test_tools_{}.json",
+            env::temp_dir().display(),
+            std::process::id()
+        );
+        let shell = Tool::new("shell", "", rmcp::object!({"type": "object"}));
+        let search = Tool::new("search", "", rmcp::object!({"type": "object"}));
+
+        let mock = Arc::new(MockProvider {
+            model_config: ModelConfig::new_or_fail("mock-model"),
+            response: "Hello, world!".to_string(),
+        });
+        let recorder = TestProvider::new_recording(mock, &temp_file)
+            .unwrap()
+            .with_tool_matching()
+            .with_autosave();
+        recorder
+            .complete("You are helpful", &[], &[shell.clone()])
+            .await
+            .unwrap();
+
+        let replay_provider = TestProvider::new_replaying(&temp_file)
+            .unwrap()
+            .with_tool_matching();
+        assert!(replay_provider
+            .complete("You are helpful", &[], &[shell])
+            .await
+            .is_ok());
+        assert!(replay_provider
+            .complete("You are helpful", &[], &[search])
+            .await
+            .is_err());
+
+        let _ = fs::remove_file(temp_file);
+    }
+
+    #[tokio::test]
+    async fn test_recording_keeps_earlier_records() {
+        let temp_file = format!(
+            "{}/test_append_{}.json",
+            env::temp_dir().display(),
+            std::process::id()
+        );
+        let mock = Arc::new(MockProvider {
+            model_config: ModelConfig::new_or_fail("mock-model"),
+            response: "Hello, world!".to_string(),
+        });
+
+        let first = TestProvider::new_recording(mock.clone(), &temp_file)
+            .unwrap()
+            .with_autosave();
+        first.complete("You are helpful", &[], &[]).await.unwrap();
+        assert_eq!(first.get_model_config().model_name, "mock-model");
+
+        let second = TestProvider::new_recording(mock, &temp_file)
+            .unwrap()
+            .with_autosave();
+        second
+            .complete("You are helpful", &[Message::user().with_text("hi")], &[])
+            .await
+            .unwrap();
+        assert_eq!(second.get_record_count(), 2);
+
+        let replay_provider = TestProvider::new_replaying(&temp_file).unwrap();
+        assert_eq!(replay_provider.get_record_count(), 2);
+        assert!(!replay_provider.supports_streaming());
+
+        let _ = fs::remove_file(temp_file);
+    }
+
+    #[test]
+    fn test_merge_chunks() {
+        let merged = merge_chunks(vec![
+            Message::assistant().with_text("Hel"),
+            Message::assistant().with_text("lo"),
+        ])
+        .unwrap();
+        assert_eq!(merged.content.len(), 1);
+        assert_eq!(merged.as_concat_text(), "Hello");
+        assert!(merge_chunks(vec![]).is_none());
+    }
 }
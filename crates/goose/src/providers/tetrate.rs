@@ -15,7 +15,7 @@ use super::errors::ProviderError;
 use super::formats::openai::response_to_streaming_message;
 use super::retry::ProviderRetry;
 use super::utils::{
-    get_model, handle_response_google_compat, handle_response_openai_compat,
+    get_model, get_stop_reason, handle_response_google_compat, handle_response_openai_compat,
     handle_status_openai_compat, is_google_model, RequestLog,
 };
 use crate::config::signup_tetrate::TETRATE_DEFAULT_MODEL;
@@ -191,7 +191,10 @@ impl Provider for TetrateProvider {
         });
         let model = get_model(&response);
         log.write(&response, Some(&usage))?;
-        Ok((message, ProviderUsage::new(model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
     }
 
     async fn stream(
@@ -1,4 +1,4 @@
-use super::base::Usage;
+use super::base::{StopReason, Usage};
 use super::errors::GoogleErrorCode;
 use crate::config::paths::Paths;
 use crate::model::ModelConfig;
@@ -6,6 +6,7 @@ use crate::providers::errors::{OpenAIError, ProviderError};
 use anyhow::{anyhow, Result};
 use base64::Engine;
 use regex::Regex;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
 use reqwest::{Response, StatusCode};
 use rmcp::model::{AnnotateAble, ImageContent, RawImageContent};
 use serde::{Deserialize, Serialize};
@@ -132,6 +133,35 @@ pub fn map_http_error_to_provider_error(
     error
 }
 
+/// Parses a `Retry-After` header, which is either a number of seconds or an HTTP-date.
+/// Dates in the past yield a zero delay.
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let retry_at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = retry_at.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Fills in the retry delay of a rate limit error from a `Retry-After` header,
+/// keeping any delay the provider already reported in the body.
+pub fn with_retry_after(error: ProviderError, retry_after: Option<Duration>) -> ProviderError {
+    match error {
+        ProviderError::RateLimitExceeded {
+            details,
+            retry_delay: None,
+        } => ProviderError::RateLimitExceeded {
+            details,
+            retry_delay: retry_after,
+        },
+        other => other,
+    }
+}
+
 /// Handles HTTP responses from OpenAI-compatible endpoints.
 ///
 /// Returns the response if status is OK; otherwise, reads the body and maps to a `ProviderError`,
@@ -153,6 +183,7 @@ pub async fn handle_status_openai_compat(response: Response) -> Result<Response,
         return Ok(response);
     }
 
+    let retry_after = parse_retry_after(response.headers());
     let body_str = response
         .text()
         .await
@@ -176,7 +207,10 @@ pub async fn handle_status_openai_compat(response: Response) -> Result<Response,
     }
 
     let payload = serde_json::from_str::<Value>(&body_str).ok();
-    Err(map_http_error_to_provider_error(status, payload))
+    Err(with_retry_after(
+        map_http_error_to_provider_error(status, payload),
+        retry_after,
+    ))
 }
 
 pub async fn handle_response_openai_compat(response: Response) -> Result<Value, ProviderError> {
@@ -333,6 +367,15 @@ pub fn get_model(data: &Value) -> String {
     }
 }
 
+/// Extract why the model stopped from a response, wherever the provider's API reports it
+pub fn get_stop_reason(data: &Value) -> Option<StopReason> {
+    data.pointer("/choices/0/finish_reason")
+        .or_else(|| data.get("stop_reason"))
+        .or_else(|| data.pointer("/candidates/0/finishReason"))
+        .and_then(Value::as_str)
+        .map(StopReason::from_provider)
+}
+
 /// Check if a file is actually an image by examining its magic bytes
 fn is_image_file(path: &Path) -> bool {
     if let Ok(mut file) = std::fs::File::open(path) {
@@ -614,6 +657,26 @@ mod tests {
     use serde_json::json;
     use wiremock::{matchers, Mock, MockServer, ResponseTemplate};
 
+    #[test]
+    fn test_get_stop_reason() {
+        let openai = json!({"choices": [{"finish_reason": "length", "message": {}}]});
+        assert_eq!(get_stop_reason(&openai), Some(StopReason::MaxTokens));
+
+        let anthropic = json!({"stop_reason": "tool_use", "content": []});
+        assert_eq!(get_stop_reason(&anthropic), Some(StopReason::ToolUse));
+
+        let google = json!({"candidates": [{"finishReason": "SAFETY"}]});
+        assert_eq!(get_stop_reason(&google), Some(StopReason::ContentFilter));
+
+        let unknown = json!({"choices": [{"finish_reason": "eos"}]});
+        assert_eq!(
+            get_stop_reason(&unknown),
+            Some(StopReason::Other("eos".to_string()))
+        );
+
+        assert_eq!(get_stop_reason(&json!({"choices": [{}]})), None);
+    }
+
     #[test]
     fn test_detect_image_path() {
         // Create a temporary PNG file with valid PNG magic numbers
@@ -1056,6 +1119,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(
+            RETRY_AFTER,
+            "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap(),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+        let future = chrono::Utc::now() + chrono::Duration::seconds(90);
+        headers.insert(RETRY_AFTER, future.to_rfc2822().parse().unwrap());
+        let delay = parse_retry_after(&headers).unwrap();
+        assert!(delay > Duration::from_secs(80) && delay <= Duration::from_secs(90));
+
+        headers.insert(RETRY_AFTER, "soon".parse().unwrap());
+        assert_eq!(parse_retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_with_retry_after_keeps_body_delay() {
+        let error = ProviderError::RateLimitExceeded {
+            details: "slow down".to_string(),
+            retry_delay: Some(Duration::from_secs(5)),
+        };
+        assert_eq!(
+            with_retry_after(error, Some(Duration::from_secs(30))),
+            ProviderError::RateLimitExceeded {
+                details: "slow down".to_string(),
+                retry_delay: Some(Duration::from_secs(5)),
+            }
+        );
+
+        let error = ProviderError::RateLimitExceeded {
+            details: "slow down".to_string(),
+            retry_delay: None,
+        };
+        assert_eq!(
+            with_retry_after(error, Some(Duration::from_secs(30))),
+            ProviderError::RateLimitExceeded {
+                details: "slow down".to_string(),
+                retry_delay: Some(Duration::from_secs(30)),
+            }
+        );
+    }
+
     #[test]
     fn test_map_http_error_to_provider_error() {
         let test_cases = vec![
@@ -5,10 +5,12 @@ use serde::Serialize;
 use serde_json::{json, Value};
 
 use super::api_client::{ApiClient, AuthMethod};
-use super::base::{ConfigKey, Provider, ProviderMetadata, ProviderUsage, Usage};
+use super::base::{
+    ConfigKey, Provider, ProviderCapabilities, ProviderMetadata, ProviderUsage, Usage,
+};
 use super::errors::ProviderError;
 use super::retry::ProviderRetry;
-use super::utils::map_http_error_to_provider_error;
+use super::utils::{map_http_error_to_provider_error, parse_retry_after, with_retry_after};
 use crate::conversation::message::{Message, MessageContent};
 
 use crate::mcp_utils::ToolResult;
@@ -114,6 +116,7 @@ impl VeniceProvider {
         let response = self.api_client.response_post(path, payload).await?;
 
         let status = response.status();
+        let retry_after = parse_retry_after(response.headers());
         tracing::debug!("Venice response status: {}", status);
 
         if !status.is_success() {
@@ -168,7 +171,10 @@ impl VeniceProvider {
 
             // Use the common error mapping function
             let error_json = serde_json::from_str::<Value>(&error_body).ok();
-            return Err(map_http_error_to_provider_error(status, error_json));
+            return Err(with_retry_after(
+                map_http_error_to_provider_error(status, error_json),
+                retry_after,
+            ));
         }
 
         let response_text = response.text().await?;
@@ -214,6 +220,18 @@ impl Provider for VeniceProvider {
         self.model.clone()
     }
 
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            // Only the text of each message is sent
+            vision: false,
+            tools: true,
+            streaming: self.supports_streaming(),
+            embeddings: self.supports_embeddings(),
+            json_mode: false,
+            max_tools: None,
+        }
+    }
+
     async fn fetch_supported_models(&self) -> Result<Option<Vec<String>>, ProviderError> {
         let response = self.api_client.response_get(&self.models_path).await?;
         let json: serde_json::Value = response.json().await?;
@@ -401,7 +419,7 @@ impl Provider for VeniceProvider {
             "messages": formatted_messages,
             "stream": false,
             "temperature": 0.7,
-            "max_tokens": 2048,
+            "max_tokens": model_config.max_tokens.unwrap_or(2048),
         });
 
         if !tools.is_empty() {
@@ -501,11 +519,11 @@ impl Provider for VeniceProvider {
 
         // Extract usage
         let usage_data = &response_json["usage"];
-        let usage = Usage {
-            input_tokens: usage_data["prompt_tokens"].as_i64().map(|v| v as i32),
-            output_tokens: usage_data["completion_tokens"].as_i64().map(|v| v as i32),
-            total_tokens: usage_data["total_tokens"].as_i64().map(|v| v as i32),
-        };
+        let usage = Usage::new(
+            usage_data["prompt_tokens"].as_i64().map(|v| v as i32),
+            usage_data["completion_tokens"].as_i64().map(|v| v as i32),
+            usage_data["total_tokens"].as_i64().map(|v| v as i32),
+        );
 
         Ok((
             Message::new(Role::Assistant, Utc::now().timestamp(), content),
@@ -1,7 +1,7 @@
 use super::api_client::{ApiClient, AuthMethod};
 use super::errors::ProviderError;
 use super::retry::ProviderRetry;
-use super::utils::{get_model, handle_response_openai_compat, RequestLog};
+use super::utils::{get_model, get_stop_reason, handle_response_openai_compat, RequestLog};
 use crate::conversation::message::Message;
 
 use crate::model::ModelConfig;
@@ -120,6 +120,9 @@ impl Provider for XaiProvider {
         });
         let response_model = get_model(&response);
         log.write(&response, Some(&usage))?;
-        Ok((message, ProviderUsage::new(response_model, usage)))
+        Ok((
+            message,
+            ProviderUsage::new(response_model, usage).with_stop_reason(get_stop_reason(&response)),
+        ))
     }
 }
@@ -2,8 +2,8 @@ use crate::recipe::read_recipe_file_content::read_parameter_file_content;
 use crate::recipe::template_recipe::render_recipe_content_with_params;
 use crate::recipe::validate_recipe::validate_recipe_template_from_content;
 use crate::recipe::{
-    Recipe, RecipeParameter, RecipeParameterInputType, RecipeParameterRequirement,
-    BUILT_IN_RECIPE_DIR_PARAM,
+    take_secret_values, Recipe, RecipeParameter, RecipeParameterInputType,
+    RecipeParameterRequirement, RecipeSecrets, BUILT_IN_RECIPE_DIR_PARAM,
 };
 use anyhow::Result;
 use std::collections::HashMap;
@@ -24,9 +24,9 @@ fn render_recipe_template<F>(
     recipe_dir: &Path,
     params: Vec<(String, String)>,
     user_prompt_fn: Option<F>,
-) -> Result<(String, Vec<String>)>
+) -> Result<(String, Vec<String>, RecipeSecrets)>
 where
-    F: Fn(&str, &str) -> Result<String, anyhow::Error>,
+    F: Fn(&RecipeParameter) -> Result<String, anyhow::Error>,
 {
     let recipe_dir_str = recipe_dir.display().to_string();
 
@@ -34,8 +34,13 @@ where
         validate_recipe_template_from_content(&recipe_content, Some(recipe_dir_str.clone()))?
             .parameters;
 
-    let (params_for_template, missing_params) =
-        apply_values_to_parameters(&params, recipe_parameters, &recipe_dir_str, user_prompt_fn)?;
+    let (mut params_for_template, missing_params) = apply_values_to_parameters(
+        &params,
+        recipe_parameters.clone(),
+        &recipe_dir_str,
+        user_prompt_fn,
+    )?;
+    let secrets = take_secret_values(recipe_parameters.as_deref(), &mut params_for_template)?;
 
     let rendered_content = if missing_params.is_empty() {
         render_recipe_content_with_params(&recipe_content, &params_for_template)?
@@ -43,9 +48,11 @@ where
         String::new()
     };
 
-    Ok((rendered_content, missing_params))
+    Ok((rendered_content, missing_params, secrets))
 }
 
+/// Build a recipe for display or inspection. Secret parameters are rendered as references to
+/// variables whose values are dropped; use [`build_recipe_with_secrets_from_template`] to run it.
 pub fn build_recipe_from_template<F>(
     recipe_content: String,
     recipe_dir: &Path,
@@ -53,10 +60,25 @@ pub fn build_recipe_from_template<F>(
     user_prompt_fn: Option<F>,
 ) -> Result<Recipe, RecipeError>
 where
-    F: Fn(&str, &str) -> Result<String, anyhow::Error>,
+    F: Fn(&RecipeParameter) -> Result<String, anyhow::Error>,
 {
-    let (rendered_content, missing_params) =
-        render_recipe_template(recipe_content, recipe_dir, params.clone(), user_prompt_fn)
+    build_recipe_with_secrets_from_template(recipe_content, recipe_dir, params, user_prompt_fn)
+        .map(|(recipe, _)| recipe)
+}
+
+/// Build a recipe along with the values of its secret parameters, which the session passes to
+/// the extensions it starts.
+pub fn build_recipe_with_secrets_from_template<F>(
+    recipe_content: String,
+    recipe_dir: &Path,
+    params: Vec<(String, String)>,
+    user_prompt_fn: Option<F>,
+) -> Result<(Recipe, RecipeSecrets), RecipeError>
+where
+    F: Fn(&RecipeParameter) -> Result<String, anyhow::Error>,
+{
+    let (rendered_content, missing_params, secrets) =
+        render_recipe_template(recipe_content, recipe_dir, params, user_prompt_fn)
             .map_err(|source| RecipeError::TemplateRendering { source })?;
 
     if !missing_params.is_empty() {
@@ -74,7 +96,7 @@ where
         }
     }
 
-    Ok(recipe)
+    Ok((recipe, secrets))
 }
 
 pub fn apply_values_to_parameters<F>(
@@ -84,7 +106,7 @@ pub fn apply_values_to_parameters<F>(
     user_prompt_fn: Option<F>,
 ) -> Result<(HashMap<String, String>, Vec<String>)>
 where
-    F: Fn(&str, &str) -> Result<String, anyhow::Error>,
+    F: Fn(&RecipeParameter) -> Result<String, anyhow::Error>,
 {
     let mut param_map: HashMap<String, String> = user_params.iter().cloned().collect();
     param_map.insert(
@@ -94,11 +116,15 @@ where
     let mut missing_params: Vec<String> = Vec::new();
     for param in recipe_parameters.unwrap_or_default() {
         if !param_map.contains_key(&param.key) {
-            match (&param.default, &param.requirement) {
-                (Some(default), _) => param_map.insert(param.key.clone(), default.clone()),
-                (None, RecipeParameterRequirement::UserPrompt) if user_prompt_fn.is_some() => {
-                    let input_value =
-                        user_prompt_fn.as_ref().unwrap()(&param.key, &param.description)?;
+            // Secrets are always asked for, whatever requirement the recipe gives them
+            let prompt_for_value = param.is_secret()
+                || matches!(param.requirement, RecipeParameterRequirement::UserPrompt);
+            match &param.default {
+                Some(default) if !param.is_secret() => {
+                    param_map.insert(param.key.clone(), default.clone())
+                }
+                _ if prompt_for_value && user_prompt_fn.is_some() => {
+                    let input_value = user_prompt_fn.as_ref().unwrap()(&param)?;
                     param_map.insert(param.key.clone(), input_value)
                 }
                 _ => {
@@ -115,7 +141,7 @@ where
     Ok((param_map, missing_params))
 }
 
-fn resolve_sub_recipe_path(
+pub(crate) fn resolve_sub_recipe_path(
     sub_recipe_path: &str,
     parent_recipe_dir: &Path,
 ) -> Result<String, RecipeError> {
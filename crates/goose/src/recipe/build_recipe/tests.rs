@@ -1,13 +1,15 @@
 use crate::recipe::build_recipe::{
-    build_recipe_from_template, resolve_sub_recipe_path, RecipeError,
+    build_recipe_from_template, build_recipe_with_secrets_from_template, resolve_sub_recipe_path,
+    RecipeError,
 };
 use crate::recipe::read_recipe_file_content::RecipeFile;
-use crate::recipe::{RecipeParameterInputType, RecipeParameterRequirement};
+use crate::recipe::{RecipeParameter, RecipeParameterInputType, RecipeParameterRequirement};
+use serial_test::serial;
 use std::path::PathBuf;
 use tempfile::TempDir;
 
 #[allow(clippy::type_complexity)]
-const NO_USER_PROMPT: Option<fn(&str, &str) -> Result<String, anyhow::Error>> = None;
+const NO_USER_PROMPT: Option<fn(&RecipeParameter) -> Result<String, anyhow::Error>> = None;
 
 fn setup_recipe_file(instructions_and_parameters: &str) -> (TempDir, String, PathBuf) {
     let recipe_content = format!(
@@ -267,6 +269,78 @@ fn test_build_recipe_from_template_optional_parameters_without_default_values_in
     }
 }
 
+#[test]
+#[serial]
+fn test_build_recipe_from_template_prompts_for_secret_parameters() {
+    let instructions_and_parameters = r#"
+                "instructions": "Use the token {{ test_secret_token }}",
+                "parameters": [
+                    {
+                        "key": "test_secret_token",
+                        "input_type": "secret",
+                        "requirement": "required",
+                        "description": "The API token"
+                    }
+                ]"#;
+    let (_temp_dir, recipe_content, recipe_dir) = setup_recipe_file(instructions_and_parameters);
+
+    let prompt = |param: &RecipeParameter| -> Result<String, anyhow::Error> {
+        assert!(param.is_secret());
+        Ok("s3cret".to_string())
+    };
+    let (recipe, secrets) = build_recipe_with_secrets_from_template(
+        recipe_content,
+        &recipe_dir,
+        Vec::new(),
+        Some(prompt),
+    )
+    .unwrap();
+    assert_eq!(
+        recipe.instructions.unwrap(),
+        "Use the token $GOOSE_RECIPE_SECRET_TEST_SECRET_TOKEN"
+    );
+    assert_eq!(
+        secrets
+            .get("GOOSE_RECIPE_SECRET_TEST_SECRET_TOKEN")
+            .map(String::as_str),
+        Some("s3cret")
+    );
+    // The secret only goes to the session's extensions, never into goose's own environment
+    assert!(std::env::var_os("GOOSE_RECIPE_SECRET_TEST_SECRET_TOKEN").is_none());
+}
+
+#[test]
+#[serial]
+fn test_build_recipe_from_template_rejects_secrets_that_collide_with_the_environment() {
+    let instructions_and_parameters = r#"
+                "instructions": "Use the token {{ colliding_token }}",
+                "parameters": [
+                    {
+                        "key": "colliding_token",
+                        "input_type": "secret",
+                        "requirement": "required",
+                        "description": "The API token"
+                    }
+                ]"#;
+    let (_temp_dir, recipe_content, recipe_dir) = setup_recipe_file(instructions_and_parameters);
+
+    let prompt =
+        |_: &RecipeParameter| -> Result<String, anyhow::Error> { Ok("s3cret".to_string()) };
+    temp_env::with_var(
+        "GOOSE_RECIPE_SECRET_COLLIDING_TOKEN",
+        Some("already set"),
+        || {
+            let result = build_recipe_with_secrets_from_template(
+                recipe_content,
+                &recipe_dir,
+                Vec::new(),
+                Some(prompt),
+            );
+            assert!(matches!(result, Err(RecipeError::TemplateRendering { .. })));
+        },
+    );
+}
+
 #[test]
 fn test_build_recipe_from_template_wrong_input_type_in_recipe_file() {
     let instructions_and_parameters = r#"
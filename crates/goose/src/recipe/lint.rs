@@ -0,0 +1,304 @@
+//! Dry-run checks of a recipe that report every problem found at once, rather than stopping at
+//! the first one like validation does when a recipe is loaded.
+
+use crate::agents::extension_manager::preflight_check;
+use crate::recipe::build_recipe::resolve_sub_recipe_path;
+use crate::recipe::template_recipe::parse_recipe_content;
+use crate::recipe::validate_recipe::{
+    validate_json_schema, validate_optional_parameters, validate_parameters_in_template,
+    validate_prompt_or_instructions, validate_recipe_template_from_content,
+};
+use crate::recipe::{Recipe, RecipeParameterRequirement};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    /// The recipe will fail to load or run
+    Error,
+    /// The recipe runs, but probably not as intended
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct LintDiagnostic {
+    pub severity: LintSeverity,
+    /// The field the diagnostic is about, e.g. `extensions[0]` or `response.json_schema`
+    pub location: String,
+    pub message: String,
+}
+
+impl LintDiagnostic {
+    fn error(location: impl Into<String>, message: impl fmt::Display) -> Self {
+        Self {
+            severity: LintSeverity::Error,
+            location: location.into(),
+            message: message.to_string(),
+        }
+    }
+
+    fn warning(location: impl Into<String>, message: impl fmt::Display) -> Self {
+        Self {
+            severity: LintSeverity::Warning,
+            location: location.into(),
+            message: message.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for LintDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            LintSeverity::Error => "error",
+            LintSeverity::Warning => "warning",
+        };
+        write!(f, "{} at {}: {}", severity, self.location, self.message)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LintContext {
+    /// The directory the recipe was loaded from; relative sub-recipe paths are resolved against
+    /// it. Sub-recipe paths are not checked without one.
+    pub recipe_dir: Option<PathBuf>,
+    /// The parameter values the recipe would be run with
+    pub params: HashMap<String, String>,
+}
+
+/// Check `recipe_content` without running it. Returns no diagnostics for a recipe that is
+/// expected to load and run cleanly.
+pub async fn lint(recipe_content: &str, context: &LintContext) -> Vec<LintDiagnostic> {
+    let recipe_dir = context
+        .recipe_dir
+        .as_ref()
+        .map(|dir| dir.to_string_lossy().to_string());
+    let (recipe, template_variables) = match parse_recipe_content(recipe_content, recipe_dir) {
+        Ok(parsed) => parsed,
+        Err(e) => return vec![LintDiagnostic::error("recipe", e)],
+    };
+
+    let mut diagnostics = Vec::new();
+
+    if let Err(e) = validate_prompt_or_instructions(&recipe) {
+        diagnostics.push(LintDiagnostic::error("instructions", e));
+    }
+    if let Err(e) = validate_parameters_in_template(&recipe.parameters, &template_variables) {
+        diagnostics.push(LintDiagnostic::error("parameters", e));
+    }
+    if let Err(e) = validate_optional_parameters(&recipe.parameters) {
+        diagnostics.push(LintDiagnostic::error("parameters", e));
+    }
+    lint_param_values(&recipe, &context.params, &mut diagnostics);
+
+    if let Some(json_schema) = recipe
+        .response
+        .as_ref()
+        .and_then(|r| r.json_schema.as_ref())
+    {
+        if let Err(e) = validate_json_schema(json_schema) {
+            diagnostics.push(LintDiagnostic::error("response.json_schema", e));
+        }
+    }
+
+    for (i, extension) in recipe.extensions.iter().flatten().enumerate() {
+        if let Err(e) = preflight_check(extension).await {
+            diagnostics.push(LintDiagnostic::error(
+                format!("extensions[{}]", i),
+                format!("extension '{}' will not start: {}", extension.name(), e),
+            ));
+        }
+    }
+
+    if let Some(recipe_dir) = &context.recipe_dir {
+        lint_sub_recipes(&recipe, recipe_dir, &mut diagnostics);
+    }
+
+    diagnostics
+}
+
+fn lint_param_values(
+    recipe: &Recipe,
+    values: &HashMap<String, String>,
+    diagnostics: &mut Vec<LintDiagnostic>,
+) {
+    let params = recipe.parameters.as_deref().unwrap_or_default();
+
+    for (i, param) in params.iter().enumerate() {
+        if matches!(param.requirement, RecipeParameterRequirement::Required)
+            && param.default.is_none()
+            && !values.contains_key(&param.key)
+        {
+            diagnostics.push(LintDiagnostic::warning(
+                format!("parameters[{}]", i),
+                format!("no value given for required parameter '{}'", param.key),
+            ));
+        }
+    }
+
+    let mut unknown: Vec<&String> = values
+        .keys()
+        .filter(|key| !params.iter().any(|p| &p.key == *key))
+        .collect();
+    unknown.sort();
+    for key in unknown {
+        diagnostics.push(LintDiagnostic::warning(
+            "parameters",
+            format!(
+                "value given for '{}', which the recipe does not define",
+                key
+            ),
+        ));
+    }
+}
+
+fn lint_sub_recipes(recipe: &Recipe, recipe_dir: &Path, diagnostics: &mut Vec<LintDiagnostic>) {
+    for (i, sub_recipe) in recipe.sub_recipes.iter().flatten().enumerate() {
+        let location = format!("sub_recipes[{}]", i);
+        let unreachable = |e: &dyn fmt::Display| {
+            LintDiagnostic::error(
+                format!("{}.path", location),
+                format!("sub-recipe '{}' is unreachable: {}", sub_recipe.name, e),
+            )
+        };
+
+        let path = match resolve_sub_recipe_path(&sub_recipe.path, recipe_dir) {
+            Ok(path) => PathBuf::from(path),
+            Err(e) => {
+                diagnostics.push(unreachable(&e));
+                continue;
+            }
+        };
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) => {
+                diagnostics.push(unreachable(&e));
+                continue;
+            }
+        };
+
+        let sub_recipe_dir = path.parent().map(|dir| dir.to_string_lossy().to_string());
+        let child = match validate_recipe_template_from_content(&content, sub_recipe_dir) {
+            Ok(child) => child,
+            Err(e) => {
+                diagnostics.push(LintDiagnostic::error(
+                    location,
+                    format!("sub-recipe '{}' is invalid: {}", sub_recipe.name, e),
+                ));
+                continue;
+            }
+        };
+
+        let child_params = child.parameters.as_deref().unwrap_or_default();
+        let mut unknown: Vec<&String> = sub_recipe
+            .values
+            .iter()
+            .flat_map(|values| values.keys())
+            .filter(|key| !child_params.iter().any(|p| &p.key == *key))
+            .collect();
+        unknown.sort();
+        for key in unknown {
+            diagnostics.push(LintDiagnostic::warning(
+                format!("{}.values", location),
+                format!(
+                    "sub-recipe '{}' does not define a parameter '{}'",
+                    sub_recipe.name, key
+                ),
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn locations(diagnostics: &[LintDiagnostic]) -> Vec<(LintSeverity, &str)> {
+        diagnostics
+            .iter()
+            .map(|d| (d.severity, d.location.as_str()))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_lint_clean_recipe() {
+        let content = r#"
+version: 1.0.0
+title: Clean
+description: A recipe with nothing wrong
+instructions: Greet {{ name }}
+parameters:
+  - key: name
+    input_type: string
+    requirement: required
+    description: Who to greet
+"#;
+        let context = LintContext {
+            recipe_dir: None,
+            params: HashMap::from([("name".to_string(), "goose".to_string())]),
+        };
+        assert!(lint(content, &context).await.is_empty());
+
+        let diagnostics = lint(content, &LintContext::default()).await;
+        assert_eq!(
+            locations(&diagnostics),
+            vec![(LintSeverity::Warning, "parameters[0]")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lint_reports_every_problem() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = r#"
+version: 1.0.0
+title: Broken
+description: A recipe with several problems
+parameters:
+  - key: unused
+    input_type: string
+    requirement: optional
+    description: Never referenced
+extensions:
+  - type: stdio
+    name: missing
+    description: ""
+    cmd: definitely-not-a-real-goose-command
+    args: []
+response:
+  json_schema:
+    type: 12
+sub_recipes:
+  - name: child
+    path: ./does-not-exist.yaml
+"#;
+        let context = LintContext {
+            recipe_dir: Some(temp_dir.path().to_path_buf()),
+            params: HashMap::new(),
+        };
+        let diagnostics = lint(content, &context).await;
+        assert_eq!(
+            locations(&diagnostics),
+            vec![
+                (LintSeverity::Error, "instructions"),
+                (LintSeverity::Error, "parameters"),
+                (LintSeverity::Error, "parameters"),
+                (LintSeverity::Error, "response.json_schema"),
+                (LintSeverity::Error, "extensions[0]"),
+                (LintSeverity::Error, "sub_recipes[0].path"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_lint_unparseable_recipe() {
+        let diagnostics = lint("title: [unterminated", &LintContext::default()).await;
+        assert_eq!(
+            locations(&diagnostics),
+            vec![(LintSeverity::Error, "recipe")]
+        );
+    }
+}
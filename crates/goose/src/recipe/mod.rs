@@ -13,12 +13,15 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
 pub mod build_recipe;
+pub mod lint;
 pub mod local_recipes;
 pub mod read_recipe_file_content;
 mod recipe_extension_adapter;
 pub mod template_recipe;
 pub mod validate_recipe;
 
+pub use lint::{lint, LintContext, LintDiagnostic, LintSeverity};
+
 pub const BUILT_IN_RECIPE_DIR_PARAM: &str = "recipe_dir";
 pub const RECIPE_FILE_EXTENSIONS: &[&str] = &["yaml", "json"];
 
@@ -95,6 +98,14 @@ pub struct Settings {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub temperature: Option<f32>,
+
+    /// Overrides `GOOSE_MAX_OUTPUT_TOKENS` for sessions running this recipe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<i32>,
+
+    /// Calls per minute allowed for each named extension, see `GOOSE_TOOL_RATE_LIMITS`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_rate_limits: Option<HashMap<String, u32>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
@@ -169,6 +180,9 @@ pub enum RecipeParameterInputType {
     /// Cannot have default values to prevent importing sensitive user files.
     File,
     Select,
+    /// A value such as an API token. Always prompted for, never given a default, and
+    /// redacted wherever parameter values are shown or stored.
+    Secret,
 }
 
 impl fmt::Display for RecipeParameterInputType {
@@ -193,6 +207,114 @@ pub struct RecipeParameter {
     pub options: Option<Vec<String>>,
 }
 
+/// Shown in place of the value of a secret parameter
+pub const REDACTED_SECRET_VALUE: &str = "********";
+
+impl RecipeParameter {
+    pub fn is_secret(&self) -> bool {
+        matches!(self.input_type, RecipeParameterInputType::Secret)
+    }
+}
+
+/// Replace the values of secret parameters in `values` so they can be displayed
+pub fn redact_secret_values(
+    parameters: Option<&[RecipeParameter]>,
+    values: &mut HashMap<String, String>,
+) {
+    for param in parameters.unwrap_or_default() {
+        if param.is_secret() {
+            if let Some(value) = values.get_mut(&param.key) {
+                *value = REDACTED_SECRET_VALUE.to_string();
+            }
+        }
+    }
+}
+
+/// Prefix of the environment variables that carry secret parameter values, kept separate from
+/// anything goose or the user's shell reads
+pub const SECRET_ENV_VAR_PREFIX: &str = "GOOSE_RECIPE_SECRET_";
+
+/// Values of secret recipe parameters, keyed by the environment variable each is passed in.
+/// Debug output lists only the variable names.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct RecipeSecrets(HashMap<String, String>);
+
+impl RecipeSecrets {
+    pub fn envs(&self) -> &HashMap<String, String> {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for RecipeSecrets {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+/// The environment variable the value of a secret parameter is passed in
+pub fn secret_env_var(key: &str) -> String {
+    format!("{}{}", SECRET_ENV_VAR_PREFIX, key.to_uppercase())
+}
+
+/// Move the values of secret parameters out of `values`, leaving a `$VAR` reference in their
+/// place, and return them keyed by that variable. The rendered recipe, and so the conversation,
+/// never holds the secret; the session passes the returned variables only to the extension
+/// processes it starts, so their shells can expand the reference.
+pub fn take_secret_values(
+    parameters: Option<&[RecipeParameter]>,
+    values: &mut HashMap<String, String>,
+) -> Result<RecipeSecrets> {
+    let mut secrets = HashMap::new();
+    for param in parameters.unwrap_or_default() {
+        if !param.is_secret() {
+            continue;
+        }
+        let Some(value) = values.get_mut(&param.key) else {
+            continue;
+        };
+
+        if !param
+            .key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(anyhow::anyhow!(
+                "Secret parameter '{}' can only use letters, digits and underscores",
+                param.key
+            ));
+        }
+        let var = secret_env_var(&param.key);
+        if std::env::var_os(&var).is_some() || secrets.contains_key(&var) {
+            return Err(anyhow::anyhow!(
+                "Secret parameter '{}' would overwrite the environment variable {}",
+                param.key,
+                var
+            ));
+        }
+
+        let secret = std::mem::replace(value, format!("${}", var));
+        secrets.insert(var, secret);
+    }
+    Ok(RecipeSecrets(secrets))
+}
+
+/// Drop the values of secret parameters from `values` so they are never persisted
+pub fn without_secret_values(
+    parameters: Option<&[RecipeParameter]>,
+    mut values: HashMap<String, String>,
+) -> HashMap<String, String> {
+    for param in parameters.unwrap_or_default() {
+        if param.is_secret() {
+            values.remove(&param.key);
+        }
+    }
+    values
+}
+
 /// Builder for creating Recipe instances
 pub struct RecipeBuilder {
     // Required fields with default values
@@ -388,6 +510,41 @@ impl RecipeBuilder {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_secret_values_are_redacted_and_dropped() {
+        let parameters = vec![
+            RecipeParameter {
+                key: "api_token".to_string(),
+                input_type: RecipeParameterInputType::Secret,
+                requirement: RecipeParameterRequirement::UserPrompt,
+                description: "The API token".to_string(),
+                default: None,
+                options: None,
+            },
+            RecipeParameter {
+                key: "name".to_string(),
+                input_type: RecipeParameterInputType::String,
+                requirement: RecipeParameterRequirement::Required,
+                description: "A name".to_string(),
+                default: None,
+                options: None,
+            },
+        ];
+        let values = HashMap::from([
+            ("api_token".to_string(), "s3cret".to_string()),
+            ("name".to_string(), "goose".to_string()),
+        ]);
+
+        let mut shown = values.clone();
+        redact_secret_values(Some(&parameters), &mut shown);
+        assert_eq!(shown["api_token"], REDACTED_SECRET_VALUE);
+        assert_eq!(shown["name"], "goose");
+
+        let stored = without_secret_values(Some(&parameters), values);
+        assert!(!stored.contains_key("api_token"));
+        assert_eq!(stored["name"], "goose");
+    }
+
     #[test]
     fn test_from_content_with_json() {
         let content = r#"{
@@ -19,7 +19,7 @@ pub fn parse_and_validate_parameters(
     Ok(recipe_template)
 }
 
-fn validate_json_schema(schema: &serde_json::Value) -> Result<()> {
+pub(crate) fn validate_json_schema(schema: &serde_json::Value) -> Result<()> {
     match jsonschema::validator_for(schema) {
         Ok(_) => Ok(()),
         Err(err) => Err(anyhow::anyhow!("JSON schema validation failed: {}", err)),
@@ -53,7 +53,7 @@ pub fn validate_recipe_template_from_content(
     Ok(recipe)
 }
 
-fn validate_prompt_or_instructions(recipe: &Recipe) -> Result<()> {
+pub(crate) fn validate_prompt_or_instructions(recipe: &Recipe) -> Result<()> {
     let has_instructions = recipe
         .instructions
         .as_ref()
@@ -74,7 +74,7 @@ fn validate_prompt_or_instructions(recipe: &Recipe) -> Result<()> {
     ))
 }
 
-fn validate_parameters_in_template(
+pub(crate) fn validate_parameters_in_template(
     recipe_parameters: &Option<Vec<RecipeParameter>>,
     template_variables: &HashSet<String>,
 ) -> Result<()> {
@@ -126,7 +126,9 @@ fn validate_parameters_in_template(
     Err(anyhow::anyhow!("{}", message.trim_end()))
 }
 
-fn validate_optional_parameters(parameters: &Option<Vec<RecipeParameter>>) -> Result<()> {
+pub(crate) fn validate_optional_parameters(
+    parameters: &Option<Vec<RecipeParameter>>,
+) -> Result<()> {
     let empty_params = vec![];
     let params = parameters.as_ref().unwrap_or(&empty_params);
 
@@ -140,10 +142,26 @@ fn validate_optional_parameters(parameters: &Option<Vec<RecipeParameter>>) -> Re
         return Err(anyhow::anyhow!("File parameters cannot have default values to avoid importing sensitive user files: {}", file_params_with_defaults.join(", ")));
     }
 
+    let secret_params_with_defaults: Vec<String> = params
+        .iter()
+        .filter(|p| p.is_secret() && p.default.is_some())
+        .map(|p| p.key.clone())
+        .collect();
+
+    if !secret_params_with_defaults.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Secret parameters cannot have default values, they would be saved with the recipe: {}",
+            secret_params_with_defaults.join(", ")
+        ));
+    }
+
+    // Secrets are prompted for, so they never need a default
     let optional_params_without_default_values: Vec<String> = params
         .iter()
         .filter(|p| {
-            matches!(p.requirement, RecipeParameterRequirement::Optional) && p.default.is_none()
+            matches!(p.requirement, RecipeParameterRequirement::Optional)
+                && p.default.is_none()
+                && !p.is_secret()
         })
         .map(|p| p.key.clone())
         .collect();
@@ -197,4 +215,26 @@ parameters:
         assert!(recipe.instructions.is_some());
         println!("Recipe: {:?}", recipe.prompt);
     }
+
+    #[test]
+    fn test_secret_parameter_cannot_have_default() {
+        let recipe_content = r#"
+version: 1.0.0
+title: Test Recipe
+description: A recipe with a secret
+instructions: Use the token {{ api_token }}
+parameters:
+  - key: api_token
+    input_type: secret
+    requirement: optional
+    description: The API token
+"#;
+        assert!(validate_recipe_template_from_content(recipe_content, None).is_ok());
+
+        let with_default = format!("{}    default: hunter2\n", recipe_content);
+        let err = validate_recipe_template_from_content(&with_default, None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Secret parameters cannot have default values"));
+    }
 }
@@ -11,7 +11,14 @@ pub enum DecodeError {
 }
 
 pub fn encode(recipe: &Recipe) -> Result<String, serde_json::Error> {
-    let recipe_json = serde_json::to_string(recipe)?;
+    // Deeplinks get shared, so never carry a value for a secret parameter
+    let mut recipe = recipe.clone();
+    for param in recipe.parameters.iter_mut().flatten() {
+        if param.is_secret() {
+            param.default = None;
+        }
+    }
+    let recipe_json = serde_json::to_string(&recipe)?;
     let encoded = URL_SAFE_NO_PAD.encode(recipe_json.as_bytes());
     Ok(encoded)
 }
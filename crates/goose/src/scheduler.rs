@@ -18,6 +18,7 @@ use crate::config::Config;
 use crate::conversation::message::Message;
 use crate::conversation::Conversation;
 use crate::providers::base::Provider as GooseProvider; // Alias to avoid conflict in test section
+use crate::providers::base::REQUEST_METADATA_RECIPE_NAME;
 use crate::providers::create;
 use crate::recipe::Recipe;
 use crate::scheduler_trait::SchedulerTrait;
@@ -1140,14 +1141,16 @@ async fn run_scheduled_job_internal(
     }
 
     if let Some(ref recipe_extensions) = recipe.extensions {
-        for extension in recipe_extensions {
-            agent
-                .add_extension(extension.clone())
-                .await
-                .map_err(|e| JobExecutionError {
-                    job_id: job.id.clone(),
-                    error: format!("Failed to add extension '{}': {}", extension.name(), e),
-                })?;
+        let failures = agent.add_extensions(recipe_extensions.clone()).await;
+        if !failures.is_empty() {
+            let errors: Vec<String> = failures
+                .iter()
+                .map(|(name, e)| format!("'{}': {}", name, e))
+                .collect();
+            return Err(JobExecutionError {
+                job_id: job.id.clone(),
+                error: format!("Failed to add extensions {}", errors.join("; ")),
+            });
         }
     }
 
@@ -1209,7 +1212,13 @@ async fn run_scheduled_job_internal(
             schedule_id: Some(job.id.clone()),
             execution_mode: job.execution_mode.clone(),
             max_turns: None,
+            max_tool_calls_per_turn: None,
             retry_config: None,
+            request_metadata: HashMap::from([(
+                REQUEST_METADATA_RECIPE_NAME.to_string(),
+                recipe.title.clone(),
+            )]),
+            dry_run: false,
         };
 
         match agent
@@ -1231,6 +1240,7 @@ async fn run_scheduled_job_internal(
                         }
                         Ok(AgentEvent::McpNotification(_)) => {}
                         Ok(AgentEvent::ModelChange { .. }) => {}
+                        Ok(AgentEvent::StopReason(_)) => {}
                         Ok(AgentEvent::HistoryReplaced(updated_conversation)) => {
                             conversation = updated_conversation;
                         }
@@ -1,3 +1,4 @@
+pub mod output_scanner;
 pub mod patterns;
 pub mod scanner;
 pub mod security_inspector;
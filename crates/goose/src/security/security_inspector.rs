@@ -46,6 +46,7 @@ impl SecurityInspector {
             confidence: security_result.confidence,
             inspector_name: self.name().to_string(),
             finding_id: Some(security_result.finding_id.clone()),
+            decision_source: None,
         }
     }
 }
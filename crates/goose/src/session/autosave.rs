@@ -0,0 +1,173 @@
+//! Periodic saving of the messages of a turn that is still running, so that a crash during a
+//! long tool call loses at most a few seconds of the session.
+
+use crate::config::Config;
+use crate::conversation::message::Message;
+use crate::conversation::Conversation;
+use crate::session::SessionManager;
+use anyhow::Result;
+use tokio::time::{Duration, Instant};
+
+pub const SESSION_AUTOSAVE_SECONDS_KEY: &str = "GOOSE_SESSION_AUTOSAVE_SECONDS";
+const DEFAULT_SESSION_AUTOSAVE_SECONDS: u64 = 10;
+
+/// How often a running turn is saved, from `GOOSE_SESSION_AUTOSAVE_SECONDS`. None when set to 0,
+/// in which case messages are only saved as each model response and its tool calls complete.
+pub fn autosave_interval() -> Option<Duration> {
+    let seconds = Config::global()
+        .get_param::<u64>(SESSION_AUTOSAVE_SECONDS_KEY)
+        .unwrap_or(DEFAULT_SESSION_AUTOSAVE_SECONDS);
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+/// Appends the messages a turn produces to the session store, once when they are complete and
+/// in between whenever the interval has passed. Only whole messages are written, so a tool
+/// response that is still being put together is saved with the next call to `save`. Streamed
+/// chunks are merged into the last message, so if that grew after it was saved it is rewritten.
+pub struct Autosave {
+    session_id: String,
+    interval: Option<Duration>,
+    last_save: Instant,
+    saved: usize,
+    last_saved: Option<Message>,
+}
+
+/// What a save has to write to bring the store up to date
+struct PendingSave<'a> {
+    /// The last saved message, if it has changed since
+    rewrite: Option<&'a Message>,
+    append: &'a [Message],
+}
+
+impl Autosave {
+    pub fn new(session_id: impl Into<String>, interval: Option<Duration>) -> Self {
+        Self {
+            session_id: session_id.into(),
+            interval,
+            last_save: Instant::now(),
+            saved: 0,
+            last_saved: None,
+        }
+    }
+
+    /// When the next periodic save is due, None if periodic saving is off
+    pub fn next_due(&self) -> Option<Instant> {
+        self.interval.map(|interval| self.last_save + interval)
+    }
+
+    pub fn is_due(&self) -> bool {
+        self.next_due().is_some_and(|due| Instant::now() >= due)
+    }
+
+    /// Completes when a periodic save is due, never if periodic saving is off
+    pub async fn wait_until_due(&self) {
+        match self.next_due() {
+            Some(due) => tokio::time::sleep_until(due).await,
+            None => std::future::pending().await,
+        }
+    }
+
+    /// Start over for a new batch of messages, e.g. after the stored conversation was replaced
+    pub fn reset(&mut self) {
+        self.saved = 0;
+        self.last_saved = None;
+        self.last_save = Instant::now();
+    }
+
+    fn pending<'a>(&self, messages: &'a Conversation) -> PendingSave<'a> {
+        let messages = messages.messages();
+        let rewrite = self
+            .saved
+            .checked_sub(1)
+            .and_then(|index| messages.get(index))
+            .filter(|message| self.last_saved.as_ref() != Some(*message));
+        PendingSave {
+            rewrite,
+            append: messages.get(self.saved..).unwrap_or_default(),
+        }
+    }
+
+    fn mark_saved(&mut self, messages: &Conversation) {
+        self.saved = messages.len();
+        self.last_saved = messages.last().cloned();
+    }
+
+    /// Write the messages of `messages` not saved yet, and the last saved one if it changed
+    pub async fn save(&mut self, messages: &Conversation) -> Result<()> {
+        let pending = self.pending(messages);
+        if let Some(message) = pending.rewrite {
+            SessionManager::replace_last_message(&self.session_id, message).await?;
+        }
+        for message in pending.append {
+            SessionManager::add_message(&self.session_id, message).await?;
+        }
+        self.mark_saved(messages);
+        self.last_save = Instant::now();
+        Ok(())
+    }
+
+    /// `save`, if the interval has passed since the last save
+    pub async fn save_if_due(&mut self, messages: &Conversation) -> Result<()> {
+        if self.is_due() {
+            self.save(messages).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether a periodic save would write anything
+    pub fn has_unsaved(&self, messages: &Conversation) -> bool {
+        let pending = self.pending(messages);
+        self.interval.is_some() && (pending.rewrite.is_some() || !pending.append.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_autosave_schedule() {
+        let messages = Conversation::new_unvalidated(vec![Message::user().with_text("hi")]);
+
+        let hourly = Autosave::new("session", Some(Duration::from_secs(3600)));
+        assert!(!hourly.is_due());
+        assert!(hourly.has_unsaved(&messages));
+        assert!(!hourly.has_unsaved(&Conversation::default()));
+
+        let immediate = Autosave::new("session", Some(Duration::ZERO));
+        assert!(immediate.is_due());
+
+        let disabled = Autosave::new("session", None);
+        assert!(disabled.next_due().is_none());
+        assert!(!disabled.is_due());
+        assert!(!disabled.has_unsaved(&messages));
+    }
+
+    #[test]
+    fn test_streamed_message_is_rewritten_after_save() {
+        let mut messages = Conversation::new_unvalidated(vec![Message::user().with_text("hi")]);
+        let mut autosave = Autosave::new("session", Some(Duration::from_secs(3600)));
+
+        messages.push(Message::assistant().with_id("reply").with_text("Hel"));
+        autosave.mark_saved(&messages);
+        assert!(!autosave.has_unsaved(&messages));
+
+        // Later chunks of the same message are merged into the saved one
+        messages.push(Message::assistant().with_id("reply").with_text("lo"));
+        messages.push(Message::assistant().with_id("reply").with_text(" there"));
+        assert!(autosave.has_unsaved(&messages));
+        let pending = autosave.pending(&messages);
+        assert_eq!(
+            pending.rewrite.map(|message| message.as_concat_text()),
+            Some("Hello there".to_string())
+        );
+        assert!(pending.append.is_empty());
+
+        messages.push(Message::user().with_text("next"));
+        autosave.mark_saved(&messages);
+        assert!(!autosave.has_unsaved(&messages));
+        let pending = autosave.pending(&messages);
+        assert!(pending.rewrite.is_none());
+        assert!(pending.append.is_empty());
+    }
+}
@@ -113,6 +113,39 @@ impl EnabledExtensionsState {
     }
 }
 
+/// Token usage of each provider call in a session, so exports can show where context grew
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenUsageState {
+    pub turns: Vec<TurnUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TurnUsage {
+    /// Number of messages in the conversation when the call was made
+    pub message_count: usize,
+    pub input_tokens: Option<i32>,
+    pub output_tokens: Option<i32>,
+    pub total_tokens: Option<i32>,
+}
+
+impl ExtensionState for TokenUsageState {
+    const EXTENSION_NAME: &'static str = "token_usage";
+    const VERSION: &'static str = "v0";
+}
+
+impl TokenUsageState {
+    /// Oldest turns are dropped beyond this, to bound the size of session metadata
+    const MAX_TURNS: usize = 1000;
+
+    pub fn record(&mut self, turn: TurnUsage) {
+        self.turns.push(turn);
+        if self.turns.len() > Self::MAX_TURNS {
+            let excess = self.turns.len() - Self::MAX_TURNS;
+            self.turns.drain(..excess);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -188,4 +221,20 @@ mod tests {
             Some(&json!({"key": "value"}))
         );
     }
+
+    #[test]
+    fn test_token_usage_state_roundtrip() {
+        let mut extension_data = ExtensionData::new();
+        let mut usage = TokenUsageState::default();
+        usage.record(TurnUsage {
+            message_count: 1,
+            input_tokens: Some(100),
+            output_tokens: Some(20),
+            total_tokens: Some(120),
+        });
+        usage.to_extension_data(&mut extension_data).unwrap();
+
+        let restored = TokenUsageState::from_extension_data(&extension_data).unwrap();
+        assert_eq!(restored, usage);
+    }
 }
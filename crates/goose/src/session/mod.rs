@@ -1,8 +1,12 @@
+pub mod autosave;
 mod diagnostics;
 pub mod extension_data;
 mod legacy;
 pub mod session_manager;
 
+pub use autosave::{autosave_interval, Autosave};
 pub use diagnostics::generate_diagnostics;
-pub use extension_data::{EnabledExtensionsState, ExtensionData, ExtensionState, TodoState};
-pub use session_manager::{Session, SessionInsights, SessionManager};
+pub use extension_data::{
+    EnabledExtensionsState, ExtensionData, ExtensionState, TodoState, TokenUsageState, TurnUsage,
+};
+pub use session_manager::{Checkpoint, CheckpointId, Session, SessionInsights, SessionManager};
@@ -1,6 +1,7 @@
 use crate::config::paths::Paths;
 use crate::conversation::message::Message;
 use crate::conversation::Conversation;
+use crate::permission::PermissionDecision;
 use crate::providers::base::{Provider, MSG_COUNT_FOR_SESSION_NAME_GENERATION};
 use crate::recipe::Recipe;
 use crate::session::extension_data::ExtensionData;
@@ -9,7 +10,7 @@ use chrono::{DateTime, Utc};
 use rmcp::model::Role;
 use serde::{Deserialize, Serialize};
 use sqlx::sqlite::SqliteConnectOptions;
-use sqlx::{Pool, Sqlite};
+use sqlx::{Pool, Sqlite, Transaction};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -18,7 +19,7 @@ use tokio::sync::OnceCell;
 use tracing::{info, warn};
 use utoipa::ToSchema;
 
-const CURRENT_SCHEMA_VERSION: i32 = 3;
+const CURRENT_SCHEMA_VERSION: i32 = 5;
 
 static SESSION_STORAGE: OnceCell<Arc<SessionStorage>> = OnceCell::const_new();
 
@@ -60,6 +61,23 @@ pub struct SessionUpdateBuilder {
     user_recipe_values: Option<Option<HashMap<String, String>>>,
 }
 
+/// Identifies a checkpoint of a session, see [`SessionManager::checkpoint`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointId(pub i64);
+
+impl std::fmt::Display for CheckpointId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub id: CheckpointId,
+    pub created_at: DateTime<Utc>,
+    pub message_count: usize,
+}
+
 #[derive(Serialize, ToSchema, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionInsights {
@@ -197,6 +215,26 @@ impl SessionManager {
             .await
     }
 
+    /// Keep only the first `len` messages of a session, returning the remaining conversation
+    pub async fn truncate_conversation(id: &str, len: usize) -> Result<Conversation> {
+        Self::instance().await?.truncate_conversation(id, len).await
+    }
+
+    /// Overwrite the last stored message of a session, e.g. one saved while it was still streaming
+    pub async fn replace_last_message(id: &str, message: &Message) -> Result<()> {
+        let storage = Self::instance().await?;
+        let len = storage
+            .get_session(id, true)
+            .await?
+            .conversation
+            .map(|conversation| conversation.len())
+            .unwrap_or(0);
+        storage
+            .truncate_conversation(id, len.saturating_sub(1))
+            .await?;
+        storage.add_message(id, message).await
+    }
+
     pub async fn list_sessions() -> Result<Vec<Session>> {
         Self::instance().await?.list_sessions().await
     }
@@ -217,6 +255,36 @@ impl SessionManager {
         Self::instance().await?.import_session(json).await
     }
 
+    /// Save the session's conversation and extension state so it can be restored later
+    pub async fn checkpoint(id: &str) -> Result<CheckpointId> {
+        Self::instance().await?.checkpoint(id).await
+    }
+
+    /// Put a session back to the state saved by `checkpoint`, returning the restored
+    /// conversation. Unlike forking, this changes the session itself; later checkpoints are
+    /// kept, so a restore can be undone by restoring one of them.
+    pub async fn restore(id: &str, checkpoint: CheckpointId) -> Result<Conversation> {
+        Self::instance().await?.restore(id, checkpoint).await
+    }
+
+    /// The checkpoints of a session, oldest first
+    pub async fn list_checkpoints(id: &str) -> Result<Vec<Checkpoint>> {
+        Self::instance().await?.list_checkpoints(id).await
+    }
+
+    /// Add a tool call that was allowed or denied to the session's audit trail
+    pub async fn record_permission_decision(id: &str, decision: &PermissionDecision) -> Result<()> {
+        Self::instance()
+            .await?
+            .record_permission_decision(id, decision)
+            .await
+    }
+
+    /// The audit trail of a session, oldest first
+    pub async fn list_permission_decisions(id: &str) -> Result<Vec<PermissionDecision>> {
+        Self::instance().await?.list_permission_decisions(id).await
+    }
+
     pub async fn maybe_update_description(id: &str, provider: Arc<dyn Provider>) -> Result<()> {
         let session = Self::get_session(id, true).await?;
         let conversation = session
@@ -433,6 +501,8 @@ impl SessionStorage {
         sqlx::query("CREATE INDEX idx_messages_session ON messages(session_id)")
             .execute(&pool)
             .await?;
+        Self::create_checkpoints_table(&pool).await?;
+        Self::create_permission_decisions_table(&pool).await?;
         sqlx::query("CREATE INDEX idx_messages_timestamp ON messages(timestamp)")
             .execute(&pool)
             .await?;
@@ -532,6 +602,50 @@ impl SessionStorage {
         Ok(())
     }
 
+    async fn create_checkpoints_table(pool: &Pool<Sqlite>) -> Result<()> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS checkpoints (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id),
+                conversation_json TEXT NOT NULL,
+                extension_data TEXT NOT NULL DEFAULT '{}',
+                message_count INTEGER NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            )
+        "#,
+        )
+        .execute(pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_checkpoints_session ON checkpoints(session_id)",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn create_permission_decisions_table(pool: &Pool<Sqlite>) -> Result<()> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS permission_decisions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                session_id TEXT NOT NULL REFERENCES sessions(id),
+                decision_json TEXT NOT NULL,
+                created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
+            )
+        "#,
+        )
+        .execute(pool)
+        .await?;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_permission_decisions_session ON permission_decisions(session_id)",
+        )
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+
     async fn run_migrations(&self) -> Result<()> {
         let current_version = self.get_schema_version().await?;
 
@@ -617,6 +731,12 @@ impl SessionStorage {
                 .execute(&self.pool)
                 .await?;
             }
+            4 => {
+                Self::create_checkpoints_table(&self.pool).await?;
+            }
+            5 => {
+                Self::create_permission_decisions_table(&self.pool).await?;
+            }
             _ => {
                 anyhow::bail!("Unknown migration version: {}", version);
             }
@@ -668,7 +788,17 @@ impl SessionStorage {
         .ok_or_else(|| anyhow::anyhow!("Session not found"))?;
 
         if include_messages {
-            let conv = self.get_conversation(&session.id).await?;
+            let (conv, issues) = self
+                .get_conversation(&session.id)
+                .await?
+                .validate_and_repair();
+            if !issues.is_empty() {
+                warn!(
+                    session_id = %session.id,
+                    "Repaired stored conversation: {}",
+                    issues.join("; ")
+                );
+            }
             session.message_count = conv.messages().len();
             session.conversation = Some(conv);
         } else {
@@ -838,10 +968,19 @@ impl SessionStorage {
         conversation: &Conversation,
     ) -> Result<()> {
         let mut tx = self.pool.begin().await?;
+        Self::write_conversation(&mut tx, session_id, conversation).await?;
+        tx.commit().await?;
+        Ok(())
+    }
 
+    async fn write_conversation(
+        tx: &mut Transaction<'_, Sqlite>,
+        session_id: &str,
+        conversation: &Conversation,
+    ) -> Result<()> {
         sqlx::query("DELETE FROM messages WHERE session_id = ?")
             .bind(session_id)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await?;
 
         for message in conversation.messages() {
@@ -858,14 +997,129 @@ impl SessionStorage {
             .bind(serde_json::to_string(&message.content)?)
             .bind(message.created)
             .bind(metadata_json)
-            .execute(&mut *tx)
+            .execute(&mut **tx)
             .await?;
         }
 
+        Ok(())
+    }
+
+    async fn truncate_conversation(&self, session_id: &str, len: usize) -> Result<Conversation> {
+        let mut conversation = self
+            .get_session(session_id, true)
+            .await?
+            .conversation
+            .unwrap_or_default();
+        let message_count = conversation.messages().len();
+        if len > message_count {
+            return Err(anyhow::anyhow!(
+                "Cannot truncate session {} to {} messages, it only has {}",
+                session_id,
+                len,
+                message_count
+            ));
+        }
+
+        conversation.truncate(len);
+        self.replace_conversation(session_id, &conversation).await?;
+        Ok(conversation)
+    }
+
+    async fn checkpoint(&self, session_id: &str) -> Result<CheckpointId> {
+        let session = self.get_session(session_id, true).await?;
+        let conversation = session.conversation.unwrap_or_default();
+
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO checkpoints (session_id, conversation_json, extension_data, message_count)
+            VALUES (?, ?, ?, ?)
+            RETURNING id
+        "#,
+        )
+        .bind(session_id)
+        .bind(serde_json::to_string(&conversation)?)
+        .bind(serde_json::to_string(&session.extension_data)?)
+        .bind(conversation.len() as i64)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(CheckpointId(id))
+    }
+
+    async fn restore(&self, session_id: &str, checkpoint: CheckpointId) -> Result<Conversation> {
+        let row = sqlx::query_as::<_, (String, String)>(
+            "SELECT conversation_json, extension_data FROM checkpoints WHERE id = ? AND session_id = ?",
+        )
+        .bind(checkpoint.0)
+        .bind(session_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| {
+            anyhow::anyhow!("Session {} has no checkpoint {}", session_id, checkpoint)
+        })?;
+
+        let conversation: Conversation = serde_json::from_str(&row.0)?;
+        let extension_data: ExtensionData = serde_json::from_str(&row.1)?;
+
+        // Messages and extension state go back together or not at all
+        let mut tx = self.pool.begin().await?;
+        Self::write_conversation(&mut tx, session_id, &conversation).await?;
+        sqlx::query(
+            "UPDATE sessions SET extension_data = ?, updated_at = datetime('now') WHERE id = ?",
+        )
+        .bind(serde_json::to_string(&extension_data)?)
+        .bind(session_id)
+        .execute(&mut *tx)
+        .await?;
         tx.commit().await?;
+
+        Ok(conversation)
+    }
+
+    async fn list_checkpoints(&self, session_id: &str) -> Result<Vec<Checkpoint>> {
+        let rows = sqlx::query_as::<_, (i64, DateTime<Utc>, i64)>(
+            "SELECT id, created_at, message_count FROM checkpoints WHERE session_id = ? ORDER BY id",
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, created_at, message_count)| Checkpoint {
+                id: CheckpointId(id),
+                created_at,
+                message_count: message_count as usize,
+            })
+            .collect())
+    }
+
+    async fn record_permission_decision(
+        &self,
+        session_id: &str,
+        decision: &PermissionDecision,
+    ) -> Result<()> {
+        sqlx::query("INSERT INTO permission_decisions (session_id, decision_json) VALUES (?, ?)")
+            .bind(session_id)
+            .bind(serde_json::to_string(decision)?)
+            .execute(&self.pool)
+            .await?;
         Ok(())
     }
 
+    async fn list_permission_decisions(&self, session_id: &str) -> Result<Vec<PermissionDecision>> {
+        let rows = sqlx::query_scalar::<_, String>(
+            "SELECT decision_json FROM permission_decisions WHERE session_id = ? ORDER BY id",
+        )
+        .bind(session_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|json| serde_json::from_str(json).map_err(Into::into))
+            .collect()
+    }
+
     async fn list_sessions(&self) -> Result<Vec<Session>> {
         sqlx::query_as::<_, Session>(
             r#"
@@ -901,6 +1155,16 @@ impl SessionStorage {
             .execute(&self.pool)
             .await?;
 
+        sqlx::query("DELETE FROM checkpoints WHERE session_id = ?")
+            .bind(session_id)
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query("DELETE FROM permission_decisions WHERE session_id = ?")
+            .bind(session_id)
+            .execute(&self.pool)
+            .await?;
+
         sqlx::query("DELETE FROM sessions WHERE id = ?")
             .bind(session_id)
             .execute(&self.pool)
@@ -966,6 +1230,8 @@ impl SessionStorage {
 mod tests {
     use super::*;
     use crate::conversation::message::{Message, MessageContent};
+    use crate::permission::permission_confirmation::PrincipalType;
+    use crate::permission::{DecisionSource, Permission, PermissionConfirmation};
     use tempfile::TempDir;
 
     const NUM_CONCURRENT_SESSIONS: i32 = 10;
@@ -1063,6 +1329,125 @@ mod tests {
         assert_eq!(insights.total_tokens, expected_tokens as i64);
     }
 
+    #[tokio::test]
+    async fn test_truncate_conversation() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_truncate.db");
+        let storage = SessionStorage::create(&db_path).await.unwrap();
+
+        let session = storage
+            .create_session(PathBuf::from("/tmp/test"), "Truncate".to_string())
+            .await
+            .unwrap();
+        for text in ["first", "reply", "second", "reply again"] {
+            let message = if text.starts_with("reply") {
+                Message::assistant().with_text(text)
+            } else {
+                Message::user().with_text(text)
+            };
+            storage.add_message(&session.id, &message).await.unwrap();
+        }
+
+        let truncated = storage.truncate_conversation(&session.id, 3).await.unwrap();
+        assert_eq!(truncated.messages().len(), 3);
+        assert_eq!(truncated.messages()[2].as_concat_text(), "second");
+
+        let stored = storage.get_session(&session.id, true).await.unwrap();
+        assert_eq!(stored.conversation.unwrap().messages().len(), 3);
+
+        assert!(storage
+            .truncate_conversation(&session.id, 10)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_and_restore() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_checkpoint.db");
+        let storage = SessionStorage::create(&db_path).await.unwrap();
+
+        let session = storage
+            .create_session(PathBuf::from("/tmp/test"), "Checkpoint".to_string())
+            .await
+            .unwrap();
+        storage
+            .add_message(&session.id, &Message::user().with_text("first"))
+            .await
+            .unwrap();
+        let checkpoint = storage.checkpoint(&session.id).await.unwrap();
+
+        storage
+            .add_message(&session.id, &Message::assistant().with_text("rm -rf"))
+            .await
+            .unwrap();
+        storage
+            .add_message(&session.id, &Message::user().with_text("oops"))
+            .await
+            .unwrap();
+
+        let restored = storage.restore(&session.id, checkpoint).await.unwrap();
+        assert_eq!(restored.len(), 1);
+        let stored = storage.get_session(&session.id, true).await.unwrap();
+        let messages = stored.conversation.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages.messages()[0].as_concat_text(), "first");
+
+        let checkpoints = storage.list_checkpoints(&session.id).await.unwrap();
+        assert_eq!(checkpoints.len(), 1);
+        assert_eq!(checkpoints[0].id, checkpoint);
+        assert_eq!(checkpoints[0].message_count, 1);
+
+        let other = storage
+            .create_session(PathBuf::from("/tmp/test"), "Other".to_string())
+            .await
+            .unwrap();
+        assert!(storage.restore(&other.id, checkpoint).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_permission_decisions_are_kept_per_session() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test_permission_decisions.db");
+        let storage = SessionStorage::create(&db_path).await.unwrap();
+
+        let session = storage
+            .create_session(PathBuf::from("/tmp/test"), "Audit".to_string())
+            .await
+            .unwrap();
+        let decision = PermissionDecision::new(
+            "request",
+            "developer__shell",
+            serde_json::json!({"command": "ls"}),
+            &PermissionConfirmation {
+                principal_type: PrincipalType::Tool,
+                permission: Permission::AllowOnce,
+            },
+            DecisionSource::Mode,
+        );
+        storage
+            .record_permission_decision(&session.id, &decision)
+            .await
+            .unwrap();
+
+        let decisions = storage
+            .list_permission_decisions(&session.id)
+            .await
+            .unwrap();
+        assert_eq!(decisions, vec![decision]);
+        assert!(decisions[0].allowed());
+
+        let other = storage
+            .create_session(PathBuf::from("/tmp/test"), "Other".to_string())
+            .await
+            .unwrap();
+        assert!(storage
+            .list_permission_decisions(&other.id)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_export_import_roundtrip() {
         const DESCRIPTION: &str = "Original session";
@@ -2,9 +2,11 @@ use anyhow::Result;
 use async_trait::async_trait;
 use std::collections::HashMap;
 
+use crate::config::GooseMode;
 use crate::conversation::message::{Message, ToolRequest};
 use crate::permission::permission_inspector::PermissionInspector;
 use crate::permission::permission_judge::PermissionCheckResult;
+use crate::permission::DecisionSource;
 
 /// Result of inspecting a tool call
 #[derive(Debug, Clone)]
@@ -15,6 +17,8 @@ pub struct InspectionResult {
     pub confidence: f32,
     pub inspector_name: String,
     pub finding_id: Option<String>,
+    /// What made the decision, set by the permission inspector when it decides without asking
+    pub decision_source: Option<DecisionSource>,
 }
 
 /// Action to take based on inspection result
@@ -116,7 +120,7 @@ impl ToolInspectionManager {
     }
 
     /// Update the permission inspector's mode
-    pub async fn update_permission_inspector_mode(&self, mode: String) {
+    pub async fn update_permission_inspector_mode(&self, mode: GooseMode) {
         for inspector in &self.inspectors {
             if inspector.name() == "permission" {
                 // Downcast to PermissionInspector to access update_mode method
@@ -153,6 +157,21 @@ impl ToolInspectionManager {
         tracing::warn!("Permission inspector not found for permission manager update");
     }
 
+    /// Let calls to a tool run without asking for a while
+    pub async fn allow_tool_for(&self, tool_name: &str, duration: std::time::Duration) {
+        for inspector in &self.inspectors {
+            if inspector.name() == "permission" {
+                if let Some(permission_inspector) =
+                    inspector.as_any().downcast_ref::<PermissionInspector>()
+                {
+                    permission_inspector.allow_for(tool_name, duration).await;
+                    return;
+                }
+            }
+        }
+        tracing::warn!("Permission inspector not found for time limited permission");
+    }
+
     /// Process inspection results using the permission inspector
     /// This delegates to the permission inspector's process_inspection_results method
     pub fn process_inspection_results_with_permission_inspector(
@@ -279,6 +298,25 @@ pub fn get_security_finding_id_from_results(
         .and_then(|result| result.finding_id.clone())
 }
 
+/// What decided a call that was allowed or denied without asking: an inspector that overrode
+/// the permission inspector, or else whatever the permission inspector went by
+pub fn get_decision_source_from_results(
+    tool_request_id: &str,
+    inspection_results: &[InspectionResult],
+) -> DecisionSource {
+    let mut results = inspection_results
+        .iter()
+        .filter(|result| result.tool_request_id == tool_request_id);
+    if results.clone().any(|result| {
+        result.inspector_name != "permission" && result.action != InspectionAction::Allow
+    }) {
+        return DecisionSource::Inspector;
+    }
+    results
+        .find_map(|result| result.decision_source)
+        .unwrap_or(DecisionSource::Inspector)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -309,6 +347,7 @@ mod tests {
             confidence: 0.9,
             inspector_name: "test_inspector".to_string(),
             finding_id: Some("TEST-001".to_string()),
+            decision_source: None,
         }];
 
         let updated_result =
@@ -318,4 +357,43 @@ mod tests {
         assert_eq!(updated_result.denied.len(), 1);
         assert_eq!(updated_result.denied[0].id, "req_1");
     }
+
+    #[test]
+    fn test_decision_source_from_results() {
+        let result = |inspector_name: &str, action, decision_source| InspectionResult {
+            tool_request_id: "req_1".to_string(),
+            action,
+            reason: String::new(),
+            confidence: 1.0,
+            inspector_name: inspector_name.to_string(),
+            finding_id: None,
+            decision_source,
+        };
+
+        let allowed_by_rule = vec![
+            result("security", InspectionAction::Allow, None),
+            result(
+                "permission",
+                InspectionAction::Allow,
+                Some(DecisionSource::Rule),
+            ),
+        ];
+        assert_eq!(
+            get_decision_source_from_results("req_1", &allowed_by_rule),
+            DecisionSource::Rule
+        );
+
+        let overridden = vec![
+            result(
+                "permission",
+                InspectionAction::Allow,
+                Some(DecisionSource::Mode),
+            ),
+            result("repetition", InspectionAction::Deny, None),
+        ];
+        assert_eq!(
+            get_decision_source_from_results("req_1", &overridden),
+            DecisionSource::Inspector
+        );
+    }
 }
@@ -1,8 +1,8 @@
-use crate::conversation::message::{Message, ToolRequest};
+use crate::conversation::message::{Message, MessageContent, ToolRequest};
 use crate::tool_inspection::{InspectionAction, InspectionResult, ToolInspector};
 use anyhow::Result;
 use async_trait::async_trait;
-use rmcp::model::CallToolRequestParam;
+use rmcp::model::{CallToolRequestParam, Role};
 use serde_json::Value;
 use std::collections::HashMap;
 
@@ -15,7 +15,8 @@ struct InternalToolCall {
 
 impl InternalToolCall {
     fn matches(&self, other: &InternalToolCall) -> bool {
-        self.name == other.name && self.parameters == other.parameters
+        self.name == other.name
+            && normalize_parameters(&self.parameters) == normalize_parameters(&other.parameters)
     }
 
     fn from_tool_call(tool_call: &CallToolRequestParam) -> Self {
@@ -29,6 +30,15 @@ impl InternalToolCall {
     }
 }
 
+// Missing and empty arguments are the same call; object key order never matters to `Value` equality
+fn normalize_parameters(parameters: &Value) -> &Value {
+    static EMPTY: Value = Value::Null;
+    match parameters {
+        Value::Object(map) if map.is_empty() => &EMPTY,
+        other => other,
+    }
+}
+
 #[derive(Debug)]
 pub struct RepetitionInspector {
     max_repetitions: Option<u32>,
@@ -98,20 +108,30 @@ impl ToolInspector for RepetitionInspector {
     async fn inspect(
         &self,
         tool_requests: &[ToolRequest],
-        _messages: &[Message],
+        messages: &[Message],
     ) -> Result<Vec<InspectionResult>> {
         let mut results = Vec::new();
+        let Some(max_repetitions) = self.max_repetitions else {
+            return Ok(results);
+        };
+
+        let history = previous_tool_calls(messages);
+        let mut batch: Vec<InternalToolCall> = Vec::new();
 
-        // Check repetition limits for each tool request
         for tool_request in tool_requests {
             if let Ok(tool_call) = &tool_request.tool_call {
-                // Create a temporary clone to check without modifying state
-                let mut temp_inspector = RepetitionInspector::new(self.max_repetitions);
-                temp_inspector.last_call = self.last_call.clone();
-                temp_inspector.repeat_count = self.repeat_count;
-                temp_inspector.call_counts = self.call_counts.clone();
-
-                if !temp_inspector.check_tool_call(tool_call.clone()) {
+                let call = InternalToolCall::from_tool_call(tool_call);
+
+                // Calls earlier in this response run just before this one
+                let in_batch = batch.iter().rev().take_while(|c| c.matches(&call)).count();
+                let repeats = if in_batch == batch.len() {
+                    in_batch + consecutive_repeats(&history, &call)
+                } else {
+                    in_batch
+                };
+                batch.push(call);
+
+                if repeats as u32 >= max_repetitions {
                     results.push(InspectionResult {
                         tool_request_id: tool_request.id.clone(),
                         action: InspectionAction::Deny,
@@ -122,6 +142,7 @@ impl ToolInspector for RepetitionInspector {
                         confidence: 1.0,
                         inspector_name: "repetition".to_string(),
                         finding_id: Some("REP-001".to_string()),
+                        decision_source: None,
                     });
                 }
             }
@@ -130,3 +151,40 @@ impl ToolInspector for RepetitionInspector {
         Ok(results)
     }
 }
+
+/// Tool calls made by the assistant since the last user text, most recent first. Each entry
+/// holds the calls from one assistant message.
+fn previous_tool_calls(messages: &[Message]) -> Vec<Vec<InternalToolCall>> {
+    let mut turns = Vec::new();
+    for message in messages.iter().rev() {
+        match message.role {
+            Role::User if message.is_tool_response() => continue,
+            Role::User => break,
+            Role::Assistant => {
+                let calls: Vec<InternalToolCall> = message
+                    .content
+                    .iter()
+                    .filter_map(|content| match content {
+                        MessageContent::ToolRequest(request) => request.tool_call.as_ref().ok(),
+                        _ => None,
+                    })
+                    .map(InternalToolCall::from_tool_call)
+                    .collect();
+                if calls.is_empty() {
+                    break;
+                }
+                turns.push(calls);
+            }
+        }
+    }
+    turns
+}
+
+/// How many of the most recent assistant turns made exactly this call and nothing else
+fn consecutive_repeats(history: &[Vec<InternalToolCall>], call: &InternalToolCall) -> usize {
+    history
+        .iter()
+        .take_while(|calls| calls.iter().all(|c| c.matches(call)))
+        .map(Vec::len)
+        .sum()
+}
@@ -141,8 +141,8 @@ async fn run_truncate_test(
             Ok(AgentEvent::McpNotification(n)) => {
                 println!("MCP Notification: {n:?}");
             }
-            Ok(AgentEvent::ModelChange { .. }) => {
-                // Model change events are informational, just continue
+            Ok(AgentEvent::ModelChange { .. }) | Ok(AgentEvent::StopReason(_)) => {
+                // Model change and stop reason events are informational, just continue
             }
             Ok(AgentEvent::HistoryReplaced(_updated_conversation)) => {
                 // Should update the conversation here, but we're not reading it
@@ -1112,6 +1112,7 @@ mod max_turns_tests {
                 }
                 Ok(AgentEvent::McpNotification(_)) => {}
                 Ok(AgentEvent::ModelChange { .. }) => {}
+                Ok(AgentEvent::StopReason(_)) => {}
                 Ok(AgentEvent::HistoryReplaced(_updated_conversation)) => {
                     // We should update the conversation here, but we're not reading it
                 }
@@ -1,5 +1,7 @@
+use goose::conversation::message::{Message, ToolRequest};
+use goose::tool_inspection::{InspectionAction, ToolInspector};
 use goose::tool_monitor::RepetitionInspector;
-use rmcp::model::CallToolRequestParam;
+use rmcp::model::{CallToolRequestParam, Content};
 use rmcp::object;
 
 // This test targets RepetitionInspector::check_tool_call
@@ -39,3 +41,53 @@ fn test_repetition_inspector_denies_after_exceeding_and_resets_on_param_change()
     // One more identical call with new params → denied again
     assert!(!inspector.check_tool_call(call_v2));
 }
+
+fn tool_turn(id: &str, call: &CallToolRequestParam) -> Vec<Message> {
+    vec![
+        Message::assistant().with_tool_request(id, Ok(call.clone())),
+        Message::user().with_tool_response(id, Ok(vec![Content::text("done")])),
+    ]
+}
+
+// inspect counts the identical calls that directly precede the request in the conversation
+#[tokio::test]
+async fn test_repetition_inspector_denies_repeated_calls_from_history() {
+    let inspector = RepetitionInspector::new(Some(2));
+    let call = CallToolRequestParam {
+        name: "fetch_user".into(),
+        arguments: Some(object!({"id": 123, "verbose": true})),
+    };
+    // Same arguments in a different order
+    let request = ToolRequest {
+        id: "next".to_string(),
+        tool_call: Ok(CallToolRequestParam {
+            name: "fetch_user".into(),
+            arguments: Some(object!({"verbose": true, "id": 123})),
+        }),
+    };
+
+    let mut messages = vec![Message::user().with_text("look up user 123")];
+    messages.extend(tool_turn("1", &call));
+    let results = inspector
+        .inspect(std::slice::from_ref(&request), &messages)
+        .await
+        .unwrap();
+    assert!(results.is_empty());
+
+    messages.extend(tool_turn("2", &call));
+    let results = inspector
+        .inspect(std::slice::from_ref(&request), &messages)
+        .await
+        .unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].tool_request_id, "next");
+    assert_eq!(results[0].action, InspectionAction::Deny);
+
+    // A new user message starts the count again
+    messages.push(Message::user().with_text("try again"));
+    let results = inspector
+        .inspect(std::slice::from_ref(&request), &messages)
+        .await
+        .unwrap();
+    assert!(results.is_empty());
+}
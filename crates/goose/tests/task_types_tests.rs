@@ -124,3 +124,44 @@ fn test_task_with_missing_fields() {
     assert!(task.get_command_parameters().is_none());
     assert!(!task.get_sequential_when_repeated());
 }
+
+#[test]
+fn test_task_description() {
+    let sub_recipe_task = Task {
+        id: "test-5".to_string(),
+        task_type: TaskType::SubRecipe,
+        payload: json!({
+            "sub_recipe": {
+                "name": "weather",
+                "recipe_path": "/path/to/recipe",
+                "command_parameters": {"city": "Paris"}
+            }
+        }),
+    };
+    assert_eq!(
+        sub_recipe_task.get_description(),
+        "sub-recipe 'weather' with city=Paris"
+    );
+
+    let inline_task = Task {
+        id: "test-6".to_string(),
+        task_type: TaskType::InlineRecipe,
+        payload: json!({
+            "recipe": {
+                "title": "Summarize logs",
+                "instructions": "Test instructions"
+            }
+        }),
+    };
+    assert_eq!(
+        inline_task.get_description(),
+        "inline recipe 'Summarize logs'"
+    );
+
+    let empty_task = Task {
+        id: "test-7".to_string(),
+        task_type: TaskType::SubRecipe,
+        payload: json!({}),
+    };
+    assert_eq!(empty_task.get_description(), "ID: test-7");
+}
@@ -59,6 +59,7 @@ async fn test_inspect_tools_aggregates_and_handles_errors() {
             confidence: 0.95,
             inspector_name: "ok".to_string(),
             finding_id: None,
+            decision_source: None,
         },
         InspectionResult {
             tool_request_id: "req_2".to_string(),
@@ -67,6 +68,7 @@ async fn test_inspect_tools_aggregates_and_handles_errors() {
             confidence: 0.7,
             inspector_name: "ok".to_string(),
             finding_id: Some("FND-123".to_string()),
+            decision_source: None,
         },
     ];
 